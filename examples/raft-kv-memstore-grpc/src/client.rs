@@ -0,0 +1,116 @@
+use std::collections::BTreeSet;
+
+use openraft::error::NetworkError;
+use openraft::RaftMetrics;
+use tonic::transport::Channel;
+
+use crate::grpc::decode_reply;
+use crate::grpc::encode_request;
+use crate::pb::app_service_client::AppServiceClient;
+use crate::typ;
+use crate::NodeId;
+use crate::Request;
+use crate::TypeConfig;
+
+/// A client that sends requests to the [`AppService`] of a node.
+///
+/// Unlike the HTTP based example, it does not follow `ForwardToLeader`: a write or a membership
+/// change must be sent to the leader.
+///
+/// [`AppService`]: crate::pb::app_service_server::AppService
+pub struct ExampleClient {
+    /// The id of the node to send request to.
+    pub target: NodeId,
+
+    pub inner: AppServiceClient<Channel>,
+}
+
+impl ExampleClient {
+    /// Create a client connected to the node `target` at `addr`.
+    pub async fn new(target: NodeId, addr: String) -> Result<Self, typ::RPCError> {
+        let inner = AppServiceClient::connect(format!("http://{}", addr))
+            .await
+            .map_err(|e| typ::RPCError::Network(NetworkError::new(&e)))?;
+
+        Ok(Self { target, inner })
+    }
+
+    // --- Application API
+
+    /// Submit a write request to the raft cluster.
+    ///
+    /// The request will be processed by raft protocol: it will be replicated to a quorum and then
+    /// will be applied to state machine.
+    ///
+    /// The result of applying the request will be returned.
+    pub async fn write(
+        &mut self,
+        req: &Request,
+    ) -> Result<typ::ClientWriteResponse, typ::RPCError<typ::ClientWriteError>> {
+        let req = encode_request(req)?;
+        let res = self.inner.write(req).await;
+        decode_reply(self.target, res)
+    }
+
+    /// Read value by key, in an inconsistent mode.
+    ///
+    /// This method may return stale value because it does not force to read on a legal leader.
+    pub async fn read(&mut self, req: &String) -> Result<String, typ::RPCError> {
+        let req = encode_request(req)?;
+        let res = self.inner.read(req).await;
+        decode_reply(self.target, res)
+    }
+
+    // --- Cluster management API
+
+    /// Initialize a cluster of only the node that receives this request.
+    ///
+    /// This is the first step to initialize a cluster.
+    /// With a initialized cluster, new node can be added with [`add_learner`].
+    /// Then make the new node a member with [`change_membership`].
+    ///
+    /// [`add_learner`]: Self::add_learner
+    /// [`change_membership`]: Self::change_membership
+    pub async fn init(&mut self) -> Result<(), typ::RPCError<typ::InitializeError>> {
+        let req = encode_request(&Vec::<(NodeId, String)>::new())?;
+        let res = self.inner.init(req).await;
+        decode_reply(self.target, res)
+    }
+
+    /// Add a node as learner.
+    pub async fn add_learner(
+        &mut self,
+        req: (NodeId, String),
+    ) -> Result<typ::ClientWriteResponse, typ::RPCError<typ::ClientWriteError>> {
+        let req = encode_request(&req)?;
+        let res = self.inner.add_learner(req).await;
+        decode_reply(self.target, res)
+    }
+
+    /// Change membership to the specified set of nodes.
+    ///
+    /// All nodes in `req` have to be already added as learner with [`add_learner`],
+    /// or an error [`LearnerNotFound`] will be returned.
+    ///
+    /// [`add_learner`]: Self::add_learner
+    /// [`LearnerNotFound`]: openraft::error::LearnerNotFound
+    pub async fn change_membership(
+        &mut self,
+        req: &BTreeSet<NodeId>,
+    ) -> Result<typ::ClientWriteResponse, typ::RPCError<typ::ClientWriteError>> {
+        let req = encode_request(req)?;
+        let res = self.inner.change_membership(req).await;
+        decode_reply(self.target, res)
+    }
+
+    /// Get the metrics about the cluster.
+    ///
+    /// Metrics contains various information about the cluster, such as current leader,
+    /// membership config, replication status etc.
+    /// See [`RaftMetrics`].
+    pub async fn metrics(&mut self) -> Result<RaftMetrics<TypeConfig>, typ::RPCError> {
+        let req = encode_request(&())?;
+        let res = self.inner.metrics(req).await;
+        decode_reply(self.target, res)
+    }
+}
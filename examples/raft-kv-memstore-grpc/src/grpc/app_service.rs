@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use openraft::error::Infallible;
+use openraft::BasicNode;
+use openraft::RaftMetrics;
+use tonic::Request;
+use tonic::Response;
+use tonic::Status;
+
+use crate::app::App;
+use crate::grpc::decode_request;
+use crate::grpc::encode_reply;
+use crate::pb;
+use crate::pb::app_service_server::AppService;
+use crate::NodeId;
+use crate::TypeConfig;
+
+/// Serves the application API and the cluster management API sent by an [`ExampleClient`].
+///
+/// [`ExampleClient`]: crate::client::ExampleClient
+pub struct AppServiceImpl {
+    app: Arc<App>,
+}
+
+impl AppServiceImpl {
+    pub fn new(app: Arc<App>) -> Self {
+        Self { app }
+    }
+}
+
+#[tonic::async_trait]
+impl AppService for AppServiceImpl {
+    /// Initialize a single-node cluster if the request is empty vec.
+    /// Otherwise initialize a cluster with the specified vec of node-id and node-address.
+    async fn init(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: Vec<(NodeId, String)> = decode_request(request.into_inner())?;
+
+        let mut nodes = BTreeMap::new();
+        if req.is_empty() {
+            nodes.insert(self.app.id, BasicNode {
+                addr: self.app.addr.clone(),
+            });
+        } else {
+            for (id, addr) in req.into_iter() {
+                nodes.insert(id, BasicNode { addr });
+            }
+        };
+        let res = self.app.raft.initialize(nodes).await;
+        encode_reply(res)
+    }
+
+    /// Add a node as **Learner**.
+    ///
+    /// A Learner receives log replication from the leader but does not vote.
+    /// This should be done before adding a node as a member into the cluster
+    /// (by calling `ChangeMembership`)
+    async fn add_learner(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let (node_id, addr): (NodeId, String) = decode_request(request.into_inner())?;
+        let res = self.app.raft.add_learner(node_id, BasicNode { addr }, true).await;
+        encode_reply(res)
+    }
+
+    /// Changes specified learners to members, or remove members.
+    async fn change_membership(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: BTreeSet<NodeId> = decode_request(request.into_inner())?;
+        let res = self.app.raft.change_membership(req, false).await;
+        encode_reply(res)
+    }
+
+    /// Get the latest metrics of the cluster.
+    async fn metrics(&self, _request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let metrics = self.app.raft.metrics().borrow().clone();
+        let res: Result<RaftMetrics<TypeConfig>, Infallible> = Ok(metrics);
+        encode_reply(res)
+    }
+
+    /// Submit a write request to the raft cluster.
+    async fn write(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: crate::store::Request = decode_request(request.into_inner())?;
+        let res = self.app.raft.client_write(req).await;
+        encode_reply(res)
+    }
+
+    /// Read value by key from the local state machine, which may be stale.
+    async fn read(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let key: String = decode_request(request.into_inner())?;
+
+        let state_machine = self.app.state_machine_store.state_machine.read().await;
+        let value = state_machine.data.get(&key).cloned();
+
+        let res: Result<String, Infallible> = Ok(value.unwrap_or_default());
+        encode_reply(res)
+    }
+}
@@ -0,0 +1,91 @@
+//! The gRPC services a node serves, and the codec shared by the services and the clients.
+//!
+//! Every request and reply carries its payload encoded in JSON, thus the openraft types do not
+//! need to be defined in `proto/raft.proto`. It keeps the demo short; a production transport
+//! should define them as protobuf messages.
+
+use std::error::Error;
+
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::error::RemoteError;
+use openraft::error::Unreachable;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tonic::Code;
+use tonic::Response;
+use tonic::Status;
+
+use crate::pb;
+use crate::NodeId;
+use crate::TypeConfig;
+
+pub mod app_service;
+pub mod raft_service;
+
+/// Encode a request into a [`pb::RaftRequest`], on the client side.
+pub fn encode_request<T>(req: &T) -> Result<pb::RaftRequest, NetworkError>
+where T: Serialize {
+    let data = serde_json::to_string(req).map_err(|e| NetworkError::new(&e))?;
+    Ok(pb::RaftRequest { data })
+}
+
+/// Decode the reply of an RPC to `target` into the response, or the error returned by `target`,
+/// on the client side.
+pub fn decode_reply<T, E>(
+    target: NodeId,
+    res: Result<Response<pb::RaftReply>, Status>,
+) -> Result<T, RPCError<TypeConfig, E>>
+where
+    T: DeserializeOwned,
+    E: Error + DeserializeOwned,
+{
+    let reply = match res {
+        Ok(reply) => reply.into_inner(),
+        Err(status) => return Err(status_to_rpc_error(status)),
+    };
+
+    if reply.error.is_empty() {
+        serde_json::from_str(&reply.data).map_err(|e| RPCError::Network(NetworkError::new(&e)))
+    } else {
+        let err: E = serde_json::from_str(&reply.error).map_err(|e| RPCError::Network(NetworkError::new(&e)))?;
+        Err(RPCError::RemoteError(RemoteError::new(target, err)))
+    }
+}
+
+/// Convert a gRPC error status into an [`RPCError`].
+///
+/// If the target can not be connected, it returns [`Unreachable`] so that openraft backs off
+/// before retrying.
+fn status_to_rpc_error<E>(status: Status) -> RPCError<TypeConfig, E>
+where E: Error {
+    match status.code() {
+        Code::Unavailable => RPCError::Unreachable(Unreachable::new(&status)),
+        _ => RPCError::Network(NetworkError::new(&status)),
+    }
+}
+
+/// Decode a [`pb::RaftRequest`], on the server side.
+fn decode_request<T>(req: pb::RaftRequest) -> Result<T, Status>
+where T: DeserializeOwned {
+    serde_json::from_str(&req.data).map_err(|e| Status::invalid_argument(e.to_string()))
+}
+
+/// Encode the result of handling a request into a [`pb::RaftReply`], on the server side.
+fn encode_reply<T, E>(res: Result<T, E>) -> Result<Response<pb::RaftReply>, Status>
+where
+    T: Serialize,
+    E: Serialize,
+{
+    let reply = match res {
+        Ok(x) => pb::RaftReply {
+            data: serde_json::to_string(&x).map_err(|e| Status::internal(e.to_string()))?,
+            error: String::new(),
+        },
+        Err(e) => pb::RaftReply {
+            data: String::new(),
+            error: serde_json::to_string(&e).map_err(|e| Status::internal(e.to_string()))?,
+        },
+    };
+    Ok(Response::new(reply))
+}
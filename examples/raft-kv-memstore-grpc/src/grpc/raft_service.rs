@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::VoteRequest;
+use tonic::Request;
+use tonic::Response;
+use tonic::Status;
+
+use crate::app::App;
+use crate::grpc::decode_request;
+use crate::grpc::encode_reply;
+use crate::pb;
+use crate::pb::raft_service_server::RaftService;
+use crate::TypeConfig;
+
+/// Serves the raft internal RPC sent by other nodes with a [`NetworkConnection`].
+///
+/// [`NetworkConnection`]: crate::network::NetworkConnection
+pub struct RaftServiceImpl {
+    app: Arc<App>,
+}
+
+impl RaftServiceImpl {
+    pub fn new(app: Arc<App>) -> Self {
+        Self { app }
+    }
+}
+
+#[tonic::async_trait]
+impl RaftService for RaftServiceImpl {
+    async fn append_entries(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: AppendEntriesRequest<TypeConfig> = decode_request(request.into_inner())?;
+        let res = self.app.raft.append_entries(req).await;
+        encode_reply(res)
+    }
+
+    async fn vote(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: VoteRequest<TypeConfig> = decode_request(request.into_inner())?;
+        let res = self.app.raft.vote(req).await;
+        encode_reply(res)
+    }
+
+    async fn install_snapshot(&self, request: Request<pb::RaftRequest>) -> Result<Response<pb::RaftReply>, Status> {
+        let req: InstallSnapshotRequest<TypeConfig> = decode_request(request.into_inner())?;
+        let res = self.app.raft.install_snapshot(req).await;
+        encode_reply(res)
+    }
+}
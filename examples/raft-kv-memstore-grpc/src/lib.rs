@@ -0,0 +1,106 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+use std::sync::Arc;
+
+use openraft::Config;
+use tonic::transport::Server;
+
+use crate::app::App;
+use crate::grpc::app_service::AppServiceImpl;
+use crate::grpc::raft_service::RaftServiceImpl;
+use crate::network::Network;
+use crate::pb::app_service_server::AppServiceServer;
+use crate::pb::raft_service_server::RaftServiceServer;
+use crate::store::Request;
+use crate::store::Response;
+
+pub mod app;
+pub mod client;
+pub mod grpc;
+pub mod network;
+pub mod store;
+
+/// The types generated from `proto/raft.proto`.
+pub mod pb {
+    tonic::include_proto!("openraftpb");
+}
+
+pub type NodeId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store.
+    pub TypeConfig:
+        D = Request,
+        R = Response,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+pub type Raft = openraft::Raft<TypeConfig>;
+
+pub mod typ {
+
+    use crate::TypeConfig;
+
+    pub type RaftError<E = openraft::error::Infallible> = openraft::error::RaftError<TypeConfig, E>;
+    pub type RPCError<E = openraft::error::Infallible> = openraft::error::RPCError<TypeConfig, RaftError<E>>;
+
+    pub type ClientWriteError = openraft::error::ClientWriteError<TypeConfig>;
+    pub type InitializeError = openraft::error::InitializeError<TypeConfig>;
+    pub type InstallSnapshotError = openraft::error::InstallSnapshotError;
+
+    pub type ClientWriteResponse = openraft::raft::ClientWriteResponse<TypeConfig>;
+}
+
+pub async fn start_example_raft_node(node_id: NodeId, addr: String) -> Result<(), Box<dyn std::error::Error>> {
+    // Create a configuration for the raft instance.
+    let config = Config {
+        heartbeat_interval: 500,
+        election_timeout_min: 1500,
+        election_timeout_max: 3000,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config.validate().unwrap());
+
+    // Create a instance of where the Raft logs will be stored.
+    let log_store = LogStore::default();
+    // Create a instance of where the Raft data will be stored.
+    let state_machine_store = Arc::new(StateMachineStore::default());
+
+    // Create the network layer that will connect and communicate the raft instances and
+    // will be used in conjunction with the store created above.
+    let network = Network { id: node_id };
+
+    // Create a local raft instance.
+    let raft = openraft::Raft::new(
+        node_id,
+        config.clone(),
+        network,
+        log_store.clone(),
+        state_machine_store.clone(),
+    )
+    .await
+    .unwrap();
+
+    // Create an application that will store all the instances created above, this will
+    // later be used by the gRPC services.
+    let app = Arc::new(App {
+        id: node_id,
+        addr: addr.clone(),
+        raft,
+        log_store,
+        state_machine_store,
+        config,
+    });
+
+    // Start the gRPC server, serving both the raft internal RPC and the application API.
+    Server::builder()
+        .add_service(RaftServiceServer::new(RaftServiceImpl::new(app.clone())))
+        .add_service(AppServiceServer::new(AppServiceImpl::new(app)))
+        .serve(addr.parse()?)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+mod raft_network_impl;
+
+pub use raft_network_impl::Network;
+pub use raft_network_impl::NetworkConnection;
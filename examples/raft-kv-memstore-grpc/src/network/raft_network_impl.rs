@@ -0,0 +1,151 @@
+use std::error::Error;
+use std::future::Future;
+
+use openraft::error::RPCError;
+use openraft::error::Timeout;
+use openraft::error::Unreachable;
+use openraft::network::RPCOption;
+use openraft::network::RPCTypes;
+use openraft::network::RaftNetwork;
+use openraft::network::RaftNetworkFactory;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::InstallSnapshotResponse;
+use openraft::raft::VoteRequest;
+use openraft::raft::VoteResponse;
+use openraft::BasicNode;
+use serde::de::DeserializeOwned;
+use tonic::transport::Channel;
+
+use crate::grpc::decode_reply;
+use crate::grpc::encode_request;
+use crate::pb;
+use crate::pb::raft_service_client::RaftServiceClient;
+use crate::typ;
+use crate::NodeId;
+use crate::TypeConfig;
+
+/// Creates a [`NetworkConnection`] to a target node for the local node `id`.
+pub struct Network {
+    pub id: NodeId,
+}
+
+impl RaftNetworkFactory<TypeConfig> for Network {
+    type Network = NetworkConnection;
+
+    async fn new_client(&mut self, target: NodeId, node: &BasicNode) -> Self::Network {
+        NetworkConnection {
+            id: self.id,
+            target,
+            target_node: node.clone(),
+            client: None,
+        }
+    }
+}
+
+/// A connection to a target node, owned by a single replication stream or election.
+///
+/// The gRPC channel is connected when the first RPC is sent, and is kept for the following RPCs.
+pub struct NetworkConnection {
+    id: NodeId,
+    target: NodeId,
+    target_node: BasicNode,
+
+    /// The connected client, or `None` if it is not connected yet or the connection is broken.
+    client: Option<RaftServiceClient<Channel>>,
+}
+
+impl NetworkConnection {
+    /// Return the connected client, connecting to the target if it is not connected.
+    async fn client(&mut self) -> Result<RaftServiceClient<Channel>, Unreachable> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+
+        let addr = format!("http://{}", self.target_node.addr);
+        tracing::debug!("connect to target: {}", addr);
+
+        let client = RaftServiceClient::connect(addr).await.map_err(|e| Unreachable::new(&e))?;
+        self.client = Some(client.clone());
+        Ok(client)
+    }
+
+    /// Send an RPC with `send` and decode the reply.
+    ///
+    /// It returns a [`Timeout`] error if connecting and sending take longer than
+    /// [`RPCOption::hard_ttl()`]. The connection is dropped if the RPC fails, so that the next RPC
+    /// reconnects.
+    async fn call<T, E, F, Fut>(
+        &mut self,
+        action: RPCTypes,
+        option: &RPCOption,
+        send: F,
+    ) -> Result<T, RPCError<TypeConfig, E>>
+    where
+        T: DeserializeOwned,
+        E: Error + DeserializeOwned,
+        F: FnOnce(RaftServiceClient<Channel>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<pb::RaftReply>, tonic::Status>>,
+    {
+        let ttl = option.hard_ttl();
+
+        let fu = async {
+            let client = self.client().await?;
+            Ok::<_, Unreachable>(send(client).await)
+        };
+
+        let res = match tokio::time::timeout(ttl, fu).await {
+            Ok(res) => res?,
+            Err(_elapsed) => {
+                self.client = None;
+                return Err(RPCError::Timeout(Timeout {
+                    action,
+                    id: self.id,
+                    target: self.target,
+                    timeout: ttl,
+                }));
+            }
+        };
+
+        if res.is_err() {
+            self.client = None;
+        }
+        decode_reply(self.target, res)
+    }
+}
+
+impl RaftNetwork<TypeConfig> for NetworkConnection {
+    async fn append_entries(
+        &mut self,
+        req: AppendEntriesRequest<TypeConfig>,
+        option: RPCOption,
+    ) -> Result<AppendEntriesResponse<TypeConfig>, typ::RPCError> {
+        let req = encode_request(&req)?;
+        self.call(RPCTypes::AppendEntries, &option, |mut c| async move {
+            c.append_entries(req).await
+        })
+        .await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        req: InstallSnapshotRequest<TypeConfig>,
+        option: RPCOption,
+    ) -> Result<InstallSnapshotResponse<TypeConfig>, typ::RPCError<typ::InstallSnapshotError>> {
+        let req = encode_request(&req)?;
+        self.call(RPCTypes::InstallSnapshot, &option, |mut c| async move {
+            c.install_snapshot(req).await
+        })
+        .await
+    }
+
+    async fn vote(
+        &mut self,
+        req: VoteRequest<TypeConfig>,
+        option: RPCOption,
+    ) -> Result<VoteResponse<TypeConfig>, typ::RPCError> {
+        let req = encode_request(&req)?;
+        self.call(RPCTypes::Vote, &option, |mut c| async move { c.vote(req).await }).await
+    }
+}
@@ -0,0 +1,174 @@
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+#[allow(deprecated)] // since nightly 1.82
+use std::panic::PanicInfo;
+use std::thread;
+use std::time::Duration;
+
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::BasicNode;
+use raft_kv_memstore_grpc::client::ExampleClient;
+use raft_kv_memstore_grpc::start_example_raft_node;
+use raft_kv_memstore_grpc::store::Request;
+use tokio::runtime::Runtime;
+use tracing_subscriber::EnvFilter;
+
+#[allow(deprecated)] // PanicInfo deprecated since nightly 1.82
+pub fn log_panic(panic: &PanicInfo) {
+    let backtrace = {
+        format!("{:?}", Backtrace::force_capture())
+        // #[cfg(feature = "bt")]
+        // {
+        //     format!("{:?}", Backtrace::force_capture())
+        // }
+        //
+        // #[cfg(not(feature = "bt"))]
+        // {
+        //     "backtrace is disabled without --features 'bt'".to_string()
+        // }
+    };
+
+    eprintln!("{}", panic);
+
+    if let Some(location) = panic.location() {
+        tracing::error!(
+            message = %panic,
+            backtrace = %backtrace,
+            panic.file = location.file(),
+            panic.line = location.line(),
+            panic.column = location.column(),
+        );
+        eprintln!("{}:{}:{}", location.file(), location.line(), location.column());
+    } else {
+        tracing::error!(message = %panic, backtrace = %backtrace);
+    }
+
+    eprintln!("{}", backtrace);
+}
+
+/// Setup a cluster of 3 nodes that communicate with gRPC.
+/// Write to it and read from it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_cluster() -> anyhow::Result<()> {
+    std::panic::set_hook(Box::new(|panic| {
+        log_panic(panic);
+    }));
+
+    tracing_subscriber::fmt()
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_level(true)
+        .with_ansi(false)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let get_addr = |node_id| {
+        let addr = match node_id {
+            1 => "127.0.0.1:22001".to_string(),
+            2 => "127.0.0.1:22002".to_string(),
+            3 => "127.0.0.1:22003".to_string(),
+            _ => {
+                return Err(anyhow::anyhow!("node {} not found", node_id));
+            }
+        };
+        Ok(addr)
+    };
+
+    // --- Start 3 raft node in 3 threads.
+
+    for node_id in [1, 2, 3] {
+        let addr = get_addr(node_id)?;
+        thread::spawn(move || {
+            let rt = Runtime::new().unwrap();
+            let x = rt.block_on(start_example_raft_node(node_id, addr));
+            println!("x: {:?}", x);
+        });
+    }
+
+    // Wait for server to start up.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // --- Create a client to the first node, as a control handle to the cluster.
+
+    let mut client = ExampleClient::new(1, get_addr(1)?).await?;
+
+    // --- 1. Initialize the target node as a cluster of only one node.
+
+    println!("=== init single node cluster");
+    client.init().await?;
+
+    // --- 2. Add node 2 and 3 to the cluster as `Learner`.
+
+    println!("=== add-learner 2");
+    let _x = client.add_learner((2, get_addr(2)?)).await?;
+
+    println!("=== add-learner 3");
+    let _x = client.add_learner((3, get_addr(3)?)).await?;
+
+    println!("=== metrics after add-learner");
+    let x = client.metrics().await?;
+
+    assert_eq!(&vec![btreeset![1]], x.membership_config.membership().get_joint_config());
+
+    let nodes_in_cluster =
+        x.membership_config.nodes().map(|(nid, node)| (*nid, node.clone())).collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        btreemap! {
+            1 => BasicNode::new("127.0.0.1:22001"),
+            2 => BasicNode::new("127.0.0.1:22002"),
+            3 => BasicNode::new("127.0.0.1:22003"),
+        },
+        nodes_in_cluster
+    );
+
+    // --- 3. Turn the two learners to members.
+
+    println!("=== change-membership to 1,2,3");
+    let _x = client.change_membership(&btreeset! {1,2,3}).await?;
+
+    println!("=== metrics after change-member");
+    let x = client.metrics().await?;
+    assert_eq!(
+        &vec![btreeset![1, 2, 3]],
+        x.membership_config.membership().get_joint_config()
+    );
+
+    // --- Write some application data through the leader.
+
+    println!("=== write `foo=bar`");
+    let _x = client
+        .write(&Request::Set {
+            key: "foo".to_string(),
+            value: "bar".to_string(),
+        })
+        .await?;
+
+    // --- Wait for a while to let the replication get done.
+
+    tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+    // --- Read it on every node.
+
+    for node_id in [1, 2, 3] {
+        println!("=== read `foo` on node {}", node_id);
+        let mut c = ExampleClient::new(node_id, get_addr(node_id)?).await?;
+        let x = c.read(&("foo".to_string())).await?;
+        assert_eq!("bar", x);
+    }
+
+    // --- A write to a follower returns an error from the remote node.
+
+    println!("=== write `foo=wow` to node 2 MUST return ForwardToLeader");
+    let mut client2 = ExampleClient::new(2, get_addr(2)?).await?;
+    let x = client2
+        .write(&Request::Set {
+            key: "foo".to_string(),
+            value: "wow".to_string(),
+        })
+        .await;
+    let err = x.unwrap_err();
+    assert_eq!(Some(1), err.forward_to_leader().and_then(|f| f.leader_id));
+
+    Ok(())
+}
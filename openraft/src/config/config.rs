@@ -29,6 +29,18 @@ pub enum SnapshotPolicy {
     /// the last snapshot.
     LogsSinceLast(u64),
 
+    /// A snapshot will be generated once the specified duration has passed since the last
+    /// snapshot was built or installed, if there are new committed logs since the last snapshot.
+    ///
+    /// It lets a cluster with a low write rate still compact its logs periodically.
+    Interval(Duration),
+
+    /// A snapshot will be generated once any of the policies is satisfied.
+    ///
+    /// For example, `Any(vec![LogsSinceLast(5000), Interval(Duration::from_secs(3600))])`
+    /// generates a snapshot every 5000 logs, or at least once an hour.
+    Any(Vec<SnapshotPolicy>),
+
     /// Openraft will never trigger a snapshot building.
     /// With this option, the application calls
     /// [`Raft::trigger().snapshot()`](`crate::raft::trigger::Trigger::snapshot`) to manually
@@ -37,15 +49,86 @@ pub enum SnapshotPolicy {
 }
 
 impl SnapshotPolicy {
+    /// Whether to build a snapshot, according to the number of logs since the last snapshot.
+    ///
+    /// Time based policies are checked with [`Self::interval()`].
     pub(crate) fn should_snapshot<C>(&self, state: &impl Deref<Target = impl LogStateReader<C>>) -> bool
     where C: RaftTypeConfig {
         match self {
             SnapshotPolicy::LogsSinceLast(threshold) => {
                 state.committed().next_index() >= state.snapshot_last_log_id().next_index() + threshold
             }
+            SnapshotPolicy::Interval(_) => false,
+            SnapshotPolicy::Any(policies) => policies.iter().any(|p| p.should_snapshot(state)),
             SnapshotPolicy::Never => false,
         }
     }
+
+    /// Returns the shortest interval between two snapshots if there is a time based policy.
+    pub(crate) fn interval(&self) -> Option<Duration> {
+        match self {
+            SnapshotPolicy::LogsSinceLast(_) => None,
+            SnapshotPolicy::Interval(interval) => Some(*interval),
+            SnapshotPolicy::Any(policies) => policies.iter().filter_map(|p| p.interval()).min(),
+            SnapshotPolicy::Never => None,
+        }
+    }
+}
+
+/// When the log store syncs appended logs to disk.
+///
+/// Openraft does not call fsync itself: the policy is passed to
+/// [`RaftLogStorage::set_fsync_policy()`] when a Raft node starts, and the log store implements
+/// it in [`RaftLogStorage::append()`].
+///
+/// [`RaftLogStorage::set_fsync_policy()`]: crate::storage::RaftLogStorage::set_fsync_policy
+/// [`RaftLogStorage::append()`]: crate::storage::RaftLogStorage::append
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FsyncPolicy {
+    /// Sync on every call to `append()` before calling its callback.
+    PerAppend,
+
+    /// Sync once for the entries of several `append()` calls that are buffered, then call their
+    /// callbacks in order.
+    PerBatch,
+
+    /// Sync at most once per the specified duration; the callbacks of the entries appended in
+    /// between are called after the next sync.
+    Interval(Duration),
+
+    /// Never sync, and call the callback as soon as the entries are written.
+    ///
+    /// Logs acknowledged by this node may be lost on a crash, which may lose committed data.
+    /// It is only meant for testing.
+    Never,
+}
+
+/// Parse fsync policy such as `per_append`, `per_batch`, `interval:10` or `never`.
+fn parse_fsync_policy(src: &str) -> Result<FsyncPolicy, ConfigError> {
+    let invalid = || ConfigError::InvalidFsyncPolicy {
+        syntax: "per_append|per_batch|interval:<ms>|never".to_string(),
+        invalid: src.to_string(),
+    };
+
+    match src {
+        "per_append" => return Ok(FsyncPolicy::PerAppend),
+        "per_batch" => return Ok(FsyncPolicy::PerBatch),
+        "never" => return Ok(FsyncPolicy::Never),
+        _ => {}
+    }
+
+    let Some(ms) = src.strip_prefix("interval:") else {
+        return Err(invalid());
+    };
+
+    let n = ms.parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
+        invalid: src.to_string(),
+        reason: e.to_string(),
+    })?;
+
+    Ok(FsyncPolicy::Interval(Duration::from_millis(n)))
 }
 
 /// Parse number with unit such as 5.3 KB
@@ -58,31 +141,43 @@ fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     Ok(res.as_u64())
 }
 
+/// Parse snapshot policy such as `since_last:5000`, `interval:60000` or
+/// `since_last:5000,interval:60000`.
 fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
     if src == "never" {
         return Ok(SnapshotPolicy::Never);
     }
 
-    let elts = src.split(':').collect::<Vec<_>>();
-    if elts.len() != 2 {
-        return Err(ConfigError::InvalidSnapshotPolicy {
-            syntax: "never|since_last:<num>".to_string(),
-            invalid: src.to_string(),
-        });
+    let mut policies = src.split(',').map(parse_single_snapshot_policy).collect::<Result<Vec<_>, _>>()?;
+
+    if policies.len() == 1 {
+        Ok(policies.pop().unwrap())
+    } else {
+        Ok(SnapshotPolicy::Any(policies))
     }
+}
 
-    if elts[0] != "since_last" {
-        return Err(ConfigError::InvalidSnapshotPolicy {
-            syntax: "never|since_last:<num>".to_string(),
-            invalid: src.to_string(),
-        });
+fn parse_single_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
+    let invalid = || ConfigError::InvalidSnapshotPolicy {
+        syntax: "never|<since_last:<num>|interval:<ms>>[,...]".to_string(),
+        invalid: src.to_string(),
+    };
+
+    let elts = src.split(':').collect::<Vec<_>>();
+    if elts.len() != 2 {
+        return Err(invalid());
     }
 
-    let n_logs = elts[1].parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
+    let n = elts[1].parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
         invalid: src.to_string(),
         reason: e.to_string(),
     })?;
-    Ok(SnapshotPolicy::LogsSinceLast(n_logs))
+
+    match elts[0] {
+        "since_last" => Ok(SnapshotPolicy::LogsSinceLast(n)),
+        "interval" => Ok(SnapshotPolicy::Interval(Duration::from_millis(n))),
+        _ => Err(invalid()),
+    }
 }
 
 /// The runtime configuration for a Raft node.
@@ -145,6 +240,27 @@ pub struct Config {
     #[clap(long, default_value = "0")]
     pub send_snapshot_timeout: u64,
 
+    /// The timeout for an AppendEntries RPC, including a heartbeat, in milliseconds.
+    ///
+    /// Openraft enforces it around every [`RaftNetwork::append_entries()`] call, thus a transport
+    /// does not have to implement its own deadline. `0` means to use `heartbeat_interval`.
+    ///
+    /// [`RaftNetwork::append_entries()`]: crate::network::RaftNetwork::append_entries
+    #[clap(long, default_value = "0")]
+    pub append_entries_timeout: u64,
+
+    /// The timeout for a Vote or PreVote RPC, in milliseconds.
+    ///
+    /// `0` means to use `election_timeout_min`.
+    #[clap(long, default_value = "0")]
+    pub vote_timeout: u64,
+
+    /// The timeout for forwarding a client write to the leader, in milliseconds.
+    ///
+    /// See [`Config::forward_to_leader`]. `0` means to use `election_timeout_max`.
+    #[clap(long, default_value = "0")]
+    pub forward_to_leader_timeout: u64,
+
     /// The maximum number of entries per payload allowed to be transmitted during replication
     ///
     /// If this is too low, it will take longer for the nodes to be brought up to
@@ -152,6 +268,125 @@ pub struct Config {
     #[clap(long, default_value = "300")]
     pub max_payload_entries: u64,
 
+    /// The maximum number of AppendEntries RPCs in flight to a single follower/learner.
+    ///
+    /// With a value greater than 1, up to `max_in_flight_appends * max_payload_entries` logs are
+    /// sent at a time, in several AppendEntries RPCs of at most `max_payload_entries` entries
+    /// each, without waiting for the response of the previous one. Every in-flight RPC uses its
+    /// own connection created by [`RaftNetworkFactory::new_client()`].
+    /// This improves the replication throughput on links with high latency.
+    ///
+    /// The default value 1 disables pipelining.
+    ///
+    /// [`RaftNetworkFactory::new_client()`]: crate::network::RaftNetworkFactory::new_client
+    #[clap(long, default_value = "1")]
+    pub max_in_flight_appends: u64,
+
+    /// Whether to adapt the number of entries in an AppendEntries RPC to the condition of the
+    /// target.
+    ///
+    /// When enabled, the number of entries is halved when an AppendEntries RPC times out or
+    /// fails with a network error, and is doubled, up to `max_payload_entries`, when it succeeds.
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_adaptive_payload: bool,
+
+    /// The maximum total size in bytes of the entries in an AppendEntries RPC.
+    ///
+    /// The size of an entry is provided by [`RaftPayload::payload_size()`]. An AppendEntries RPC
+    /// always includes at least one entry, even if it is larger than this limit.
+    ///
+    /// The default value 0 means no limit.
+    ///
+    /// [`RaftPayload::payload_size()`]: crate::entry::RaftPayload::payload_size
+    #[clap(long, default_value = "0")]
+    pub max_payload_bytes: u64,
+
+    /// The size in bytes of the serialized entries of an AppendEntries RPC, at or above which
+    /// they should be compressed on the wire.
+    ///
+    /// The leader asks a target whether it can decompress entries with
+    /// [`RaftNetworkV2::entries_compression_supported()`] before replicating logs to it. If it
+    /// can, the threshold is passed to [`RaftNetworkV2::append_entries()`] via
+    /// [`RPCOption::entries_compression_threshold()`], and the network implementation compresses
+    /// the entries, e.g., with [`network::compress()`].
+    ///
+    /// The default value 0 disables compression.
+    ///
+    /// It requires feature flag `entries-compression`.
+    ///
+    /// [`RaftNetworkV2::entries_compression_supported()`]: crate::network::v2::RaftNetworkV2::entries_compression_supported
+    /// [`RaftNetworkV2::append_entries()`]: crate::network::v2::RaftNetworkV2::append_entries
+    /// [`RPCOption::entries_compression_threshold()`]: crate::network::RPCOption::entries_compression_threshold
+    /// [`network::compress()`]: crate::network::compress
+    #[clap(long, default_value = "0")]
+    pub entries_compression_threshold: u64,
+
+    /// The max interval in milliseconds to back off after a failed replication RPC.
+    ///
+    /// When it is greater than 0, after an AppendEntries RPC to a follower/learner fails, the
+    /// leader waits before the next RPC to it with an exponential backoff: starting from
+    /// `heartbeat_interval`, doubled on every failure, up to this value, and randomized with
+    /// jitter. It is reset when an RPC succeeds.
+    ///
+    /// The default value 0 disables it: the leader only backs off for an
+    /// [`Unreachable`](`crate::error::Unreachable`) error, with the policy returned by
+    /// [`RaftNetworkV2::backoff()`](`crate::network::v2::RaftNetworkV2::backoff`).
+    #[clap(long, default_value = "0")]
+    pub replication_backoff_max: u64,
+
+    /// The number of consecutive failed replication RPCs to a follower/learner to open its
+    /// circuit breaker.
+    ///
+    /// While the circuit breaker to a target is open, the errors of replicating to it are no
+    /// longer logged as warnings, and the target is listed in
+    /// [`RaftMetrics::replication_breaker`]. It is closed when an RPC succeeds.
+    ///
+    /// The default value 0 disables the circuit breaker.
+    ///
+    /// [`RaftMetrics::replication_breaker`]: crate::metrics::RaftMetrics::replication_breaker
+    #[clap(long, default_value = "0")]
+    pub replication_breaker_threshold: u64,
+
+    /// The number of logs a follower/learner has accepted but not yet applied, at which it asks
+    /// the leader to slow down replication.
+    ///
+    /// When the apply lag of this node reaches this value, it replies to AppendEntries with
+    /// [`AppendEntriesResponse::Backpressure`], and the leader pauses replication to it for a
+    /// `heartbeat_interval`, and sends smaller batches if `enable_adaptive_payload` is enabled.
+    /// This prevents a slow follower from being overrun until it falls behind
+    /// `replication_lag_threshold` and has to be sent a snapshot.
+    ///
+    /// A leader built before protocol version 2 does not understand this reply, and is replied
+    /// with a plain success instead, i.e., it is not throttled.
+    ///
+    /// The default value 0 disables it.
+    ///
+    /// [`AppendEntriesResponse::Backpressure`]: crate::raft::AppendEntriesResponse::Backpressure
+    #[clap(long, default_value = "0")]
+    pub backpressure_apply_lag: u64,
+
+    /// The number of logs a leader has appended but not yet applied, at which it rejects client
+    /// writes.
+    ///
+    /// When the leader's own state machine falls behind its log by this many entries,
+    /// [`Raft::client_write()`] returns an [`Overloaded`] error at once instead of appending more
+    /// logs, so that a slow state machine does not let the log and the pending responses grow
+    /// without bound. Membership changes are not rejected.
+    ///
+    /// The default value 0 disables it.
+    ///
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`Overloaded`]: crate::error::Overloaded
+    #[clap(long, default_value = "0")]
+    pub max_unapplied_logs: u64,
+
     /// The distance behind in log replication a follower must fall before it is considered lagging
     ///
     /// A follower falls behind this index are replicated with snapshot.
@@ -163,6 +398,9 @@ pub struct Config {
     pub replication_lag_threshold: u64,
 
     /// The snapshot policy to use for a Raft node.
+    ///
+    /// Syntax: `never`, or a comma separated list of `since_last:<num_logs>` and
+    /// `interval:<milliseconds>`. A snapshot is built when any of them is satisfied.
     #[clap(
         long,
         default_value = "since_last:5000",
@@ -170,20 +408,226 @@ pub struct Config {
     )]
     pub snapshot_policy: SnapshotPolicy,
 
+    /// When the log store syncs appended logs to disk.
+    ///
+    /// Syntax: `per_append`, `per_batch`, `interval:<milliseconds>` or `never`.
+    /// See [`FsyncPolicy`].
+    #[clap(
+        long,
+        default_value = "per_append",
+        value_parser=parse_fsync_policy
+    )]
+    pub fsync_policy: FsyncPolicy,
+
+    /// The max number of times `RaftCore` retries a log store operation that fails with a
+    /// [transient] [`StorageError`], before shutting down.
+    ///
+    /// It waits `heartbeat_interval` before the first retry, doubled on every retry. `RaftCore`
+    /// keeps handling other events while waiting, such as heartbeats and elections, but does not
+    /// run other storage operations, to keep them in order.
+    ///
+    /// It applies to saving the vote and the committed log id, truncating and purging logs, and
+    /// appending logs if the log store gives the entries back with
+    /// [`RaftLogStorage::try_append()`]. In the state machine, it applies to applying entries and
+    /// getting the current snapshot; an `apply()` that returns a transient error must leave the
+    /// state machine unchanged.
+    ///
+    /// The default value 0 disables it: any storage error shuts down the node.
+    ///
+    /// [transient]: crate::StorageError::is_transient
+    /// [`StorageError`]: crate::StorageError
+    /// [`RaftLogStorage::try_append()`]: crate::storage::RaftLogStorage::try_append
+    #[clap(long, default_value = "0")]
+    pub storage_retry_max: u64,
+
+    /// The duration in milliseconds after which a call to the log store or the state machine is
+    /// reported as slow.
+    ///
+    /// A slow call is logged with a `tracing` warning and counted in
+    /// [`RaftMetrics::slow_io`]. For appending logs, the time from submitting the logs until they
+    /// are flushed is checked. A failed call and the delay before retrying it are not counted.
+    /// A slow fsync is a common cause of missed heartbeats and unexpected elections.
+    ///
+    /// The value 0 disables it.
+    ///
+    /// [`RaftMetrics::slow_io`]: crate::metrics::RaftMetrics::slow_io
+    #[clap(long, default_value = "100")]
+    pub slow_io_threshold: u64,
+
+    /// The maximum number of [`RaftEvent`]s buffered for the subscribers.
+    ///
+    /// A subscriber registered with [`Raft::subscribe()`] that falls behind by more than this
+    /// number of events misses the oldest ones, and receives a [`RaftEvent::Lagged`] instead.
+    ///
+    /// [`RaftEvent`]: crate::raft::RaftEvent
+    /// [`RaftEvent::Lagged`]: crate::raft::RaftEvent::Lagged
+    /// [`Raft::subscribe()`]: crate::Raft::subscribe
+    #[clap(long, default_value = "1024")]
+    pub event_buffer_size: u64,
+
     /// The maximum snapshot chunk size allowed when transmitting snapshots (in bytes)
+    ///
+    /// It is used by the default chunked snapshot transport to slice
+    /// [`InstallSnapshotRequest::data`]. It is passed to [`RaftNetworkV2::full_snapshot()`] via
+    /// [`RPCOption::snapshot_chunk_size()`], so that an application defined transport can use
+    /// it too.
+    ///
+    /// A smaller value suits slow WAN links, where a large chunk may exceed the RPC timeout; a
+    /// larger value reduces the number of round trips on a fast LAN.
+    ///
+    /// It must be greater than 0.
+    ///
+    /// [`InstallSnapshotRequest::data`]: crate::raft::InstallSnapshotRequest::data
+    /// [`RaftNetworkV2::full_snapshot()`]: crate::network::v2::RaftNetworkV2::full_snapshot
+    /// [`RPCOption::snapshot_chunk_size()`]: crate::network::RPCOption::snapshot_chunk_size
     #[clap(long, default_value = "3MiB", value_parser=parse_bytes_with_unit)]
     pub snapshot_max_chunk_size: u64,
 
+    /// The maximum rate, in bytes per second, at which a snapshot is transmitted to a target.
+    ///
+    /// Transmitting a large snapshot at full speed may saturate the network and delay
+    /// AppendEntries and heartbeat RPCs, which may cause unnecessary elections.
+    /// The default chunked snapshot transport sleeps between chunks to keep the average rate
+    /// below this value. It is passed to [`RaftNetworkV2::full_snapshot()`] via
+    /// [`RPCOption::snapshot_max_bytes_per_sec()`].
+    ///
+    /// `0` means no limit.
+    ///
+    /// [`RaftNetworkV2::full_snapshot()`]: crate::network::v2::RaftNetworkV2::full_snapshot
+    /// [`RPCOption::snapshot_max_bytes_per_sec()`]: crate::network::RPCOption::snapshot_max_bytes_per_sec
+    #[clap(long, default_value = "0", value_parser=parse_bytes_with_unit)]
+    pub snapshot_max_bytes_per_sec: u64,
+
+    /// Whether to compress snapshot chunks with gzip when sending a snapshot by chunks.
+    ///
+    /// It is passed to the default chunked snapshot transport via
+    /// [`RPCOption::snapshot_compression()`].
+    /// The snapshot is compressed only if [`RaftNetworkV2::capabilities()`] of the target reports
+    /// [`Capabilities::snapshot_compression`]; it is sent uncompressed to a target that does not
+    /// support negotiation or does not answer. If the target still fails to decompress a chunk,
+    /// the sender falls back to sending uncompressed data.
+    ///
+    /// It requires feature flag `snapshot-compression`.
+    ///
+    /// [`RPCOption::snapshot_compression()`]: crate::network::RPCOption::snapshot_compression
+    /// [`RaftNetworkV2::capabilities()`]: crate::network::v2::RaftNetworkV2::capabilities
+    /// [`Capabilities::snapshot_compression`]: crate::network::Capabilities::snapshot_compression
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub snapshot_compression: bool,
+
+    /// The maximum time in milliseconds a follower waits for the next chunk of a snapshot being
+    /// received by [`Raft::install_snapshot()`], before dropping the partially received snapshot.
+    ///
+    /// A Leader may crash or step down in the middle of sending a snapshot. Without a timeout, the
+    /// receiving state and the `SnapshotData` being written are kept until another snapshot is
+    /// received.
+    ///
+    /// A dropped snapshot is counted in [`RaftMetrics::snapshot_receive_timeouts`] and reported
+    /// with a [`RaftEvent::SnapshotReceiveTimedOut`]. Its received data is discarded with
+    /// [`RaftStateMachine::discard_receiving_snapshot()`] and will not be resumed.
+    ///
+    /// `0` means no timeout.
+    ///
+    /// [`Raft::install_snapshot()`]: crate::Raft::install_snapshot
+    /// [`RaftMetrics::snapshot_receive_timeouts`]: crate::RaftMetrics::snapshot_receive_timeouts
+    /// [`RaftEvent::SnapshotReceiveTimedOut`]: crate::raft::RaftEvent::SnapshotReceiveTimedOut
+    /// [`RaftStateMachine::discard_receiving_snapshot()`]: crate::storage::RaftStateMachine::discard_receiving_snapshot
+    #[clap(long, default_value = "0")]
+    pub snapshot_receive_idle_timeout: u64,
+
+    /// Whether to reject a snapshot chunk whose offset does not follow the last received one.
+    ///
+    /// By default, a follower receiving snapshot chunks with [`Raft::install_snapshot()`] seeks
+    /// to any offset a chunk specifies, which leaves a gap in the data if the sender skips a
+    /// chunk. If enabled, such a chunk is rejected with a [`SnapshotMismatch`] error that tells
+    /// the sender the expected offset, so that a transport bug is caught early.
+    /// A chunk with offset `0` is always accepted, which restarts the transmission.
+    ///
+    /// [`Raft::install_snapshot()`]: crate::Raft::install_snapshot
+    /// [`SnapshotMismatch`]: crate::error::SnapshotMismatch
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub strict_snapshot_offset: bool,
+
     /// The maximum number of logs to keep that are already included in **snapshot**.
     ///
     /// Logs that are not in snapshot will never be purged.
     #[clap(long, default_value = "1000")]
     pub max_in_snapshot_log_to_keep: u64,
 
+    /// Whether to keep logs after installing a snapshot received from the leader.
+    ///
+    /// By default, all logs included in an installed snapshot are purged at once.
+    /// If enabled, up to `max_in_snapshot_log_to_keep` logs are kept, as is done after building a
+    /// snapshot locally, so that other lagging nodes can still be replicated with logs, e.g., when
+    /// this node becomes the leader.
+    /// Logs are only kept if the local logs do not conflict with the installed snapshot.
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub keep_logs_after_snapshot_install: bool,
+
     /// The minimal number of applied logs to purge in a batch.
     #[clap(long, default_value = "1")]
     pub purge_batch_size: u64,
 
+    /// The maximum number of logs to purge with one call to [`RaftLogStorage::purge()`].
+    ///
+    /// A large purge, e.g., after installing a snapshot, is split into parts of at most this many
+    /// logs. A part is purged every tick or replication progress, so that a huge purge does not
+    /// block `RaftCore` for long.
+    ///
+    /// The default value 0 purges all the logs at once.
+    ///
+    /// [`RaftLogStorage::purge()`]: crate::storage::RaftLogStorage::purge
+    #[clap(long, default_value = "0")]
+    pub purge_max_batch_size: u64,
+
+    /// Whether a leader keeps the logs that are not yet replicated to every follower and learner.
+    ///
+    /// If enabled, the leader does not purge a log until all the followers and learners have
+    /// replicated it, so that a lagging node catches up with logs instead of a snapshot.
+    /// Note that an offline node blocks purging on the leader until it is removed from the
+    /// membership, unless [`keep_logs_for_lagging_max`] is set.
+    ///
+    /// [`keep_logs_for_lagging_max`]: Self::keep_logs_for_lagging_max
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub keep_logs_for_lagging: bool,
+
+    /// The maximum number of logs a follower or learner can lag behind the leader's last log for
+    /// the leader to keep the logs for it, when [`keep_logs_for_lagging`] is enabled.
+    ///
+    /// A node lagging further, e.g., one that has been offline for long, no longer holds back
+    /// purging on the leader, and catches up with a snapshot. So that a routinely restarted node
+    /// catches up with logs, while the disk usage of the leader is still bounded.
+    ///
+    /// The default value 0 means unlimited.
+    ///
+    /// [`keep_logs_for_lagging`]: Self::keep_logs_for_lagging
+    #[clap(long, default_value = "0")]
+    pub keep_logs_for_lagging_max: u64,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout based events are all disabled:
@@ -206,6 +650,19 @@ pub struct Config {
     )]
     pub enable_tick: bool,
 
+    /// The interval of the tick in milliseconds.
+    ///
+    /// Every tick `RaftCore` checks the election timeout, sends a heartbeat if it is due, checks
+    /// the quorum and the leader lease, and continues a split purge. A smaller interval makes
+    /// these timeouts more precise at the cost of more wakeups.
+    ///
+    /// It must be smaller than `heartbeat_interval`, otherwise heartbeats are delayed and
+    /// followers may start spurious elections.
+    ///
+    /// `0` means to use `heartbeat_interval / 2`.
+    #[clap(long, default_value = "0")]
+    pub tick_interval: u64,
+
     /// Whether a leader sends heartbeat log to following nodes, i.e., followers and learners.
     // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
     // https://github.com/clap-rs/clap/discussions/4374
@@ -228,6 +685,241 @@ pub struct Config {
            default_missing_value = "true"
     )]
     pub enable_elect: bool,
+
+    /// Whether this node is a witness.
+    ///
+    /// A witness is a voter that takes part in elections and commit quorums, but stores only the
+    /// log ids and membership logs, not the application data: a normal log entry it receives is
+    /// stored as a blank log with the same log id. A witness never starts an election and never
+    /// becomes the leader.
+    ///
+    /// A snapshot is still installed on a witness as is. To avoid sending snapshots to a witness,
+    /// the leader should keep enough logs with [`Config::max_in_snapshot_log_to_keep`].
+    ///
+    /// Because a witness does not have the data of the logs it acknowledged, when all of the other
+    /// up-to-date voters are lost, the cluster can not elect a new leader.
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub witness: bool,
+
+    /// Whether a follower runs a pre-vote before starting an election on election timeout.
+    ///
+    /// With pre-vote, a follower asks the voters whether they would grant its vote, with
+    /// [`RaftNetworkV2::pre_vote()`], without increasing its term. It starts an election only if a
+    /// quorum would grant it. This way a node rejoining from a network partition does not
+    /// disturb a stable leader with a higher term.
+    ///
+    /// The application has to implement [`RaftNetworkV2::pre_vote()`] for it to take effect.
+    /// Otherwise every pre-vote is assumed to be granted, and an election starts at once.
+    ///
+    /// [`RaftNetworkV2::pre_vote()`]: crate::network::v2::RaftNetworkV2::pre_vote
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_pre_vote: bool,
+
+    /// Whether a leader steps down if it has not heard from a quorum for a while.
+    ///
+    /// If enabled, a leader that has not received a response from a quorum for the leader lease,
+    /// i.e., `election_timeout_max`, reverts to a candidate and stops replicating, instead of
+    /// accepting proposals that can not be committed. Such a leader is usually partitioned from
+    /// the other nodes, which may have elected a new leader.
+    ///
+    /// The leader learns that a quorum is alive with heartbeats, thus `enable_heartbeat` should
+    /// also be enabled.
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_check_quorum: bool,
+
+    /// Whether a leader serves [`Raft::ensure_linearizable()`] with a lease, without sending
+    /// heartbeats to a quorum.
+    ///
+    /// A follower does not grant a vote to another node for `election_timeout_max` since it
+    /// received the last heartbeat from the leader. Thus the leader is still the leader in such a
+    /// period since the last heartbeat acknowledged by a quorum is sent, minus
+    /// `max_clock_drift`. During this period, a read does not need to send heartbeats to
+    /// confirm the leadership.
+    ///
+    /// It relies on the bounded clock drift among the nodes. If the drift exceeds
+    /// `max_clock_drift`, a read may not be linearizable.
+    ///
+    /// [`Raft::ensure_linearizable()`]: crate::Raft::ensure_linearizable
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_lease_read: bool,
+
+    /// The maximum clock drift in milliseconds among nodes, which shortens the lease for lease
+    /// read.
+    ///
+    /// It is only used when `enable_lease_read` is enabled.
+    #[clap(long, default_value = "50")]
+    pub max_clock_drift: u64,
+
+    /// The priority of this node to become the leader, in range `0..=max_election_priority`.
+    ///
+    /// A node with a lower priority waits for additional
+    /// `(max_election_priority - election_priority) * election_timeout_max` before starting an
+    /// election, so that a higher priority node has a chance to become the leader first.
+    #[clap(long, default_value = "0")]
+    pub election_priority: u64,
+
+    /// Whether a newly established leader appends a blank log entry at once.
+    ///
+    /// A leader can not commit logs of previous terms, nor serve linearizable reads, until a log
+    /// entry of its own term is committed, which is the blank log by default.
+    /// When disabled, the first entry proposed by the application in the new term plays this role,
+    /// e.g., an entry carrying application data that should be written when a leader is
+    /// established. Before it is committed, [`RaftMetrics::leader_ready`] is `false` and
+    /// [`Raft::ensure_linearizable()`] waits for it.
+    ///
+    /// [`RaftMetrics::leader_ready`]: crate::metrics::RaftMetrics::leader_ready
+    /// [`Raft::ensure_linearizable()`]: crate::Raft::ensure_linearizable
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_blank_log: bool,
+
+    /// The max election priority of the cluster.
+    ///
+    /// It should be the same on every node. The default value 0 disables election priority.
+    #[clap(long, default_value = "0")]
+    pub max_election_priority: u64,
+
+    /// Whether [`Raft::change_membership()`] waits for the learners it promotes to voters to catch
+    /// up with the leader, before proposing the new membership.
+    ///
+    /// A learner is considered caught up when its replication lag is within
+    /// `replication_lag_threshold`. Adding a far-behind voter may make the cluster unavailable
+    /// until the new voter catches up, if the quorum depends on it.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub wait_learner_caught_up: bool,
+
+    /// The max time in milliseconds [`Raft::change_membership()`] waits for a learner to catch up,
+    /// if [`Config::wait_learner_caught_up`] is enabled.
+    ///
+    /// If a learner does not catch up in time, the change is rejected with
+    /// [`ChangeMembershipError::LearnerLagging`].
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`ChangeMembershipError::LearnerLagging`]: crate::error::ChangeMembershipError::LearnerLagging
+    #[clap(long, default_value = "10000")]
+    pub wait_learner_caught_up_timeout: u64,
+
+    /// Whether [`Raft::change_membership()`] checks that the new voters are reachable before
+    /// proposing the new membership.
+    ///
+    /// A voter is considered reachable if the leader has heard from it within the leader lease.
+    /// If the reachable voters do not constitute a quorum of the new membership, the change is
+    /// rejected with [`ChangeMembershipError::VotersUnreachable`], which lists the unreachable
+    /// voters. For example, adding three unreachable nodes to a cluster of two voters at once.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`ChangeMembershipError::VotersUnreachable`]: crate::error::ChangeMembershipError::VotersUnreachable
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub check_voters_reachable: bool,
+
+    /// Whether a leader broadcasts a heartbeat at once when the commit index advances, instead of
+    /// waiting for the next replication or heartbeat to carry it to the followers.
+    ///
+    /// The heartbeat is an empty AppendEntries carrying the new `leader_commit`, sent by the
+    /// dedicated heartbeat workers, so that followers and learners learn about the commit, apply
+    /// the logs and serve reads sooner. It costs one extra RPC per target for every commit.
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_commit_broadcast: bool,
+
+    /// Whether to save the committed log id with [`RaftLogStorage::save_committed()`] every time
+    /// it advances.
+    ///
+    /// If the log store persists it, a restarted node re-applies the logs up to the saved
+    /// committed log id with [`RaftLogStorage::read_committed()`], without waiting for a leader to
+    /// tell it the commit index. Disable it to skip the call if the log store does not implement
+    /// it, or if the state machine persists every applied log.
+    ///
+    /// [`RaftLogStorage::save_committed()`]: crate::storage::RaftLogStorage::save_committed
+    /// [`RaftLogStorage::read_committed()`]: crate::storage::RaftLogStorage::read_committed
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub save_committed: bool,
+
+    /// Whether a follower or learner forwards [`Raft::client_write()`] to the leader, instead of
+    /// returning a [`ForwardToLeader`] error.
+    ///
+    /// The request is sent with [`RaftNetworkV2::forward_client_write()`], and the leader's
+    /// response is returned to the caller. If the leader is unknown or can not be reached, the
+    /// caller still receives a [`ForwardToLeader`] error. If the request may have been received by
+    /// the leader but no response is received within [`Config::forward_to_leader_timeout`], the
+    /// caller receives a [`ForwardUnknownOutcome`] error: the write may or may not be applied.
+    ///
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`ForwardUnknownOutcome`]: crate::error::ForwardUnknownOutcome
+    /// [`RaftNetworkV2::forward_client_write()`]: crate::network::v2::RaftNetworkV2::forward_client_write
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub forward_to_leader: bool,
 }
 
 /// Updatable config for a raft runtime.
@@ -257,11 +949,92 @@ impl Config {
         RT::thread_rng().gen_range(self.election_timeout_min..self.election_timeout_max)
     }
 
+    /// Get the interval of the tick.
+    pub fn tick_interval(&self) -> Duration {
+        if self.tick_interval > 0 {
+            Duration::from_millis(self.tick_interval)
+        } else {
+            Duration::from_millis(std::cmp::max(self.heartbeat_interval / 2, 1))
+        }
+    }
+
+    /// Get the timeout for an AppendEntries RPC.
+    pub fn append_entries_timeout(&self) -> Duration {
+        if self.append_entries_timeout > 0 {
+            Duration::from_millis(self.append_entries_timeout)
+        } else {
+            Duration::from_millis(self.heartbeat_interval)
+        }
+    }
+
+    /// Get the timeout for a Vote or PreVote RPC.
+    pub fn vote_timeout(&self) -> Duration {
+        if self.vote_timeout > 0 {
+            Duration::from_millis(self.vote_timeout)
+        } else {
+            Duration::from_millis(self.election_timeout_min)
+        }
+    }
+
+    /// Get the timeout for forwarding a client write to the leader.
+    pub fn forward_to_leader_timeout(&self) -> Duration {
+        if self.forward_to_leader_timeout > 0 {
+            Duration::from_millis(self.forward_to_leader_timeout)
+        } else {
+            Duration::from_millis(self.election_timeout_max)
+        }
+    }
+
+    /// Get the max time to wait for a learner to catch up before promoting it to a voter.
+    pub fn wait_learner_caught_up_timeout(&self) -> Duration {
+        Duration::from_millis(self.wait_learner_caught_up_timeout)
+    }
+
     /// Get the timeout for sending and installing the last snapshot segment.
     pub fn install_snapshot_timeout(&self) -> Duration {
         Duration::from_millis(self.install_snapshot_timeout)
     }
 
+    /// Get the idle timeout for receiving a snapshot by chunks, or `None` if there is no timeout.
+    pub fn snapshot_receive_idle_timeout(&self) -> Option<Duration> {
+        if self.snapshot_receive_idle_timeout > 0 {
+            Some(Duration::from_millis(self.snapshot_receive_idle_timeout))
+        } else {
+            None
+        }
+    }
+
+    /// Get the additional time to wait before starting an election, for a node with a lower
+    /// election priority.
+    pub(crate) fn election_priority_delay(&self) -> Duration {
+        let levels = self.max_election_priority.saturating_sub(self.election_priority);
+        Duration::from_millis(levels.saturating_mul(self.election_timeout_max))
+    }
+
+    /// Get the lease in which a leader serves reads without sending heartbeats, or `None` if lease
+    /// read is disabled.
+    pub(crate) fn read_lease(&self) -> Option<Duration> {
+        if !self.enable_lease_read {
+            return None;
+        }
+
+        let lease = self.election_timeout_max.saturating_sub(self.max_clock_drift);
+        if lease > 0 {
+            Some(Duration::from_millis(lease))
+        } else {
+            None
+        }
+    }
+
+    /// Get the max snapshot transmission rate in bytes per second, or `None` if it is unlimited.
+    pub fn snapshot_max_bytes_per_sec(&self) -> Option<u64> {
+        if self.snapshot_max_bytes_per_sec > 0 {
+            Some(self.snapshot_max_bytes_per_sec)
+        } else {
+            None
+        }
+    }
+
     /// Get the timeout for sending a non-last snapshot segment.
     #[deprecated(
         since = "0.9.0",
@@ -303,10 +1076,44 @@ impl Config {
             });
         }
 
+        if self.tick_interval >= self.heartbeat_interval {
+            return Err(ConfigError::TickIntervalGEHeartBeat {
+                tick_interval: self.tick_interval,
+                heartbeat_interval: self.heartbeat_interval,
+            });
+        }
+
         if self.max_payload_entries == 0 {
             return Err(ConfigError::MaxPayloadIs0);
         }
 
+        if self.max_in_flight_appends == 0 {
+            return Err(ConfigError::MaxInFlightAppendsIs0);
+        }
+
+        if self.event_buffer_size == 0 {
+            return Err(ConfigError::EventBufferSizeIs0);
+        }
+
+        if self.snapshot_max_chunk_size == 0 {
+            return Err(ConfigError::SnapshotMaxChunkSizeIs0);
+        }
+
+        if self.election_priority > self.max_election_priority {
+            return Err(ConfigError::ElectionPriority {
+                priority: self.election_priority,
+                max: self.max_election_priority,
+            });
+        }
+
+        if self.snapshot_compression && !cfg!(feature = "snapshot-compression") {
+            return Err(ConfigError::SnapshotCompressionNotEnabled);
+        }
+
+        if self.entries_compression_threshold > 0 && !cfg!(feature = "entries-compression") {
+            return Err(ConfigError::EntriesCompressionNotEnabled);
+        }
+
         Ok(self)
     }
 }
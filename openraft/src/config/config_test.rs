@@ -2,6 +2,7 @@ use core::time::Duration;
 
 use crate::config::error::ConfigError;
 use crate::Config;
+use crate::FsyncPolicy;
 use crate::SnapshotPolicy;
 
 #[test]
@@ -13,9 +14,30 @@ fn test_config_defaults() {
 
     assert_eq!(50, cfg.heartbeat_interval);
     assert_eq!(300, cfg.max_payload_entries);
+    assert_eq!(1, cfg.max_in_flight_appends);
+    assert!(!cfg.enable_adaptive_payload);
+    assert_eq!(0, cfg.max_payload_bytes);
+    assert_eq!(0, cfg.replication_backoff_max);
+    assert_eq!(0, cfg.replication_breaker_threshold);
+    assert_eq!(0, cfg.backpressure_apply_lag);
+    assert_eq!(0, cfg.max_unapplied_logs);
     assert_eq!(5000, cfg.replication_lag_threshold);
 
     assert_eq!(3 * 1024 * 1024, cfg.snapshot_max_chunk_size);
+    assert_eq!(0, cfg.snapshot_max_bytes_per_sec);
+    assert_eq!(None, cfg.snapshot_max_bytes_per_sec());
+    assert!(!cfg.snapshot_compression);
+    assert_eq!(0, cfg.entries_compression_threshold);
+    assert!(!cfg.keep_logs_after_snapshot_install);
+    assert_eq!(None, cfg.snapshot_receive_idle_timeout());
+    assert!(!cfg.strict_snapshot_offset);
+    assert!(!cfg.enable_lease_read);
+    assert!(!cfg.forward_to_leader);
+    assert_eq!(50, cfg.max_clock_drift);
+    assert_eq!(None, cfg.read_lease());
+    assert_eq!(0, cfg.election_priority);
+    assert_eq!(0, cfg.max_election_priority);
+    assert_eq!(Duration::from_millis(0), cfg.election_priority_delay());
     assert_eq!(SnapshotPolicy::LogsSinceLast(5000), cfg.snapshot_policy);
 }
 
@@ -46,6 +68,66 @@ fn test_invalid_election_timeout_config_produces_expected_error() {
     });
 }
 
+#[test]
+fn test_invalid_election_priority() -> anyhow::Result<()> {
+    let config = Config {
+        election_priority: 3,
+        max_election_priority: 2,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    let err = res.unwrap_err();
+    assert_eq!(err, ConfigError::ElectionPriority { priority: 3, max: 2 });
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_snapshot_max_chunk_size() -> anyhow::Result<()> {
+    let config = Config {
+        snapshot_max_chunk_size: 0,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::SnapshotMaxChunkSizeIs0);
+
+    let res = Config::build(&["foo", "--snapshot-max-chunk-size=0"]);
+    assert_eq!(res.unwrap_err(), ConfigError::SnapshotMaxChunkSizeIs0);
+
+    let config = Config::build(&["foo", "--snapshot-max-chunk-size=64KiB"])?;
+    assert_eq!(64 * 1024, config.snapshot_max_chunk_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_compression() -> anyhow::Result<()> {
+    let res = Config::build(&["foo", "--snapshot-compression"]);
+
+    if cfg!(feature = "snapshot-compression") {
+        assert!(res?.snapshot_compression);
+    } else {
+        assert_eq!(res.unwrap_err(), ConfigError::SnapshotCompressionNotEnabled);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_entries_compression_threshold() -> anyhow::Result<()> {
+    let res = Config::build(&["foo", "--entries-compression-threshold=4096"]);
+
+    if cfg!(feature = "entries-compression") {
+        assert_eq!(4096, res?.entries_compression_threshold);
+    } else {
+        assert_eq!(res.unwrap_err(), ConfigError::EntriesCompressionNotEnabled);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_build() -> anyhow::Result<()> {
     let config = Config::build(&[
@@ -57,11 +139,26 @@ fn test_build() -> anyhow::Result<()> {
         "--send-snapshot-timeout=199",
         "--install-snapshot-timeout=200",
         "--max-payload-entries=201",
+        "--max-in-flight-appends=4",
+        "--max-payload-bytes=1024",
+        "--replication-backoff-max=2000",
+        "--replication-breaker-threshold=5",
+        "--backpressure-apply-lag=100",
+        "--max-unapplied-logs=150",
         "--snapshot-policy=since_last:202",
         "--replication-lag-threshold=203",
         "--snapshot-max-chunk-size=204",
+        "--snapshot-max-bytes-per-sec=1KiB",
         "--max-in-snapshot-log-to-keep=205",
+        "--keep-logs-after-snapshot-install",
+        "--snapshot-receive-idle-timeout=208",
+        "--strict-snapshot-offset",
+        "--enable-lease-read",
+        "--max-clock-drift=5",
+        "--election-priority=1",
+        "--max-election-priority=3",
         "--purge-batch-size=207",
+        "--forward-to-leader",
     ])?;
 
     assert_eq!("bar", config.cluster_name);
@@ -75,11 +172,30 @@ fn test_build() -> anyhow::Result<()> {
     }
     assert_eq!(200, config.install_snapshot_timeout);
     assert_eq!(201, config.max_payload_entries);
+    assert_eq!(4, config.max_in_flight_appends);
+    assert_eq!(1024, config.max_payload_bytes);
+    assert_eq!(2000, config.replication_backoff_max);
+    assert_eq!(5, config.replication_breaker_threshold);
+    assert_eq!(100, config.backpressure_apply_lag);
+    assert_eq!(150, config.max_unapplied_logs);
     assert_eq!(SnapshotPolicy::LogsSinceLast(202), config.snapshot_policy);
     assert_eq!(203, config.replication_lag_threshold);
     assert_eq!(204, config.snapshot_max_chunk_size);
+    assert_eq!(1024, config.snapshot_max_bytes_per_sec);
+    assert_eq!(Some(1024), config.snapshot_max_bytes_per_sec());
     assert_eq!(205, config.max_in_snapshot_log_to_keep);
+    assert!(config.keep_logs_after_snapshot_install);
+    assert_eq!(208, config.snapshot_receive_idle_timeout);
+    assert_eq!(Some(Duration::from_millis(208)), config.snapshot_receive_idle_timeout());
+    assert!(config.strict_snapshot_offset);
+    assert!(config.enable_lease_read);
+    assert_eq!(5, config.max_clock_drift);
+    assert_eq!(Some(Duration::from_millis(15)), config.read_lease());
+    assert_eq!(1, config.election_priority);
+    assert_eq!(3, config.max_election_priority);
+    assert_eq!(Duration::from_millis(40), config.election_priority_delay());
     assert_eq!(207, config.purge_batch_size);
+    assert!(config.forward_to_leader);
 
     // Test config methods
     #[allow(deprecated)]
@@ -106,9 +222,29 @@ fn test_config_snapshot_policy() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--snapshot-policy=since_last:3"])?;
     assert_eq!(SnapshotPolicy::LogsSinceLast(3), config.snapshot_policy);
 
+    let config = Config::build(&["foo", "--snapshot-policy=interval:1000"])?;
+    assert_eq!(
+        SnapshotPolicy::Interval(Duration::from_millis(1000)),
+        config.snapshot_policy
+    );
+
+    let config = Config::build(&["foo", "--snapshot-policy=since_last:3,interval:1000,interval:500"])?;
+    assert_eq!(
+        SnapshotPolicy::Any(vec![
+            SnapshotPolicy::LogsSinceLast(3),
+            SnapshotPolicy::Interval(Duration::from_millis(1000)),
+            SnapshotPolicy::Interval(Duration::from_millis(500)),
+        ]),
+        config.snapshot_policy
+    );
+    assert_eq!(Some(Duration::from_millis(500)), config.snapshot_policy.interval());
+
     let res = Config::build(&["foo", "--snapshot-policy=bar:3"]);
     assert!(res.is_err());
 
+    let res = Config::build(&["foo", "--snapshot-policy=since_last:3,never"]);
+    assert!(res.is_err());
+
     Ok(())
 }
 
@@ -162,3 +298,304 @@ fn test_config_enable_elect() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_config_enable_pre_vote() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--enable-pre-vote=false"])?;
+    assert_eq!(false, config.enable_pre_vote);
+
+    let config = Config::build(&["foo", "--enable-pre-vote=true"])?;
+    assert_eq!(true, config.enable_pre_vote);
+
+    let config = Config::build(&["foo", "--enable-pre-vote"])?;
+    assert_eq!(true, config.enable_pre_vote);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.enable_pre_vote);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_enable_blank_log() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--enable-blank-log=false"])?;
+    assert_eq!(false, config.enable_blank_log);
+
+    let config = Config::build(&["foo", "--enable-blank-log=true"])?;
+    assert_eq!(true, config.enable_blank_log);
+
+    let config = Config::build(&["foo", "--enable-blank-log"])?;
+    assert_eq!(true, config.enable_blank_log);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(true, config.enable_blank_log);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_wait_learner_caught_up() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--wait-learner-caught-up=false"])?;
+    assert_eq!(false, config.wait_learner_caught_up);
+
+    let config = Config::build(&["foo", "--wait-learner-caught-up=true"])?;
+    assert_eq!(true, config.wait_learner_caught_up);
+
+    let config = Config::build(&["foo", "--wait-learner-caught-up"])?;
+    assert_eq!(true, config.wait_learner_caught_up);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.wait_learner_caught_up);
+    assert_eq!(Duration::from_millis(10_000), config.wait_learner_caught_up_timeout());
+
+    let config = Config::build(&["foo", "--wait-learner-caught-up-timeout=200"])?;
+    assert_eq!(Duration::from_millis(200), config.wait_learner_caught_up_timeout());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_check_voters_reachable() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--check-voters-reachable=false"])?;
+    assert_eq!(false, config.check_voters_reachable);
+
+    let config = Config::build(&["foo", "--check-voters-reachable=true"])?;
+    assert_eq!(true, config.check_voters_reachable);
+
+    let config = Config::build(&["foo", "--check-voters-reachable"])?;
+    assert_eq!(true, config.check_voters_reachable);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.check_voters_reachable);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_enable_adaptive_payload() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--enable-adaptive-payload=false"])?;
+    assert_eq!(false, config.enable_adaptive_payload);
+
+    let config = Config::build(&["foo", "--enable-adaptive-payload=true"])?;
+    assert_eq!(true, config.enable_adaptive_payload);
+
+    let config = Config::build(&["foo", "--enable-adaptive-payload"])?;
+    assert_eq!(true, config.enable_adaptive_payload);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.enable_adaptive_payload);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_witness() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--witness=false"])?;
+    assert_eq!(false, config.witness);
+
+    let config = Config::build(&["foo", "--witness=true"])?;
+    assert_eq!(true, config.witness);
+
+    let config = Config::build(&["foo", "--witness"])?;
+    assert_eq!(true, config.witness);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.witness);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_enable_check_quorum() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--enable-check-quorum=false"])?;
+    assert_eq!(false, config.enable_check_quorum);
+
+    let config = Config::build(&["foo", "--enable-check-quorum=true"])?;
+    assert_eq!(true, config.enable_check_quorum);
+
+    let config = Config::build(&["foo", "--enable-check-quorum"])?;
+    assert_eq!(true, config.enable_check_quorum);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.enable_check_quorum);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_enable_commit_broadcast() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--enable-commit-broadcast=false"])?;
+    assert_eq!(false, config.enable_commit_broadcast);
+
+    let config = Config::build(&["foo", "--enable-commit-broadcast=true"])?;
+    assert_eq!(true, config.enable_commit_broadcast);
+
+    let config = Config::build(&["foo", "--enable-commit-broadcast"])?;
+    assert_eq!(true, config.enable_commit_broadcast);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.enable_commit_broadcast);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_save_committed() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--save-committed=false"])?;
+    assert_eq!(false, config.save_committed);
+
+    let config = Config::build(&["foo", "--save-committed=true"])?;
+    assert_eq!(true, config.save_committed);
+
+    let config = Config::build(&["foo", "--save-committed"])?;
+    assert_eq!(true, config.save_committed);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(true, config.save_committed);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_fsync_policy() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(FsyncPolicy::PerAppend, config.fsync_policy);
+
+    let config = Config::build(&["foo", "--fsync-policy=per_batch"])?;
+    assert_eq!(FsyncPolicy::PerBatch, config.fsync_policy);
+
+    let config = Config::build(&["foo", "--fsync-policy=interval:10"])?;
+    assert_eq!(FsyncPolicy::Interval(Duration::from_millis(10)), config.fsync_policy);
+
+    let config = Config::build(&["foo", "--fsync-policy=never"])?;
+    assert_eq!(FsyncPolicy::Never, config.fsync_policy);
+
+    let res = Config::build(&["foo", "--fsync-policy=always"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_storage_retry_max() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.storage_retry_max);
+
+    let config = Config::build(&["foo", "--storage-retry-max=3"])?;
+    assert_eq!(3, config.storage_retry_max);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_slow_io_threshold() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(100, config.slow_io_threshold);
+
+    let config = Config::build(&["foo", "--slow-io-threshold=0"])?;
+    assert_eq!(0, config.slow_io_threshold);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_event_buffer_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(1024, config.event_buffer_size);
+
+    let config = Config::build(&["foo", "--event-buffer-size=8"])?;
+    assert_eq!(8, config.event_buffer_size);
+
+    let res = Config::build(&["foo", "--event-buffer-size=0"]);
+    assert_eq!(Err(ConfigError::EventBufferSizeIs0), res);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_purge_max_batch_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.purge_max_batch_size);
+
+    let config = Config::build(&["foo", "--purge-max-batch-size=1024"])?;
+    assert_eq!(1024, config.purge_max_batch_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_keep_logs_for_lagging() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--keep-logs-for-lagging=false"])?;
+    assert_eq!(false, config.keep_logs_for_lagging);
+
+    let config = Config::build(&["foo", "--keep-logs-for-lagging=true"])?;
+    assert_eq!(true, config.keep_logs_for_lagging);
+
+    let config = Config::build(&["foo", "--keep-logs-for-lagging"])?;
+    assert_eq!(true, config.keep_logs_for_lagging);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.keep_logs_for_lagging);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_keep_logs_for_lagging_max() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.keep_logs_for_lagging_max);
+
+    let config = Config::build(&["foo", "--keep-logs-for-lagging", "--keep-logs-for-lagging-max=1000"])?;
+    assert_eq!(true, config.keep_logs_for_lagging);
+    assert_eq!(1000, config.keep_logs_for_lagging_max);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_rpc_timeout() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--heartbeat-interval=5", "--election-timeout-min=10"])?;
+    assert_eq!(Duration::from_millis(5), config.append_entries_timeout());
+    assert_eq!(Duration::from_millis(10), config.vote_timeout());
+    assert_eq!(
+        Duration::from_millis(config.election_timeout_max),
+        config.forward_to_leader_timeout()
+    );
+
+    let config = Config::build(&[
+        "foo",
+        "--append-entries-timeout=100",
+        "--vote-timeout=200",
+        "--forward-to-leader-timeout=300",
+    ])?;
+    assert_eq!(100, config.append_entries_timeout);
+    assert_eq!(200, config.vote_timeout);
+    assert_eq!(300, config.forward_to_leader_timeout);
+    assert_eq!(Duration::from_millis(100), config.append_entries_timeout());
+    assert_eq!(Duration::from_millis(200), config.vote_timeout());
+    assert_eq!(Duration::from_millis(300), config.forward_to_leader_timeout());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_tick_interval() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--heartbeat-interval=10"])?;
+    assert_eq!(0, config.tick_interval);
+    assert_eq!(Duration::from_millis(5), config.tick_interval());
+
+    let config = Config::build(&["foo", "--heartbeat-interval=10", "--tick-interval=3"])?;
+    assert_eq!(3, config.tick_interval);
+    assert_eq!(Duration::from_millis(3), config.tick_interval());
+
+    let res = Config::build(&["foo", "--heartbeat-interval=10", "--tick-interval=10"]);
+    assert_eq!(
+        Err(ConfigError::TickIntervalGEHeartBeat {
+            tick_interval: 10,
+            heartbeat_interval: 10
+        }),
+        res
+    );
+
+    Ok(())
+}
@@ -11,18 +11,46 @@ pub enum ConfigError {
     #[error("election timeout: min({min}) must be < max({max})")]
     ElectionTimeout { min: u64, max: u64 },
 
+    /// The election priority is greater than the max election priority.
+    #[error("election_priority({priority}) must be <= max_election_priority({max})")]
+    ElectionPriority { priority: u64, max: u64 },
+
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    #[error("max_in_flight_appends must be > 0")]
+    MaxInFlightAppendsIs0,
+
+    #[error("event_buffer_size must be > 0")]
+    EventBufferSizeIs0,
+
+    #[error("snapshot_max_chunk_size must be > 0")]
+    SnapshotMaxChunkSizeIs0,
+
+    #[error("snapshot_compression requires feature flag `snapshot-compression`")]
+    SnapshotCompressionNotEnabled,
+
+    #[error("entries_compression_threshold requires feature flag `entries-compression`")]
+    EntriesCompressionNotEnabled,
+
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
         election_timeout_min: u64,
         heartbeat_interval: u64,
     },
 
+    #[error("tick_interval({tick_interval}) must be < heartbeat_interval({heartbeat_interval})")]
+    TickIntervalGEHeartBeat {
+        tick_interval: u64,
+        heartbeat_interval: u64,
+    },
+
     #[error("snapshot policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
     InvalidSnapshotPolicy { invalid: String, syntax: String },
 
+    #[error("fsync policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidFsyncPolicy { invalid: String, syntax: String },
+
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber { invalid: String, reason: String },
 }
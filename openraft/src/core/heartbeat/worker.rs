@@ -1,7 +1,6 @@
 use std::fmt;
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
 
 use futures::FutureExt;
 
@@ -11,7 +10,7 @@ use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::notification::Notification;
 use crate::network::v2::RaftNetworkV2;
 use crate::network::RPCOption;
-use crate::raft::AppendEntriesRequest;
+use crate::raft::HeartbeatRequest;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::WatchReceiverOf;
@@ -20,6 +19,10 @@ use crate::Config;
 use crate::RaftTypeConfig;
 
 /// A dedicate worker sending heartbeat to a specific follower.
+///
+/// It runs in its own task with its own network client, separate from the replication task to
+/// the same target, so that a heartbeat is never delayed by a large AppendEntries or a snapshot
+/// being sent.
 pub struct HeartbeatWorker<C, N>
 where
     C: RaftTypeConfig,
@@ -79,20 +82,29 @@ where
                 continue;
             };
 
-            let timeout = Duration::from_millis(self.config.heartbeat_interval);
+            let timeout = self.config.append_entries_timeout();
             let option = RPCOption::new(timeout);
 
-            let payload = AppendEntriesRequest {
-                vote: *heartbeat.session_id.leader_vote.deref(),
-                prev_log_id: None,
-                leader_commit: heartbeat.committed,
-                entries: vec![],
-            };
+            let leader_vote = *heartbeat.session_id.leader_vote.deref();
+            let payload = HeartbeatRequest::new(leader_vote, heartbeat.committed);
 
-            let res = C::timeout(timeout, self.network.append_entries(payload, option)).await;
+            let res = C::timeout(timeout, self.network.heartbeat(payload, option)).await;
             tracing::debug!("{} sent a heartbeat: {}, result: {:?}", self, heartbeat, res);
 
             match res {
+                Ok(Ok(resp)) if resp.vote != leader_vote => {
+                    // A heartbeat is not an acknowledgement if the target has seen a higher vote.
+                    let res = self.tx_notification.send(Notification::HigherVote {
+                        target: self.target,
+                        higher: resp.vote,
+                        sender_vote: leader_vote,
+                    });
+
+                    if res.is_err() {
+                        tracing::error!("{} failed to send a higher vote to RaftCore. quit", self);
+                        return;
+                    }
+                }
                 Ok(Ok(_)) => {
                     let res = self.tx_notification.send(Notification::HeartbeatProgress {
                         session_id: heartbeat.session_id,
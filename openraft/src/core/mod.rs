@@ -11,7 +11,10 @@ mod raft_core;
 pub(crate) mod raft_msg;
 mod replication_state;
 mod server_state;
+mod slow_io;
 pub(crate) mod sm;
+mod snapshot_progress;
+mod storage_retry;
 mod tick;
 
 pub(crate) use raft_core::ApplyResult;
@@ -19,5 +22,8 @@ pub(crate) use raft_core::ApplyingEntry;
 pub use raft_core::RaftCore;
 pub(crate) use replication_state::replication_lag;
 pub use server_state::ServerState;
+pub(crate) use slow_io::SlowIO;
+pub(crate) use snapshot_progress::SnapshotProgress;
+pub(crate) use storage_retry::StorageRetry;
 pub(crate) use tick::Tick;
 pub(crate) use tick::TickHandle;
@@ -46,6 +46,9 @@ where C: RaftTypeConfig
     /// and [`RaftCore`](`crate::core::RaftCore`) needs to shutdown.
     StorageError { error: StorageError<C> },
 
+    /// It is time to retry a storage operation that failed with a transient [`StorageError`].
+    StorageRetry,
+
     /// Completion of an IO operation to local store.
     LocalIO { io_id: IOId<C> },
 
@@ -104,6 +107,7 @@ where C: RaftTypeConfig
                 )
             }
             Self::StorageError { error } => write!(f, "StorageError: {}", error),
+            Self::StorageRetry => write!(f, "StorageRetry"),
             Self::LocalIO { io_id } => write!(f, "IOFlushed: {}", io_id),
             Self::ReplicationProgress { progress } => {
                 write!(f, "{}", progress)
@@ -1,5 +1,6 @@
 use std::borrow::Borrow;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -19,6 +20,7 @@ use tracing::Span;
 
 use crate::async_runtime::watch::WatchSender;
 use crate::async_runtime::MpscUnboundedSender;
+use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::async_runtime::OneshotSender;
 use crate::async_runtime::TryRecvError;
 use crate::config::Config;
@@ -35,6 +37,9 @@ use crate::core::raft_msg::ResultSender;
 use crate::core::raft_msg::VoteTx;
 use crate::core::sm;
 use crate::core::ServerState;
+use crate::core::SlowIO;
+use crate::core::SnapshotProgress;
+use crate::core::StorageRetry;
 use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
@@ -46,37 +51,57 @@ use crate::engine::ReplicationProgress;
 use crate::engine::Respond;
 use crate::entry::FromAppData;
 use crate::entry::RaftEntry;
+use crate::entry::RaftPayload;
 use crate::error::ClientWriteError;
 use crate::error::Fatal;
+use crate::error::ForceSetMembershipError;
 use crate::error::ForwardToLeader;
+use crate::error::ForwardUnknownOutcome;
 use crate::error::Infallible;
 use crate::error::InitializeError;
 use crate::error::QuorumNotEnough;
 use crate::error::RPCError;
+use crate::error::RaftError;
+use crate::error::ReadIndexError;
+use crate::error::RemoteError;
 use crate::error::Timeout;
+use crate::error::Unreachable;
+use crate::error::VotersUnreachable;
+use crate::error::WriteTimeout;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id::RaftLogId;
 use crate::metrics::HeartbeatMetrics;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::ReplicationBreakerMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationState;
+use crate::metrics::ReplicationStatus;
+use crate::metrics::ReplicationStatusMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SnapshotSendingMetrics;
 use crate::network::v2::RaftNetworkV2;
+use crate::network::Capabilities;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::network::RaftNetworkFactory;
 use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
 use crate::progress::Progress;
 use crate::quorum::QuorumSet;
+use crate::raft::event_broadcast::EventSender;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::ClientWriteResponse;
+use crate::raft::RaftEvent;
+use crate::raft::RaftEventHandler;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft_state::io_state::io_id::IOId;
+use crate::raft_state::io_state::log_io_id::LogIOId;
 use crate::raft_state::LogStateReader;
 use crate::replication::request::Replicate;
 use crate::replication::ReplicationCore;
@@ -85,10 +110,14 @@ use crate::replication::ReplicationSessionId;
 use crate::runtime::RaftRuntime;
 use crate::storage::IOFlushed;
 use crate::storage::RaftLogStorage;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
+use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::async_runtime::MpscUnboundedReceiver;
@@ -190,11 +219,65 @@ where
     /// Channels to send result back to client when logs are applied.
     pub(crate) client_resp_channels: BTreeMap<u64, ResponderOf<C>>,
 
+    /// The deadline and timeout of a client write in `client_resp_channels`, by log index.
+    ///
+    /// When the deadline expires, the responder is sent a timeout error and is removed.
+    pub(crate) client_write_deadlines: BTreeMap<u64, (InstantOf<C>, Duration)>,
+
     /// A mapping of node IDs the replication state of the target node.
     pub(crate) replications: BTreeMap<C::NodeId, ReplicationHandle<C>>,
 
     pub(crate) heartbeat_handle: HeartbeatWorkersHandle<C>,
 
+    /// Detects slow calls to the log store and the state machine.
+    pub(crate) slow_io: SlowIO,
+
+    /// The progress of the snapshot being received, shared with the [`Raft`](crate::Raft) handle
+    /// that receives the chunks.
+    pub(crate) snapshot_receiving: SnapshotProgress<C>,
+
+    /// The number of partially received snapshots dropped by the idle timeout.
+    pub(crate) snapshot_receive_timeouts: u64,
+
+    /// The retries of the command at the head of the queue that failed with a transient
+    /// [`StorageError`], and the time not to retry it before.
+    ///
+    /// The command is postponed instead of waiting for the retry, so that `RaftCore` keeps
+    /// handling other events, such as heartbeats and elections.
+    pub(crate) storage_retry: Option<(StorageRetry, InstantOf<C>)>,
+
+    /// The time when a snapshot is last built or installed, or when this node started.
+    ///
+    /// It is used by a time based [`SnapshotPolicy`](crate::SnapshotPolicy) to decide when to
+    /// build the next snapshot.
+    pub(crate) last_snapshot_at: InstantOf<C>,
+
+    /// Callers waiting for a snapshot to be built.
+    ///
+    /// Each of them waits for a snapshot that includes the logs applied when the request is
+    /// received.
+    pub(crate) snapshot_waiters: Vec<(Option<LogId<C::NodeId>>, ResultSender<C, SnapshotMeta<C>>)>,
+
+    /// Callers of [`Raft::client_write_submit()`](crate::Raft::client_write_submit) waiting for
+    /// their entries to be flushed to the local log store.
+    pub(crate) flush_waiters: Vec<(LogId<C::NodeId>, OneshotSenderOf<C, Option<LogId<C::NodeId>>>)>,
+
+    /// Sends events to the subscribers registered with
+    /// [`Raft::subscribe()`](crate::Raft::subscribe).
+    pub(crate) event_sender: EventSender<C>,
+
+    /// The handler registered with
+    /// [`Raft::new_with_event_handler()`](crate::Raft::new_with_event_handler).
+    pub(crate) event_handler: Option<Box<dyn RaftEventHandler<C>>>,
+
+    /// Idle clients to the leader, reused to forward client writes.
+    ///
+    /// See [`Config::forward_to_leader`].
+    pub(crate) forward_clients: Arc<std::sync::Mutex<BTreeMap<C::NodeId, Vec<NF::Network>>>>,
+
+    /// The vote with which this node is the leader, as last reported to `event_handler`.
+    pub(crate) leading_vote: Option<Vote<C::NodeId>>,
+
     #[allow(dead_code)]
     pub(crate) tx_api: MpscUnboundedSenderOf<C, RaftMsg<C>>,
     pub(crate) rx_api: MpscUnboundedReceiverOf<C, RaftMsg<C>>,
@@ -224,8 +307,19 @@ where
         let span = tracing::span!(parent: &self.span, Level::DEBUG, "main");
         let res = self.do_main(rx_shutdown).instrument(span).await;
 
+        if let Err(Fatal::Stopped) = &res {
+            self.drain().await;
+        }
+
+        // A leader that shuts down steps down.
+        if let Some(vote) = self.leading_vote.take() {
+            if let Some(handler) = self.event_handler.as_mut() {
+                handler.on_step_down(vote).await;
+            }
+        }
+
         // Flush buffered metrics
-        self.report_metrics(None, None);
+        self.report_metrics(None, None, None, None, None);
 
         // Safe unwrap: res is Result<Infallible, _>
         let err = res.unwrap_err();
@@ -250,16 +344,49 @@ where
         Err(err)
     }
 
+    /// Finish the in-flight work when shutting down.
+    ///
+    /// Replication is stopped, and the logs already sent to the state machine worker are applied
+    /// so that their clients receive the responses. Other pending requests are dropped when
+    /// `RaftCore` quits, and their clients receive a [`Fatal::Stopped`] error.
+    async fn drain(&mut self) {
+        tracing::info!("drain in-flight work before shutting down");
+
+        self.remove_all_replication().await;
+
+        let (tx, rx) = C::oneshot();
+        if self.sm_handle.send(sm::Command::drain(tx)).is_err() {
+            tracing::warn!("state machine worker has quit, can not drain");
+            return;
+        }
+        let _ = rx.await;
+
+        while let Ok(notify) = self.rx_notification.try_recv() {
+            if let Notification::StateMachine { command_result } = notify {
+                if let Ok(sm::Response::Apply(res)) = command_result.result {
+                    self.handle_apply_result(res);
+                }
+            }
+        }
+    }
+
     #[tracing::instrument(level="trace", skip_all, fields(id=display(self.id), cluster=%self.config.cluster_name))]
     async fn do_main(&mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         tracing::debug!("raft node is initializing");
 
         self.engine.startup();
+
+        // The only voter does not wait for an election timeout, there is no other leader to hear
+        // from. It is ignored if it is already a leader.
+        if self.engine.state.membership_state.effective().voter_ids().count() == 1 {
+            self.handle_tick_election();
+        }
+
         // It may not finish running all of the commands, if there is a command waiting for a callback.
         self.run_engine_commands().await?;
 
         // Initialize metrics.
-        self.report_metrics(None, None);
+        self.report_metrics(None, None, None, None, None);
 
         self.runtime_loop(rx_shutdown).await
     }
@@ -279,7 +406,7 @@ where
     pub(super) async fn handle_check_is_leader_request(&mut self, tx: ClientReadTx<C>) {
         // Setup sentinel values to track when we've received majority confirmation of leadership.
 
-        let resp = {
+        let (resp, transferring) = {
             let l = self.engine.leader_handler();
             let lh = match l {
                 Ok(leading_handler) => leading_handler,
@@ -295,12 +422,31 @@ where
             //       Fix this when the following heartbeats are replaced with calling RaftNetwork.
             let applied = self.engine.state.io_applied().copied();
 
-            (read_log_id, applied)
+            ((read_log_id, applied), lh.leader.get_transfer_to().is_some())
         };
 
+        // Within the lease since the last heartbeat acknowledged by a quorum, no other node can be
+        // elected, and there is no need to send heartbeats.
+        //
+        // While transferring leadership, the followers have given up the lease and a new leader
+        // may be elected at any time: the leadership has to be confirmed by a quorum.
+        if let Some(lease) = self.config.read_lease().filter(|_| !transferring) {
+            if let Some(acked) = self.last_quorum_acked_time() {
+                if C::now() < acked + lease {
+                    tracing::debug!(
+                        last_quorum_acked = display(acked.display()),
+                        "{}: serve read with lease",
+                        func_name!()
+                    );
+                    let _ = tx.send(Ok(resp));
+                    return;
+                }
+            }
+        }
+
         let my_id = self.id;
         let my_vote = *self.engine.state.vote_ref();
-        let ttl = Duration::from_millis(self.config.heartbeat_interval);
+        let ttl = self.config.append_entries_timeout();
         let eff_mem = self.engine.state.membership_state.effective().clone();
         let core_tx = self.tx_notification.clone();
 
@@ -331,6 +477,7 @@ where
                 prev_log_id: progress.matching,
                 entries: vec![],
                 leader_commit: self.engine.state.committed().copied(),
+                protocol_version: Capabilities::PROTOCOL_VERSION,
             };
 
             // Safe unwrap(): target is in membership
@@ -459,10 +606,54 @@ where
             }
         };
 
+        if self.config.check_voters_reachable {
+            if let Err(e) = self.check_voters_reachable(&new_membership) {
+                tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+                return;
+            }
+        }
+
         let ent = C::Entry::new_membership(LogId::default(), new_membership);
         self.write_entry(ent, Some(tx));
     }
 
+    /// Check if the voters the leader has heard from within the leader lease constitute a quorum
+    /// of the new membership.
+    ///
+    /// If this node is not a leader, it does nothing and the membership change will be rejected
+    /// by [`Self::write_entry()`].
+    fn check_voters_reachable(&self, membership: &Membership<C>) -> Result<(), VotersUnreachable<C>> {
+        let Some(leader) = self.engine.leader.as_ref() else {
+            return Ok(());
+        };
+
+        let now = C::now();
+        let lease = self.engine.config.timer_config.leader_lease;
+
+        let is_reachable = |id: &C::NodeId| {
+            if *id == self.id {
+                return true;
+            }
+            let acked = leader.clock_progress.try_get(id).copied().flatten();
+            acked.map(|t| now <= t + lease).unwrap_or(false)
+        };
+
+        let reachable = membership.voter_ids().filter(is_reachable).collect::<BTreeSet<_>>();
+
+        if membership.to_quorum_set().is_quorum(reachable.iter()) {
+            return Ok(());
+        }
+
+        let unreachable = membership.voter_ids().filter(|id| !reachable.contains(id)).collect::<BTreeSet<_>>();
+
+        tracing::warn!(
+            unreachable = debug(&unreachable),
+            "reject membership change: reachable voters are not a quorum"
+        );
+
+        Err(VotersUnreachable { unreachable })
+    }
+
     /// Write a log entry to the cluster through raft protocol.
     ///
     /// I.e.: append the log entry to local store, forward it to a quorum(including the leader),
@@ -470,13 +661,13 @@ where
     ///
     /// The result of applying it to state machine is sent to `resp_tx`, if it is not `None`.
     /// The calling side may not receive a result from `resp_tx`, if raft is shut down.
+    ///
+    /// It returns the log id assigned to the entry, or `None` if the entry is rejected.
     #[tracing::instrument(level = "debug", skip_all, fields(id = display(self.id)))]
-    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<ResponderOf<C>>) {
+    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<ResponderOf<C>>) -> Option<LogIdOf<C>> {
         tracing::debug!(payload = display(&entry), "write_entry");
 
-        let Some((mut lh, tx)) = self.engine.get_leader_handler_or_reject(resp_tx) else {
-            return;
-        };
+        let (mut lh, tx) = self.engine.get_leader_handler_or_reject(resp_tx)?;
 
         // If the leader is transferring leadership, forward writes to the new leader.
         if let Some(to) = lh.leader.get_transfer_to() {
@@ -484,21 +675,126 @@ where
                 let err = lh.state.new_forward_to_leader(*to);
                 tx.send(Err(ClientWriteError::ForwardToLeader(err)));
             }
-            return;
+            return None;
+        }
+
+        if entry.get_membership().is_none() {
+            if let Err(e) = lh.check_overloaded() {
+                tracing::debug!(error = display(&e), "reject client write");
+                if let Some(tx) = tx {
+                    tx.send(Err(e.into()));
+                }
+                return None;
+            }
         }
 
         let entries = vec![entry];
         // TODO: it should returns membership config error etc. currently this is done by the
         //       caller.
         lh.leader_append_entries(entries);
-        let index = lh.state.last_log_id().unwrap().index;
+        let log_id = *lh.state.last_log_id().unwrap();
 
         // Install callback channels.
         if let Some(tx) = tx {
-            self.client_resp_channels.insert(index, tx);
+            self.client_resp_channels.insert(log_id.index, tx);
+        }
+
+        Some(log_id)
+    }
+
+    /// Write a batch of log entries to the cluster in order through raft protocol.
+    ///
+    /// All of the entries are appended to the log in one IO, and each response is sent to the
+    /// corresponding responder when its entry is applied.
+    ///
+    /// If this node is not a leader, a [`ForwardToLeader`] error is sent to every responder.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn write_entries(&mut self, requests: Vec<(C::Entry, ResponderOf<C>)>) {
+        tracing::debug!(n = requests.len(), "write_entries");
+
+        let (entries, txs): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+
+        let mut lh = match self.engine.leader_handler() {
+            Ok(lh) => lh,
+            Err(forward_err) => {
+                for tx in txs {
+                    tx.send(Err(forward_err.clone().into()));
+                }
+                return;
+            }
+        };
+
+        // If the leader is transferring leadership, forward writes to the new leader.
+        if let Some(to) = lh.leader.get_transfer_to() {
+            let err = lh.state.new_forward_to_leader(*to);
+            for tx in txs {
+                tx.send(Err(ClientWriteError::ForwardToLeader(err.clone())));
+            }
+            return;
+        }
+
+        if entries.is_empty() {
+            return;
+        }
+
+        if let Err(e) = lh.check_overloaded() {
+            tracing::debug!(error = display(&e), "reject client writes");
+            for tx in txs {
+                tx.send(Err(e.clone().into()));
+            }
+            return;
+        }
+
+        let n = entries.len() as u64;
+        lh.leader_append_entries(entries);
+        let last_index = lh.state.last_log_id().unwrap().index;
+
+        // Install callback channels, the entries are assigned consecutive indexes.
+        let first_index = last_index + 1 - n;
+        for (i, tx) in txs.into_iter().enumerate() {
+            self.client_resp_channels.insert(first_index + i as u64, tx);
         }
     }
 
+    /// Check the result of a storage operation, return `true` if the operation failed with a
+    /// transient error and should be retried later.
+    ///
+    /// A retry is scheduled with [`Notification::StorageRetry`] instead of waiting, and the failed
+    /// command should be postponed until then. It returns the error if there is no retry left.
+    pub(crate) fn retry_later(&mut self, res: Result<(), StorageError<C>>) -> Result<bool, StorageError<C>> {
+        let err = match res {
+            Ok(()) => {
+                self.storage_retry = None;
+                return Ok(false);
+            }
+            Err(e) => e,
+        };
+
+        let mut retry = match self.storage_retry.take() {
+            Some((retry, _)) => retry,
+            None => StorageRetry::new(&self.config),
+        };
+
+        let Some(delay) = retry.next_delay(&err) else {
+            return Err(err);
+        };
+
+        self.storage_retry = Some((retry, C::now() + delay));
+
+        let weak = self.tx_notification.downgrade();
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(async move {
+            C::sleep(delay).await;
+            if let Some(tx) = weak.upgrade() {
+                let _ = tx.send(Notification::StorageRetry);
+            }
+        });
+
+        Ok(true)
+    }
+
     /// Send a heartbeat message to every follower/learners.
     #[tracing::instrument(level = "debug", skip_all, fields(id = display(self.id)))]
     pub(crate) fn send_heartbeat(&mut self, emitter: impl fmt::Display) -> bool {
@@ -530,18 +826,84 @@ where
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub fn flush_metrics(&mut self) {
-        let (replication, heartbeat) = if let Some(leader) = self.engine.leader.as_ref() {
+        let (replication, heartbeat, snapshot_sending, replication_breaker, replication_status) = if let Some(leader) =
+            self.engine.leader.as_ref()
+        {
             let replication_prog = &leader.progress;
             let replication = Some(replication_prog.iter().map(|(id, p)| (*id, *p.borrow())).collect());
 
+            let now = C::now();
+            let snapshot_sending = Some(
+                replication_prog
+                    .iter()
+                    .filter(|(_id, p)| matches!(p.inflight, Inflight::Snapshot { .. }))
+                    .filter_map(|(id, _p)| {
+                        let transfer = self.replications.get(id)?.snapshot_progress.get(now)?;
+                        Some((*id, transfer))
+                    })
+                    .collect(),
+            );
+
             let clock_prog = &leader.clock_progress;
             let heartbeat = Some(clock_prog.iter().map(|(id, opt_t)| (*id, opt_t.map(SerdeInstant::new))).collect());
 
-            (replication, heartbeat)
+            let replication_breaker = Some(
+                self.replications
+                    .iter()
+                    .filter(|(_id, h)| h.breaker_open.load(Ordering::Relaxed))
+                    .map(|(id, _h)| *id)
+                    .collect(),
+            );
+
+            let replication_status = Some(
+                replication_prog
+                    .iter()
+                    .filter_map(|(id, p)| {
+                        let handle = self.replications.get(id)?;
+
+                        let (next_index, inflight) = match p.inflight {
+                            Inflight::None => (p.matching.next_index(), 0),
+                            Inflight::Logs { log_id_range } => (log_id_range.last.next_index(), log_id_range.len()),
+                            Inflight::Snapshot { last_log_id } => (last_log_id.next_index(), 0),
+                        };
+
+                        let state = if handle.backing_off.load(Ordering::Relaxed) {
+                            ReplicationState::BackingOff
+                        } else if matches!(p.inflight, Inflight::Snapshot { .. }) {
+                            ReplicationState::Snapshotting
+                        } else {
+                            ReplicationState::LineRate
+                        };
+
+                        let status = ReplicationStatus {
+                            matched: p.matching,
+                            next_index,
+                            inflight,
+                            last_contact: clock_prog.try_get(id).and_then(|t| t.map(SerdeInstant::new)),
+                            state,
+                        };
+                        Some((*id, status))
+                    })
+                    .collect(),
+            );
+
+            (
+                replication,
+                heartbeat,
+                snapshot_sending,
+                replication_breaker,
+                replication_status,
+            )
         } else {
-            (None, None)
+            (None, None, None, None, None)
         };
-        self.report_metrics(replication, heartbeat);
+        self.report_metrics(
+            replication,
+            heartbeat,
+            snapshot_sending,
+            replication_breaker,
+            replication_status,
+        );
     }
 
     /// Report a metrics payload on the current state of the Raft node.
@@ -550,14 +912,19 @@ where
         &mut self,
         replication: Option<ReplicationMetrics<C>>,
         heartbeat: Option<HeartbeatMetrics<C>>,
+        snapshot_sending: Option<SnapshotSendingMetrics<C>>,
+        replication_breaker: Option<ReplicationBreakerMetrics<C>>,
+        replication_status: Option<ReplicationStatusMetrics<C>>,
     ) {
         let last_quorum_acked = self.last_quorum_acked_time();
         let millis_since_quorum_ack = last_quorum_acked.map(|t| t.elapsed().as_millis() as u64);
+        let snapshot_receiving = self.snapshot_receiving.get(C::now());
 
         let st = &self.engine.state;
 
         let membership_config = st.membership_state.effective().stored_membership().clone();
         let current_leader = self.current_leader();
+        let leader_ready = self.engine.leader.as_ref().map(|l| st.committed() >= l.noop_log_id());
 
         #[allow(deprecated)]
         let m = RaftMetrics {
@@ -570,11 +937,16 @@ where
             last_log_index: st.last_log_id().index(),
             last_applied: st.io_applied().copied(),
             snapshot: st.io_snapshot_last_log_id().copied(),
+            snapshot_building: st.io_state().building_snapshot(),
+            snapshot_receiving: snapshot_receiving.clone(),
             purged: st.io_purged().copied(),
+            slow_io: self.slow_io.count(),
+            snapshot_receive_timeouts: self.snapshot_receive_timeouts,
 
             // --- cluster ---
             state: st.server_state,
             current_leader,
+            leader_ready: leader_ready.unwrap_or(false),
             millis_since_quorum_ack,
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             membership_config: membership_config.clone(),
@@ -582,6 +954,9 @@ where
 
             // --- replication ---
             replication: replication.clone(),
+            snapshot_sending: snapshot_sending.clone(),
+            replication_breaker: replication_breaker.clone(),
+            replication_status: replication_status.clone(),
         };
 
         #[allow(deprecated)]
@@ -589,11 +964,18 @@ where
             last_log: st.last_log_id().copied(),
             last_applied: st.io_applied().copied(),
             snapshot: st.io_snapshot_last_log_id().copied(),
+            snapshot_building: st.io_state().building_snapshot(),
+            snapshot_receiving,
             purged: st.io_purged().copied(),
+            slow_io: self.slow_io.count(),
+            snapshot_receive_timeouts: self.snapshot_receive_timeouts,
             millis_since_quorum_ack,
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             replication,
             heartbeat,
+            snapshot_sending,
+            replication_breaker,
+            replication_status,
         };
 
         let server_metrics = RaftServerMetrics {
@@ -611,7 +993,7 @@ where
 
         self.tx_data_metrics.send_if_modified(|metrix| {
             if data_metrics.ne(metrix) {
-                *metrix = data_metrics.clone();
+                *metrix = data_metrics;
                 return true;
             }
             false
@@ -619,7 +1001,7 @@ where
 
         self.tx_server_metrics.send_if_modified(|metrix| {
             if server_metrics.ne(metrix) {
-                *metrix = server_metrics.clone();
+                *metrix = server_metrics;
                 return true;
             }
             false
@@ -633,6 +1015,13 @@ where
         }
     }
 
+    /// Send an event to the subscribers.
+    pub(crate) fn emit_event(&mut self, event: RaftEvent<C>) {
+        tracing::debug!(event = display(&event), "emit_event");
+
+        self.event_sender.send(event);
+    }
+
     /// Handle the admin command `initialize`.
     ///
     /// It is allowed to initialize only when `last_log_id.is_none()` and `vote==(0,0)`.
@@ -669,6 +1058,57 @@ where
         });
     }
 
+    /// Initialize this node with a snapshot instead of a membership log.
+    ///
+    /// It responds when the snapshot is installed, or at once if there is an error.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_initialize_with_snapshot(
+        &mut self,
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, (), InitializeError<C>>,
+    ) {
+        tracing::debug!(snapshot = display(&snapshot), "{}", func_name!());
+
+        let (res, condition) = match self.engine.initialize_with_snapshot(snapshot) {
+            Ok(condition) => (Ok(()), condition),
+            Err(e) => (Err(e), None),
+        };
+
+        self.engine.output.push_command(Command::Respond {
+            when: condition,
+            resp: Respond::new(res, tx),
+        });
+    }
+
+    /// Forcibly set the membership config on this node, bypassing the consensus.
+    ///
+    /// It responds when the membership log is flushed, or at once if there is an error.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(crate) fn handle_force_set_membership(
+        &mut self,
+        member_nodes: BTreeMap<C::NodeId, C::Node>,
+        tx: ResultSender<C, (), ForceSetMembershipError<C>>,
+    ) {
+        tracing::debug!(member_nodes = debug(&member_nodes), "{}", func_name!());
+
+        let membership = Membership::from(member_nodes);
+
+        let entry = C::Entry::new_membership(LogId::default(), membership);
+        let res = self.engine.force_set_membership(entry);
+
+        let condition = if res.is_err() {
+            None
+        } else {
+            Some(Condition::LogFlushed {
+                log_id: self.engine.state.last_log_id().copied(),
+            })
+        };
+        self.engine.output.push_command(Command::Respond {
+            when: condition,
+            resp: Respond::new(res, tx),
+        });
+    }
+
     /// Trigger a snapshot building(log compaction) job if there is no pending building job.
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn trigger_snapshot(&mut self) {
@@ -676,6 +1116,40 @@ where
         self.engine.snapshot_handler().trigger_snapshot();
     }
 
+    /// Send the built snapshot meta to the waiters that are satisfied by it.
+    fn respond_snapshot_waiters(&mut self, meta: &SnapshotMeta<C>) {
+        let waiters = std::mem::take(&mut self.snapshot_waiters);
+
+        for (applied, tx) in waiters {
+            if applied <= meta.last_log_id {
+                let _ = tx.send(Ok(meta.clone()));
+            } else {
+                self.snapshot_waiters.push((applied, tx));
+            }
+        }
+    }
+
+    /// Send the log id to the waiters whose entries are flushed by the log IO `flushed`.
+    ///
+    /// An entry is flushed if it is proposed by the same leader and is not after the last flushed
+    /// log id, because a leader never truncates its own entries.
+    fn respond_flush_waiters(&mut self, flushed: &LogIOId<C>) {
+        let Some(last) = flushed.log_id else {
+            return;
+        };
+
+        let leader_id = flushed.committed_vote.committed_leader_id();
+        let waiters = std::mem::take(&mut self.flush_waiters);
+
+        for (log_id, tx) in waiters {
+            if log_id.leader_id == leader_id && log_id.index <= last.index {
+                let _ = tx.send(Some(log_id));
+            } else {
+                self.flush_waiters.push((log_id, tx));
+            }
+        }
+    }
+
     /// Reject a request due to the Raft node being in a state which prohibits the request.
     #[tracing::instrument(level = "trace", skip(self, tx))]
     pub(crate) fn reject_with_forward_to_leader<T: OptionalSend, E>(&self, tx: ResultSender<C, T, E>)
@@ -774,6 +1248,7 @@ where
             let ent = applying_entries.next().unwrap();
             let apply_res = results.next().unwrap();
             let tx = self.client_resp_channels.remove(&log_index);
+            self.client_write_deadlines.remove(&log_index);
 
             Self::send_response(ent, apply_res, tx);
         }
@@ -815,6 +1290,11 @@ where
         let network = self.network_factory.new_client(target, target_node).await;
         let snapshot_network = self.network_factory.new_client(target, target_node).await;
 
+        let mut pipeline_networks = vec![];
+        for _ in 1..self.config.max_in_flight_appends {
+            pipeline_networks.push(self.network_factory.new_client(target, target_node).await);
+        }
+
         let leader = self.engine.leader.as_ref().unwrap();
 
         let session_id = ReplicationSessionId::new(leader.committed_vote, *membership_log_id);
@@ -826,6 +1306,7 @@ where
             self.engine.state.committed().copied(),
             progress_entry.matching,
             network,
+            pipeline_networks,
             snapshot_network,
             self.log_store.get_log_reader().await,
             self.sm_handle.new_snapshot_reader(),
@@ -866,6 +1347,12 @@ where
     /// next RaftMsg.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn run_engine_commands(&mut self) -> Result<(), StorageError<C>> {
+        self.handle_leadership_change().await;
+
+        for event in self.engine.output.take_events() {
+            self.emit_event(event);
+        }
+
         if tracing::enabled!(Level::DEBUG) {
             tracing::debug!("queued commands: start...");
             for c in self.engine.output.iter_commands() {
@@ -892,6 +1379,19 @@ where
                     }
                 }
 
+                // While a storage operation waits to be retried, keep sending heartbeats, which do
+                // not depend on the storage, so that the followers do not start an election.
+                if self.storage_retry.is_some() {
+                    let heartbeats =
+                        self.engine.output.extract_commands(|c| matches!(c, Command::BroadcastHeartbeat { .. }));
+
+                    for c in heartbeats {
+                        if let Command::BroadcastHeartbeat { session_id, committed } = c {
+                            self.heartbeat_handle.broadcast(HeartbeatEvent::new(C::now(), session_id, committed));
+                        }
+                    }
+                }
+
                 return Ok(());
             }
         }
@@ -899,6 +1399,34 @@ where
         Ok(())
     }
 
+    /// Call the [`RaftEventHandler`] if this node became the leader or stepped down since the last
+    /// call.
+    ///
+    /// It is called before running the commands output by the `Engine`, so that the callback
+    /// returns before the new leader replicates or handles any further request.
+    async fn handle_leadership_change(&mut self) {
+        let Some(handler) = self.event_handler.as_mut() else {
+            return;
+        };
+
+        let curr = self.engine.leader.as_ref().map(|l| **l.committed_vote_ref());
+        if curr == self.leading_vote {
+            return;
+        }
+
+        if let Some(prev) = self.leading_vote.take() {
+            tracing::info!("id={} step down from leader: {}", self.id, prev);
+            handler.on_step_down(prev).await;
+        }
+
+        if let Some(vote) = curr {
+            tracing::info!("id={} become leader: {}", self.id, vote);
+            handler.on_become_leader(vote).await;
+        }
+
+        self.leading_vote = curr;
+    }
+
     /// Run an event handling loop
     ///
     /// It always returns a [`Fatal`] error upon returning.
@@ -943,7 +1471,7 @@ where
 
                 msg_res = self.rx_api.recv().fuse() => {
                     match msg_res {
-                        Some(msg) => self.handle_api_msg(msg).await,
+                        Some(msg) => self.handle_api_msg_and_run(msg).await?,
                         None => {
                             tracing::info!("all rx_api senders are dropped");
                             return Err(Fatal::Stopped);
@@ -1001,13 +1529,11 @@ where
                 },
             };
 
-            self.handle_api_msg(msg).await;
-
             // TODO: does run_engine_commands() run too frequently?
             //       to run many commands in one shot, it is possible to batch more commands to gain
             //       better performance.
 
-            self.run_engine_commands().await?;
+            self.handle_api_msg_and_run(msg).await?;
         }
 
         tracing::debug!("at_most({}) reached, there are more queued RaftMsg to process", at_most);
@@ -1053,9 +1579,10 @@ where
         Ok(at_most)
     }
 
-    /// Spawn parallel vote requests to all cluster members.
+    /// Spawn parallel vote requests, or pre-vote requests if `pre_vote` is true, to all cluster
+    /// members.
     #[tracing::instrument(level = "trace", skip_all)]
-    async fn spawn_parallel_vote_requests(&mut self, vote_req: &VoteRequest<C>) {
+    async fn spawn_parallel_vote_requests(&mut self, vote_req: &VoteRequest<C>, pre_vote: bool) {
         let members = self.engine.state.membership_state.effective().voter_ids();
 
         let vote = vote_req.vote;
@@ -1073,7 +1600,7 @@ where
 
             let tx = self.tx_notification.clone();
 
-            let ttl = Duration::from_millis(self.config.election_timeout_min);
+            let ttl = self.config.vote_timeout();
             let id = self.id;
             let option = RPCOption::new(ttl);
 
@@ -1081,13 +1608,17 @@ where
             #[allow(clippy::let_underscore_future)]
             let _ = C::spawn(
                 async move {
-                    let tm_res = C::timeout(ttl, client.vote(req, option)).await;
+                    let tm_res = if pre_vote {
+                        C::timeout(ttl, client.pre_vote(req, option)).await
+                    } else {
+                        C::timeout(ttl, client.vote(req, option)).await
+                    };
                     let res = match tm_res {
                         Ok(res) => res,
 
                         Err(_timeout) => {
                             let timeout_err = Timeout::<C> {
-                                action: RPCTypes::Vote,
+                                action: if pre_vote { RPCTypes::PreVote } else { RPCTypes::Vote },
                                 id,
                                 target,
                                 timeout: ttl,
@@ -1165,6 +1696,160 @@ where
         }
     }
 
+    /// Forward a read index request to the current leader and send back the returned read log id.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(super) async fn handle_read_index_request(
+        &mut self,
+        tx: ResultSender<C, Option<LogIdOf<C>>, ReadIndexError<C>>,
+    ) {
+        let leader_id = self.current_leader();
+        let leader_node = self.get_leader_node(leader_id);
+
+        let (target, target_node) = match (leader_id, leader_node) {
+            (Some(id), Some(node)) if id != self.id => (id, node),
+            (leader_id, leader_node) => {
+                let _ = tx.send(Err(ForwardToLeader { leader_id, leader_node }.into()));
+                return;
+            }
+        };
+
+        let mut client = self.network_factory.new_client(target, &target_node).await;
+
+        let ttl = Duration::from_millis(self.config.election_timeout_min);
+        let option = RPCOption::new(ttl);
+        let my_id = self.id;
+
+        let fut = async move {
+            let res = match C::timeout(ttl, client.read_index(option)).await {
+                Ok(res) => res,
+                Err(_e) => {
+                    let timeout = Timeout {
+                        action: RPCTypes::ReadIndex,
+                        id: my_id,
+                        target,
+                        timeout: ttl,
+                    };
+                    Err(RPCError::Timeout(timeout))
+                }
+            };
+
+            let res = res.map_err(|e| match e {
+                RPCError::Timeout(e) => ReadIndexError::RPCError(RPCError::Timeout(e)),
+                RPCError::Unreachable(e) => ReadIndexError::RPCError(RPCError::Unreachable(e)),
+                RPCError::PayloadTooLarge(e) => ReadIndexError::RPCError(RPCError::PayloadTooLarge(e)),
+                RPCError::Network(e) => ReadIndexError::RPCError(RPCError::Network(e)),
+                RPCError::RemoteError(e) => match e.source {
+                    RaftError::APIError(api_err) => api_err.into(),
+                    RaftError::Fatal(fatal) => {
+                        ReadIndexError::RPCError(RPCError::Unreachable(Unreachable::new(&fatal)))
+                    }
+                },
+            });
+
+            if let Err(e) = &res {
+                tracing::warn!({error = display(e), target = display(target)}, "error sending read_index");
+            }
+
+            let _ = tx.send(res);
+        };
+
+        let span = tracing::debug_span!(parent: &Span::current(), "send_read_index", target = display(target));
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fut.instrument(span));
+    }
+
+    /// Forward a client write request received by a non-leader to the current leader.
+    ///
+    /// If the leader is unknown or can not be reached, it responds with a [`ForwardToLeader`]
+    /// error, as if forwarding is disabled. If the request may have been received by the leader
+    /// but no response is received, it responds with a [`ForwardUnknownOutcome`] error.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(super) async fn forward_client_write(&mut self, app_data: C::D, tx: ResponderOf<C>) {
+        let leader_id = self.current_leader();
+        let leader_node = self.get_leader_node(leader_id);
+
+        let (target, target_node) = match (leader_id, leader_node) {
+            (Some(id), Some(node)) if id != self.id => (id, node),
+            (leader_id, leader_node) => {
+                tx.send(Err(ClientWriteError::ForwardToLeader(ForwardToLeader {
+                    leader_id,
+                    leader_node,
+                })));
+                return;
+            }
+        };
+
+        // Reuse an idle client to the leader. Clients to a previous leader are dropped.
+        let idle = {
+            let mut clients = self.forward_clients.lock().unwrap();
+            clients.retain(|id, _| *id == target);
+            clients.get_mut(&target).and_then(|x| x.pop())
+        };
+
+        let mut client = match idle {
+            Some(client) => client,
+            None => self.network_factory.new_client(target, &target_node).await,
+        };
+
+        let ttl = self.config.forward_to_leader_timeout();
+        let option = RPCOption::new(ttl);
+        let forward_err = ForwardToLeader {
+            leader_id: Some(target),
+            leader_node: Some(target_node),
+        };
+        let unknown = move |reason: String| {
+            ClientWriteError::ForwardUnknownOutcome(ForwardUnknownOutcome {
+                leader_id: target,
+                reason,
+            })
+        };
+
+        let clients = self.forward_clients.clone();
+
+        let fut = async move {
+            let res = match C::timeout(ttl, client.forward_client_write(app_data, option)).await {
+                Ok(res) => res,
+                Err(_e) => {
+                    tracing::warn!(target = display(target), "timeout forwarding client write");
+                    tx.send(Err(unknown(format!("timeout after {:?}", ttl))));
+                    return;
+                }
+            };
+
+            let res = match res {
+                Ok(resp) => Ok(resp),
+                Err(RPCError::RemoteError(RemoteError {
+                    source: RaftError::APIError(e),
+                    ..
+                })) => Err(e),
+                // The request is not delivered, it is safe to retry.
+                Err(e @ RPCError::Unreachable(_)) | Err(e @ RPCError::PayloadTooLarge(_)) => {
+                    tracing::warn!({error = display(&e), target = display(target)}, "error forwarding client write");
+                    tx.send(Err(ClientWriteError::ForwardToLeader(forward_err)));
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!({error = display(&e), target = display(target)}, "error forwarding client write");
+                    tx.send(Err(unknown(e.to_string())));
+                    return;
+                }
+            };
+
+            tx.send(res);
+
+            // The client works, return it for reuse.
+            clients.lock().unwrap().entry(target).or_default().push(client);
+        };
+
+        let span = tracing::debug_span!(parent: &Span::current(), "forward_client_write", target = display(target));
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fut.instrument(span));
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(super) fn handle_vote_request(&mut self, req: VoteRequest<C>, tx: VoteTx<C>) {
         tracing::info!(req = display(&req), func = func_name!());
@@ -1183,23 +1868,59 @@ where
     pub(super) fn handle_append_entries_request(&mut self, req: AppendEntriesRequest<C>, tx: AppendEntriesTx<C>) {
         tracing::debug!(req = display(&req), func = func_name!());
 
-        let is_ok = self.engine.handle_append_entries(&req.vote, req.prev_log_id, req.entries, Some(tx));
+        // A witness stores only log ids and memberships.
+        let entries = if self.config.witness {
+            req.entries
+                .into_iter()
+                .map(|ent| {
+                    if ent.get_membership().is_some() {
+                        ent
+                    } else {
+                        C::Entry::new_blank(*ent.get_log_id())
+                    }
+                })
+                .collect()
+        } else {
+            req.entries
+        };
+
+        let version = req.protocol_version;
+        let is_ok = self.engine.handle_append_entries(&req.vote, req.prev_log_id, entries, version, Some(tx));
 
         if is_ok {
             self.engine.handle_commit_entries(req.leader_commit);
         }
     }
 
+    /// Handle a [`RaftMsg`] and run the commands it produces.
+    ///
+    /// An RPC from another node is handled in the span of the caller of the [`Raft`] API, which
+    /// may be restored from the tracing context of the sender with [`Raft::trace_span()`]. Thus
+    /// the storage IO it triggers is traced in that span too.
+    ///
+    /// [`Raft`]: crate::Raft
+    /// [`Raft::trace_span()`]: crate::Raft::trace_span
+    async fn handle_api_msg_and_run(&mut self, msg: RaftMsg<C>) -> Result<(), Fatal<C>> {
+        let span = msg.span().cloned().unwrap_or_else(Span::none);
+
+        self.handle_api_msg(msg).instrument(span.clone()).await;
+        self.run_engine_commands().instrument(span).await?;
+        Ok(())
+    }
+
     // TODO: Make this method non-async. It does not need to run any async command in it.
     #[tracing::instrument(level = "debug", skip(self, msg), fields(state = debug(self.engine.state.server_state), id=display(self.id)))]
     pub(crate) async fn handle_api_msg(&mut self, msg: RaftMsg<C>) {
         tracing::debug!("RAFT_event id={:<2}  input: {}", self.id, msg);
 
         match msg {
-            RaftMsg::AppendEntries { rpc, tx } => {
+            RaftMsg::AppendEntries { rpc, tx, .. } => {
                 self.handle_append_entries_request(rpc, tx);
             }
-            RaftMsg::RequestVote { rpc, tx } => {
+            RaftMsg::Heartbeat { rpc, tx, .. } => {
+                self.engine.handle_heartbeat(rpc, tx);
+            }
+            RaftMsg::RequestVote { rpc, tx, .. } => {
                 let now = C::now();
                 tracing::info!(
                     now = display(now.display()),
@@ -1210,17 +1931,81 @@ where
 
                 self.handle_vote_request(rpc, tx);
             }
+            RaftMsg::RequestPreVote { rpc, tx, .. } => {
+                tracing::info!(
+                    pre_vote_request = display(&rpc),
+                    "received RaftMsg::RequestPreVote: {}",
+                    func_name!()
+                );
+
+                // Pre-vote does not update the vote, no IO to wait for.
+                let resp = self.engine.handle_pre_vote_req(rpc);
+                let _ = tx.send(Ok(resp));
+            }
             RaftMsg::BeginReceivingSnapshot { tx } => {
                 self.engine.handle_begin_receiving_snapshot(tx);
             }
-            RaftMsg::InstallFullSnapshot { vote, snapshot, tx } => {
+            RaftMsg::ResumeReceivingSnapshot { snapshot_id, tx } => {
+                self.engine.handle_resume_receiving_snapshot(snapshot_id, tx);
+            }
+            RaftMsg::DiscardReceivingSnapshot { snapshot_id, tx } => {
+                self.engine.handle_discard_receiving_snapshot(snapshot_id, tx);
+            }
+            RaftMsg::SnapshotReceiveTimedOut { snapshot_id } => {
+                self.snapshot_receive_timeouts += 1;
+
+                // The dropped snapshot is received again from the beginning, do not resume it.
+                let (tx, _rx) = C::oneshot();
+                self.engine.handle_discard_receiving_snapshot(snapshot_id.clone(), tx);
+
+                self.emit_event(RaftEvent::SnapshotReceiveTimedOut { snapshot_id });
+            }
+            RaftMsg::InstallFullSnapshot { vote, snapshot, tx, .. } => {
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
             }
             RaftMsg::CheckIsLeaderRequest { tx } => {
                 self.handle_check_is_leader_request(tx).await;
             }
+            RaftMsg::ReadIndex { tx } => {
+                self.handle_read_index_request(tx).await;
+            }
             RaftMsg::ClientWriteRequest { app_data, tx } => {
-                self.write_entry(C::Entry::from_app_data(app_data), Some(tx));
+                if self.config.forward_to_leader && self.engine.leader.is_none() {
+                    self.forward_client_write(app_data, tx).await;
+                } else {
+                    self.write_entry(C::Entry::from_app_data(app_data), Some(tx));
+                }
+            }
+            RaftMsg::ClientWriteWithDeadline {
+                app_data,
+                tx,
+                deadline,
+                timeout,
+            } => {
+                if self.config.forward_to_leader && self.engine.leader.is_none() {
+                    self.forward_client_write(app_data, tx).await;
+                } else if let Some(log_id) = self.write_entry(C::Entry::from_app_data(app_data), Some(tx)) {
+                    self.client_write_deadlines.insert(log_id.index, (deadline, timeout));
+                }
+            }
+            RaftMsg::ClientWriteSubmit {
+                app_data,
+                tx,
+                log_id_tx,
+            } => {
+                let log_id = self.write_entry(C::Entry::from_app_data(app_data), Some(tx));
+                match log_id {
+                    // Respond when the entry is flushed.
+                    Some(log_id) => self.flush_waiters.push((log_id, log_id_tx)),
+                    None => {
+                        let _ = log_id_tx.send(None);
+                    }
+                }
+            }
+            RaftMsg::ClientWriteMany { requests } => {
+                let requests =
+                    requests.into_iter().map(|(app_data, tx)| (C::Entry::from_app_data(app_data), tx)).collect();
+                self.write_entries(requests);
             }
             RaftMsg::Initialize { members, tx } => {
                 tracing::info!(
@@ -1231,6 +2016,24 @@ where
 
                 self.handle_initialize(members, tx);
             }
+            RaftMsg::InitializeWithSnapshot { snapshot, tx } => {
+                tracing::info!(
+                    snapshot = display(&snapshot),
+                    "received RaftMsg::InitializeWithSnapshot: {}",
+                    func_name!()
+                );
+
+                self.handle_initialize_with_snapshot(snapshot, tx);
+            }
+            RaftMsg::ForceSetMembership { members, tx } => {
+                tracing::warn!(
+                    members = debug(&members),
+                    "received RaftMsg::ForceSetMembership: {}",
+                    func_name!()
+                );
+
+                self.handle_force_set_membership(members, tx);
+            }
             RaftMsg::ChangeMembership { changes, retain, tx } => {
                 tracing::info!(
                     members = debug(&changes),
@@ -1247,12 +2050,13 @@ where
             RaftMsg::HandleTransferLeader {
                 from: current_leader_vote,
                 to,
+                ..
             } => {
                 if self.engine.state.vote_ref() == &current_leader_vote {
                     tracing::info!("Transfer Leader from: {}, to {}", current_leader_vote, to);
 
                     self.engine.state.vote.disable_lease();
-                    if self.id == to {
+                    if self.id == to && !self.config.witness {
                         self.engine.elect();
                     }
                 }
@@ -1262,7 +2066,9 @@ where
 
                 match cmd {
                     ExternalCommand::Elect => {
-                        if self.engine.state.membership_state.effective().is_voter(&self.id) {
+                        if self.config.witness {
+                            tracing::info!("ExternalCommand: a witness does not elect");
+                        } else if self.engine.state.membership_state.effective().is_voter(&self.id) {
                             // TODO: reject if it is already a leader?
                             self.engine.elect();
                             tracing::debug!("ExternalCommand: triggered election");
@@ -1273,7 +2079,15 @@ where
                     ExternalCommand::Heartbeat => {
                         self.send_heartbeat("ExternalCommand");
                     }
+                    ExternalCommand::Tick => {
+                        self.handle_tick();
+                    }
                     ExternalCommand::Snapshot => self.trigger_snapshot(),
+                    ExternalCommand::SnapshotAndWait { tx } => {
+                        let applied = self.engine.state.io_applied().copied();
+                        self.snapshot_waiters.push((applied, tx));
+                        self.trigger_snapshot();
+                    }
                     ExternalCommand::GetSnapshot { tx } => {
                         let cmd = sm::Command::get_snapshot(tx);
                         let res = self.sm_handle.send(cmd);
@@ -1345,51 +2159,8 @@ where
             }
 
             Notification::Tick { i } => {
-                // check every timer
-
-                let now = C::now();
-                tracing::debug!("received tick: {}, now: {}", i, now.display());
-
-                self.handle_tick_election();
-
-                // TODO: test: fixture: make isolated_nodes a single-way isolating.
-
-                // Leader send heartbeat
-                let heartbeat_at = self.engine.leader_ref().map(|l| l.next_heartbeat);
-                if let Some(t) = heartbeat_at {
-                    if now >= t {
-                        if self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) {
-                            self.send_heartbeat("tick");
-                        }
-
-                        // Install next heartbeat
-                        if let Some(l) = self.engine.leader_mut() {
-                            l.next_heartbeat = C::now() + Duration::from_millis(self.config.heartbeat_interval);
-                        }
-                    }
-                }
-
-                // When a membership that removes the leader is committed,
-                // the leader continue to work for a short while before reverting to a learner.
-                // This way, let the leader replicate the `membership-log-is-committed` message to
-                // followers.
-                // Otherwise, if the leader step down at once, the follower might have to
-                // re-commit the membership log again, electing itself.
-                //
-                // ---
-                //
-                // Stepping down only when the response of the second change-membership is sent.
-                // Otherwise the Sender to the caller will be dropped before sending back the
-                // response.
-
-                // TODO: temp solution: Manually wait until the second membership log being applied to state
-                //       machine. Because the response is sent back to the caller after log is
-                //       applied.
-                //       ---
-                //       A better way is to make leader step down a command that waits for the log to be applied.
-                if self.engine.state.io_applied() >= self.engine.state.membership_state.effective().log_id().as_ref() {
-                    self.engine.leader_step_down();
-                }
+                tracing::debug!("received tick: {}", i);
+                self.handle_tick();
             }
 
             Notification::StorageError { error } => {
@@ -1397,6 +2168,11 @@ where
                 return Err(Fatal::StorageError(error));
             }
 
+            Notification::StorageRetry => {
+                // The postponed command is retried when running the queued commands.
+                tracing::debug!("received storage retry");
+            }
+
             Notification::LocalIO { io_id } => {
                 self.engine.state.io_state.io_progress.flush(io_id);
 
@@ -1411,6 +2187,8 @@ where
                                 self.engine.replication_handler().update_local_progress(log_io_id.log_id);
                             }
                         }
+
+                        self.respond_flush_waiters(&log_io_id);
                     }
                     IOId::Vote(_vote) => {
                         // nothing to do
@@ -1465,7 +2243,16 @@ where
                         // In-memory state should always be ahead or equal to the io state.
 
                         let last_log_id = meta.last_log_id;
+                        self.emit_event(RaftEvent::SnapshotBuilt { meta: meta.clone() });
+                        self.respond_snapshot_waiters(&meta);
                         self.engine.finish_building_snapshot(meta);
+                        self.last_snapshot_at = C::now();
+
+                        if !self.snapshot_waiters.is_empty() {
+                            // The built snapshot was triggered before some waiters arrived and
+                            // does not include all logs they wait for.
+                            self.trigger_snapshot();
+                        }
 
                         let st = self.engine.state.io_state_mut();
                         st.update_snapshot(last_log_id);
@@ -1484,6 +2271,9 @@ where
                             let st = self.engine.state.io_state_mut();
                             st.update_applied(meta.last_log_id);
                             st.update_snapshot(meta.last_log_id);
+
+                            self.last_snapshot_at = C::now();
+                            self.emit_event(RaftEvent::SnapshotInstalled { meta });
                         }
                     }
                     sm::Response::Apply(res) => {
@@ -1497,6 +2287,115 @@ where
         Ok(())
     }
 
+    /// Trigger a snapshot building if the time based snapshot policy is due.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn handle_tick_snapshot(&mut self, now: InstantOf<C>) {
+        let Some(interval) = self.engine.config.snapshot_policy.interval() else {
+            return;
+        };
+
+        if now < self.last_snapshot_at + interval {
+            return;
+        }
+
+        if self.engine.state.committed() <= self.engine.state.snapshot_last_log_id() {
+            tracing::debug!("no new committed log since last snapshot, do not build snapshot");
+            return;
+        }
+
+        tracing::info!(
+            "snapshot interval {:?} passed since last snapshot at {}, trigger snapshot",
+            interval,
+            self.last_snapshot_at.display()
+        );
+
+        if self.engine.snapshot_handler().trigger_snapshot() {
+            // Do not trigger again before this building is finished.
+            self.last_snapshot_at = now;
+        }
+    }
+
+    /// Fail the client writes whose deadline has expired with a timeout error, and drop their
+    /// responders.
+    ///
+    /// The entries are not removed: they may still be committed and applied.
+    fn expire_client_writes(&mut self, now: InstantOf<C>) {
+        let expired: Vec<_> = self
+            .client_write_deadlines
+            .iter()
+            .filter(|(_, (deadline, _))| *deadline <= now)
+            .map(|(log_index, (_, timeout))| (*log_index, *timeout))
+            .collect();
+
+        for (log_index, timeout) in expired {
+            self.client_write_deadlines.remove(&log_index);
+
+            if let Some(tx) = self.client_resp_channels.remove(&log_index) {
+                tracing::debug!(log_index, timeout = debug(timeout), "client write timeout");
+                tx.send(Err(ClientWriteError::Timeout(WriteTimeout { timeout })));
+            }
+        }
+    }
+
+    /// Check every timer: election timeout, heartbeat, quorum, snapshot and purge.
+    ///
+    /// It is called by the internal [`Tick`](crate::core::Tick) or by an external tick source via
+    /// [`Trigger::tick()`](crate::raft::trigger::Trigger::tick).
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn handle_tick(&mut self) {
+        let now = C::now();
+        tracing::debug!("handle tick, now: {}", now.display());
+
+        self.handle_tick_election();
+        self.handle_tick_snapshot(now);
+        self.expire_client_writes(now);
+
+        // Continue a purge that is split into parts by `purge_max_batch_size`.
+        if self.engine.state.purge_upto() > self.engine.state.last_purged_log_id() {
+            self.engine.try_purge_log();
+        }
+        self.engine.leader_check_quorum();
+
+        // TODO: test: fixture: make isolated_nodes a single-way isolating.
+
+        // Leader send heartbeat
+        let heartbeat_at = self.engine.leader_ref().map(|l| l.next_heartbeat);
+        if let Some(t) = heartbeat_at {
+            if now >= t {
+                if self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) {
+                    self.send_heartbeat("tick");
+                }
+
+                // Install next heartbeat
+                if let Some(l) = self.engine.leader_mut() {
+                    l.next_heartbeat = C::now() + Duration::from_millis(self.config.heartbeat_interval);
+                }
+            }
+        }
+
+        // When a membership that removes the leader is committed,
+        // the leader continue to work for a short while before reverting to a learner.
+        // This way, let the leader replicate the `membership-log-is-committed` message to
+        // followers.
+        // Otherwise, if the leader step down at once, the follower might have to
+        // re-commit the membership log again, electing itself.
+        //
+        // ---
+        //
+        // Stepping down only when the response of the second change-membership is sent.
+        // Otherwise the Sender to the caller will be dropped before sending back the
+        // response.
+
+        // TODO: temp solution: Manually wait until the second membership log being applied to state
+        //       machine. Because the response is sent back to the caller after log is
+        //       applied.
+        //       ---
+        //       A better way is to make leader step down a command that waits for the log to be applied.
+        if self.engine.state.io_applied() >= self.engine.state.membership_state.effective().log_id().as_ref() {
+            self.engine.leader_step_down();
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     fn handle_tick_election(&mut self) {
         let now = C::now();
@@ -1520,6 +2419,11 @@ where
             return;
         }
 
+        if self.config.witness {
+            tracing::debug!("this node is a witness");
+            return;
+        }
+
         if self.engine.state.membership_state.effective().voter_ids().count() == 1 {
             tracing::debug!("this is the only voter, do election at once");
         } else {
@@ -1528,7 +2432,7 @@ where
             let local_vote = &self.engine.state.vote;
             let timer_config = &self.engine.config.timer_config;
 
-            let mut election_timeout = timer_config.election_timeout;
+            let mut election_timeout = timer_config.election_timeout + timer_config.priority_delay;
 
             if self.engine.is_there_greater_log() {
                 election_timeout += timer_config.smaller_log_timeout;
@@ -1548,7 +2452,7 @@ where
         self.engine.reset_greater_log();
 
         tracing::info!("do trigger election");
-        self.engine.elect();
+        self.engine.pre_elect();
     }
 
     /// If a message is sent by a previous server state but is received by current server state,
@@ -1666,6 +2570,13 @@ where
             }
         }
 
+        if let Some((_, retry_at)) = &self.storage_retry {
+            if C::now() < *retry_at {
+                tracing::debug!("storage retry is not yet due, postpone cmd: {}", cmd);
+                return Ok(Some(cmd));
+            }
+        }
+
         tracing::debug!("RAFT_event id={:<2}    cmd: {}", self.id, cmd);
 
         match cmd {
@@ -1685,7 +2596,8 @@ where
 
                 let io_id = IOId::new_log_io(vote.into_committed(), Some(last_log_id));
                 let notify = Notification::LocalIO { io_id };
-                let callback = IOFlushed::new(notify, self.tx_notification.downgrade());
+                let callback =
+                    IOFlushed::new(notify, self.tx_notification.downgrade()).with_slow_io(self.slow_io.clone());
 
                 // Mark this IO request as submitted,
                 // other commands relying on it can then be processed.
@@ -1698,11 +2610,41 @@ where
                 self.engine.state.io_state.io_progress.submit(io_id);
 
                 // Submit IO request, do not wait for the response.
-                self.log_store.append(entries, callback).await?;
+                // The time until the entries are flushed is checked by the callback.
+                if self.config.storage_retry_max == 0 {
+                    self.log_store.append(entries, callback).await?;
+                } else {
+                    let n = entries.len();
+                    let res = self.log_store.try_append(entries, callback).await;
+                    match res {
+                        Ok(()) => {
+                            self.storage_retry = None;
+                        }
+                        // The append can be retried only if all the entries are given back.
+                        Err((e, entries)) if entries.len() == n => {
+                            self.retry_later(Err(e))?;
+                            return Ok(Some(Command::AppendInputEntries {
+                                committed_vote: vote,
+                                entries,
+                            }));
+                        }
+                        Err((e, _)) => {
+                            self.storage_retry = None;
+                            return Err(e);
+                        }
+                    }
+                }
             }
             Command::SaveVote { vote } => {
                 self.engine.state.io_state_mut().io_progress.submit(IOId::new(vote));
-                self.log_store.save_vote(&vote).await?;
+                let start = C::now();
+                let res = self.log_store.save_vote(&vote).await;
+                if res.is_ok() {
+                    self.slow_io.check(format_args!("save_vote {}", vote), start.elapsed());
+                }
+                if self.retry_later(res)? {
+                    return Ok(Some(Command::SaveVote { vote }));
+                }
 
                 let _ = self.tx_notification.send(Notification::LocalIO { io_id: IOId::new(vote) });
 
@@ -1714,14 +2656,41 @@ where
                 });
             }
             Command::PurgeLog { upto } => {
-                self.log_store.purge(upto).await?;
+                let start = C::now();
+                let res = self.log_store.purge(upto).await;
+                if res.is_ok() {
+                    self.slow_io.check(format_args!("purge {}", upto), start.elapsed());
+                }
+                if self.retry_later(res)? {
+                    return Ok(Some(Command::PurgeLog { upto }));
+                }
                 self.engine.state.io_state_mut().update_purged(Some(upto));
+                self.emit_event(RaftEvent::LogPurged { upto });
             }
             Command::TruncateLog { since } => {
-                self.log_store.truncate(since).await?;
+                let start = C::now();
+                let res = self.log_store.truncate(since).await;
+                if res.is_ok() {
+                    self.slow_io.check(format_args!("truncate {}", since), start.elapsed());
+                }
+                if self.retry_later(res)? {
+                    return Ok(Some(Command::TruncateLog { since }));
+                }
+
+                // Inform clients waiting for truncated logs to be flushed: the error is sent to
+                // their responders below.
+                let waiters = std::mem::take(&mut self.flush_waiters);
+                for (log_id, tx) in waiters {
+                    if log_id.index >= since.index {
+                        let _ = tx.send(None);
+                    } else {
+                        self.flush_waiters.push((log_id, tx));
+                    }
+                }
 
                 // Inform clients waiting for logs to be applied.
                 let removed = self.client_resp_channels.split_off(&since.index);
+                self.client_write_deadlines.split_off(&since.index);
                 if !removed.is_empty() {
                     let leader_id = self.current_leader();
                     let leader_node = self.get_leader_node(leader_id);
@@ -1741,7 +2710,10 @@ where
                 }
             }
             Command::SendVote { vote_req } => {
-                self.spawn_parallel_vote_requests(&vote_req).await;
+                self.spawn_parallel_vote_requests(&vote_req, false).await;
+            }
+            Command::SendPreVote { vote_req } => {
+                self.spawn_parallel_vote_requests(&vote_req, true).await;
             }
             Command::ReplicateCommitted { committed } => {
                 for node in self.replications.values() {
@@ -1752,7 +2724,14 @@ where
                 self.heartbeat_handle.broadcast(HeartbeatEvent::new(C::now(), session_id, committed))
             }
             Command::SaveCommitted { committed } => {
-                self.log_store.save_committed(Some(committed)).await?;
+                let start = C::now();
+                let res = self.log_store.save_committed(Some(committed)).await;
+                if res.is_ok() {
+                    self.slow_io.check(format_args!("save_committed {}", committed), start.elapsed());
+                }
+                if self.retry_later(res)? {
+                    return Ok(Some(Command::SaveCommitted { committed }));
+                }
             }
             Command::Apply {
                 already_committed,
@@ -6,6 +6,7 @@ use crate::core::raft_msg::ResultSender;
 use crate::core::sm;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
+use crate::SnapshotMeta;
 
 /// Application-triggered Raft actions for testing and administration.
 ///
@@ -20,9 +21,16 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     /// Send a heartbeat message, only if the node is leader, or it will be ignored.
     Heartbeat,
 
+    /// Check every timer as if the internal tick fired.
+    Tick,
+
     /// Initiate to build a snapshot on this node.
     Snapshot,
 
+    /// Initiate to build a snapshot on this node,
+    /// and send back the meta of the snapshot once it is built.
+    SnapshotAndWait { tx: ResultSender<C, SnapshotMeta<C>> },
+
     /// Get a snapshot from the state machine, send back via a oneshot::Sender.
     GetSnapshot { tx: ResultSender<C, Option<Snapshot<C>>> },
 
@@ -60,9 +68,15 @@ where C: RaftTypeConfig
             ExternalCommand::Heartbeat => {
                 write!(f, "Heartbeat")
             }
+            ExternalCommand::Tick => {
+                write!(f, "Tick")
+            }
             ExternalCommand::Snapshot => {
                 write!(f, "Snapshot")
             }
+            ExternalCommand::SnapshotAndWait { .. } => {
+                write!(f, "SnapshotAndWait")
+            }
             ExternalCommand::GetSnapshot { .. } => {
                 write!(f, "GetSnapshot")
             }
@@ -1,17 +1,23 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Duration;
 
 use crate::base::BoxOnce;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::error::CheckIsLeaderError;
+use crate::error::ForceSetMembershipError;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::ReadIndexError;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::storage::Snapshot;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::ResponderOf;
@@ -19,6 +25,7 @@ use crate::type_config::alias::SnapshotDataOf;
 use crate::ChangeMembers;
 use crate::RaftState;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::Vote;
 
 pub(crate) mod external_command;
@@ -32,6 +39,9 @@ pub(crate) type VoteTx<C> = ResultSender<C, VoteResponse<C>>;
 /// TX for Append Entries Response
 pub(crate) type AppendEntriesTx<C> = ResultSender<C, AppendEntriesResponse<C>>;
 
+/// TX for Heartbeat Response
+pub(crate) type HeartbeatTx<C> = ResultSender<C, HeartbeatResponse<C>>;
+
 /// TX for Linearizable Read Response
 pub(crate) type ClientReadTx<C> = ResultSender<C, (Option<LogIdOf<C>>, Option<LogIdOf<C>>), CheckIsLeaderError<C>>;
 
@@ -44,17 +54,42 @@ where C: RaftTypeConfig
     AppendEntries {
         rpc: AppendEntriesRequest<C>,
         tx: AppendEntriesTx<C>,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
+    },
+
+    Heartbeat {
+        rpc: HeartbeatRequest<C>,
+        tx: HeartbeatTx<C>,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
     },
 
     RequestVote {
         rpc: VoteRequest<C>,
         tx: VoteTx<C>,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
+    },
+
+    RequestPreVote {
+        rpc: VoteRequest<C>,
+        tx: VoteTx<C>,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
     },
 
     InstallFullSnapshot {
         vote: Vote<C::NodeId>,
         snapshot: Snapshot<C>,
         tx: ResultSender<C, SnapshotResponse<C>>,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
     },
 
     /// Begin receiving a snapshot from the leader.
@@ -67,20 +102,79 @@ where C: RaftTypeConfig
         tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>,
     },
 
+    /// Reopen a snapshot partially received before, to resume receiving it.
+    ///
+    /// Returns the size of the data already received and a snapshot data handle, or `None`.
+    ResumeReceivingSnapshot {
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, Option<(u64, Box<SnapshotDataOf<C>>)>, Infallible>,
+    },
+
+    /// Discard a partially received snapshot, so that it will be received from the beginning.
+    DiscardReceivingSnapshot {
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, (), Infallible>,
+    },
+
+    /// A partially received snapshot is dropped because no chunk is received within
+    /// [`Config::snapshot_receive_idle_timeout`](crate::Config::snapshot_receive_idle_timeout).
+    SnapshotReceiveTimedOut {
+        snapshot_id: SnapshotId,
+    },
+
     ClientWriteRequest {
         app_data: C::D,
         tx: ResponderOf<C>,
     },
 
+    /// A client write request whose responder is failed with a timeout error and dropped, if it
+    /// is not applied before `deadline`.
+    ClientWriteWithDeadline {
+        app_data: C::D,
+        tx: ResponderOf<C>,
+        deadline: InstantOf<C>,
+        timeout: Duration,
+    },
+
+    /// A client write request that responds with the assigned log id once the entry is flushed,
+    /// in addition to the response sent to `tx` when it is applied.
+    ClientWriteSubmit {
+        app_data: C::D,
+        tx: ResponderOf<C>,
+        log_id_tx: OneshotSenderOf<C, Option<LogIdOf<C>>>,
+    },
+
+    /// A batch of client write requests, to be appended to the log in order in one IO.
+    ClientWriteMany {
+        requests: Vec<(C::D, ResponderOf<C>)>,
+    },
+
     CheckIsLeaderRequest {
         tx: ClientReadTx<C>,
     },
 
+    /// Get a read index from the leader, sent by a non-leader node.
+    ReadIndex {
+        tx: ResultSender<C, Option<LogIdOf<C>>, ReadIndexError<C>>,
+    },
+
     Initialize {
         members: BTreeMap<C::NodeId, C::Node>,
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
+    /// Initialize an uninitialized node with a snapshot, e.g., restored from a backup.
+    InitializeWithSnapshot {
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, (), InitializeError<C>>,
+    },
+
+    /// Forcibly set the membership config on this node, bypassing the consensus.
+    ForceSetMembership {
+        members: BTreeMap<C::NodeId, C::Node>,
+        tx: ResultSender<C, (), ForceSetMembershipError<C>>,
+    },
+
     ChangeMembership {
         changes: ChangeMembers<C>,
 
@@ -104,6 +198,9 @@ where C: RaftTypeConfig
         from: Vote<C::NodeId>,
         /// The assigned node to be the next Leader.
         to: C::NodeId,
+
+        /// The span of the caller, in which the request is handled.
+        span: tracing::Span,
     },
 
     ExternalCommand {
@@ -111,6 +208,23 @@ where C: RaftTypeConfig
     },
 }
 
+impl<C> RaftMsg<C>
+where C: RaftTypeConfig
+{
+    /// The span of the caller of an RPC from another node, in which the message is handled.
+    pub(crate) fn span(&self) -> Option<&tracing::Span> {
+        match self {
+            RaftMsg::AppendEntries { span, .. }
+            | RaftMsg::Heartbeat { span, .. }
+            | RaftMsg::RequestVote { span, .. }
+            | RaftMsg::RequestPreVote { span, .. }
+            | RaftMsg::InstallFullSnapshot { span, .. }
+            | RaftMsg::HandleTransferLeader { span, .. } => Some(span),
+            _ => None,
+        }
+    }
+}
+
 impl<C> fmt::Display for RaftMsg<C>
 where C: RaftTypeConfig
 {
@@ -120,27 +234,55 @@ where C: RaftTypeConfig
                 // TODO: avoid using summary()
                 write!(f, "AppendEntries: {}", rpc)
             }
+            RaftMsg::Heartbeat { rpc, .. } => {
+                write!(f, "Heartbeat: {}", rpc)
+            }
             RaftMsg::RequestVote { rpc, .. } => {
                 write!(f, "RequestVote: {}", rpc)
             }
+            RaftMsg::RequestPreVote { rpc, .. } => {
+                write!(f, "RequestPreVote: {}", rpc)
+            }
             RaftMsg::BeginReceivingSnapshot { .. } => {
                 write!(f, "BeginReceivingSnapshot")
             }
+            RaftMsg::ResumeReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "ResumeReceivingSnapshot: {}", snapshot_id)
+            }
+            RaftMsg::DiscardReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "DiscardReceivingSnapshot: {}", snapshot_id)
+            }
+            RaftMsg::SnapshotReceiveTimedOut { snapshot_id } => {
+                write!(f, "SnapshotReceiveTimedOut: {}", snapshot_id)
+            }
             RaftMsg::InstallFullSnapshot { vote, snapshot, .. } => {
                 write!(f, "InstallFullSnapshot: vote: {}, snapshot: {}", vote, snapshot)
             }
             RaftMsg::ClientWriteRequest { .. } => write!(f, "ClientWriteRequest"),
+            RaftMsg::ClientWriteWithDeadline { timeout, .. } => {
+                write!(f, "ClientWriteWithDeadline: timeout: {:?}", timeout)
+            }
+            RaftMsg::ClientWriteSubmit { .. } => write!(f, "ClientWriteSubmit"),
+            RaftMsg::ClientWriteMany { requests } => write!(f, "ClientWriteMany: {} requests", requests.len()),
             RaftMsg::CheckIsLeaderRequest { .. } => write!(f, "CheckIsLeaderRequest"),
+            RaftMsg::ReadIndex { .. } => write!(f, "ReadIndex"),
             RaftMsg::Initialize { members, .. } => {
                 // TODO: avoid using Debug
                 write!(f, "Initialize: {:?}", members)
             }
+            RaftMsg::InitializeWithSnapshot { snapshot, .. } => {
+                write!(f, "InitializeWithSnapshot: {}", snapshot)
+            }
+            RaftMsg::ForceSetMembership { members, .. } => {
+                // TODO: avoid using Debug
+                write!(f, "ForceSetMembership: {:?}", members)
+            }
             RaftMsg::ChangeMembership { changes, retain, .. } => {
                 // TODO: avoid using Debug
                 write!(f, "ChangeMembership: {:?}, retain: {}", changes, retain,)
             }
             RaftMsg::ExternalCoreRequest { .. } => write!(f, "External Request"),
-            RaftMsg::HandleTransferLeader { from, to } => {
+            RaftMsg::HandleTransferLeader { from, to, .. } => {
                 write!(f, "TransferLeader: from_leader: vote={}, to: {}", from, to)
             }
             RaftMsg::ExternalCommand { cmd } => {
@@ -0,0 +1,101 @@
+//! Detect slow calls to the log store and the state machine.
+
+use std::fmt;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Config;
+
+/// Reports the storage calls that take longer than [`Config::slow_io_threshold`].
+///
+/// It is cloned into every component that calls the storage, such as `RaftCore`, the state
+/// machine worker and the [`IOFlushed`] callback, and they share one counter.
+///
+/// [`IOFlushed`]: crate::storage::IOFlushed
+#[derive(Debug, Clone)]
+pub(crate) struct SlowIO {
+    /// `None` if the detection is disabled.
+    threshold: Option<Duration>,
+
+    /// The number of slow calls detected.
+    count: Arc<AtomicU64>,
+}
+
+impl SlowIO {
+    pub(crate) fn new(config: &Config) -> Self {
+        let threshold = if config.slow_io_threshold == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(config.slow_io_threshold))
+        };
+
+        Self {
+            threshold,
+            count: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Check the `elapsed` time of a call to `op`, warn and count it if it is slow.
+    ///
+    /// It returns `true` if the call is slow.
+    pub(crate) fn check(&self, op: impl fmt::Display, elapsed: Duration) -> bool {
+        let Some(threshold) = self.threshold else {
+            return false;
+        };
+
+        if elapsed <= threshold {
+            return false;
+        }
+
+        self.count.fetch_add(1, Ordering::Relaxed);
+
+        tracing::warn!(
+            op = display(&op),
+            elapsed = debug(elapsed),
+            threshold = debug(threshold),
+            "slow storage IO: {} took {:?}, longer than {:?}",
+            op,
+            elapsed,
+            threshold
+        );
+        true
+    }
+
+    /// Return the number of slow calls detected.
+    pub(crate) fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::SlowIO;
+    use crate::Config;
+
+    #[test]
+    fn test_slow_io_check() {
+        let config = Config {
+            slow_io_threshold: 100,
+            ..Default::default()
+        };
+        let slow_io = SlowIO::new(&config);
+        let cloned = slow_io.clone();
+
+        assert!(!slow_io.check("append", Duration::from_millis(100)));
+        assert!(slow_io.check("append", Duration::from_millis(101)));
+        assert!(cloned.check("apply", Duration::from_millis(200)));
+        assert_eq!(2, slow_io.count());
+
+        let config = Config {
+            slow_io_threshold: 0,
+            ..Default::default()
+        };
+        let slow_io = SlowIO::new(&config);
+        assert!(!slow_io.check("append", Duration::from_secs(10)));
+        assert_eq!(0, slow_io.count());
+    }
+}
@@ -7,9 +7,11 @@ use crate::core::raft_msg::ResultSender;
 use crate::error::Infallible;
 use crate::raft_state::IOId;
 use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 
 /// The payload of a state machine command.
 pub(crate) enum Command<C>
@@ -21,10 +23,28 @@ where C: RaftTypeConfig
     /// Get the latest built snapshot.
     GetSnapshot { tx: ResultSender<C, Option<Snapshot<C>>> },
 
+    /// Build a snapshot containing only the changes since the snapshot `base`.
+    GetDeltaSnapshot {
+        base: SnapshotSignature<C>,
+        tx: ResultSender<C, Option<Snapshot<C>>>,
+    },
+
     BeginReceivingSnapshot {
         tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>,
     },
 
+    /// Reopen a partially received snapshot.
+    ResumeReceivingSnapshot {
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, Option<(u64, Box<SnapshotDataOf<C>>)>, Infallible>,
+    },
+
+    /// Discard a partially received snapshot.
+    DiscardReceivingSnapshot {
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, (), Infallible>,
+    },
+
     InstallFullSnapshot {
         /// The IO id used to update IO progress.
         ///
@@ -53,6 +73,11 @@ where C: RaftTypeConfig
         /// The SM type user specified, for debug purpose.
         input_sm_type: &'static str,
     },
+
+    /// Respond after all of the previously sent commands are executed.
+    ///
+    /// It is used when shutting down, to wait for the queued logs to be applied.
+    Drain { tx: ResultSender<C, (), Infallible> },
 }
 
 impl<C> Command<C>
@@ -66,10 +91,25 @@ where C: RaftTypeConfig
         Command::GetSnapshot { tx }
     }
 
+    pub(crate) fn get_delta_snapshot(base: SnapshotSignature<C>, tx: ResultSender<C, Option<Snapshot<C>>>) -> Self {
+        Command::GetDeltaSnapshot { base, tx }
+    }
+
     pub(crate) fn begin_receiving_snapshot(tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>) -> Self {
         Command::BeginReceivingSnapshot { tx }
     }
 
+    pub(crate) fn resume_receiving_snapshot(
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, Option<(u64, Box<SnapshotDataOf<C>>)>, Infallible>,
+    ) -> Self {
+        Command::ResumeReceivingSnapshot { snapshot_id, tx }
+    }
+
+    pub(crate) fn discard_receiving_snapshot(snapshot_id: SnapshotId, tx: ResultSender<C, (), Infallible>) -> Self {
+        Command::DiscardReceivingSnapshot { snapshot_id, tx }
+    }
+
     pub(crate) fn install_full_snapshot(snapshot: Snapshot<C>, io_id: IOId<C>) -> Self {
         Command::InstallFullSnapshot { io_id, snapshot }
     }
@@ -79,15 +119,23 @@ where C: RaftTypeConfig
         Command::Apply { first, last }
     }
 
+    pub(crate) fn drain(tx: ResultSender<C, (), Infallible>) -> Self {
+        Command::Drain { tx }
+    }
+
     /// Return the IOId if this command submit any IO.
     pub(crate) fn get_submit_io(&self) -> Option<IOId<C>> {
         match self {
             Command::BuildSnapshot => None,
             Command::GetSnapshot { .. } => None,
+            Command::GetDeltaSnapshot { .. } => None,
             Command::BeginReceivingSnapshot { .. } => None,
+            Command::ResumeReceivingSnapshot { .. } => None,
+            Command::DiscardReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { io_id, .. } => Some(*io_id),
             Command::Apply { .. } => None,
             Command::Func { .. } => None,
+            Command::Drain { .. } => None,
         }
     }
 }
@@ -99,14 +147,22 @@ where C: RaftTypeConfig
         match self {
             Command::BuildSnapshot => write!(f, "BuildSnapshot"),
             Command::GetSnapshot { .. } => write!(f, "GetSnapshot"),
+            Command::GetDeltaSnapshot { base, .. } => write!(f, "GetDeltaSnapshot: base: {:?}", base),
             Command::InstallFullSnapshot { io_id, snapshot } => {
                 write!(f, "InstallFullSnapshot: meta: {:?}, io_id: {:?}", snapshot.meta, io_id)
             }
             Command::BeginReceivingSnapshot { .. } => {
                 write!(f, "BeginReceivingSnapshot")
             }
+            Command::ResumeReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "ResumeReceivingSnapshot: snapshot_id: {}", snapshot_id)
+            }
+            Command::DiscardReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "DiscardReceivingSnapshot: snapshot_id: {}", snapshot_id)
+            }
             Command::Apply { first, last } => write!(f, "Apply: [{},{}]", first, last),
             Command::Func { .. } => write!(f, "Func"),
+            Command::Drain { .. } => write!(f, "Drain"),
         }
     }
 }
@@ -118,14 +174,24 @@ where C: RaftTypeConfig
         match self {
             Command::BuildSnapshot => write!(f, "BuildSnapshot"),
             Command::GetSnapshot { .. } => write!(f, "GetSnapshot"),
+            Command::GetDeltaSnapshot { base, .. } => {
+                write!(f, "GetDeltaSnapshot: base: {}", base.snapshot_id)
+            }
             Command::InstallFullSnapshot { io_id, snapshot } => {
                 write!(f, "InstallFullSnapshot: meta: {}, io_id: {}", snapshot.meta, io_id)
             }
             Command::BeginReceivingSnapshot { .. } => {
                 write!(f, "BeginReceivingSnapshot")
             }
+            Command::ResumeReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "ResumeReceivingSnapshot: snapshot_id: {}", snapshot_id)
+            }
+            Command::DiscardReceivingSnapshot { snapshot_id, .. } => {
+                write!(f, "DiscardReceivingSnapshot: snapshot_id: {}", snapshot_id)
+            }
             Command::Apply { first, last } => write!(f, "Apply: [{},{}]", first, last),
             Command::Func { .. } => write!(f, "Func"),
+            Command::Drain { .. } => write!(f, "Drain"),
         }
     }
 }
@@ -138,7 +204,16 @@ where C: RaftTypeConfig
         match (self, other) {
             (Command::BuildSnapshot, Command::BuildSnapshot) => true,
             (Command::GetSnapshot { .. }, Command::GetSnapshot { .. }) => true,
+            (Command::GetDeltaSnapshot { base: b1, .. }, Command::GetDeltaSnapshot { base: b2, .. }) => b1 == b2,
             (Command::BeginReceivingSnapshot { .. }, Command::BeginReceivingSnapshot { .. }) => true,
+            (
+                Command::ResumeReceivingSnapshot { snapshot_id: id1, .. },
+                Command::ResumeReceivingSnapshot { snapshot_id: id2, .. },
+            ) => id1 == id2,
+            (
+                Command::DiscardReceivingSnapshot { snapshot_id: id1, .. },
+                Command::DiscardReceivingSnapshot { snapshot_id: id2, .. },
+            ) => id1 == id2,
             (
                 Command::InstallFullSnapshot {
                     io_id: io1,
@@ -157,6 +232,7 @@ where C: RaftTypeConfig
                 },
             ) => first == first2 && last == last2,
             (Command::Func { .. }, Command::Func { .. }) => false,
+            (Command::Drain { .. }, Command::Drain { .. }) => true,
             _ => false,
         }
     }
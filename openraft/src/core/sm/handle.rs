@@ -3,12 +3,15 @@
 use crate::async_runtime::MpscUnboundedSender;
 use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::async_runtime::SendError;
+use crate::core::raft_msg::ResultSender;
 use crate::core::sm;
 use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
 use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::MpscUnboundedWeakSenderOf;
 use crate::type_config::TypeConfigExt;
+use crate::OptionalSend;
 use crate::RaftTypeConfig;
 
 /// State machine worker handle for sending command to it.
@@ -38,6 +41,7 @@ where C: RaftTypeConfig
 }
 
 /// A handle for retrieving a snapshot from the state machine.
+#[derive(Clone)]
 pub(crate) struct SnapshotReader<C>
 where C: RaftTypeConfig
 {
@@ -57,9 +61,27 @@ where C: RaftTypeConfig
     /// If the state machine worker has shutdown, it will return an error.
     /// If there is not snapshot available, it will return `Ok(None)`.
     pub(crate) async fn get_snapshot(&self) -> Result<Option<Snapshot<C>>, &'static str> {
+        self.call(sm::Command::get_snapshot).await
+    }
+
+    /// Get a snapshot containing only the changes since the snapshot `base` from the state
+    /// machine.
+    ///
+    /// If the state machine worker has shutdown, it will return an error.
+    /// If the state machine can not build such a snapshot, it will return `Ok(None)`.
+    pub(crate) async fn get_delta_snapshot(
+        &self,
+        base: SnapshotSignature<C>,
+    ) -> Result<Option<Snapshot<C>>, &'static str> {
+        self.call(|tx| sm::Command::get_delta_snapshot(base, tx)).await
+    }
+
+    /// Send a command built by `make_cmd` to the state machine worker and wait for the reply.
+    async fn call<T>(&self, make_cmd: impl FnOnce(ResultSender<C, T>) -> sm::Command<C>) -> Result<T, &'static str>
+    where T: OptionalSend {
         let (tx, rx) = C::oneshot();
 
-        let cmd = sm::Command::get_snapshot(tx);
+        let cmd = make_cmd(tx);
         tracing::debug!("SnapshotReader sending command to sm::Worker: {:?}", cmd);
 
         let Some(cmd_tx) = self.cmd_tx.upgrade() else {
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyerror::AnyError;
 use tracing_futures::Instrument;
 
@@ -13,6 +15,8 @@ use crate::core::sm::CommandResult;
 use crate::core::sm::Response;
 use crate::core::ApplyResult;
 use crate::core::ApplyingEntry;
+use crate::core::SlowIO;
+use crate::core::StorageRetry;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySliceExt;
 use crate::entry::RaftPayload;
@@ -23,6 +27,8 @@ use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::TypeConfigExt;
+use crate::Config;
+use crate::Instant;
 use crate::RaftLogId;
 use crate::RaftLogReader;
 use crate::RaftSnapshotBuilder;
@@ -48,6 +54,11 @@ where
 
     /// Send back the result of the command to RaftCore.
     resp_tx: MpscUnboundedSenderOf<C, Notification<C>>,
+
+    /// Detects slow calls to the state machine.
+    slow_io: SlowIO,
+
+    config: Arc<Config>,
 }
 
 impl<C, SM, LR> Worker<C, SM, LR>
@@ -61,6 +72,8 @@ where
         state_machine: SM,
         log_reader: LR,
         resp_tx: MpscUnboundedSenderOf<C, Notification<C>>,
+        slow_io: SlowIO,
+        config: Arc<Config>,
         span: tracing::Span,
     ) -> Handle<C> {
         let (cmd_tx, cmd_rx) = C::mpsc_unbounded();
@@ -70,6 +83,8 @@ where
             log_reader,
             cmd_rx,
             resp_tx,
+            slow_io,
+            config,
         };
 
         let join_handle = worker.do_spawn(span);
@@ -94,8 +109,14 @@ where
 
     #[tracing::instrument(level = "debug", skip_all)]
     async fn worker_loop(&mut self) -> Result<(), StorageError<C>> {
+        // A command received while batching `Apply` commands, to execute next.
+        let mut pending: Option<Command<C>> = None;
+
         loop {
-            let cmd = self.cmd_rx.recv().await;
+            let cmd = match pending.take() {
+                Some(x) => Some(x),
+                None => self.cmd_rx.recv().await,
+            };
             let cmd = match cmd {
                 None => {
                     tracing::info!("{}: rx closed, state machine worker quit", func_name!());
@@ -119,11 +140,21 @@ where
                     self.get_snapshot(tx).await?;
                     // GetSnapshot does not respond to RaftCore
                 }
+                Command::GetDeltaSnapshot { base, tx } => {
+                    tracing::info!("{}: get delta snapshot since: {}", func_name!(), base.snapshot_id);
+
+                    let snapshot = self.state_machine.build_delta_snapshot(&base).await?;
+
+                    let _ = tx.send(Ok(snapshot));
+                    // GetDeltaSnapshot does not respond to RaftCore
+                }
                 Command::InstallFullSnapshot { io_id, snapshot } => {
                     tracing::info!("{}: install complete snapshot", func_name!());
 
                     let meta = snapshot.meta.clone();
+                    let start = C::now();
                     self.state_machine.install_snapshot(&meta, snapshot.snapshot).await?;
+                    self.slow_io.check(format_args!("install snapshot {}", meta), start.elapsed());
 
                     tracing::info!("Done install complete snapshot, meta: {}", meta);
 
@@ -138,8 +169,52 @@ where
                     let _ = tx.send(Ok(snapshot_data));
                     // No response to RaftCore
                 }
-                Command::Apply { first, last } => {
-                    let resp = self.apply(first, last).await?;
+                Command::ResumeReceivingSnapshot { snapshot_id, tx } => {
+                    tracing::info!("{}: ResumeReceivingSnapshot: {}", func_name!(), snapshot_id);
+
+                    let resumed = self.state_machine.resume_receiving_snapshot(&snapshot_id).await?;
+
+                    let _ = tx.send(Ok(resumed));
+                    // No response to RaftCore
+                }
+                Command::DiscardReceivingSnapshot { snapshot_id, tx } => {
+                    tracing::info!("{}: DiscardReceivingSnapshot: {}", func_name!(), snapshot_id);
+
+                    self.state_machine.discard_receiving_snapshot(&snapshot_id).await?;
+
+                    let _ = tx.send(Ok(()));
+                    // No response to RaftCore
+                }
+                Command::Apply { first, mut last } => {
+                    // Apply the consecutive `Apply` commands that are already queued in one batch.
+                    while let Ok(next) = self.cmd_rx.try_recv() {
+                        match next {
+                            Command::Apply {
+                                first: next_first,
+                                last: next_last,
+                            } if next_first.index == last.index + 1 => {
+                                last = next_last;
+                            }
+                            other => {
+                                pending = Some(other);
+                                break;
+                            }
+                        }
+                    }
+
+                    // A transient error leaves the state machine unchanged, the entries are read
+                    // and applied again.
+                    let mut retry = StorageRetry::new(&self.config);
+                    let resp = loop {
+                        match self.apply(first, last).await {
+                            Ok(x) => break x,
+                            Err(e) => {
+                                if !retry.backoff(&e).await {
+                                    return Err(e);
+                                }
+                            }
+                        }
+                    };
                     let res = CommandResult::new(Ok(Response::Apply(resp)));
                     let _ = self.resp_tx.send(Notification::sm(res));
                 }
@@ -157,6 +232,11 @@ where
                         );
                     };
                 }
+                Command::Drain { tx } => {
+                    tracing::info!("{}: all previous commands are executed", func_name!());
+
+                    let _ = tx.send(Ok(()));
+                }
             };
         }
     }
@@ -191,7 +271,12 @@ where
 
         let n_entries = end - since;
 
+        let start = C::now();
         let apply_results = self.state_machine.apply(entries).await?;
+        self.slow_io.check(
+            format_args!("apply {} entries upto {}", n_entries, last_applied),
+            start.elapsed(),
+        );
 
         let n_replies = apply_results.len() as u64;
 
@@ -229,9 +314,12 @@ where
         tracing::info!("{}", func_name!());
 
         let mut builder = self.state_machine.get_snapshot_builder().await;
+        let slow_io = self.slow_io.clone();
 
         let _handle = C::spawn(async move {
+            let start = C::now();
             let res = builder.build_snapshot().await;
+            slow_io.check("build snapshot", start.elapsed());
             let res = res.map(|snap| Response::BuildSnapshot(snap.meta));
             let cmd_res = CommandResult::new(res);
             let _ = resp_tx.send(Notification::sm(cmd_res));
@@ -243,7 +331,17 @@ where
     async fn get_snapshot(&mut self, tx: ResultSender<C, Option<Snapshot<C>>>) -> Result<(), StorageError<C>> {
         tracing::info!("{}", func_name!());
 
-        let snapshot = self.state_machine.get_current_snapshot().await?;
+        let mut retry = StorageRetry::new(&self.config);
+        let snapshot = loop {
+            match self.state_machine.get_current_snapshot().await {
+                Ok(x) => break x,
+                Err(e) => {
+                    if !retry.backoff(&e).await {
+                        return Err(e);
+                    }
+                }
+            }
+        };
 
         tracing::info!(
             "sending back snapshot: meta: {}",
@@ -253,3 +351,179 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::ops::RangeBounds;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use super::Worker;
+    use crate::async_runtime::MpscUnboundedReceiver;
+    use crate::async_runtime::MpscUnboundedSender;
+    use crate::core::notification::Notification;
+    use crate::core::sm::Command;
+    use crate::core::sm::Response;
+    use crate::core::SlowIO;
+    use crate::engine::testing::UTConfig;
+    use crate::storage::RaftStateMachine;
+    use crate::storage::Snapshot;
+    use crate::testing::blank_ent;
+    use crate::testing::log_id;
+    use crate::type_config::TypeConfigExt;
+    use crate::Config;
+    use crate::Entry;
+    use crate::LogId;
+    use crate::OptionalSend;
+    use crate::RaftLogId;
+    use crate::RaftLogReader;
+    use crate::RaftSnapshotBuilder;
+    use crate::SnapshotMeta;
+    use crate::StorageError;
+    use crate::StoredMembership;
+    use crate::Vote;
+
+    /// Provides blank entries at index 1 to 10.
+    struct LogReader;
+
+    impl RaftLogReader<UTConfig> for LogReader {
+        async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+            &mut self,
+            range: RB,
+        ) -> Result<Vec<Entry<UTConfig>>, StorageError<UTConfig>> {
+            Ok((1..=10).filter(|i| range.contains(i)).map(|i| blank_ent::<UTConfig>(1, 1, i)).collect())
+        }
+
+        async fn read_vote(&mut self) -> Result<Option<Vote<u64>>, StorageError<UTConfig>> {
+            Ok(None)
+        }
+    }
+
+    /// Records the calls in `events`.
+    #[derive(Clone, Default)]
+    struct StateMachine {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RaftSnapshotBuilder<UTConfig> for StateMachine {
+        async fn build_snapshot(&mut self) -> Result<Snapshot<UTConfig>, StorageError<UTConfig>> {
+            unreachable!("not used")
+        }
+    }
+
+    impl RaftStateMachine<UTConfig> for StateMachine {
+        type SnapshotBuilder = Self;
+
+        async fn applied_state(
+            &mut self,
+        ) -> Result<(Option<LogId<u64>>, StoredMembership<UTConfig>), StorageError<UTConfig>> {
+            Ok((None, StoredMembership::default()))
+        }
+
+        async fn apply<I>(&mut self, entries: I) -> Result<Vec<()>, StorageError<UTConfig>>
+        where
+            I: IntoIterator<Item = Entry<UTConfig>> + OptionalSend,
+            I::IntoIter: OptionalSend,
+        {
+            let indexes = entries.into_iter().map(|e| e.get_log_id().index).collect::<Vec<_>>();
+            let res = vec![(); indexes.len()];
+            self.events.lock().unwrap().push(format!("apply {:?}", indexes));
+            Ok(res)
+        }
+
+        async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+            self.clone()
+        }
+
+        async fn begin_receiving_snapshot(&mut self) -> Result<Box<std::io::Cursor<Vec<u8>>>, StorageError<UTConfig>> {
+            unreachable!("not used")
+        }
+
+        async fn install_snapshot(
+            &mut self,
+            _meta: &SnapshotMeta<UTConfig>,
+            _snapshot: Box<std::io::Cursor<Vec<u8>>>,
+        ) -> Result<(), StorageError<UTConfig>> {
+            unreachable!("not used")
+        }
+
+        async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<UTConfig>>, StorageError<UTConfig>> {
+            self.events.lock().unwrap().push("get_snapshot".to_string());
+            Ok(None)
+        }
+    }
+
+    /// Queued consecutive `Apply` commands are applied in one batch. A batch ends at a command of
+    /// another kind or at a non-consecutive `Apply`, and the responses are sent in order.
+    #[tokio::test]
+    async fn test_apply_batch() -> anyhow::Result<()> {
+        let sm = StateMachine::default();
+        let config = Config::default();
+
+        let (cmd_tx, cmd_rx) = UTConfig::mpsc_unbounded();
+        let (resp_tx, mut resp_rx) = UTConfig::mpsc_unbounded();
+
+        let mut worker = Worker {
+            state_machine: sm.clone(),
+            log_reader: LogReader,
+            cmd_rx,
+            resp_tx,
+            slow_io: SlowIO::new(&config),
+            config: Arc::new(config),
+        };
+
+        let (snapshot_tx, snapshot_rx) = UTConfig::oneshot();
+
+        // Queue all the commands before the worker starts, so that they are batched.
+        let cmds = [
+            Command::apply(log_id(1, 1, 1), log_id(1, 1, 2)),
+            Command::apply(log_id(1, 1, 3), log_id(1, 1, 3)),
+            Command::apply(log_id(1, 1, 4), log_id(1, 1, 5)),
+            Command::get_snapshot(snapshot_tx),
+            Command::apply(log_id(1, 1, 6), log_id(1, 1, 6)),
+            Command::apply(log_id(1, 1, 8), log_id(1, 1, 9)),
+        ];
+        for cmd in cmds {
+            assert!(cmd_tx.send(cmd).is_ok());
+        }
+        drop(cmd_tx);
+
+        worker.worker_loop().await?;
+
+        assert!(snapshot_rx.await?.unwrap().is_none());
+
+        assert_eq!(
+            vec![
+                "apply [1, 2, 3, 4, 5]".to_string(),
+                "get_snapshot".to_string(),
+                "apply [6]".to_string(),
+                "apply [8, 9]".to_string(),
+            ],
+            *sm.events.lock().unwrap()
+        );
+
+        let mut applied = vec![];
+        while let Ok(notification) = resp_rx.try_recv() {
+            let Notification::StateMachine { command_result } = notification else {
+                panic!("expect a state machine response");
+            };
+            let Ok(Response::Apply(res)) = command_result.result else {
+                panic!("expect an apply response");
+            };
+            assert_eq!(res.end - res.since, res.apply_results.len() as u64);
+            applied.push((res.since, res.end, res.last_applied));
+        }
+
+        assert_eq!(
+            vec![
+                (1, 6, log_id(1, 1, 5)),
+                (6, 7, log_id(1, 1, 6)),
+                (8, 10, log_id(1, 1, 9))
+            ],
+            applied
+        );
+
+        Ok(())
+    }
+}
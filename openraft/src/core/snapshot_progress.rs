@@ -0,0 +1,164 @@
+//! Track the progress of transferring a snapshot to report metrics.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::metrics::SnapshotTransfer;
+use crate::network::ReportSnapshotProgress;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::TypeConfigExt;
+use crate::RaftTypeConfig;
+use crate::SnapshotMeta;
+
+/// The progress of the snapshot being transferred to or from a node.
+///
+/// It is cloned into the task that sends or receives the snapshot, which updates it, and into
+/// `RaftCore`, which reports it in the metrics.
+#[derive(Clone)]
+pub(crate) struct SnapshotProgress<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Option<Transfer<C>>>>,
+}
+
+struct Transfer<C>
+where C: RaftTypeConfig
+{
+    metrics: SnapshotTransfer<C>,
+    started_at: InstantOf<C>,
+
+    /// The bytes already transferred when it started, which do not count in the rate.
+    start_bytes: u64,
+}
+
+impl<C> SnapshotProgress<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Start tracking the transfer of the snapshot of `meta`, of which `bytes` are already
+    /// transferred, e.g., when resuming.
+    pub(crate) fn begin(&self, meta: &SnapshotMeta<C>, bytes: u64) {
+        let transfer = Transfer {
+            metrics: SnapshotTransfer {
+                snapshot_id: meta.snapshot_id.clone(),
+                last_log_id: meta.last_log_id,
+                bytes,
+                total_bytes: None,
+                eta_millis: None,
+            },
+            started_at: C::now(),
+            start_bytes: bytes,
+        };
+        *self.inner.lock().unwrap() = Some(transfer);
+    }
+
+    /// Update the number of bytes transferred, and the size of the snapshot if it is known.
+    pub(crate) fn update(&self, bytes: u64, total_bytes: Option<u64>) {
+        if let Some(t) = self.inner.lock().unwrap().as_mut() {
+            t.metrics.bytes = bytes;
+            if total_bytes.is_some() {
+                t.metrics.total_bytes = total_bytes;
+            }
+        }
+    }
+
+    /// Stop tracking, when the transfer is finished or aborted.
+    pub(crate) fn clear(&self) {
+        *self.inner.lock().unwrap() = None;
+    }
+
+    /// Return the progress of the transfer, if any, with the completion time estimated at `now`.
+    pub(crate) fn get(&self, now: InstantOf<C>) -> Option<SnapshotTransfer<C>> {
+        let inner = self.inner.lock().unwrap();
+        let t = inner.as_ref()?;
+
+        let mut metrics = t.metrics.clone();
+
+        let transferred = metrics.bytes.saturating_sub(t.start_bytes);
+        if let Some(total) = metrics.total_bytes {
+            if transferred > 0 {
+                let elapsed = (now - t.started_at).as_millis();
+                let remaining = total.saturating_sub(metrics.bytes) as u128;
+                metrics.eta_millis = Some((remaining * elapsed / transferred as u128) as u64);
+            }
+        }
+
+        Some(metrics)
+    }
+}
+
+impl<C> fmt::Debug for SnapshotProgress<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotProgress").finish()
+    }
+}
+
+impl<C> ReportSnapshotProgress for SnapshotProgress<C>
+where C: RaftTypeConfig
+{
+    fn report(&self, sent: u64, total: u64) {
+        self.update(sent, Some(total));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::SnapshotProgress;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::SnapshotTransfer;
+    use crate::testing::log_id;
+    use crate::type_config::TypeConfigExt;
+    use crate::SnapshotMeta;
+
+    #[test]
+    fn test_snapshot_progress() {
+        let meta = SnapshotMeta::<UTConfig> {
+            last_log_id: Some(log_id(1, 1, 5)),
+            snapshot_id: "s1".to_string(),
+            ..Default::default()
+        };
+
+        let p = SnapshotProgress::<UTConfig>::new();
+        assert_eq!(None, p.get(UTConfig::<()>::now()));
+
+        p.begin(&meta, 0);
+        let now = UTConfig::<()>::now();
+        let want = |bytes, total_bytes, eta_millis| SnapshotTransfer {
+            snapshot_id: "s1".to_string(),
+            last_log_id: Some(log_id(1, 1, 5)),
+            bytes,
+            total_bytes,
+            eta_millis,
+        };
+
+        // Nothing is sent, the completion time is unknown.
+        assert_eq!(Some(want(0, None, None)), p.get(now));
+        p.update(0, Some(1000));
+        assert_eq!(Some(want(0, Some(1000), None)), p.get(now));
+
+        // A quarter is sent in 100 ms, the rest takes 300 ms.
+        p.update(250, Some(1000));
+        assert_eq!(
+            Some(want(250, Some(1000), Some(300))),
+            p.get(now + Duration::from_millis(100))
+        );
+
+        // The size is unknown to a receiver.
+        p.begin(&meta, 100);
+        p.update(200, None);
+        assert_eq!(Some(want(200, None, None)), p.get(now));
+
+        p.clear();
+        assert_eq!(None, p.get(now));
+    }
+}
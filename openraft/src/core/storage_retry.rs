@@ -0,0 +1,100 @@
+//! Retry a storage operation that fails with a transient error.
+
+use std::time::Duration;
+
+use crate::type_config::TypeConfigExt;
+use crate::Config;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// Tracks the retries of a storage operation that fails with a transient [`StorageError`].
+pub(crate) struct StorageRetry {
+    /// The number of retries left.
+    remaining: u64,
+
+    /// The time to wait before the next retry.
+    delay: Duration,
+}
+
+impl StorageRetry {
+    pub(crate) fn new(config: &Config) -> Self {
+        Self {
+            remaining: config.storage_retry_max,
+            delay: Duration::from_millis(config.heartbeat_interval),
+        }
+    }
+
+    /// Return the time to wait before retrying the operation that failed with `err`.
+    ///
+    /// It returns `None` if the error is not transient or there is no retry left, in which case
+    /// the error should be returned.
+    pub(crate) fn next_delay<C>(&mut self, err: &StorageError<C>) -> Option<Duration>
+    where C: RaftTypeConfig {
+        if !err.is_transient() || self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        let delay = self.delay;
+        self.delay *= 2;
+
+        tracing::warn!(
+            "transient storage error: {}; retry in {:?}, {} retries left",
+            err,
+            delay,
+            self.remaining
+        );
+
+        Some(delay)
+    }
+
+    /// Wait before retrying the operation that failed with `err`.
+    ///
+    /// It returns `false` without waiting if the operation should not be retried. It is used by a
+    /// task that has nothing else to do meanwhile, such as the state machine worker; `RaftCore`
+    /// must not wait.
+    pub(crate) async fn backoff<C>(&mut self, err: &StorageError<C>) -> bool
+    where C: RaftTypeConfig {
+        let Some(delay) = self.next_delay(err) else {
+            return false;
+        };
+
+        C::sleep(delay).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::StorageRetry;
+    use crate::engine::testing::UTConfig;
+    use crate::Config;
+    use crate::ErrorSubject;
+    use crate::ErrorVerb;
+    use crate::StorageError;
+
+    #[test]
+    fn test_next_delay() {
+        let config = Config {
+            heartbeat_interval: 10,
+            storage_retry_max: 2,
+            ..Default::default()
+        };
+
+        let transient = StorageError::<UTConfig>::new_transient(ErrorSubject::Vote, ErrorVerb::Write, &io_err());
+        let fatal = StorageError::<UTConfig>::new(ErrorSubject::Vote, ErrorVerb::Write, &io_err());
+
+        let mut retry = StorageRetry::new(&config);
+        assert_eq!(None, retry.next_delay(&fatal));
+        assert_eq!(Some(Duration::from_millis(10)), retry.next_delay(&transient));
+        assert_eq!(Some(Duration::from_millis(20)), retry.next_delay(&transient));
+        assert_eq!(None, retry.next_delay(&transient), "no retry left");
+    }
+
+    fn io_err() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Interrupted, "foo")
+    }
+}
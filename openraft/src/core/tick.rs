@@ -159,8 +159,7 @@ where C: RaftTypeConfig
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
-
-    use tokio::time::Duration;
+    use std::time::Duration;
 
     use crate::core::Tick;
     use crate::impls::TokioRuntime;
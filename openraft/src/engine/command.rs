@@ -13,6 +13,7 @@ use crate::error::InitializeError;
 use crate::error::InstallSnapshotError;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::HeartbeatResponse;
 use crate::raft::InstallSnapshotResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
@@ -117,6 +118,9 @@ where C: RaftTypeConfig
     /// Send vote to all other members
     SendVote { vote_req: VoteRequest<C> },
 
+    /// Send pre-vote to all other members, to check if a quorum would grant the vote.
+    SendPreVote { vote_req: VoteRequest<C> },
+
     /// Purge log from the beginning to `upto`, inclusive.
     PurgeLog { upto: LogId<C::NodeId> },
 
@@ -176,6 +180,7 @@ where C: RaftTypeConfig
             }
             Command::SaveVote { vote } => write!(f, "SaveVote: {}", vote),
             Command::SendVote { vote_req } => write!(f, "SendVote: {}", vote_req),
+            Command::SendPreVote { vote_req } => write!(f, "SendPreVote: {}", vote_req),
             Command::PurgeLog { upto } => write!(f, "PurgeLog: upto: {}", upto),
             Command::TruncateLog { since } => write!(f, "TruncateLog: since: {}", since),
             Command::StateMachine { command } => write!(f, "StateMachine: command: {}", command),
@@ -212,6 +217,7 @@ where
             (Command::RebuildReplicationStreams { targets },   Command::RebuildReplicationStreams { targets: b }, )                  => targets == b,
             (Command::SaveVote { vote },                       Command::SaveVote { vote: b })                                        => vote == b,
             (Command::SendVote { vote_req },                   Command::SendVote { vote_req: b }, )                                  => vote_req == b,
+            (Command::SendPreVote { vote_req },                Command::SendPreVote { vote_req: b }, )                               => vote_req == b,
             (Command::PurgeLog { upto },                       Command::PurgeLog { upto: b })                                        => upto == b,
             (Command::TruncateLog { since },                   Command::TruncateLog { since: b }, )                                        => since == b,
             (Command::Respond { when, resp: send },            Command::Respond { when: b_when, resp: b })                           => send == b && when == b_when,
@@ -246,6 +252,7 @@ where C: RaftTypeConfig
             Command::Replicate { .. }                 => CommandKind::Network,
             Command::BroadcastTransferLeader { .. }            => CommandKind::Network,
             Command::SendVote { .. }                  => CommandKind::Network,
+            Command::SendPreVote { .. }               => CommandKind::Network,
 
             Command::Apply { .. }                     => CommandKind::StateMachine,
             Command::StateMachine { .. }              => CommandKind::StateMachine,
@@ -272,6 +279,7 @@ where C: RaftTypeConfig
             Command::Replicate { .. }                 => None,
             Command::BroadcastTransferLeader { .. }            => None,
             Command::SendVote { .. }                  => None,
+            Command::SendPreVote { .. }               => None,
 
             Command::Apply { .. }                     => None,
             Command::StateMachine { .. }              => None,
@@ -331,6 +339,7 @@ where C: RaftTypeConfig
 {
     Vote(ValueSender<C, Result<VoteResponse<C>, Infallible>>),
     AppendEntries(ValueSender<C, Result<AppendEntriesResponse<C>, Infallible>>),
+    Heartbeat(ValueSender<C, Result<HeartbeatResponse<C>, Infallible>>),
     ReceiveSnapshotChunk(ValueSender<C, Result<(), InstallSnapshotError>>),
     InstallSnapshot(ValueSender<C, Result<InstallSnapshotResponse<C>, InstallSnapshotError>>),
     InstallFullSnapshot(ValueSender<C, Result<SnapshotResponse<C>, Infallible>>),
@@ -344,6 +353,7 @@ where C: RaftTypeConfig
         match self {
             Respond::Vote(vs) => write!(f, "Vote {}", vs.value().display()),
             Respond::AppendEntries(vs) => write!(f, "AppendEntries {}", vs.value().display()),
+            Respond::Heartbeat(vs) => write!(f, "Heartbeat {}", vs.value().display()),
             Respond::ReceiveSnapshotChunk(vs) => {
                 write!(
                     f,
@@ -373,6 +383,7 @@ where C: RaftTypeConfig
         match self {
             Respond::Vote(x) => x.send(),
             Respond::AppendEntries(x) => x.send(),
+            Respond::Heartbeat(x) => x.send(),
             Respond::ReceiveSnapshotChunk(x) => x.send(),
             Respond::InstallSnapshot(x) => x.send(),
             Respond::InstallFullSnapshot(x) => x.send(),
@@ -19,12 +19,49 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The maximum number of applied logs to keep before purging.
     pub(crate) max_in_snapshot_log_to_keep: u64,
 
+    /// Whether to keep applied logs according to `max_in_snapshot_log_to_keep` after installing a
+    /// snapshot.
+    pub(crate) keep_logs_after_snapshot_install: bool,
+
     /// The minimal number of applied logs to purge in a batch.
     pub(crate) purge_batch_size: u64,
 
+    /// The maximum number of logs to purge in one `PurgeLog` command. 0 means unlimited.
+    pub(crate) purge_max_batch_size: u64,
+
+    /// Whether a leader keeps the logs that are not yet replicated to every target.
+    pub(crate) keep_logs_for_lagging: bool,
+
+    /// The lag beyond which a target no longer holds back purging. 0 means unlimited.
+    pub(crate) keep_logs_for_lagging_max: u64,
+
     /// The maximum number of entries per payload allowed to be transmitted during replication
     pub(crate) max_payload_entries: u64,
 
+    /// The maximum number of AppendEntries RPCs in flight to a single follower/learner.
+    pub(crate) max_in_flight_appends: u64,
+
+    /// The apply lag at which a follower asks the leader to slow down replication. 0 disables it.
+    pub(crate) backpressure_apply_lag: u64,
+
+    /// The number of unapplied logs at which a leader rejects client writes. 0 disables it.
+    pub(crate) max_unapplied_logs: u64,
+
+    /// Whether to run a pre-vote before starting an election on election timeout.
+    pub(crate) enable_pre_vote: bool,
+
+    /// Whether a leader steps down if it has not heard from a quorum for the leader lease.
+    pub(crate) enable_check_quorum: bool,
+
+    /// Whether a newly established leader appends a blank log entry at once.
+    pub(crate) enable_blank_log: bool,
+
+    /// Whether a leader broadcasts a heartbeat at once when the commit index advances.
+    pub(crate) enable_commit_broadcast: bool,
+
+    /// Whether to save the committed log id to the log store every time it advances.
+    pub(crate) save_committed: bool,
+
     pub(crate) timer_config: time_state::Config,
 }
 
@@ -37,11 +74,24 @@ where C: RaftTypeConfig
             id,
             snapshot_policy: config.snapshot_policy.clone(),
             max_in_snapshot_log_to_keep: config.max_in_snapshot_log_to_keep,
+            keep_logs_after_snapshot_install: config.keep_logs_after_snapshot_install,
             purge_batch_size: config.purge_batch_size,
+            purge_max_batch_size: config.purge_max_batch_size,
+            keep_logs_for_lagging: config.keep_logs_for_lagging,
+            keep_logs_for_lagging_max: config.keep_logs_for_lagging_max,
             max_payload_entries: config.max_payload_entries,
+            max_in_flight_appends: config.max_in_flight_appends,
+            backpressure_apply_lag: config.backpressure_apply_lag,
+            max_unapplied_logs: config.max_unapplied_logs,
+            enable_pre_vote: config.enable_pre_vote,
+            enable_check_quorum: config.enable_check_quorum,
+            enable_blank_log: config.enable_blank_log,
+            enable_commit_broadcast: config.enable_commit_broadcast,
+            save_committed: config.save_committed,
             timer_config: time_state::Config {
                 election_timeout,
                 smaller_log_timeout: Duration::from_millis(config.election_timeout_max * 2),
+                priority_delay: config.election_priority_delay(),
                 leader_lease: Duration::from_millis(config.election_timeout_max),
             },
         }
@@ -53,8 +103,20 @@ where C: RaftTypeConfig
             id,
             snapshot_policy: SnapshotPolicy::LogsSinceLast(5000),
             max_in_snapshot_log_to_keep: 1000,
+            keep_logs_after_snapshot_install: false,
             purge_batch_size: 256,
+            purge_max_batch_size: 0,
+            keep_logs_for_lagging: false,
+            keep_logs_for_lagging_max: 0,
             max_payload_entries: 300,
+            max_in_flight_appends: 1,
+            backpressure_apply_lag: 0,
+            max_unapplied_logs: 0,
+            enable_pre_vote: false,
+            enable_check_quorum: false,
+            enable_blank_log: true,
+            enable_commit_broadcast: false,
+            save_committed: true,
             timer_config: time_state::Config::default(),
         }
     }
@@ -4,9 +4,11 @@ use std::time::Duration;
 use validit::Valid;
 
 use crate::core::raft_msg::AppendEntriesTx;
+use crate::core::raft_msg::HeartbeatTx;
 use crate::core::raft_msg::ResultSender;
 use crate::core::sm;
 use crate::core::ServerState;
+use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySliceExt;
 use crate::engine::engine_config::EngineConfig;
@@ -24,12 +26,16 @@ use crate::engine::EngineOutput;
 use crate::engine::Respond;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
+use crate::error::AlreadyInitialized;
+use crate::error::ForceSetMembershipError;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::LeaderAlive;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
 use crate::error::RejectAppendEntries;
+use crate::network::Capabilities;
 use crate::proposer::leader_state::CandidateState;
 use crate::proposer::Candidate;
 use crate::proposer::Leader;
@@ -37,6 +43,8 @@ use crate::proposer::LeaderQuorumSet;
 use crate::proposer::LeaderState;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
@@ -53,6 +61,7 @@ use crate::LogIdOptionExt;
 use crate::Membership;
 use crate::RaftLogId;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::Vote;
 
 /// Raft protocol algorithm.
@@ -181,9 +190,30 @@ where C: RaftTypeConfig
     /// follower. This step is not confined by the consensus protocol and has to be dealt with
     /// differently.
     ///
+    /// If the node is already initialized with the same membership, it does nothing and returns
+    /// `Ok`; if with a different one, it returns [`AlreadyInitialized`] with the current
+    /// membership.
+    ///
     /// [precondition]: crate::docs::cluster_control::cluster_formation#preconditions-for-initialization
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn initialize(&mut self, mut entry: C::Entry) -> Result<(), InitializeError<C>> {
+        if self.state.is_initialized() {
+            let m = entry.get_membership().expect("the only log entry for initializing has to be membership log");
+            let effective = self.state.membership_state.effective();
+
+            if effective.log_id().is_some() {
+                if effective.membership() == m {
+                    tracing::info!(membership = display(m), "already initialized with the same membership");
+                    return Ok(());
+                }
+
+                return Err(AlreadyInitialized {
+                    membership: effective.stored_membership().as_ref().clone(),
+                }
+                .into());
+            }
+        }
+
         self.check_initialize()?;
 
         // The very first log id
@@ -206,6 +236,96 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Initialize a node with a snapshot, e.g., restored from a backup, instead of a membership
+    /// log.
+    ///
+    /// The snapshot is installed in the same way as one received from a Leader:
+    /// conflicting logs are deleted, the membership is updated to the one in the snapshot and the
+    /// logs included in the snapshot are purged.
+    ///
+    /// It returns the condition to wait for before the snapshot is installed,
+    /// or `None` if the snapshot is empty and nothing is done.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn initialize_with_snapshot(
+        &mut self,
+        snapshot: Snapshot<C>,
+    ) -> Result<Option<Condition<C>>, InitializeError<C>> {
+        self.check_initialize()?;
+
+        if snapshot.meta.last_log_id.is_none() {
+            tracing::info!("empty snapshot, nothing to install: {}", snapshot.meta);
+            return Ok(None);
+        }
+
+        // The vote is left untouched: no leader is granted by installing a snapshot,
+        // a leader is elected in the normal way after this node starts up.
+        // The IO is submitted on behalf of the default vote, which is the least vote.
+        let vote = *self.state.vote_ref();
+
+        let mut fh = FollowingHandler {
+            leader_vote: vote.into_committed(),
+            config: &mut self.config,
+            state: &mut self.state,
+            output: &mut self.output,
+        };
+
+        Ok(fh.install_full_snapshot(snapshot))
+    }
+
+    /// Forcibly append a membership log on this node, without the agreement of a quorum, and
+    /// start to elect with it.
+    ///
+    /// The log is appended as if it were proposed by a leader of the next term,
+    /// so that it is greater than any log this node has seen.
+    ///
+    /// It is refused if this node is a leader or the lease of the leader it knows has not yet
+    /// expired: in such case the membership should be changed with a normal membership log.
+    #[tracing::instrument(level = "debug", skip(self, entry))]
+    pub(crate) fn force_set_membership(&mut self, mut entry: C::Entry) -> Result<(), ForceSetMembershipError<C>> {
+        let now = C::now();
+        let local_leased_vote = &self.state.vote;
+
+        if self.leader.is_some()
+            || (local_leased_vote.is_committed() && !local_leased_vote.is_expired(now, Duration::from_millis(0)))
+        {
+            tracing::error!(
+                vote = display(&**local_leased_vote),
+                lease = display(local_leased_vote.display_lease_info(now)),
+                "Can not force set membership: leader is alive"
+            );
+
+            return Err(LeaderAlive {
+                vote: **local_leased_vote,
+            }
+            .into());
+        }
+
+        let m = entry.get_membership().expect("the log entry for forcing membership has to be membership log");
+        self.check_members_contain_me(m)?;
+
+        // FollowingHandler requires vote to be committed.
+        // The vote has to be persisted before the log, as a normal leader does:
+        // the log must not be seen by a restarted node with a smaller vote.
+        // `SaveVote` is flushed before the following `AppendInputEntries` is submitted.
+        // It does not go through `VoteHandler`, which would make this node a leader.
+        let vote = Vote::new_committed(self.state.vote_ref().leader_id().term + 1, self.config.id);
+        self.state.vote.update(C::now(), Duration::default(), vote);
+        self.state.accept_io(IOId::new(vote));
+        self.output.push_command(Command::SaveVote { vote });
+
+        let log_id = LogId::new(vote.leader_id().to_committed(), self.state.last_log_id().next_index());
+        entry.set_log_id(&log_id);
+
+        tracing::warn!("force set membership: {}", entry);
+
+        self.following_handler().do_append_entries(vec![entry]);
+
+        // With the new config, start to elect to become leader
+        self.elect();
+
+        Ok(())
+    }
+
     /// Start to elect this node as leader
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn elect(&mut self) {
@@ -229,6 +349,45 @@ where C: RaftTypeConfig
         self.server_state_handler().update_server_state_if_changed();
     }
 
+    /// Start a pre-vote before electing, or elect at once if pre-vote is disabled.
+    ///
+    /// A pre-vote asks the voters whether they would grant a vote with a greater term, without
+    /// updating the vote on any node. This node starts to elect only when a quorum would grant it.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn pre_elect(&mut self) {
+        if !self.config.enable_pre_vote {
+            self.elect();
+            return;
+        }
+
+        let new_term = self.state.vote.leader_id().term + 1;
+        let pre_vote = Vote::new(new_term, self.config.id);
+
+        let candidate = self.new_candidate(pre_vote);
+        candidate.set_pre_vote();
+
+        tracing::info!("{}, new pre-vote candidate: {}", func_name!(), candidate);
+
+        let last_log_id = candidate.last_log_id().copied();
+
+        // The vote is not updated, reset the election timer so that a failed pre-vote is retried
+        // after another election timeout.
+        self.state.vote.touch(C::now(), Duration::default());
+
+        // A node always grants its own pre-vote.
+        let id = self.config.id;
+        let quorum_granted = self.candidate_mut().unwrap().grant_by(&id);
+        if quorum_granted {
+            tracing::info!("a quorum granted my pre-vote, start to elect");
+            self.elect();
+            return;
+        }
+
+        self.output.push_command(Command::SendPreVote {
+            vote_req: VoteRequest::new(pre_vote, last_log_id),
+        });
+    }
+
     pub(crate) fn leader_ref(&self) -> Option<&Leader<C, LeaderQuorumSet<C>>> {
         self.leader.as_deref()
     }
@@ -324,6 +483,48 @@ where C: RaftTypeConfig
         VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().copied(), res.is_ok())
     }
 
+    /// Check if a vote request would be granted, without updating the vote.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_pre_vote_req(&self, req: VoteRequest<C>) -> VoteResponse<C> {
+        let now = C::now();
+        let local_leased_vote = &self.state.vote;
+
+        tracing::info!(
+            req = display(&req),
+            my_vote = display(&**local_leased_vote),
+            my_last_log_id = display(self.state.last_log_id().display()),
+            lease = display(local_leased_vote.display_lease_info(now)),
+            "Engine::handle_pre_vote_req"
+        );
+
+        let granted =
+            if local_leased_vote.is_committed() && !local_leased_vote.is_expired(now, Duration::from_millis(0)) {
+                tracing::info!(
+                    "reject pre-vote-request: leader lease has not yet expire: {}",
+                    local_leased_vote.display_lease_info(now)
+                );
+                false
+            } else if req.last_log_id.as_ref() < self.state.last_log_id() {
+                tracing::info!(
+                    "reject pre-vote-request: by last_log_id: req.last_log_id({}) < my_last_log_id({})",
+                    req.last_log_id.display(),
+                    self.state.last_log_id().display(),
+                );
+                false
+            } else if &req.vote >= self.state.vote_ref() {
+                true
+            } else {
+                tracing::info!(
+                    "reject pre-vote-request: by vote: !(req.vote({}) >= my_vote({}))",
+                    req.vote,
+                    self.state.vote_ref(),
+                );
+                false
+            };
+
+        VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().copied(), granted)
+    }
+
     #[tracing::instrument(level = "debug", skip(self, resp))]
     pub(crate) fn handle_vote_resp(&mut self, target: C::NodeId, resp: VoteResponse<C>) {
         tracing::info!(
@@ -341,6 +542,11 @@ where C: RaftTypeConfig
             return;
         };
 
+        if candidate.is_pre_vote() {
+            self.handle_pre_vote_resp(target, resp);
+            return;
+        }
+
         // If resp.vote is different, it may be a delay response to previous voting.
         if resp.vote_granted && &resp.vote == candidate.vote_ref() {
             let quorum_granted = candidate.grant_by(&target);
@@ -373,15 +579,54 @@ where C: RaftTypeConfig
         let _ = self.vote_handler().update_vote(&resp.vote);
     }
 
+    /// Handle the response to a pre-vote request.
+    ///
+    /// A pre-vote does not update the vote on the remote node, thus `resp.vote` is the vote of the
+    /// remote node, not the pre-vote.
+    fn handle_pre_vote_resp(&mut self, target: C::NodeId, resp: VoteResponse<C>) {
+        let Some(candidate) = self.candidate_mut() else {
+            return;
+        };
+
+        if resp.vote_granted {
+            let quorum_granted = candidate.grant_by(&target);
+            if quorum_granted {
+                tracing::info!("a quorum granted my pre-vote, start to elect");
+                self.elect();
+            }
+            return;
+        }
+
+        // Seen a higher log. Record it so that the next election will be delayed for a while.
+        if resp.last_log_id.as_ref() > self.state.last_log_id() {
+            tracing::info!(
+                greater_log_id = display(resp.last_log_id.display()),
+                "seen a greater log id when {}",
+                func_name!()
+            );
+            self.set_greater_log();
+        }
+
+        // Only a greater vote is accepted: updating with the current vote would extend the lease
+        // of the current leader and abort the pre-vote.
+        if &resp.vote > self.state.vote_ref() {
+            let _ = self.vote_handler().update_vote(&resp.vote);
+        }
+    }
+
     /// Append entries to follower/learner.
     ///
     /// Also clean conflicting entries and update membership state.
+    ///
+    /// `leader_protocol_version` is the protocol version the leader sent the request with. A
+    /// [`AppendEntriesResponse::Backpressure`] is only replied to a leader that understands it.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_append_entries(
         &mut self,
         vote: &Vote<C::NodeId>,
         prev_log_id: Option<LogId<C::NodeId>>,
         entries: Vec<C::Entry>,
+        leader_protocol_version: u32,
         tx: Option<AppendEntriesTx<C>>,
     ) -> bool {
         tracing::debug!(
@@ -398,7 +643,13 @@ where C: RaftTypeConfig
         let is_ok = res.is_ok();
 
         if let Some(tx) = tx {
-            let resp: AppendEntriesResponse<C> = res.into();
+            let mut resp: AppendEntriesResponse<C> = res.into();
+
+            if is_ok {
+                if let Some(apply_lag) = self.backpressure_apply_lag(leader_protocol_version) {
+                    resp = AppendEntriesResponse::Backpressure { apply_lag };
+                }
+            }
 
             let condition = if is_ok {
                 Some(Condition::IOFlushed {
@@ -416,6 +667,30 @@ where C: RaftTypeConfig
         is_ok
     }
 
+    /// Returns the apply lag of this node if it reaches [`Config::backpressure_apply_lag`], i.e.,
+    /// the leader should slow down replication to this node.
+    ///
+    /// It always returns `None` if the leader, of `leader_protocol_version`, does not understand
+    /// [`AppendEntriesResponse::Backpressure`].
+    ///
+    /// [`Config::backpressure_apply_lag`]: crate::Config::backpressure_apply_lag
+    pub(crate) fn backpressure_apply_lag(&self, leader_protocol_version: u32) -> Option<u64> {
+        let threshold = self.config.backpressure_apply_lag;
+        if threshold == 0 || !Capabilities::supports_backpressure(leader_protocol_version) {
+            return None;
+        }
+
+        let last = self.state.last_log_id().next_index();
+        let applied = self.state.io_applied().next_index();
+        let apply_lag = last.saturating_sub(applied);
+
+        if apply_lag >= threshold {
+            Some(apply_lag)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn append_entries(
         &mut self,
         vote: &Vote<C::NodeId>,
@@ -433,6 +708,36 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Handle a heartbeat from the Leader: accept its vote and update the committed log id.
+    ///
+    /// Unlike [`Self::handle_append_entries()`], the logs are neither checked nor updated.
+    /// The response is sent after the accepted vote is persisted.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_heartbeat(&mut self, req: HeartbeatRequest<C>, tx: HeartbeatTx<C>) {
+        tracing::debug!(
+            req = display(&req),
+            my_vote = display(self.state.vote_ref()),
+            "{}",
+            func_name!()
+        );
+
+        let vote_res = self.vote_handler().accept_vote(&req.vote, tx, |state, _rejected| {
+            Ok(HeartbeatResponse::new(*state.vote_ref()))
+        });
+
+        let Some(tx) = vote_res else {
+            return;
+        };
+
+        self.following_handler().commit_entries(req.leader_commit);
+
+        let vote = *self.state.vote_ref();
+        self.output.push_command(Command::Respond {
+            when: Some(Condition::IOFlushed { io_id: IOId::new(vote) }),
+            resp: Respond::new(Ok(HeartbeatResponse::new(vote)), tx),
+        });
+    }
+
     /// Commit entries for follower/learner.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_commit_entries(&mut self, leader_committed: Option<LogId<C::NodeId>>) {
@@ -488,6 +793,28 @@ where C: RaftTypeConfig
         self.output.push_command(Command::from(sm::Command::begin_receiving_snapshot(tx)));
     }
 
+    /// Reopen a partially received snapshot on a follower.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_resume_receiving_snapshot(
+        &mut self,
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, Option<(u64, Box<SnapshotDataOf<C>>)>, Infallible>,
+    ) {
+        tracing::info!(snapshot_id = display(&snapshot_id), "{}", func_name!());
+        self.output.push_command(Command::from(sm::Command::resume_receiving_snapshot(snapshot_id, tx)));
+    }
+
+    /// Discard a partially received snapshot on a follower.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_discard_receiving_snapshot(
+        &mut self,
+        snapshot_id: SnapshotId,
+        tx: ResultSender<C, (), Infallible>,
+    ) {
+        tracing::info!(snapshot_id = display(&snapshot_id), "{}", func_name!());
+        self.output.push_command(Command::from(sm::Command::discard_receiving_snapshot(snapshot_id, tx)));
+    }
+
     /// Leader steps down(convert to learner) once the membership not containing it is committed.
     ///
     /// This is only called by leader.
@@ -512,6 +839,56 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Leader steps down if it has not heard from a quorum for the leader lease.
+    ///
+    /// A leader partitioned from a quorum can not commit any log, and the followers may have
+    /// elected a new leader after their leases expire. Such a leader reverts to a candidate: its
+    /// vote is no longer committed, and replication is stopped. It elects again after the
+    /// election timeout, or follows another leader when it sees a greater vote.
+    ///
+    /// It does nothing unless `enable_check_quorum` is set.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn leader_check_quorum(&mut self) {
+        if !self.config.enable_check_quorum {
+            return;
+        }
+
+        let Some(leader) = self.leader.as_mut() else {
+            return;
+        };
+
+        let now = C::now();
+        let lease = self.config.timer_config.leader_lease;
+
+        // A newly established leader has not yet heard from any follower. Count the lease from
+        // when the vote is granted.
+        let last_acked = std::cmp::max(leader.last_quorum_acked_time(), self.state.vote.last_update());
+
+        if let Some(t) = last_acked {
+            if now <= t + lease {
+                return;
+            }
+        }
+
+        tracing::warn!(
+            last_quorum_acked = debug(last_acked),
+            now = display(now.display()),
+            "leader has not heard from a quorum for {:?}, step down",
+            lease
+        );
+
+        let vote = Vote {
+            committed: false,
+            ..*self.state.vote_ref()
+        };
+        self.state.vote.update(now, Duration::default(), vote);
+
+        self.leader = None;
+        self.output.push_command(Command::RebuildReplicationStreams { targets: vec![] });
+
+        self.server_state_handler().update_server_state_if_changed();
+    }
+
     /// Update Engine state when a new snapshot is built.
     ///
     /// NOTE:
@@ -650,9 +1027,13 @@ where C: RaftTypeConfig
 
         self.state.accept_io(IOId::new_log_io(vote.into_committed(), last_log_id));
 
-        self.leader_handler()
-            .unwrap()
-            .leader_append_entries(vec![C::Entry::new_blank(LogId::<C::NodeId>::default())]);
+        if self.config.enable_blank_log {
+            self.leader_handler()
+                .unwrap()
+                .leader_append_entries(vec![C::Entry::new_blank(LogId::<C::NodeId>::default())]);
+        } else {
+            self.replication_handler().initiate_replication();
+        }
     }
 
     /// Check if a raft node is in a state that allows to initialize.
@@ -801,6 +1182,7 @@ where C: RaftTypeConfig
         ServerStateHandler {
             config: &self.config,
             state: &mut self.state,
+            output: &mut self.output,
         }
     }
     pub(crate) fn establish_handler(&mut self) -> EstablishHandler<C> {
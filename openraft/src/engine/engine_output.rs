@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 
 use crate::engine::Command;
+use crate::raft::RaftEvent;
 use crate::RaftTypeConfig;
 
 /// The entry of output from Engine to the runtime.
@@ -10,6 +11,14 @@ where C: RaftTypeConfig
 {
     /// Command queue that need to be executed by `RaftRuntime`.
     pub(crate) commands: VecDeque<Command<C>>,
+
+    /// State transitions to be delivered to the subscribers by `RaftRuntime`, in the order they
+    /// happen.
+    pub(crate) events: Vec<RaftEvent<C>>,
+
+    /// The last emitted role event, such as [`RaftEvent::BecameLeader`], to emit a role event
+    /// only when the role changes.
+    last_role: Option<RaftEvent<C>>,
 }
 
 impl<C> EngineOutput<C>
@@ -18,6 +27,8 @@ where C: RaftTypeConfig
     pub(crate) fn new(command_buffer_size: usize) -> Self {
         Self {
             commands: VecDeque::with_capacity(command_buffer_size),
+            events: Vec::new(),
+            last_role: None,
         }
     }
 
@@ -31,6 +42,26 @@ where C: RaftTypeConfig
         self.commands.push_back(cmd)
     }
 
+    /// Push an event of a state transition.
+    pub(crate) fn push_event(&mut self, event: RaftEvent<C>) {
+        tracing::debug!("push event: {}", event);
+        self.events.push(event)
+    }
+
+    /// Push an event of the role of this node, if it is different from the last one.
+    pub(crate) fn push_role_event(&mut self, event: RaftEvent<C>) {
+        if self.last_role.as_ref() == Some(&event) {
+            return;
+        }
+        self.last_role = Some(event.clone());
+        self.push_event(event);
+    }
+
+    /// Take all pushed events.
+    pub(crate) fn take_events(&mut self) -> Vec<RaftEvent<C>> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Put back the command to the head of the queue.
     ///
     /// This will be used when the command is not ready to be executed.
@@ -44,6 +75,13 @@ where C: RaftTypeConfig
         self.commands.pop_front()
     }
 
+    /// Remove the queued commands that satisfy `f` and return them, in the queued order.
+    pub(crate) fn extract_commands(&mut self, f: impl Fn(&Command<C>) -> bool) -> Vec<Command<C>> {
+        let (extracted, kept) = self.commands.drain(..).partition(|c| f(c));
+        self.commands = kept;
+        extracted
+    }
+
     /// Iterate all queued commands.
     pub(crate) fn iter_commands(&self) -> impl Iterator<Item = &Command<C>> {
         self.commands.iter()
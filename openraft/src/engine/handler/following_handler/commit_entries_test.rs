@@ -124,3 +124,24 @@ fn test_following_handler_commit_entries_le_accepted() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_following_handler_commit_entries_without_save_committed() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.save_committed = false;
+    let committed_vote = eng.state.vote_ref().into_committed();
+    eng.state.io_state.io_progress.accept(IOId::new_log_io(committed_vote, Some(log_id(1, 1, 2))));
+
+    eng.following_handler().commit_entries(Some(log_id(2, 1, 3)));
+
+    assert_eq!(Some(&log_id(1, 1, 2)), eng.state.committed());
+    assert_eq!(
+        vec![Command::Apply {
+            already_committed: Some(log_id(1, 1, 1)),
+            upto: log_id(1, 1, 2),
+        }],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
@@ -13,6 +13,7 @@ use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::entry::RaftPayload;
 use crate::error::RejectAppendEntries;
+use crate::raft::RaftEvent;
 use crate::raft_state::IOId;
 use crate::raft_state::LogStateReader;
 use crate::storage::Snapshot;
@@ -172,9 +173,11 @@ where C: RaftTypeConfig
         );
 
         if let Some(prev_committed) = self.state.update_committed(&committed) {
-            self.output.push_command(Command::SaveCommitted {
-                committed: committed.unwrap(),
-            });
+            if self.config.save_committed {
+                self.output.push_command(Command::SaveCommitted {
+                    committed: committed.unwrap(),
+                });
+            }
 
             self.output.push_command(Command::Apply {
                 already_committed: prev_committed,
@@ -209,7 +212,10 @@ where C: RaftTypeConfig
         self.output.push_command(Command::TruncateLog { since: since_log_id });
 
         let changed = self.state.membership_state.truncate(since);
-        if let Some(_c) = changed {
+        if let Some(c) = changed {
+            self.output.push_event(RaftEvent::MembershipChanged {
+                membership: c.stored_membership().clone(),
+            });
             self.server_state_handler().update_server_state_if_changed();
         }
     }
@@ -239,6 +245,9 @@ where C: RaftTypeConfig
             "updated membership state"
         );
 
+        self.output.push_event(RaftEvent::MembershipChanged {
+            membership: self.state.membership_state.effective().stored_membership().clone(),
+        });
         self.server_state_handler().update_server_state_if_changed();
     }
 
@@ -252,7 +261,12 @@ where C: RaftTypeConfig
         // TODO: if effective membership changes, call `update_replication()`, if a follower has replication
         //       streams. Now we don't have replication streams for follower, so it's ok to not call
         //       `update_replication()`.
-        let _effective_changed = self.state.membership_state.update_committed(m);
+        let effective_changed = self.state.membership_state.update_committed(m);
+        if let Some(c) = effective_changed {
+            self.output.push_event(RaftEvent::MembershipChanged {
+                membership: c.stored_membership().clone(),
+            });
+        }
 
         self.server_state_handler().update_server_state_if_changed();
     }
@@ -312,6 +326,10 @@ where C: RaftTypeConfig
             }
         }
 
+        // Local logs can be kept only if they are consistent with the snapshot,
+        // otherwise there would be a gap between the last local log and the snapshot.
+        let keep_logs = self.config.keep_logs_after_snapshot_install && local == Some(snap_last_log_id);
+
         let io_id = IOId::new_log_io(self.leader_vote, Some(snap_last_log_id));
         self.state.accept_io(io_id);
         self.state.committed = Some(snap_last_log_id);
@@ -321,7 +339,11 @@ where C: RaftTypeConfig
 
         self.output.push_command(Command::from(sm::Command::install_full_snapshot(snapshot, io_id)));
 
-        self.state.purge_upto = Some(snap_last_log_id);
+        if keep_logs {
+            self.log_handler().schedule_policy_based_purge();
+        } else {
+            self.state.purge_upto = Some(snap_last_log_id);
+        }
         self.log_handler().purge_log();
 
         Some(Condition::Snapshot {
@@ -369,6 +391,7 @@ where C: RaftTypeConfig
         ServerStateHandler {
             config: self.config,
             state: self.state,
+            output: self.output,
         }
     }
 }
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::error::Overloaded;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+
+fn m01() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(3, 1),
+    );
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 5));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())),
+    );
+    eng.testing_new_leader();
+    eng.state.server_state = eng.calc_server_state();
+
+    eng
+}
+
+#[test]
+fn test_check_overloaded() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.io_state_mut().update_applied(Some(log_id(1, 1, 2)));
+
+    // Disabled
+    eng.config.max_unapplied_logs = 0;
+    assert_eq!(Ok(()), eng.leader_handler()?.check_overloaded());
+
+    // 3 logs are not applied: 3, 4, 5
+    eng.config.max_unapplied_logs = 4;
+    assert_eq!(Ok(()), eng.leader_handler()?.check_overloaded());
+
+    eng.config.max_unapplied_logs = 3;
+    assert_eq!(
+        Err(Overloaded { unapplied: 3, limit: 3 }),
+        eng.leader_handler()?.check_overloaded()
+    );
+
+    Ok(())
+}
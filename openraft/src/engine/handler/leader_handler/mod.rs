@@ -3,6 +3,7 @@ use crate::engine::Command;
 use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::entry::RaftPayload;
+use crate::error::Overloaded;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
 use crate::raft::message::TransferLeaderRequest;
@@ -10,6 +11,7 @@ use crate::raft_state::IOId;
 use crate::raft_state::LogStateReader;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::LogIdOf;
+use crate::LogIdOptionExt;
 use crate::RaftLogId;
 use crate::RaftState;
 use crate::RaftTypeConfig;
@@ -17,6 +19,8 @@ use crate::RaftTypeConfig;
 #[cfg(test)]
 mod append_entries_test;
 #[cfg(test)]
+mod check_overloaded_test;
+#[cfg(test)]
 mod get_read_log_id_test;
 #[cfg(test)]
 mod send_heartbeat_test;
@@ -106,6 +110,27 @@ where C: RaftTypeConfig
         });
     }
 
+    /// Returns an [`Overloaded`] error if the logs not yet applied reach
+    /// [`Config::max_unapplied_logs`], i.e., the leader should not accept more client writes.
+    ///
+    /// [`Config::max_unapplied_logs`]: crate::Config::max_unapplied_logs
+    pub(crate) fn check_overloaded(&self) -> Result<(), Overloaded> {
+        let limit = self.config.max_unapplied_logs;
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let last = self.state.last_log_id().next_index();
+        let applied = self.state.io_applied().next_index();
+        let unapplied = last.saturating_sub(applied);
+
+        if unapplied >= limit {
+            Err(Overloaded { unapplied, limit })
+        } else {
+            Ok(())
+        }
+    }
+
     /// Get the log id for a linearizable read.
     ///
     /// See: [Read Operation](crate::docs::protocol::read)
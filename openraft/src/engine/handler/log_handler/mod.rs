@@ -26,6 +26,9 @@ impl<'x, C> LogHandler<'x, C>
 where C: RaftTypeConfig
 {
     /// Purge log entries upto `RaftState.purge_upto()`, inclusive.
+    ///
+    /// At most `purge_max_batch_size` logs are purged if it is not 0; the rest are purged by the
+    /// next call.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn purge_log(&mut self) {
         let st = &mut self.state;
@@ -41,7 +44,17 @@ where C: RaftTypeConfig
             return;
         }
 
-        let upto = *purge_upto.unwrap();
+        let mut upto = *purge_upto.unwrap();
+
+        let max_batch = self.config.purge_max_batch_size;
+        if max_batch > 0 {
+            let end = st.last_purged_log_id().next_index() + max_batch;
+            if upto.index >= end {
+                if let Some(log_id) = st.log_ids.get(end - 1) {
+                    upto = log_id;
+                }
+            }
+        }
 
         st.purge_log(&upto);
         self.output.push_command(Command::PurgeLog { upto });
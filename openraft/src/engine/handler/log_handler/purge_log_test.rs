@@ -166,3 +166,32 @@ fn test_purge_log_to_higher_leader_lgo() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_purge_log_max_batch_size() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.purge_max_batch_size = 2;
+
+    let mut lh = eng.log_handler();
+    lh.state.purge_upto = Some(log_id(4, 1, 6));
+
+    // Purge at most 2 logs at a time.
+    lh.purge_log();
+    assert_eq!(Some(&log_id(4, 1, 4)), lh.state.last_purged_log_id());
+    assert_eq!(
+        vec![Command::PurgeLog { upto: log_id(4, 1, 4) }],
+        lh.output.take_commands()
+    );
+
+    lh.purge_log();
+    assert_eq!(Some(&log_id(4, 1, 6)), lh.state.last_purged_log_id());
+    assert_eq!(
+        vec![Command::PurgeLog { upto: log_id(4, 1, 6) }],
+        lh.output.take_commands()
+    );
+
+    lh.purge_log();
+    assert_eq!(0, lh.output.take_commands().len());
+
+    Ok(())
+}
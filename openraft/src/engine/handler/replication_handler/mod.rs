@@ -12,9 +12,11 @@ use crate::progress::Inflight;
 use crate::progress::Progress;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
+use crate::raft::RaftEvent;
 use crate::raft_state::LogStateReader;
 use crate::replication::request::Replicate;
 use crate::replication::response::ReplicationResult;
+use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::InstantOf;
 use crate::EffectiveMembership;
 use crate::LogId;
@@ -27,6 +29,8 @@ use crate::ServerState;
 #[cfg(test)]
 mod append_membership_test;
 #[cfg(test)]
+mod try_purge_log_test;
+#[cfg(test)]
 mod update_matching_test;
 
 /// Handle replication operations.
@@ -66,6 +70,9 @@ where C: RaftTypeConfig
         );
 
         self.state.membership_state.append(EffectiveMembership::new_arc(Some(*log_id), m.clone()));
+        self.output.push_event(RaftEvent::MembershipChanged {
+            membership: self.state.membership_state.effective().stored_membership().clone(),
+        });
 
         // TODO(9): currently only a leader has replication setup.
         //       It's better to setup replication for both leader and candidate.
@@ -189,9 +196,19 @@ where C: RaftTypeConfig
                 committed: self.state.committed().copied(),
             });
 
-            self.output.push_command(Command::SaveCommitted {
-                committed: self.state.committed().copied().unwrap(),
-            });
+            if self.config.enable_commit_broadcast {
+                let membership_log_id = self.state.membership_state.effective().log_id();
+                self.output.push_command(Command::BroadcastHeartbeat {
+                    session_id: ReplicationSessionId::new(self.leader.committed_vote, *membership_log_id),
+                    committed: self.state.committed().copied(),
+                });
+            }
+
+            if self.config.save_committed {
+                self.output.push_command(Command::SaveCommitted {
+                    committed: self.state.committed().copied().unwrap(),
+                });
+            }
 
             self.output.push_command(Command::Apply {
                 already_committed: prev_committed,
@@ -273,6 +290,9 @@ where C: RaftTypeConfig
     pub(crate) fn initiate_replication(&mut self) {
         tracing::debug!(progress = debug(&self.leader.progress), "{}", func_name!());
 
+        // With pipelining, the replication stream splits the logs into several AppendEntries RPCs.
+        let max_entries = self.config.max_payload_entries * self.config.max_in_flight_appends;
+
         for (id, prog_entry) in self.leader.progress.iter_mut() {
             // TODO: update matching should be done here for leader
             //       or updating matching should be queued in commands?
@@ -280,7 +300,7 @@ where C: RaftTypeConfig
                 continue;
             }
 
-            let t = prog_entry.next_send(self.state, self.config.max_payload_entries);
+            let t = prog_entry.next_send(self.state, max_entries);
             tracing::debug!(target = display(*id), send = debug(&t), "next send");
 
             match t {
@@ -311,7 +331,6 @@ where C: RaftTypeConfig
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn try_purge_log(&mut self) {
         // TODO refactor this
-        // TODO: test
 
         tracing::debug!(
             last_purged_log_id = display(self.state.last_purged_log_id().display()),
@@ -336,6 +355,35 @@ where C: RaftTypeConfig
             }
         }
 
+        // Check if any target has not yet replicated the logs that are going to purge.
+        if self.config.keep_logs_for_lagging {
+            let last_next = self.state.last_log_id().next_index();
+            let max_lag = self.config.keep_logs_for_lagging_max;
+
+            for (id, prog_entry) in self.leader.progress.iter() {
+                let matching_next = prog_entry.matching.next_index();
+                if matching_next > purge_upto.index {
+                    continue;
+                }
+
+                // A target too far behind does not hold back purging, it will catch up with a
+                // snapshot.
+                let lag = last_next.saturating_sub(matching_next);
+                if max_lag > 0 && lag > max_lag {
+                    tracing::debug!(
+                        "target {} lags {} logs behind, more than {}, do not wait for it",
+                        id,
+                        lag,
+                        max_lag
+                    );
+                    continue;
+                }
+
+                tracing::debug!("log {} is not yet replicated to {}", purge_upto, id);
+                in_use = true;
+            }
+        }
+
         if in_use {
             // Logs to purge is in use, postpone purging.
             tracing::debug!("can not purge: {} is in use", purge_upto);
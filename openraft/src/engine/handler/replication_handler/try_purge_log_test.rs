@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::Progress;
+use crate::raft_state::LogStateReader;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+
+fn m123() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {1,2,3}], None)
+}
+
+/// A leader with logs `[2, 10]`, node 2 matches log 3 and node 3 matches log 9.
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.config.keep_logs_for_lagging = true;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 1), log_id(2, 1, 10)]);
+    eng.state.purged_next = 2;
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m123())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m123())),
+    );
+
+    let leader = eng.testing_new_leader();
+    leader.progress.get_mut(&1).unwrap().matching = Some(log_id(2, 1, 10));
+    leader.progress.get_mut(&2).unwrap().matching = Some(log_id(2, 1, 3));
+    leader.progress.get_mut(&3).unwrap().matching = Some(log_id(2, 1, 9));
+
+    eng.state.purge_upto = Some(log_id(2, 1, 5));
+    eng.output.take_commands();
+    eng
+}
+
+#[test]
+fn test_try_purge_log_keep_logs_for_lagging() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().try_purge_log();
+
+    assert_eq!(Some(&log_id(1, 1, 1)), eng.state.last_purged_log_id());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_try_purge_log_keep_logs_for_lagging_max() -> anyhow::Result<()> {
+    tracing::info!("--- node 2 lags 7 logs behind, within the max, keep logs for it");
+    {
+        let mut eng = eng();
+        eng.config.keep_logs_for_lagging_max = 7;
+
+        eng.replication_handler().try_purge_log();
+
+        assert_eq!(Some(&log_id(1, 1, 1)), eng.state.last_purged_log_id());
+        assert_eq!(0, eng.output.take_commands().len());
+    }
+
+    tracing::info!("--- node 2 lags 7 logs behind, beyond the max, purge");
+    {
+        let mut eng = eng();
+        eng.config.keep_logs_for_lagging_max = 6;
+
+        eng.replication_handler().try_purge_log();
+
+        assert_eq!(Some(&log_id(2, 1, 5)), eng.state.last_purged_log_id());
+        assert_eq!(
+            vec![Command::PurgeLog { upto: log_id(2, 1, 5) }],
+            eng.output.take_commands()
+        );
+    }
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use crate::engine::Engine;
 use crate::progress::Inflight;
 use crate::progress::Progress;
 use crate::raft_state::LogStateReader;
+use crate::replication::ReplicationSessionId;
 use crate::testing::log_id;
 use crate::type_config::TypeConfigExt;
 use crate::utime::Leased;
@@ -139,3 +140,52 @@ fn test_update_matching() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_update_matching_enable_commit_broadcast() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.enable_commit_broadcast = true;
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    let mut rh = eng.replication_handler();
+    for id in [1, 2, 3] {
+        let prog_entry = rh.leader.progress.get_mut(&id).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(1, 1, 1)), Some(log_id(2, 1, 4)));
+    }
+
+    // progress: None, (2,1), None; not committed, nothing to broadcast
+    {
+        rh.update_matching(2, Some(log_id(2, 1, 1)));
+        assert_eq!(None, rh.state.committed());
+        assert_eq!(0, rh.output.take_commands().len());
+    }
+
+    // progress: None, (2,1), (2,3); committed: (2,1), broadcast at once
+    {
+        rh.output.clear_commands();
+        rh.update_matching(3, Some(log_id(2, 1, 3)));
+        assert_eq!(Some(&log_id(2, 1, 1)), rh.state.committed());
+        assert_eq!(
+            vec![
+                Command::ReplicateCommitted {
+                    committed: Some(log_id(2, 1, 1))
+                },
+                Command::BroadcastHeartbeat {
+                    session_id: ReplicationSessionId::new(Vote::new(2, 1).into_committed(), Some(log_id(2, 1, 3))),
+                    committed: Some(log_id(2, 1, 1))
+                },
+                Command::SaveCommitted {
+                    committed: log_id(2, 1, 1)
+                },
+                Command::Apply {
+                    already_committed: None,
+                    upto: log_id(2, 1, 1)
+                }
+            ],
+            rh.output.take_commands()
+        );
+    }
+
+    Ok(())
+}
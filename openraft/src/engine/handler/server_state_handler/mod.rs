@@ -1,4 +1,6 @@
 use crate::engine::EngineConfig;
+use crate::engine::EngineOutput;
+use crate::raft::RaftEvent;
 use crate::RaftState;
 use crate::RaftTypeConfig;
 use crate::ServerState;
@@ -12,6 +14,7 @@ where C: RaftTypeConfig
 {
     pub(crate) config: &'st EngineConfig<C>,
     pub(crate) state: &'st mut RaftState<C>,
+    pub(crate) output: &'st mut EngineOutput<C>,
 }
 
 impl<'st, C> ServerStateHandler<'st, C>
@@ -19,6 +22,8 @@ where C: RaftTypeConfig
 {
     /// Re-calculate the server-state, if it changed, update the `server_state` field and dispatch
     /// commands to inform a runtime.
+    ///
+    /// An event is emitted if the server-state or the known leader changed.
     pub(crate) fn update_server_state_if_changed(&mut self) {
         let server_state = self.state.calc_server_state(&self.config.id);
 
@@ -29,21 +34,55 @@ where C: RaftTypeConfig
             "update_server_state_if_changed"
         );
 
-        if self.state.server_state == server_state {
-            return;
+        if self.state.server_state != server_state {
+            let was_leader = self.state.server_state == ServerState::Leader;
+            let is_leader = server_state == ServerState::Leader;
+
+            if !was_leader && is_leader {
+                tracing::info!(id = display(self.config.id), "become leader");
+            } else if was_leader && !is_leader {
+                tracing::info!(id = display(self.config.id), "quit leader");
+            } else {
+                // nothing to do
+            }
+
+            self.state.server_state = server_state;
         }
 
-        let was_leader = self.state.server_state == ServerState::Leader;
-        let is_leader = server_state == ServerState::Leader;
+        self.push_role_event();
+    }
 
-        if !was_leader && is_leader {
-            tracing::info!(id = display(self.config.id), "become leader");
-        } else if was_leader && !is_leader {
-            tracing::info!(id = display(self.config.id), "quit leader");
-        } else {
-            // nothing to do
+    /// Emit the event about the current server-state and the leader it knows.
+    fn push_role_event(&mut self) {
+        let vote = *self.state.vote_ref();
+
+        let event = match self.state.server_state {
+            ServerState::Leader => RaftEvent::BecameLeader { vote },
+            ServerState::Candidate => RaftEvent::BecameCandidate { vote },
+            ServerState::Follower => RaftEvent::BecameFollower {
+                leader_id: self.leader_id(),
+            },
+            ServerState::Learner => RaftEvent::BecameLearner {
+                leader_id: self.leader_id(),
+            },
+            ServerState::Shutdown => return,
+        };
+
+        self.output.push_role_event(event);
+    }
+
+    /// The known leader: the node granted a committed vote, if it is a voter.
+    fn leader_id(&self) -> Option<C::NodeId> {
+        let vote = self.state.vote_ref();
+        if !vote.is_committed() {
+            return None;
         }
 
-        self.state.server_state = server_state;
+        let id = vote.leader_id().voted_for()?;
+        if self.state.membership_state.effective().is_voter(&id) {
+            Some(id)
+        } else {
+            None
+        }
     }
 }
@@ -6,6 +6,7 @@ use pretty_assertions::assert_eq;
 
 use crate::engine::testing::UTConfig;
 use crate::engine::Engine;
+use crate::raft::RaftEvent;
 use crate::testing::log_id;
 use crate::type_config::TypeConfigExt;
 use crate::utime::Leased;
@@ -55,6 +56,33 @@ fn test_update_server_state_if_changed() -> anyhow::Result<()> {
         ssh.update_server_state_if_changed();
 
         assert_eq!(ServerState::Follower, ssh.state.server_state);
+        assert_eq!(
+            vec![RaftEvent::BecameFollower { leader_id: None }],
+            ssh.output.take_events()
+        );
+    }
+
+    // A follower learns about a new leader
+    {
+        ssh.state.vote = Leased::new(
+            UTConfig::<()>::now(),
+            Duration::from_millis(500),
+            Vote::new_committed(2, 3),
+        );
+        ssh.update_server_state_if_changed();
+
+        assert_eq!(ServerState::Follower, ssh.state.server_state);
+        assert_eq!(
+            vec![RaftEvent::BecameFollower { leader_id: Some(3) }],
+            ssh.output.take_events()
+        );
+
+        ssh.update_server_state_if_changed();
+        assert_eq!(
+            Vec::<RaftEvent<UTConfig>>::new(),
+            ssh.output.take_events(),
+            "no event if nothing changed"
+        );
     }
 
     // TODO(3): add more test,
@@ -203,7 +203,7 @@ where C: RaftTypeConfig
 
         // If the leader has not yet proposed any log, propose a blank log and initiate replication;
         // Otherwise, just initiate replication.
-        if last_log_id < noop_log_id {
+        if last_log_id < noop_log_id && self.config.enable_blank_log {
             self.leader_handler()
                 .leader_append_entries(vec![C::Entry::new_blank(LogId::<C::NodeId>::default())]);
         } else {
@@ -231,6 +231,7 @@ where C: RaftTypeConfig
         ServerStateHandler {
             config: self.config,
             state: self.state,
+            output: self.output,
         }
     }
 
@@ -42,11 +42,15 @@ pub(crate) mod time_state;
 mod tests {
     mod append_entries_test;
     mod elect_test;
+    mod force_set_membership_test;
+    mod handle_heartbeat_test;
     mod handle_vote_req_test;
     mod handle_vote_resp_test;
     mod initialize_test;
     mod install_full_snapshot_test;
+    mod leader_check_quorum_test;
     mod log_id_list_test;
+    mod pre_vote_test;
     mod startup_test;
     mod trigger_purge_log_test;
 }
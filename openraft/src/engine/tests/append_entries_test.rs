@@ -11,6 +11,7 @@ use crate::engine::Condition;
 use crate::engine::Engine;
 use crate::entry::RaftEntry;
 use crate::error::RejectAppendEntries;
+use crate::network::Capabilities;
 use crate::raft_state::IOId;
 use crate::raft_state::LogStateReader;
 use crate::testing::blank_ent;
@@ -313,3 +314,35 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_append_entries_backpressure_apply_lag() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let v = Capabilities::PROTOCOL_VERSION;
+
+    // Disabled
+    eng.config.backpressure_apply_lag = 0;
+    assert_eq!(None, eng.backpressure_apply_lag(v));
+
+    // Last log index is 3, nothing applied: 4 logs are not applied.
+    eng.config.backpressure_apply_lag = 4;
+    assert_eq!(Some(4), eng.backpressure_apply_lag(v));
+
+    eng.config.backpressure_apply_lag = 5;
+    assert_eq!(None, eng.backpressure_apply_lag(v));
+
+    eng.state.io_state_mut().update_applied(Some(log_id(1, 1, 1)));
+    eng.config.backpressure_apply_lag = 2;
+    assert_eq!(Some(2), eng.backpressure_apply_lag(v));
+
+    eng.config.backpressure_apply_lag = 3;
+    assert_eq!(None, eng.backpressure_apply_lag(v));
+
+    // A leader that does not understand Backpressure is never asked to slow down.
+    eng.config.backpressure_apply_lag = 1;
+    assert_eq!(Some(2), eng.backpressure_apply_lag(v));
+    assert_eq!(None, eng.backpressure_apply_lag(1));
+    assert_eq!(None, eng.backpressure_apply_lag(0));
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
+use crate::network::Capabilities;
 use crate::raft::VoteRequest;
 use crate::testing::log_id;
 use crate::type_config::TypeConfigExt;
@@ -61,6 +62,7 @@ fn test_elect_single_node() -> anyhow::Result<()> {
                     vote_req: VoteRequest {
                         vote: Vote::new(1, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
+                        protocol_version: Capabilities::PROTOCOL_VERSION,
                     },
                 },
             ],
@@ -107,6 +109,7 @@ fn test_elect_single_node_elect_again() -> anyhow::Result<()> {
                     vote_req: VoteRequest {
                         vote: Vote::new(2, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
+                        protocol_version: Capabilities::PROTOCOL_VERSION,
                     },
                 },
             ],
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::raft::VoteRequest;
+use crate::raft_state::LogStateReader;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::EffectiveMembership;
+use crate::Entry;
+use crate::LogId;
+use crate::Membership;
+use crate::Vote;
+
+fn m12() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1,2}], None)
+}
+
+fn m1() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 2, 2)]);
+    eng.state.vote.update(UTConfig::<()>::now(), Duration::default(), Vote::new(1, 2));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 2, 1)), m12())));
+    eng.state.server_state = ServerState::Follower;
+
+    eng
+}
+
+#[test]
+fn test_force_set_membership_save_vote_before_append() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.force_set_membership(Entry::<UTConfig>::new_membership(LogId::default(), m1()))?;
+
+    assert_eq!(&m1(), eng.state.membership_state.effective().membership());
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+
+    assert_eq!(
+        vec![
+            // The vote that proposes the membership log is persisted before the log.
+            Command::SaveVote {
+                vote: Vote::new_committed(2, 1)
+            },
+            Command::AppendInputEntries {
+                committed_vote: Vote::new_committed(2, 1).into_committed(),
+                entries: vec![Entry::<UTConfig>::new_membership(log_id(2, 1, 3), m1())],
+            },
+            Command::SaveVote { vote: Vote::new(3, 1) },
+            Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(3, 1), Some(log_id(2, 1, 3)))
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
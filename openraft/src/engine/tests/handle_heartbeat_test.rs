@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Condition;
+use crate::engine::Engine;
+use crate::engine::Respond;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::raft_state::IOId;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::Vote;
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.committed = Some(log_id(1, 1, 1));
+
+    let committed_vote = eng.state.vote_ref().into_committed();
+    eng.state.io_state.io_progress.accept(IOId::new_log_io(committed_vote, Some(log_id(2, 1, 3))));
+
+    eng.state.server_state = eng.calc_server_state();
+    eng
+}
+
+#[test]
+fn test_handle_heartbeat_vote_is_rejected() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+    eng.handle_heartbeat(
+        HeartbeatRequest::new(Vote::new_committed(1, 1), Some(log_id(2, 1, 3))),
+        tx,
+    );
+
+    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(Some(&log_id(1, 1, 1)), eng.state.committed());
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![
+            //
+            Command::Respond {
+                when: Some(Condition::IOFlushed {
+                    io_id: IOId::new(Vote::new_committed(2, 1))
+                }),
+                resp: Respond::new(Ok(HeartbeatResponse::new(Vote::new_committed(2, 1))), dummy_tx),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_heartbeat_commit_upto_accepted() -> anyhow::Result<()> {
+    // The committed log id sent by the Leader is clamped to the accepted log id.
+
+    let mut eng = eng();
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+    eng.handle_heartbeat(
+        HeartbeatRequest::new(Vote::new_committed(2, 1), Some(log_id(2, 1, 5))),
+        tx,
+    );
+
+    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.committed());
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![
+            Command::SaveCommitted {
+                committed: log_id(2, 1, 3)
+            },
+            Command::Apply {
+                already_committed: Some(log_id(1, 1, 1)),
+                upto: log_id(2, 1, 3)
+            },
+            Command::Respond {
+                when: Some(Condition::IOFlushed {
+                    io_id: IOId::new(Vote::new_committed(2, 1))
+                }),
+                resp: Respond::new(Ok(HeartbeatResponse::new(Vote::new_committed(2, 1))), dummy_tx),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
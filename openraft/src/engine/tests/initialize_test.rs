@@ -1,3 +1,4 @@
+use std::io::Cursor;
 use std::time::Duration;
 
 use maplit::btreeset;
@@ -6,14 +7,19 @@ use pretty_assertions::assert_eq;
 use crate::core::ServerState;
 use crate::engine::testing::UTConfig;
 use crate::engine::Command;
+use crate::engine::Condition;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
+use crate::error::AlreadyInitialized;
 use crate::error::InitializeError;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
+use crate::network::Capabilities;
 use crate::raft::VoteRequest;
 use crate::raft_state::LogStateReader;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
 use crate::testing::log_id;
 use crate::type_config::TypeConfigExt;
 use crate::utime::Leased;
@@ -21,6 +27,7 @@ use crate::vote::CommittedLeaderId;
 use crate::Entry;
 use crate::LogId;
 use crate::Membership;
+use crate::StoredMembership;
 use crate::Vote;
 
 #[test]
@@ -126,6 +133,7 @@ fn test_initialize() -> anyhow::Result<()> {
                             leader_id: CommittedLeaderId::new(0, 0),
                             index: 0,
                         },),
+                        protocol_version: Capabilities::PROTOCOL_VERSION,
                     },
                 },
             ],
@@ -161,6 +169,30 @@ fn test_initialize() -> anyhow::Result<()> {
         );
     }
 
+    tracing::info!("--- already initialized with the same membership, do nothing");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+
+        eng.initialize(entry())?;
+        eng.output.take_commands();
+
+        eng.initialize(entry())?;
+        assert_eq!(Some(&log_id0), eng.state.last_log_id());
+        assert_eq!(0, eng.output.take_commands().len());
+
+        tracing::info!("--- already initialized with a different membership");
+
+        let m1 = Membership::<UTConfig>::new(vec![btreeset! {1}], None);
+        assert_eq!(
+            Err(InitializeError::AlreadyInitialized(AlreadyInitialized {
+                membership: StoredMembership::new(Some(log_id0), m12()),
+            })),
+            eng.initialize(Entry::new_membership(LogId::default(), m1))
+        );
+        assert_eq!(0, eng.output.take_commands().len());
+    }
+
     tracing::info!("--- node id 0 is not in membership");
     {
         let mut eng = eng();
@@ -176,3 +208,76 @@ fn test_initialize() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_initialize_with_snapshot() -> anyhow::Result<()> {
+    let eng = || {
+        let mut eng = Engine::<UTConfig>::testing_default(0);
+        eng.state.enable_validation(false); // Disable validation for incomplete state
+
+        eng.state.server_state = eng.calc_server_state();
+        eng
+    };
+
+    let m12 = || Membership::<UTConfig>::new(vec![btreeset! {1,2}], None);
+    let snapshot = || Snapshot::<UTConfig> {
+        meta: SnapshotMeta {
+            last_log_id: Some(log_id(2, 1, 5)),
+            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            snapshot_id: "2-1-5".to_string(),
+        },
+        snapshot: Box::new(Cursor::new(vec![0u8])),
+    };
+
+    tracing::info!("--- ok: init empty node 1 with a snapshot");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+
+        let cond = eng.initialize_with_snapshot(snapshot())?;
+
+        assert_eq!(
+            Some(Condition::Snapshot {
+                log_id: Some(log_id(2, 1, 5))
+            }),
+            cond
+        );
+        assert_eq!(Some(&log_id(2, 1, 5)), eng.state.committed());
+        assert_eq!(Some(&log_id(2, 1, 5)), eng.state.last_log_id());
+        assert_eq!(Some(&log_id(2, 1, 5)), eng.state.snapshot_last_log_id());
+        assert_eq!(&m12(), eng.state.membership_state.effective().membership());
+        assert_eq!(ServerState::Follower, eng.state.server_state);
+        assert_eq!(
+            &Vote::default(),
+            eng.state.vote_ref(),
+            "vote is not changed by installing a snapshot"
+        );
+        assert!(
+            !eng.output.take_commands().iter().any(|c| matches!(c, Command::SaveVote { .. })),
+            "no vote is saved"
+        );
+
+        tracing::info!("--- not allowed because it is initialized");
+
+        assert_eq!(
+            Err(InitializeError::NotAllowed(NotAllowed {
+                last_log_id: Some(log_id(2, 1, 5)),
+                vote: Vote::default(),
+            })),
+            eng.initialize_with_snapshot(snapshot())
+        );
+    }
+
+    tracing::info!("--- ok: an empty snapshot does nothing");
+    {
+        let mut eng = eng();
+        let mut snap = snapshot();
+        snap.meta.last_log_id = None;
+
+        assert_eq!(None, eng.initialize_with_snapshot(snap)?);
+        assert!(!eng.state.is_initialized());
+        assert!(eng.output.take_commands().is_empty());
+    }
+
+    Ok(())
+}
@@ -167,3 +167,105 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handle_install_full_snapshot_while_building_snapshot() -> anyhow::Result<()> {
+    // Installing a snapshot does not cancel the local snapshot building.
+    // The built snapshot is ignored if it is smaller than the installed one.
+
+    let mut eng = eng();
+    eng.state.io_state.set_building_snapshot(true);
+
+    let curr_vote = *eng.state.vote_ref();
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+
+    eng.handle_install_full_snapshot(
+        curr_vote,
+        Snapshot {
+            meta: SnapshotMeta {
+                last_log_id: Some(log_id(4, 1, 6)),
+                last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                snapshot_id: "1-2-3-4".to_string(),
+            },
+            snapshot: Box::new(Cursor::new(vec![0u8])),
+        },
+        tx,
+    );
+
+    assert!(eng.state.io_state.building_snapshot());
+
+    eng.finish_building_snapshot(SnapshotMeta {
+        last_log_id: Some(log_id(3, 1, 5)),
+        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+        snapshot_id: "3-1-5".to_string(),
+    });
+
+    assert!(!eng.state.io_state.building_snapshot());
+    assert_eq!(
+        SnapshotMeta {
+            last_log_id: Some(log_id(4, 1, 6)),
+            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            snapshot_id: "1-2-3-4".to_string(),
+        },
+        eng.state.snapshot_meta
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_install_full_snapshot_keep_logs() -> anyhow::Result<()> {
+    // With `keep_logs_after_snapshot_install` enabled, logs are purged according to
+    // `max_in_snapshot_log_to_keep`, because local logs do not conflict with the snapshot.
+
+    let mut eng = eng();
+    eng.config.keep_logs_after_snapshot_install = true;
+    eng.config.max_in_snapshot_log_to_keep = 2;
+    eng.config.purge_batch_size = 1;
+
+    let curr_vote = *eng.state.vote_ref();
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+
+    eng.handle_install_full_snapshot(
+        curr_vote,
+        Snapshot {
+            meta: SnapshotMeta {
+                last_log_id: Some(log_id(4, 1, 6)),
+                last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                snapshot_id: "1-2-3-4".to_string(),
+            },
+            snapshot: Box::new(Cursor::new(vec![0u8])),
+        },
+        tx,
+    );
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![
+            //
+            Command::from(sm::Command::install_full_snapshot(
+                Snapshot {
+                    meta: SnapshotMeta {
+                        last_log_id: Some(log_id(4, 1, 6)),
+                        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        snapshot_id: "1-2-3-4".to_string(),
+                    },
+                    snapshot: Box::new(Cursor::new(vec![0u8])),
+                },
+                IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(4, 1, 6)))
+            )),
+            Command::PurgeLog { upto: log_id(2, 1, 4) },
+            Command::Respond {
+                when: Some(Condition::Snapshot {
+                    log_id: Some(log_id(4, 1, 6))
+                }),
+                resp: Respond::new(Ok(SnapshotResponse::new(curr_vote)), dummy_tx),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::Vote;
+
+fn m123() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1,2,3}], None)
+}
+
+/// Build a leader whose vote is granted `elapsed` ago.
+fn eng(elapsed: Duration) -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.config.enable_check_quorum = true;
+    eng.config.timer_config.leader_lease = Duration::from_millis(500);
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now() - elapsed,
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.log_ids.append(log_id(2, 1, 1));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m123())));
+    eng.testing_new_leader();
+    eng.state.server_state = eng.calc_server_state();
+
+    eng
+}
+
+#[test]
+fn test_leader_check_quorum_disabled() -> anyhow::Result<()> {
+    let mut eng = eng(Duration::from_millis(1000));
+    eng.config.enable_check_quorum = false;
+
+    eng.leader_check_quorum();
+
+    assert!(eng.leader.is_some());
+    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_check_quorum_within_lease() -> anyhow::Result<()> {
+    let mut eng = eng(Duration::from_millis(1000));
+
+    // A quorum acked recently.
+    let now = UTConfig::<()>::now();
+    let l = eng.leader_mut().unwrap();
+    let _ = l.clock_progress.increase_to(&2, Some(now));
+
+    eng.leader_check_quorum();
+
+    assert!(eng.leader.is_some());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_check_quorum_newly_elected() -> anyhow::Result<()> {
+    // No follower acked, but the vote is just granted.
+    let mut eng = eng(Duration::from_millis(0));
+
+    eng.leader_check_quorum();
+
+    assert!(eng.leader.is_some());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_check_quorum_step_down() -> anyhow::Result<()> {
+    let mut eng = eng(Duration::from_millis(1000));
+
+    // Only one follower acked, long ago.
+    let t = UTConfig::<()>::now() - Duration::from_millis(1000);
+    let l = eng.leader_mut().unwrap();
+    let _ = l.clock_progress.increase_to(&2, Some(t));
+
+    eng.leader_check_quorum();
+
+    assert!(eng.leader.is_none());
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+    assert_eq!(
+        vec![
+            //
+            Command::RebuildReplicationStreams { targets: vec![] },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
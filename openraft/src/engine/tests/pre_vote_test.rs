@@ -0,0 +1,239 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::Vote;
+
+fn m1() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1}], None)
+}
+
+fn m123() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1,2,3}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.config.enable_pre_vote = true;
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 2, 2)]);
+    // The leader lease is expired.
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(0),
+        Vote::new_committed(1, 2),
+    );
+    eng.state.server_state = ServerState::Follower;
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 2, 1)), m123())));
+
+    eng
+}
+
+#[test]
+fn test_pre_elect_disabled() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.enable_pre_vote = false;
+
+    eng.pre_elect();
+
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert!(!eng.candidate_ref().unwrap().is_pre_vote());
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+
+    assert_eq!(
+        vec![
+            //
+            Command::SaveVote { vote: Vote::new(2, 1) },
+            Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 2, 2))),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pre_elect_single_node() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 2, 1)), m1())));
+
+    eng.pre_elect();
+
+    // The pre-vote is granted by itself, start to elect at once.
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert!(!eng.candidate_ref().unwrap().is_pre_vote());
+
+    assert_eq!(
+        vec![
+            //
+            Command::SaveVote { vote: Vote::new(2, 1) },
+            Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 2, 2))),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pre_elect_does_not_update_vote() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.pre_elect();
+
+    assert_eq!(Vote::new_committed(1, 2), *eng.state.vote_ref());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+
+    let candidate = eng.candidate_ref().unwrap();
+    assert!(candidate.is_pre_vote());
+    assert_eq!(Vote::new(2, 1), *candidate.vote_ref());
+
+    assert_eq!(
+        vec![
+            //
+            Command::SendPreVote {
+                vote_req: VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 2, 2))),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pre_vote_resp_granted_by_quorum() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.pre_elect();
+    eng.output.take_commands();
+
+    tracing::info!("--- rejected pre-vote does not start election");
+    {
+        eng.handle_vote_resp(
+            2,
+            VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), false),
+        );
+
+        assert!(eng.candidate_ref().unwrap().is_pre_vote());
+        assert_eq!(Vote::new_committed(1, 2), *eng.state.vote_ref());
+        assert_eq!(0, eng.output.take_commands().len());
+    }
+
+    tracing::info!("--- granted by a quorum, start to elect");
+    {
+        eng.handle_vote_resp(
+            3,
+            VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), true),
+        );
+
+        assert!(!eng.candidate_ref().unwrap().is_pre_vote());
+        assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+
+        assert_eq!(
+            vec![
+                //
+                Command::SaveVote { vote: Vote::new(2, 1) },
+                Command::SendVote {
+                    vote_req: VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 2, 2))),
+                },
+            ],
+            eng.output.take_commands()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_pre_vote_req_rejected_by_leader_lease() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.vote.update(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(1, 2),
+    );
+
+    let resp = eng.handle_pre_vote_req(VoteRequest::new(Vote::new(2, 3), Some(log_id(1, 2, 2))));
+
+    assert_eq!(
+        VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), false),
+        resp
+    );
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_pre_vote_req_rejected_by_last_log_id() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let resp = eng.handle_pre_vote_req(VoteRequest::new(Vote::new(2, 3), Some(log_id(1, 2, 1))));
+
+    assert_eq!(
+        VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), false),
+        resp
+    );
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_pre_vote_req_rejected_by_vote() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let resp = eng.handle_pre_vote_req(VoteRequest::new(Vote::new(1, 3), Some(log_id(1, 2, 2))));
+
+    assert_eq!(
+        VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), false),
+        resp
+    );
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_pre_vote_req_granted_without_updating_vote() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let resp = eng.handle_pre_vote_req(VoteRequest::new(Vote::new(2, 3), Some(log_id(1, 2, 2))));
+
+    assert_eq!(
+        VoteResponse::new(Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), true),
+        resp
+    );
+
+    assert_eq!(Vote::new_committed(1, 2), *eng.state.vote_ref());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
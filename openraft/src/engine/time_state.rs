@@ -15,6 +15,10 @@ pub(crate) struct Config {
     /// Note that this value should be greater than the `election_timeout` of every other node.
     pub(crate) smaller_log_timeout: Duration,
 
+    /// The additional time to wait before starting an election, for a node with a lower election
+    /// priority.
+    pub(crate) priority_delay: Duration,
+
     /// The duration of an active leader's lease.
     ///
     /// When a follower or learner perceives an active leader, such as by receiving an AppendEntries
@@ -27,6 +31,7 @@ impl Default for Config {
         Self {
             election_timeout: Duration::from_millis(150),
             smaller_log_timeout: Duration::from_millis(200),
+            priority_delay: Duration::from_millis(0),
             leader_lease: Duration::from_millis(150),
         }
     }
@@ -95,6 +95,10 @@ where C: RaftTypeConfig
     fn get_membership(&self) -> Option<&Membership<C>> {
         self.payload.get_membership()
     }
+
+    fn payload_size(&self) -> u64 {
+        self.payload.payload_size()
+    }
 }
 
 impl<C> RaftLogId<C::NodeId> for Entry<C>
@@ -2,6 +2,7 @@ use std::fmt;
 use std::fmt::Formatter;
 
 use crate::entry::traits::RaftPayload;
+use crate::AppData;
 use crate::Membership;
 use crate::RaftTypeConfig;
 
@@ -74,4 +75,15 @@ impl<C: RaftTypeConfig> RaftPayload<C> for EntryPayload<C> {
             None
         }
     }
+
+    /// The size of a normal payload is provided by [`AppData::size_hint()`].
+    ///
+    /// A membership payload is counted by its in-memory size, and a blank payload is 0.
+    fn payload_size(&self) -> u64 {
+        match self {
+            EntryPayload::Blank => 0,
+            EntryPayload::Normal(data) => AppData::size_hint(data),
+            EntryPayload::Membership(m) => std::mem::size_of_val(m) as u64,
+        }
+    }
 }
@@ -18,6 +18,20 @@ where C: RaftTypeConfig
 
     /// Return `Some(&Membership)` if the entry payload is a membership payload.
     fn get_membership(&self) -> Option<&Membership<C>>;
+
+    /// Return the approximate size in bytes of the entry payload.
+    ///
+    /// It is used to limit the size of an AppendEntries RPC by [`Config::max_payload_bytes`].
+    /// The default implementation returns 0, i.e., the size is unknown and only the number of
+    /// entries is limited. [`Entry`] returns the [`AppData::size_hint()`] of its application
+    /// data.
+    ///
+    /// [`Config::max_payload_bytes`]: crate::Config::max_payload_bytes
+    /// [`Entry`]: crate::Entry
+    /// [`AppData::size_hint()`]: crate::AppData::size_hint
+    fn payload_size(&self) -> u64 {
+        0
+    }
 }
 
 /// Defines operations on an entry.
@@ -4,6 +4,7 @@ pub mod decompose;
 pub mod into_ok;
 mod invalid_sm;
 mod replication_closed;
+mod startup_error;
 mod streaming_error;
 
 use std::collections::BTreeSet;
@@ -16,15 +17,18 @@ use anyerror::AnyError;
 
 pub use self::invalid_sm::InvalidStateMachineType;
 pub use self::replication_closed::ReplicationClosed;
+pub use self::startup_error::StartupError;
 pub use self::streaming_error::StreamingError;
 use crate::network::RPCTypes;
 use crate::raft::AppendEntriesResponse;
+use crate::raft_types::SnapshotId;
 use crate::raft_types::SnapshotSegmentId;
 use crate::try_as_ref::TryAsRef;
 use crate::LogId;
 use crate::Membership;
 use crate::RaftTypeConfig;
 use crate::StorageError;
+use crate::StoredMembership;
 use crate::Vote;
 
 /// RaftError is returned by API methods of `Raft`.
@@ -143,6 +147,15 @@ where C: RaftTypeConfig
 pub enum InstallSnapshotError {
     #[error(transparent)]
     SnapshotMismatch(#[from] SnapshotMismatch),
+
+    #[error(transparent)]
+    SnapshotChecksumMismatch(#[from] SnapshotChecksumMismatch),
+
+    #[error(transparent)]
+    SnapshotDecompress(#[from] SnapshotDecompress),
+
+    #[error(transparent)]
+    SnapshotDecode(#[from] SnapshotDecode),
 }
 
 /// An error related to a is_leader request.
@@ -169,6 +182,45 @@ where C: RaftTypeConfig
     }
 }
 
+/// An error related to a read_index request.
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    #[error(transparent)]
+    QuorumNotEnough(#[from] QuorumNotEnough<C>),
+
+    /// Failed to get a read index from the leader.
+    #[error(transparent)]
+    RPCError(#[from] RPCError<C>),
+}
+
+impl<C> From<CheckIsLeaderError<C>> for ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    fn from(e: CheckIsLeaderError<C>) -> Self {
+        match e {
+            CheckIsLeaderError::ForwardToLeader(e) => e.into(),
+            CheckIsLeaderError::QuorumNotEnough(e) => e.into(),
+        }
+    }
+}
+
+impl<C> TryAsRef<ForwardToLeader<C>> for ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    fn try_as_ref(&self) -> Option<&ForwardToLeader<C>> {
+        match self {
+            Self::ForwardToLeader(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
 /// An error related to a client write request.
 #[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
 #[derive(PartialEq, Eq)]
@@ -182,6 +234,18 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<C>),
+
+    /// The leader has too many logs not yet applied to accept more writes.
+    #[error(transparent)]
+    Overloaded(#[from] Overloaded),
+
+    /// The write is not applied before the deadline.
+    #[error(transparent)]
+    Timeout(#[from] WriteTimeout),
+
+    /// The write is forwarded to the leader but its outcome is unknown.
+    #[error(transparent)]
+    ForwardUnknownOutcome(#[from] ForwardUnknownOutcome<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -207,6 +271,12 @@ pub enum ChangeMembershipError<C: RaftTypeConfig> {
 
     #[error(transparent)]
     LearnerNotFound(#[from] LearnerNotFound<C>),
+
+    #[error(transparent)]
+    VotersUnreachable(#[from] VotersUnreachable<C>),
+
+    #[error(transparent)]
+    LearnerLagging(#[from] LearnerLagging<C>),
 }
 
 /// The set of errors which may take place when initializing a pristine Raft node.
@@ -218,10 +288,41 @@ where C: RaftTypeConfig
     #[error(transparent)]
     NotAllowed(#[from] NotAllowed<C>),
 
+    #[error(transparent)]
+    AlreadyInitialized(#[from] AlreadyInitialized<C>),
+
+    #[error(transparent)]
+    NotInMembers(#[from] NotInMembers<C>),
+}
+
+/// The set of errors which may take place when forcing a membership config with
+/// [`Raft::force_set_membership()`](crate::Raft::force_set_membership).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, derive_more::TryInto)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ForceSetMembershipError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    LeaderAlive(#[from] LeaderAlive<C>),
+
     #[error(transparent)]
     NotInMembers(#[from] NotInMembers<C>),
 }
 
+/// The set of errors which may take place when purging logs with
+/// [`Raft::purge_log()`](crate::Raft::purge_log).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, derive_more::TryInto)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum PurgeLogError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    NotApplied(#[from] LogNotApplied<C>),
+
+    #[error(transparent)]
+    NotInSnapshot(#[from] LogNotInSnapshot<C>),
+}
+
 /// Error variants related to the Replication.
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::large_enum_variant)]
@@ -242,6 +343,13 @@ where C: RaftTypeConfig
 }
 
 /// Error occurs when invoking a remote raft API.
+///
+/// The variant tells Openraft how to react to a failed RPC: a replication stream backs off on
+/// [`Unreachable`], splits the payload on [`PayloadTooLarge`] and retries at once, and reduces the
+/// batch size on [`Timeout`] and [`NetworkError`]. A higher vote of the remote peer is not an
+/// error; it is returned in the RPC response, e.g., [`AppendEntriesResponse::HigherVote`].
+///
+/// [`AppendEntriesResponse::HigherVote`]: crate::raft::AppendEntriesResponse::HigherVote
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 // C already has serde bound.
 // E still needs additional serde bound.
@@ -253,6 +361,7 @@ where C: RaftTypeConfig
     serde(bound(deserialize = "E: for <'d> serde::Deserialize<'d>"))
 )]
 pub enum RPCError<C: RaftTypeConfig, E: Error = Infallible> {
+    /// The RPC is not finished in time, the remote peer may be slow or the payload too large.
     #[error(transparent)]
     Timeout(#[from] Timeout<C>),
 
@@ -268,6 +377,7 @@ pub enum RPCError<C: RaftTypeConfig, E: Error = Infallible> {
     #[error(transparent)]
     Network(#[from] NetworkError),
 
+    /// The RPC is delivered but the remote peer returned an error.
     #[error(transparent)]
     RemoteError(#[from] RemoteError<C, E>),
 }
@@ -452,15 +562,27 @@ impl fmt::Display for PayloadTooLarge {
             RPCTypes::Vote => {
                 unreachable!("vote rpc should not have payload")
             }
+            RPCTypes::PreVote => {
+                unreachable!("pre-vote rpc should not have payload")
+            }
             RPCTypes::AppendEntries => {
                 write!(f, "entries:{}", self.entries_hint)?;
             }
+            RPCTypes::Heartbeat => {
+                unreachable!("heartbeat rpc should not have payload")
+            }
             RPCTypes::InstallSnapshot => {
                 write!(f, "bytes:{}", self.bytes_hint)?;
             }
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader rpc should not have payload")
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("ReadIndex rpc should not have payload")
+            }
+            RPCTypes::ClientWrite => {
+                write!(f, "bytes:{}", self.bytes_hint)?;
+            }
         }
         write!(f, ")")?;
 
@@ -567,6 +689,89 @@ pub struct SnapshotMismatch {
     pub got: SnapshotSegmentId,
 }
 
+/// The checksum of the received snapshot data does not match the one computed by the sender.
+///
+/// `got` is `None` if the receiver can not compute the checksum, e.g., the data is not received
+/// contiguously from the beginning.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("snapshot checksum mismatch, snapshot_id: {snapshot_id}, expect: {expect}, got: {got:?}")]
+pub struct SnapshotChecksumMismatch {
+    pub snapshot_id: SnapshotId,
+    pub expect: u32,
+    pub got: Option<u32>,
+}
+
+/// A compressed snapshot chunk can not be decompressed by the receiver.
+///
+/// E.g., the receiver is built without feature flag `snapshot-compression`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("failed to decompress snapshot chunk, snapshot_id: {snapshot_id}, offset: {offset}: {reason}")]
+pub struct SnapshotDecompress {
+    pub snapshot_id: SnapshotId,
+    pub offset: u64,
+    pub reason: String,
+}
+
+/// The receiver of a snapshot can not decode a chunk with its [`SnapshotTransform`].
+///
+/// E.g., the transforms of the sender and the receiver do not match. The sender stops sending
+/// the snapshot, because the chunk can not be decoded if it is re-sent.
+///
+/// [`SnapshotTransform`]: crate::network::SnapshotTransform
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("failed to decode snapshot chunk, snapshot_id: {snapshot_id}, offset: {offset}: {reason}")]
+pub struct SnapshotDecode {
+    pub snapshot_id: SnapshotId,
+    pub offset: u64,
+    pub reason: String,
+}
+
+/// The leader rejects a client write because the logs not yet applied reach
+/// [`Config::max_unapplied_logs`]. The client should retry later.
+///
+/// [`Config::max_unapplied_logs`]: crate::Config::max_unapplied_logs
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("overloaded: {unapplied} logs are not applied, limit: {limit}")]
+pub struct Overloaded {
+    /// The number of logs appended but not yet applied.
+    pub unapplied: u64,
+
+    /// The configured limit, [`Config::max_unapplied_logs`].
+    ///
+    /// [`Config::max_unapplied_logs`]: crate::Config::max_unapplied_logs
+    pub limit: u64,
+}
+
+/// A client write is not applied within the timeout passed to
+/// [`Raft::client_write_with_timeout()`].
+///
+/// The entry may have been appended to the log, and it may still be committed and applied later.
+///
+/// [`Raft::client_write_with_timeout()`]: crate::Raft::client_write_with_timeout
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("client write is not applied in {timeout:?}")]
+pub struct WriteTimeout {
+    pub timeout: Duration,
+}
+
+/// A client write is forwarded to the leader, but no response is received from it, e.g., the RPC
+/// times out or the connection is broken.
+///
+/// The leader may or may not have received the write, and it may still be applied. Retrying it
+/// may apply it twice, unless the application deduplicates writes.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("outcome of client write forwarded to leader {leader_id} is unknown: {reason}")]
+pub struct ForwardUnknownOutcome<C: RaftTypeConfig> {
+    pub leader_id: C::NodeId,
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("not enough for a quorum, cluster: {cluster}, got: {got:?}")]
@@ -598,6 +803,40 @@ pub struct NotAllowed<C: RaftTypeConfig> {
     pub vote: Vote<C::NodeId>,
 }
 
+/// The node is already initialized with a membership different from the one to initialize with.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("already initialized with a different membership: {membership}")]
+pub struct AlreadyInitialized<C: RaftTypeConfig> {
+    /// The current membership config of this node.
+    pub membership: StoredMembership<C>,
+}
+
+/// The logs to purge are not yet applied to the state machine.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("can not purge logs upto index {upto}: not applied yet, last applied: {last_applied:?}")]
+pub struct LogNotApplied<C: RaftTypeConfig> {
+    pub upto: u64,
+    pub last_applied: Option<LogId<C::NodeId>>,
+}
+
+/// The logs to purge are not yet included in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("can not purge logs upto index {upto}: not in a snapshot, last log id in snapshot: {snapshot_last_log_id:?}")]
+pub struct LogNotInSnapshot<C: RaftTypeConfig> {
+    pub upto: u64,
+    pub snapshot_last_log_id: Option<LogId<C::NodeId>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("not allowed to force set membership: a leader is still alive: vote: {vote}")]
+pub struct LeaderAlive<C: RaftTypeConfig> {
+    pub vote: Vote<C::NodeId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("node {node_id} has to be a member. membership:{membership:?}")]
@@ -608,6 +847,25 @@ where C: RaftTypeConfig
     pub membership: Membership<C>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("voters {unreachable:?} are unreachable, the new membership would lose quorum")]
+pub struct VotersUnreachable<C: RaftTypeConfig> {
+    pub unreachable: BTreeSet<C::NodeId>,
+}
+
+/// A learner to promote to a voter does not catch up with the leader in time.
+///
+/// See [`Config::wait_learner_caught_up`](crate::Config::wait_learner_caught_up).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("learner {node_id} does not catch up in {timeout:?}, matched: {matched:?}")]
+pub struct LearnerLagging<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    pub matched: Option<LogId<C::NodeId>>,
+    pub timeout: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[error("new membership can not be empty")]
@@ -0,0 +1,79 @@
+use crate::error::Fatal;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::Vote;
+
+/// Error returned by [`Raft::new_checked()`] when a node can not start up.
+///
+/// Except [`Fatal`], every variant describes an inconsistency found in the state loaded from the
+/// [`RaftLogStorage`] and the [`RaftStateMachine`], which means the storage is corrupted, e.g., a
+/// write is lost after a crash because it is not flushed.
+///
+/// [`Raft::new_checked()`]: crate::Raft::new_checked
+/// [`RaftLogStorage`]: crate::storage::RaftLogStorage
+/// [`RaftStateMachine`]: crate::storage::RaftStateMachine
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum StartupError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    Fatal(#[from] Fatal<C>),
+
+    /// The last log is before the last purged log.
+    #[error("the last log id {last_log_id:?} is before the last purged log id {last_purged:?}")]
+    LogBeforePurged {
+        last_purged: Option<LogId<C::NodeId>>,
+        last_log_id: Option<LogId<C::NodeId>>,
+    },
+
+    /// The last log is proposed in a term greater than the saved vote. A vote must be saved
+    /// before accepting any log of its term.
+    #[error("the last log id {last_log_id:?} is proposed in a term greater than the vote {vote}")]
+    LogAheadOfVote {
+        vote: Vote<C::NodeId>,
+        last_log_id: Option<LogId<C::NodeId>>,
+    },
+
+    /// The saved committed log id is neither applied nor in the log, thus it can not be re-applied.
+    #[error("the committed log id {committed:?} is not applied and is after the last log id {last_log_id:?}")]
+    CommittedNotInLog {
+        committed: Option<LogId<C::NodeId>>,
+        last_log_id: Option<LogId<C::NodeId>>,
+    },
+
+    /// Logs are purged before they are applied to the state machine.
+    #[error("logs are purged up to {last_purged:?} but only applied up to {last_applied:?}")]
+    PurgedNotApplied {
+        last_purged: Option<LogId<C::NodeId>>,
+        last_applied: Option<LogId<C::NodeId>>,
+    },
+
+    /// The snapshot includes logs that are not applied to the state machine.
+    #[error("the snapshot includes logs up to {snapshot:?} but the state machine only applied up to {last_applied:?}")]
+    SnapshotAheadOfApplied {
+        snapshot: Option<LogId<C::NodeId>>,
+        last_applied: Option<LogId<C::NodeId>>,
+    },
+}
+
+impl<C> StartupError<C>
+where C: RaftTypeConfig
+{
+    /// Try to convert self to Fatal error.
+    pub fn into_fatal(self) -> Option<Fatal<C>> {
+        match self {
+            StartupError::Fatal(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
+impl<C> From<StorageError<C>> for StartupError<C>
+where C: RaftTypeConfig
+{
+    fn from(se: StorageError<C>) -> Self {
+        StartupError::Fatal(Fatal::from(se))
+    }
+}
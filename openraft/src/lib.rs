@@ -61,6 +61,7 @@ pub mod log_id;
 pub mod metrics;
 pub mod network;
 pub mod raft;
+pub mod raft_groups;
 pub mod storage;
 pub mod testing;
 pub mod type_config;
@@ -88,6 +89,7 @@ pub use crate::base::OptionalSync;
 pub use crate::change_members::ChangeMembers;
 pub use crate::config::Config;
 pub use crate::config::ConfigError;
+pub use crate::config::FsyncPolicy;
 pub use crate::config::SnapshotPolicy;
 pub use crate::core::ServerState;
 pub use crate::entry::Entry;
@@ -142,7 +144,48 @@ pub use crate::vote::Vote;
 /// ## Note
 ///
 /// The trait is automatically implemented for all types which satisfy its supertraits.
-pub trait AppData: OptionalSend + OptionalSync + 'static + OptionalSerde {}
+///
+/// `serde::Serialize` and `serde::Deserialize` are required only when feature flag `serde` is
+/// enabled. Without it, an application can encode the data in any way, e.g., with `prost`.
+pub trait AppData: OptionalSend + OptionalSync + 'static + OptionalSerde {
+    /// Return the approximate size in bytes of this data.
+    ///
+    /// It is used by [`Entry`] to implement [`RaftPayload::payload_size()`], which limits the
+    /// size of an AppendEntries RPC by [`Config::max_payload_bytes`].
+    ///
+    /// With feature flag `serde` enabled, it is the length of the JSON encoding of the data.
+    /// Otherwise, it is the in-memory size of the value, excluding any heap allocation it owns.
+    ///
+    /// [`RaftPayload::payload_size()`]: crate::entry::RaftPayload::payload_size
+    fn size_hint(&self) -> u64 {
+        #[cfg(feature = "serde")]
+        {
+            struct Counter(u64);
+
+            impl std::io::Write for Counter {
+                fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                    self.0 += buf.len() as u64;
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> std::io::Result<()> {
+                    Ok(())
+                }
+            }
+
+            let mut counter = Counter(0);
+            match serde_json::to_writer(&mut counter, self) {
+                Ok(()) => counter.0,
+                Err(_) => std::mem::size_of_val(self) as u64,
+            }
+        }
+
+        #[cfg(not(feature = "serde"))]
+        {
+            std::mem::size_of_val(self) as u64
+        }
+    }
+}
 
 impl<T> AppData for T where T: OptionalSend + OptionalSync + 'static + OptionalSerde {}
 
@@ -163,6 +206,8 @@ impl<T> AppData for T where T: OptionalSend + OptionalSync + 'static + OptionalS
 /// ## Note
 ///
 /// The trait is automatically implemented for all types which satisfy its supertraits.
+///
+/// Like [`AppData`], `serde` traits are required only when feature flag `serde` is enabled.
 pub trait AppDataResponse: OptionalSend + OptionalSync + 'static + OptionalSerde {}
 
 impl<T> AppDataResponse for T where T: OptionalSend + OptionalSync + 'static + OptionalSerde {}
@@ -1,7 +1,8 @@
 //! Raft metrics for observability.
 //!
 //! Applications may use this data in whatever way is needed. The obvious use cases are to expose
-//! these metrics to a metrics collection system like Prometheus. Applications may also
+//! these metrics to a metrics collection system like Prometheus, which is provided by
+//! `prometheus_exporter::PrometheusExporter` with feature flag `prometheus`. Applications may also
 //! use this data to trigger events within higher levels of the parent application.
 //!
 //! Metrics are observed on a running Raft node via the [`Raft::metrics() ->
@@ -23,30 +24,51 @@
 //! Metrics can be used as a trigger of application events, as a monitoring data
 //! source, etc.
 //!
+//! ## [`RaftNetworkMetrics`]
+//!
+//! [`RaftNetworkMetrics`] is observed via
+//! [`Raft::network_metrics()`](`crate::Raft::network_metrics`). It contains the number, errors,
+//! timeouts and latency of the RPCs sent to each target, by RPC type. It is collected by Openraft
+//! thus it is available with any transport.
+//!
 //! Metrics is not a stream thus it only guarantees to provide the latest state but
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
 
 mod metric;
+mod network_metrics;
+#[cfg(feature = "prometheus")]
+pub mod prometheus_exporter;
 mod raft_metrics;
+mod replication_status;
+mod snapshot_transfer;
 mod wait;
 
 mod metric_display;
 mod serde_instant;
 mod wait_condition;
+mod wait_predicate;
 #[cfg(test)]
 mod wait_test;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 pub use metric::Metric;
+pub use network_metrics::LatencyHistogram;
+pub use network_metrics::RPCMetrics;
+pub use network_metrics::RaftNetworkMetrics;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
+pub use replication_status::ReplicationState;
+pub use replication_status::ReplicationStatus;
 pub use serde_instant::SerdeInstant;
+pub use snapshot_transfer::SnapshotTransfer;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
+pub use wait_predicate::MetricsPredicate;
 
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::NodeIdOf;
@@ -56,3 +78,15 @@ pub(crate) type ReplicationMetrics<C> = BTreeMap<NodeIdOf<C>, Option<LogIdOf<C>>
 /// Heartbeat metrics, a mapping between a node's ID and milliseconds since the
 /// last acknowledged heartbeat or replication to this node.
 pub(crate) type HeartbeatMetrics<C> = BTreeMap<NodeIdOf<C>, Option<SerdeInstantOf<C>>>;
+
+/// Snapshot sending metrics, a mapping between a node's ID and the progress of the snapshot being
+/// sent to this node.
+pub(crate) type SnapshotSendingMetrics<C> = BTreeMap<NodeIdOf<C>, SnapshotTransfer<C>>;
+
+/// Replication circuit breaker metrics, the ids of the nodes whose replication circuit breaker is
+/// open.
+pub(crate) type ReplicationBreakerMetrics<C> = BTreeSet<NodeIdOf<C>>;
+
+/// Replication status metrics, a mapping between a node's ID and the status of the replication to
+/// this node.
+pub(crate) type ReplicationStatusMetrics<C> = BTreeMap<NodeIdOf<C>, ReplicationStatus<C>>;
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::network::RPCTypes;
+use crate::RaftTypeConfig;
+
+/// Metrics of the RPCs sent by this node, collected regardless of the transport.
+///
+/// Openraft wraps every call to [`RaftNetworkV2`] and records the result in it. It is published
+/// through [`Raft::network_metrics()`].
+///
+/// The encoded size of a message is not known to Openraft, because it does not serialize
+/// messages. A transport that needs the number of bytes should count them itself.
+///
+/// [`RaftNetworkV2`]: crate::network::v2::RaftNetworkV2
+/// [`Raft::network_metrics()`]: crate::Raft::network_metrics
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RaftNetworkMetrics<C: RaftTypeConfig> {
+    /// The metrics of each type of RPC, grouped by the target node.
+    pub targets: BTreeMap<C::NodeId, BTreeMap<RPCTypes, RPCMetrics>>,
+}
+
+impl<C> RaftNetworkMetrics<C>
+where C: RaftTypeConfig
+{
+    /// Get the metrics of RPCs of type `typ` sent to `target`, if any has been sent.
+    pub fn get(&self, target: &C::NodeId, typ: RPCTypes) -> Option<&RPCMetrics> {
+        self.targets.get(target).and_then(|x| x.get(&typ))
+    }
+
+    pub(crate) fn get_mut(&mut self, target: C::NodeId, typ: RPCTypes) -> &mut RPCMetrics {
+        self.targets.entry(target).or_default().entry(typ).or_default()
+    }
+}
+
+impl<C> fmt::Display for RaftNetworkMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NetworkMetrics{{")?;
+
+        for (i, (target, rpcs)) in self.targets.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}:{{", target)?;
+            for (j, (typ, m)) in rpcs.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}:{}", typ, m)?;
+            }
+            write!(f, "}}")?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// Metrics of the RPCs of one type sent to one target.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RPCMetrics {
+    /// The number of RPCs sent.
+    pub sent: u64,
+
+    /// The number of RPCs that returned an error, including [`RPCError::Timeout`].
+    ///
+    /// [`RPCError::Timeout`]: crate::error::RPCError::Timeout
+    pub errors: u64,
+
+    /// The number of RPCs that timed out, including the ones that returned
+    /// [`RPCError::Timeout`] and the ones that are canceled by Openraft after the timeout in
+    /// [`RPCOption`].
+    ///
+    /// [`RPCError::Timeout`]: crate::error::RPCError::Timeout
+    /// [`RPCOption`]: crate::network::RPCOption
+    pub timeouts: u64,
+
+    /// The number of log entries sent, only counted for `AppendEntries` RPCs.
+    pub entries: u64,
+
+    /// The latency of the RPCs that returned, either successfully or with an error.
+    pub latency: LatencyHistogram,
+}
+
+impl fmt::Display for RPCMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{sent:{}, errors:{}, timeouts:{}, entries:{}, latency:{}}}",
+            self.sent, self.errors, self.timeouts, self.entries, self.latency
+        )
+    }
+}
+
+/// A histogram of RPC latency, with fixed buckets.
+///
+/// `buckets[i]` is the number of RPCs whose latency is less than or equal to
+/// `BUCKET_BOUNDS_MS[i]` milliseconds but greater than the previous bound. The last bucket counts
+/// the RPCs slower than all of the bounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LatencyHistogram {
+    /// The number of RPCs in each bucket.
+    pub buckets: Vec<u64>,
+
+    /// The sum of all recorded latency, in microseconds.
+    pub sum_us: u64,
+
+    /// The max recorded latency, in microseconds.
+    pub max_us: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; Self::BUCKET_BOUNDS_MS.len() + 1],
+            sum_us: 0,
+            max_us: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// The upper bounds of the buckets, in milliseconds.
+    pub const BUCKET_BOUNDS_MS: [u64; 12] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000];
+
+    /// Record the latency of an RPC.
+    pub fn record(&mut self, latency: Duration) {
+        let us = latency.as_micros().min(u64::MAX as u128) as u64;
+
+        let bounds = &Self::BUCKET_BOUNDS_MS;
+        let i = bounds.iter().position(|ms| us <= ms * 1_000).unwrap_or(bounds.len());
+        self.buckets[i] += 1;
+
+        self.sum_us = self.sum_us.saturating_add(us);
+        self.max_us = std::cmp::max(self.max_us, us);
+    }
+
+    /// The number of recorded RPCs.
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// The mean latency, or `None` if nothing is recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        let n = self.count();
+        if n == 0 {
+            return None;
+        }
+        Some(Duration::from_micros(self.sum_us / n))
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{count:{}, sum:{}us, max:{}us}}",
+            self.count(),
+            self.sum_us,
+            self.max_us
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LatencyHistogram;
+
+    #[test]
+    fn test_latency_histogram_record() {
+        let mut h = LatencyHistogram::default();
+        assert_eq!(None, h.mean());
+
+        h.record(Duration::from_micros(500));
+        h.record(Duration::from_millis(2));
+        h.record(Duration::from_millis(3));
+        h.record(Duration::from_secs(10));
+
+        assert_eq!(4, h.count());
+        assert_eq!(vec![1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], h.buckets);
+        assert_eq!(10_005_500, h.sum_us);
+        assert_eq!(10_000_000, h.max_us);
+        assert_eq!(Some(Duration::from_micros(2_501_375)), h.mean());
+    }
+}
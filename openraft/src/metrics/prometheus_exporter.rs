@@ -0,0 +1,285 @@
+//! Export [`RaftMetrics`] and [`RaftNetworkMetrics`] as Prometheus metrics.
+
+use prometheus::IntCounter;
+use prometheus::IntCounterVec;
+use prometheus::IntGauge;
+use prometheus::IntGaugeVec;
+use prometheus::Opts;
+use prometheus::Registry;
+
+use crate::metrics::RaftMetrics;
+use crate::metrics::RaftNetworkMetrics;
+use crate::LogIdOptionExt;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+
+/// Maps the metrics of a Raft node onto Prometheus gauges and counters in a [`Registry`].
+///
+/// Every metric has a const label `node_id`, so that the exporters of several nodes in one process
+/// can share a registry. The metric names are stable:
+///
+/// | name                                | type    | labels         |
+/// |-------------------------------------|---------|----------------|
+/// | `openraft_current_term`             | gauge   |                |
+/// | `openraft_server_state`             | gauge   | `state`        |
+/// | `openraft_current_leader`           | gauge   | `leader`       |
+/// | `openraft_last_log_index`           | gauge   |                |
+/// | `openraft_last_applied_index`       | gauge   |                |
+/// | `openraft_snapshot_index`           | gauge   |                |
+/// | `openraft_purged_index`             | gauge   |                |
+/// | `openraft_voters`                   | gauge   |                |
+/// | `openraft_learners`                 | gauge   |                |
+/// | `openraft_slow_io_total`            | counter |                |
+/// | `openraft_replication_matched_index`| gauge   | `target`       |
+/// | `openraft_rpc_sent_total`           | counter | `target`, `rpc`|
+/// | `openraft_rpc_errors_total`         | counter | `target`, `rpc`|
+/// | `openraft_rpc_timeouts_total`       | counter | `target`, `rpc`|
+///
+/// `openraft_server_state` is `1` for the current state and `0` for the others.
+/// `openraft_current_leader` is `1` for the known leader, and there is no sample if the leader is
+/// unknown. A log index gauge is `-1` if there is no such log.
+///
+/// The exporter does not watch the metrics itself; the application calls [`update()`] with every
+/// changed [`RaftMetrics`], e.g., in a task that waits on [`Raft::metrics()`]:
+///
+/// ```ignore
+/// let exporter = PrometheusExporter::new(id, &registry)?;
+/// let mut rx = raft.metrics();
+/// loop {
+///     exporter.update(&rx.borrow_and_update());
+///     rx.changed().await?;
+/// }
+/// ```
+///
+/// [`update()`]: PrometheusExporter::update
+/// [`Raft::metrics()`]: crate::Raft::metrics
+pub struct PrometheusExporter {
+    current_term: IntGauge,
+    server_state: IntGaugeVec,
+    current_leader: IntGaugeVec,
+    last_log_index: IntGauge,
+    last_applied_index: IntGauge,
+    snapshot_index: IntGauge,
+    purged_index: IntGauge,
+    voters: IntGauge,
+    learners: IntGauge,
+    slow_io: IntCounter,
+    replication_matched_index: IntGaugeVec,
+    rpc_sent: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    rpc_timeouts: IntCounterVec,
+}
+
+impl PrometheusExporter {
+    /// Create the metrics of node `node_id` and register them to `registry`.
+    pub fn new(node_id: impl ToString, registry: &Registry) -> Result<Self, prometheus::Error> {
+        let node_id = node_id.to_string();
+
+        let gauge = |name: &str, help: &str| -> Result<IntGauge, prometheus::Error> {
+            let g = IntGauge::with_opts(Opts::new(name, help).const_label("node_id", &node_id))?;
+            registry.register(Box::new(g.clone()))?;
+            Ok(g)
+        };
+
+        let gauge_vec = |name: &str, help: &str, labels: &[&str]| -> Result<IntGaugeVec, prometheus::Error> {
+            let g = IntGaugeVec::new(Opts::new(name, help).const_label("node_id", &node_id), labels)?;
+            registry.register(Box::new(g.clone()))?;
+            Ok(g)
+        };
+
+        let slow_io = IntCounter::with_opts(
+            Opts::new("openraft_slow_io_total", "The number of slow storage calls").const_label("node_id", &node_id),
+        )?;
+        registry.register(Box::new(slow_io.clone()))?;
+
+        let counter = |name: &str, help: &str| -> Result<IntCounterVec, prometheus::Error> {
+            let c = IntCounterVec::new(Opts::new(name, help).const_label("node_id", &node_id), &[
+                "target", "rpc",
+            ])?;
+            registry.register(Box::new(c.clone()))?;
+            Ok(c)
+        };
+
+        Ok(Self {
+            current_term: gauge("openraft_current_term", "The current term")?,
+            server_state: gauge_vec("openraft_server_state", "1 for the current server state", &["state"])?,
+            current_leader: gauge_vec("openraft_current_leader", "1 for the current leader", &["leader"])?,
+            last_log_index: gauge("openraft_last_log_index", "The index of the last log")?,
+            last_applied_index: gauge("openraft_last_applied_index", "The index of the last applied log")?,
+            snapshot_index: gauge("openraft_snapshot_index", "The last log index in the snapshot")?,
+            purged_index: gauge("openraft_purged_index", "The index of the last purged log")?,
+            voters: gauge("openraft_voters", "The number of voters in the membership")?,
+            learners: gauge("openraft_learners", "The number of learners in the membership")?,
+            slow_io,
+            replication_matched_index: gauge_vec(
+                "openraft_replication_matched_index",
+                "The index of the last log replicated to a target, on the leader",
+                &["target"],
+            )?,
+            rpc_sent: counter("openraft_rpc_sent_total", "The number of RPCs sent")?,
+            rpc_errors: counter("openraft_rpc_errors_total", "The number of RPCs that returned an error")?,
+            rpc_timeouts: counter("openraft_rpc_timeouts_total", "The number of RPCs that timed out")?,
+        })
+    }
+
+    /// Update the gauges with the latest [`RaftMetrics`].
+    pub fn update<C>(&self, metrics: &RaftMetrics<C>)
+    where C: RaftTypeConfig {
+        let m = metrics;
+
+        self.current_term.set(m.current_term as i64);
+
+        for state in [
+            ServerState::Learner,
+            ServerState::Follower,
+            ServerState::Candidate,
+            ServerState::Leader,
+            ServerState::Shutdown,
+        ] {
+            let v = if state == m.state { 1 } else { 0 };
+            self.server_state.with_label_values(&[&format!("{:?}", state)]).set(v);
+        }
+
+        self.current_leader.reset();
+        if let Some(leader) = &m.current_leader {
+            self.current_leader.with_label_values(&[&leader.to_string()]).set(1);
+        }
+
+        self.last_log_index.set(index_value(m.last_log_index));
+        self.last_applied_index.set(index_value(m.last_applied.index()));
+        self.snapshot_index.set(index_value(m.snapshot.index()));
+        self.purged_index.set(index_value(m.purged.index()));
+
+        let membership = m.membership_config.membership();
+        self.voters.set(membership.voter_ids().count() as i64);
+        self.learners.set(membership.learner_ids().count() as i64);
+
+        if m.slow_io > self.slow_io.get() {
+            self.slow_io.inc_by(m.slow_io - self.slow_io.get());
+        }
+
+        // Remove the targets that are no longer replicated to, e.g., after stepping down.
+        self.replication_matched_index.reset();
+        if let Some(replication) = &m.replication {
+            for (target, matched) in replication.iter() {
+                self.replication_matched_index
+                    .with_label_values(&[&target.to_string()])
+                    .set(index_value(matched.index()));
+            }
+        }
+    }
+
+    /// Update the counters with the latest [`RaftNetworkMetrics`].
+    pub fn update_network<C>(&self, metrics: &RaftNetworkMetrics<C>)
+    where C: RaftTypeConfig {
+        for (target, rpcs) in metrics.targets.iter() {
+            let target = target.to_string();
+
+            for (typ, m) in rpcs.iter() {
+                let typ = typ.to_string();
+                let labels = [target.as_str(), typ.as_str()];
+
+                increase_to(&self.rpc_sent, &labels, m.sent);
+                increase_to(&self.rpc_errors, &labels, m.errors);
+                increase_to(&self.rpc_timeouts, &labels, m.timeouts);
+            }
+        }
+    }
+}
+
+/// Convert an optional log index to a gauge value, `-1` for `None`.
+fn index_value(index: Option<u64>) -> i64 {
+    index.map(|x| x as i64).unwrap_or(-1)
+}
+
+/// Increase a counter to the cumulative value `v` recorded by Openraft.
+fn increase_to(counter: &IntCounterVec, labels: &[&str], v: u64) {
+    let c = counter.with_label_values(labels);
+    let curr = c.get();
+    if v > curr {
+        c.inc_by(v - curr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+    use prometheus::Registry;
+
+    use super::PrometheusExporter;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::RaftMetrics;
+    use crate::metrics::RaftNetworkMetrics;
+    use crate::network::RPCTypes;
+    use crate::testing::log_id;
+    use crate::ServerState;
+
+    /// Return the value of a metric with the given label values.
+    fn value(registry: &Registry, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        let family = registry.gather().into_iter().find(|f| f.get_name() == name)?;
+        let metric = family.get_metric().iter().find(|m| {
+            labels.iter().all(|(k, v)| m.get_label().iter().any(|l| l.get_name() == *k && l.get_value() == *v))
+        })?;
+
+        if metric.has_counter() {
+            Some(metric.get_counter().get_value())
+        } else {
+            Some(metric.get_gauge().get_value())
+        }
+    }
+
+    #[test]
+    fn test_prometheus_exporter() -> anyhow::Result<()> {
+        let registry = Registry::new();
+        let exporter = PrometheusExporter::new(1, &registry)?;
+
+        let mut m = RaftMetrics::<UTConfig>::new_initial(1);
+        m.current_term = 3;
+        m.state = ServerState::Leader;
+        m.current_leader = Some(1);
+        m.last_log_index = Some(5);
+        m.last_applied = Some(log_id(3, 1, 4));
+        m.replication = Some(btreemap! {1 => Some(log_id(3, 1, 5)), 2 => None});
+
+        exporter.update(&m);
+
+        let node = ("node_id", "1");
+        assert_eq!(Some(3.0), value(&registry, "openraft_current_term", &[node]));
+        assert_eq!(
+            Some(1.0),
+            value(&registry, "openraft_server_state", &[node, ("state", "Leader")])
+        );
+        assert_eq!(
+            Some(0.0),
+            value(&registry, "openraft_server_state", &[node, ("state", "Follower")])
+        );
+        assert_eq!(
+            Some(1.0),
+            value(&registry, "openraft_current_leader", &[node, ("leader", "1")])
+        );
+        assert_eq!(Some(5.0), value(&registry, "openraft_last_log_index", &[node]));
+        assert_eq!(Some(4.0), value(&registry, "openraft_last_applied_index", &[node]));
+        assert_eq!(Some(-1.0), value(&registry, "openraft_snapshot_index", &[node]));
+        assert_eq!(
+            Some(-1.0),
+            value(&registry, "openraft_replication_matched_index", &[
+                node,
+                ("target", "2")
+            ])
+        );
+
+        let mut nm = RaftNetworkMetrics::<UTConfig>::default();
+        nm.get_mut(2, RPCTypes::AppendEntries).sent = 10;
+        nm.get_mut(2, RPCTypes::AppendEntries).errors = 2;
+        exporter.update_network(&nm);
+
+        nm.get_mut(2, RPCTypes::AppendEntries).sent = 15;
+        exporter.update_network(&nm);
+
+        let labels = [node, ("target", "2"), ("rpc", "AppendEntries")];
+        assert_eq!(Some(15.0), value(&registry, "openraft_rpc_sent_total", &labels));
+        assert_eq!(Some(2.0), value(&registry, "openraft_rpc_errors_total", &labels));
+
+        Ok(())
+    }
+}
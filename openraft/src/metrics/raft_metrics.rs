@@ -6,8 +6,12 @@ use crate::display_ext::DisplayBTreeMapOptValue;
 use crate::display_ext::DisplayOption;
 use crate::error::Fatal;
 use crate::metrics::HeartbeatMetrics;
+use crate::metrics::ReplicationBreakerMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationStatusMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SnapshotSendingMetrics;
+use crate::metrics::SnapshotTransfer;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::SerdeInstantOf;
 use crate::Instant;
@@ -44,12 +48,30 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// If there is no snapshot, it is (0,0).
     pub snapshot: Option<LogId<C::NodeId>>,
 
+    /// Whether a snapshot is being built on this node.
+    pub snapshot_building: bool,
+
+    /// The progress of the snapshot being received from the leader, if any.
+    ///
+    /// It is only reported when the snapshot is received in chunks with
+    /// [`Raft::install_snapshot()`](crate::Raft::install_snapshot).
+    pub snapshot_receiving: Option<SnapshotTransfer<C>>,
+
     /// The last log id that has purged from storage, inclusive.
     ///
     /// `purged` is also the first log id Openraft knows, although the corresponding log entry has
     /// already been deleted.
     pub purged: Option<LogId<C::NodeId>>,
 
+    /// The number of calls to the log store and the state machine that took longer than
+    /// [`Config::slow_io_threshold`](crate::Config::slow_io_threshold).
+    pub slow_io: u64,
+
+    /// The number of snapshots partially received from the leader and dropped, because no chunk
+    /// is received within
+    /// [`Config::snapshot_receive_idle_timeout`](crate::Config::snapshot_receive_idle_timeout).
+    pub snapshot_receive_timeouts: u64,
+
     // ---
     // --- cluster ---
     // ---
@@ -59,6 +81,13 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The current cluster leader.
     pub current_leader: Option<C::NodeId>,
 
+    /// Whether this node is a leader that has committed a log entry of its own term.
+    ///
+    /// A newly established leader is ready to serve when the first log entry of its term, the blank
+    /// log by default, is committed, and all logs of previous terms are committed along with it.
+    /// It is `false` if this node is not a leader.
+    pub leader_ready: bool,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -110,6 +139,22 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     // ---
     /// The replication states. It is Some() only when this node is leader.
     pub replication: Option<ReplicationMetrics<C>>,
+
+    /// The followers and learners a snapshot is being sent to, and the progress of each of these
+    /// transfers. It is Some() only when this node is leader.
+    pub snapshot_sending: Option<SnapshotSendingMetrics<C>>,
+
+    /// The followers and learners whose replication circuit breaker is open, i.e., the replication
+    /// to them has failed [`Config::replication_breaker_threshold`] times in a row.
+    /// It is Some() only when this node is leader.
+    ///
+    /// [`Config::replication_breaker_threshold`]: crate::Config::replication_breaker_threshold
+    pub replication_breaker: Option<ReplicationBreakerMetrics<C>>,
+
+    /// The status of the replication to each follower and learner: the matched log, the next log
+    /// to send, the number of logs in flight, the last contact time and what the replication is
+    /// doing. It is Some() only when this node is leader.
+    pub replication_status: Option<ReplicationStatusMetrics<C>>,
 }
 
 impl<C> fmt::Display for RaftMetrics<C>
@@ -120,7 +165,7 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "id:{}, {:?}, term:{}, vote:{}, last_log:{}, last_applied:{}, leader:{}",
+            "id:{}, {:?}, term:{}, vote:{}, last_log:{}, last_applied:{}, leader:{}, leader_ready:{}",
             self.id,
             self.state,
             self.current_term,
@@ -128,6 +173,7 @@ where C: RaftTypeConfig
             DisplayOption(&self.last_log_index),
             DisplayOption(&self.last_applied),
             DisplayOption(&self.current_leader),
+            self.leader_ready,
         )?;
 
         if let Some(quorum_acked) = &self.last_quorum_acked {
@@ -144,14 +190,24 @@ where C: RaftTypeConfig
         write!(f, ", ")?;
         write!(
             f,
-            "membership:{}, snapshot:{}, purged:{}, replication:{{{}}}, heartbeat:{{{}}}",
+            "membership:{}, snapshot:{}, snapshot_building:{}, snapshot_receiving:{}, purged:{}, slow_io:{}, snapshot_receive_timeouts:{}, replication:{{{}}}, heartbeat:{{{}}}",
             self.membership_config,
             DisplayOption(&self.snapshot),
+            self.snapshot_building,
+            DisplayOption(&self.snapshot_receiving),
             DisplayOption(&self.purged),
+            self.slow_io,
+            self.snapshot_receive_timeouts,
             DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
             DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
         )?;
 
+        write!(
+            f,
+            ", snapshot_sending:{:?}, replication_breaker:{:?}, replication_status:{:?}",
+            self.snapshot_sending, self.replication_breaker, self.replication_status,
+        )?;
+
         write!(f, "}}")?;
         Ok(())
     }
@@ -171,15 +227,23 @@ where C: RaftTypeConfig
             last_log_index: None,
             last_applied: None,
             snapshot: None,
+            snapshot_building: false,
+            snapshot_receiving: None,
             purged: None,
+            slow_io: 0,
+            snapshot_receive_timeouts: 0,
 
             state: ServerState::Follower,
             current_leader: None,
+            leader_ready: false,
             millis_since_quorum_ack: None,
             last_quorum_acked: None,
             membership_config: Arc::new(StoredMembership::default()),
             replication: None,
             heartbeat: None,
+            snapshot_sending: None,
+            replication_breaker: None,
+            replication_status: None,
         }
     }
 }
@@ -191,8 +255,27 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     pub last_log: Option<LogId<C::NodeId>>,
     pub last_applied: Option<LogId<C::NodeId>>,
     pub snapshot: Option<LogId<C::NodeId>>,
+
+    /// Whether a snapshot is being built on this node.
+    pub snapshot_building: bool,
+
+    /// The progress of the snapshot being received from the leader, if any.
+    ///
+    /// It is only reported when the snapshot is received in chunks with
+    /// [`Raft::install_snapshot()`](crate::Raft::install_snapshot).
+    pub snapshot_receiving: Option<SnapshotTransfer<C>>,
+
     pub purged: Option<LogId<C::NodeId>>,
 
+    /// The number of calls to the log store and the state machine that took longer than
+    /// [`Config::slow_io_threshold`](crate::Config::slow_io_threshold).
+    pub slow_io: u64,
+
+    /// The number of snapshots partially received from the leader and dropped, because no chunk
+    /// is received within
+    /// [`Config::snapshot_receive_idle_timeout`](crate::Config::snapshot_receive_idle_timeout).
+    pub snapshot_receive_timeouts: u64,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -233,6 +316,22 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// This duration can be used by applications to guess if a follwer/learner
     /// node is offline, longer duration suggests higher possibility of that.
     pub heartbeat: Option<HeartbeatMetrics<C>>,
+
+    /// The followers and learners a snapshot is being sent to, and the progress of each of these
+    /// transfers. It is Some() only when this node is leader.
+    pub snapshot_sending: Option<SnapshotSendingMetrics<C>>,
+
+    /// The followers and learners whose replication circuit breaker is open, i.e., the replication
+    /// to them has failed [`Config::replication_breaker_threshold`] times in a row.
+    /// It is Some() only when this node is leader.
+    ///
+    /// [`Config::replication_breaker_threshold`]: crate::Config::replication_breaker_threshold
+    pub replication_breaker: Option<ReplicationBreakerMetrics<C>>,
+
+    /// The status of the replication to each follower and learner: the matched log, the next log
+    /// to send, the number of logs in flight, the last contact time and what the replication is
+    /// doing. It is Some() only when this node is leader.
+    pub replication_status: Option<ReplicationStatusMetrics<C>>,
 }
 
 impl<C> fmt::Display for RaftDataMetrics<C>
@@ -243,11 +342,15 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}",
+            "last_log:{}, last_applied:{}, snapshot:{}, snapshot_building:{}, snapshot_receiving:{}, purged:{}, slow_io:{}, snapshot_receive_timeouts:{}",
             DisplayOption(&self.last_log),
             DisplayOption(&self.last_applied),
             DisplayOption(&self.snapshot),
+            self.snapshot_building,
+            DisplayOption(&self.snapshot_receiving),
             DisplayOption(&self.purged),
+            self.slow_io,
+            self.snapshot_receive_timeouts,
         )?;
 
         if let Some(quorum_acked) = &self.last_quorum_acked {
@@ -263,11 +366,15 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            ", replication:{{{}}}, heartbeat:{{{}}}",
+            ", replication:{{{}}}, heartbeat:{{{}}}, snapshot_sending:{:?}, replication_breaker:{:?}",
             DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
             DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
+            self.snapshot_sending,
+            self.replication_breaker,
         )?;
 
+        write!(f, ", replication_status:{:?}", self.replication_status)?;
+
         write!(f, "}}")?;
         Ok(())
     }
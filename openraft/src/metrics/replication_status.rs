@@ -0,0 +1,69 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOption;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SerdeInstantOf;
+use crate::RaftTypeConfig;
+
+/// What the replication to a follower or learner is doing.
+#[derive(Debug, Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ReplicationState {
+    /// Logs are being replicated to the target.
+    LineRate,
+
+    /// A snapshot is being sent to the target, because the logs it needs are purged.
+    Snapshotting,
+
+    /// The replication is waiting before sending the next RPC, after an RPC failed or the target
+    /// asked to slow down.
+    BackingOff,
+}
+
+impl fmt::Display for ReplicationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationState::LineRate => write!(f, "LineRate"),
+            ReplicationState::Snapshotting => write!(f, "Snapshotting"),
+            ReplicationState::BackingOff => write!(f, "BackingOff"),
+        }
+    }
+}
+
+/// The status of the replication to a follower or learner, as seen by the leader.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReplicationStatus<C: RaftTypeConfig> {
+    /// The last log id known to be replicated to the target.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// The index of the next log to send to the target, after the data in flight.
+    pub next_index: u64,
+
+    /// The number of logs sent to the target that are not yet acknowledged.
+    pub inflight: u64,
+
+    /// The last time the target acknowledged the leader.
+    pub last_contact: Option<SerdeInstantOf<C>>,
+
+    /// What the replication to the target is doing.
+    pub state: ReplicationState,
+}
+
+impl<C> fmt::Display for ReplicationStatus<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{matched:{}, next_index:{}, inflight:{}, last_contact:{}, state:{}}}",
+            DisplayOption(&self.matched),
+            self.next_index,
+            self.inflight,
+            DisplayOption(&self.last_contact),
+            self.state
+        )
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOption;
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+use crate::SnapshotId;
+
+/// The progress of a snapshot being sent to, or received from, another node.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SnapshotTransfer<C: RaftTypeConfig> {
+    /// The id of the snapshot being transferred.
+    pub snapshot_id: SnapshotId,
+
+    /// The last log id included in the snapshot.
+    pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The number of bytes sent or received so far.
+    pub bytes: u64,
+
+    /// The size of the snapshot in bytes, if it is known.
+    ///
+    /// It is only known by the sender.
+    pub total_bytes: Option<u64>,
+
+    /// The estimated time in milliseconds until the transfer completes, at the average rate since
+    /// it started.
+    ///
+    /// It is `None` if `total_bytes` is unknown or nothing has been transferred yet.
+    pub eta_millis: Option<u64>,
+}
+
+impl<C> fmt::Display for SnapshotTransfer<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{snapshot_id:{}, last_log_id:{}, bytes:{}/{}, eta_millis:{}}}",
+            self.snapshot_id,
+            DisplayOption(&self.last_log_id),
+            self.bytes,
+            DisplayOption(&self.total_bytes),
+            DisplayOption(&self.eta_millis),
+        )
+    }
+}
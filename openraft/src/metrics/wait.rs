@@ -2,11 +2,13 @@ use core::time::Duration;
 use std::collections::BTreeSet;
 
 use futures::FutureExt;
+use openraft_macros::since;
 
 use crate::async_runtime::watch::WatchReceiver;
 use crate::core::ServerState;
 use crate::metrics::Condition;
 use crate::metrics::Metric;
+use crate::metrics::MetricsPredicate;
 use crate::metrics::RaftMetrics;
 use crate::type_config::alias::WatchReceiverOf;
 use crate::type_config::TypeConfigExt;
@@ -39,7 +41,41 @@ where C: RaftTypeConfig
     #[tracing::instrument(level = "trace", skip(self, func), fields(msg=%msg.to_string()))]
     pub async fn metrics<T>(&self, func: T, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError>
     where T: Fn(&RaftMetrics<C>) -> bool + OptionalSend {
-        let timeout_at = C::now() + self.timeout;
+        self.metrics_with_timeout(func, msg, self.timeout).await
+    }
+
+    /// Wait for metrics to satisfy a [`MetricsPredicate`], or timeout.
+    ///
+    /// `timeout` overrides the timeout of this `Wait` if it is not `None`.
+    ///
+    /// For example, to wait up to 1 second until node 1 or node 2 becomes the leader:
+    /// ```ignore
+    /// let p = MetricsPredicate::new("leader is 1", |m| m.current_leader == Some(1))
+    ///     .or(MetricsPredicate::new("leader is 2", |m| m.current_leader == Some(2)));
+    ///
+    /// my_raft.wait(None).until(p, Some(Duration::from_secs(1))).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "trace", skip_all, fields(predicate=%predicate))]
+    pub async fn until(
+        &self,
+        predicate: MetricsPredicate<C>,
+        timeout: Option<Duration>,
+    ) -> Result<RaftMetrics<C>, WaitError> {
+        let timeout = timeout.unwrap_or(self.timeout);
+        self.metrics_with_timeout(|m| predicate.is_satisfied(m), &predicate, timeout).await
+    }
+
+    async fn metrics_with_timeout<T>(
+        &self,
+        func: T,
+        msg: impl ToString,
+        timeout: Duration,
+    ) -> Result<RaftMetrics<C>, WaitError>
+    where
+        T: Fn(&RaftMetrics<C>) -> bool + OptionalSend,
+    {
+        let timeout_at = C::now() + timeout;
 
         let mut rx = self.rx.clone();
         loop {
@@ -55,7 +91,7 @@ where C: RaftTypeConfig
             let now = C::now();
             if now >= timeout_at {
                 return Err(WaitError::Timeout(
-                    self.timeout,
+                    timeout,
                     format!("{} latest: {}", msg.to_string(), latest),
                 ));
             }
@@ -67,7 +103,7 @@ where C: RaftTypeConfig
             futures::select_biased! {
                 _ = delay.fuse() => {
                     tracing::debug!( "id={} timeout wait {:} latest: {}", latest.id, msg.to_string(), latest );
-                    return Err(WaitError::Timeout(self.timeout, format!("{} latest: {}", msg.to_string(), latest)));
+                    return Err(WaitError::Timeout(timeout, format!("{} latest: {}", msg.to_string(), latest)));
                 }
                 changed = rx.changed().fuse() => {
                     match changed {
@@ -107,6 +143,15 @@ where C: RaftTypeConfig
         .await
     }
 
+    /// Wait for this node to become a leader that has committed a log entry of its own term, until
+    /// timeout.
+    ///
+    /// See: [`RaftMetrics::leader_ready`].
+    #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
+    pub async fn leader_ready(&self, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
+        self.metrics(|m| m.leader_ready, &format!("{} .leader_ready", msg.to_string())).await
+    }
+
     /// Wait until applied exactly `want_log`(inclusive) logs or timeout.
     #[deprecated(since = "0.9.0", note = "use `log_index()` and `applied_index()` instead")]
     #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
@@ -208,6 +253,27 @@ where C: RaftTypeConfig
         .await
     }
 
+    /// Block until membership contains exact the expected `learner_ids` or timeout.
+    #[tracing::instrument(level = "trace", skip_all, fields(msg=msg.to_string().as_str()))]
+    pub async fn learner_ids(
+        &self,
+        learner_ids: impl IntoIterator<Item = C::NodeId>,
+        msg: impl ToString,
+    ) -> Result<RaftMetrics<C>, WaitError> {
+        let want = learner_ids.into_iter().collect::<BTreeSet<_>>();
+
+        tracing::debug!("block until learner_ids == {:?}", want);
+
+        self.metrics(
+            |m| {
+                let got = m.membership_config.membership().learner_ids().collect();
+                want == got
+            },
+            &format!("{} .learners == {:?}", msg.to_string(), want),
+        )
+        .await
+    }
+
     /// Wait for `snapshot` to become `snapshot_last_log_id` or timeout.
     #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
     pub async fn snapshot(
@@ -235,7 +301,7 @@ where C: RaftTypeConfig
     /// my_raft.wait(None).ge(Metric::Term(2), "become term 2").await?;
     /// ```
     pub async fn ge(&self, metric: Metric<C>, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
-        self.until(Condition::ge(metric), msg).await
+        self.until_condition(Condition::ge(metric), msg).await
     }
 
     /// Block until a metric becomes equal to the specified value or timeout.
@@ -245,12 +311,16 @@ where C: RaftTypeConfig
     /// my_raft.wait(None).eq(Metric::Term(2), "become term 2").await?;
     /// ```
     pub async fn eq(&self, metric: Metric<C>, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
-        self.until(Condition::eq(metric), msg).await
+        self.until_condition(Condition::eq(metric), msg).await
     }
 
     /// Block until a metric satisfies the specified condition or timeout.
     #[tracing::instrument(level = "trace", skip_all, fields(cond=cond.to_string(), msg=msg.to_string().as_str()))]
-    pub(crate) async fn until(&self, cond: Condition<C>, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
+    pub(crate) async fn until_condition(
+        &self,
+        cond: Condition<C>,
+        msg: impl ToString,
+    ) -> Result<RaftMetrics<C>, WaitError> {
         self.metrics(
             |raft_metrics| match &cond {
                 Condition::GE(expect) => raft_metrics >= expect,
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::metrics::RaftMetrics;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+
+/// The function of a [`MetricsPredicate`].
+trait PredicateFn<C>: Fn(&RaftMetrics<C>) -> bool + OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+}
+
+impl<C, F> PredicateFn<C> for F
+where
+    C: RaftTypeConfig,
+    F: Fn(&RaftMetrics<C>) -> bool + OptionalSend + OptionalSync + 'static,
+{
+}
+
+/// A described condition on [`RaftMetrics`] to wait for with [`Wait::until()`].
+///
+/// Predicates can be composed with [`all()`](Self::all), [`any()`](Self::any),
+/// [`and()`](Self::and) and [`or()`](Self::or), for example, to wait until node 3 is a voter and
+/// at least 10 logs are applied:
+///
+/// ```ignore
+/// let voter = MetricsPredicate::new("node-3 is voter", |m| {
+///     m.membership_config.membership().voter_ids().any(|id| id == 3)
+/// });
+/// let applied = MetricsPredicate::new("applied >= 10", |m| m.last_applied.index() >= Some(10));
+///
+/// raft.wait(None).until(voter.and(applied), Some(Duration::from_secs(1))).await?;
+/// ```
+///
+/// [`Wait::until()`]: crate::metrics::Wait::until
+pub struct MetricsPredicate<C>
+where C: RaftTypeConfig
+{
+    desc: String,
+    func: Box<dyn PredicateFn<C>>,
+}
+
+impl<C> MetricsPredicate<C>
+where C: RaftTypeConfig
+{
+    /// Create a predicate from a function, `desc` is used in the timeout error message.
+    pub fn new(
+        desc: impl ToString,
+        func: impl Fn(&RaftMetrics<C>) -> bool + OptionalSend + OptionalSync + 'static,
+    ) -> Self {
+        Self {
+            desc: desc.to_string(),
+            func: Box::new(func),
+        }
+    }
+
+    /// Create a predicate that is satisfied if all of the `predicates` are satisfied.
+    ///
+    /// It is satisfied if `predicates` is empty.
+    pub fn all(predicates: impl IntoIterator<Item = Self>) -> Self {
+        let predicates = predicates.into_iter().collect::<Vec<_>>();
+        let desc = Self::join(&predicates, " && ");
+
+        Self::new(desc, move |m| predicates.iter().all(|p| p.is_satisfied(m)))
+    }
+
+    /// Create a predicate that is satisfied if any of the `predicates` is satisfied.
+    ///
+    /// It is not satisfied if `predicates` is empty.
+    pub fn any(predicates: impl IntoIterator<Item = Self>) -> Self {
+        let predicates = predicates.into_iter().collect::<Vec<_>>();
+        let desc = Self::join(&predicates, " || ");
+
+        Self::new(desc, move |m| predicates.iter().any(|p| p.is_satisfied(m)))
+    }
+
+    /// Create a predicate that is satisfied if both `self` and `other` are satisfied.
+    pub fn and(self, other: Self) -> Self {
+        Self::all([self, other])
+    }
+
+    /// Create a predicate that is satisfied if either `self` or `other` is satisfied.
+    pub fn or(self, other: Self) -> Self {
+        Self::any([self, other])
+    }
+
+    /// Return `true` if the metrics satisfies this predicate.
+    pub fn is_satisfied(&self, metrics: &RaftMetrics<C>) -> bool {
+        (self.func)(metrics)
+    }
+
+    fn join(predicates: &[Self], sep: &str) -> String {
+        let descs = predicates.iter().map(|p| p.desc.as_str()).collect::<Vec<_>>();
+        format!("({})", descs.join(sep))
+    }
+}
+
+impl<C> fmt::Display for MetricsPredicate<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.desc)
+    }
+}
+
+impl<C> fmt::Debug for MetricsPredicate<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsPredicate").field("desc", &self.desc).finish()
+    }
+}
@@ -8,6 +8,7 @@ use tokio::time::sleep;
 use crate::core::ServerState;
 use crate::engine::testing::UTConfig;
 use crate::log_id::LogIdOptionExt;
+use crate::metrics::MetricsPredicate;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
 use crate::testing::log_id;
@@ -109,6 +110,29 @@ async fn test_wait() -> anyhow::Result<()> {
         );
     }
 
+    {
+        // wait for learners
+        let (init, w, tx) = init_wait_test::<UTConfig>();
+
+        let h = tokio::spawn(async move {
+            sleep(Duration::from_millis(10)).await;
+            let mut update = init.clone();
+            update.membership_config = Arc::new(StoredMembership::new(
+                None,
+                Membership::new(vec![btreeset! {1,2}], btreemap! {3=>()}),
+            ));
+            let rst = tx.send(update);
+            assert!(rst.is_ok());
+        });
+        let got = w.learner_ids([3], "learners").await?;
+        h.await?;
+
+        assert_eq!(
+            vec![3],
+            got.membership_config.membership().learner_ids().collect::<Vec<_>>()
+        );
+    }
+
     tracing::info!("--- wait for snapshot, Ok");
     {
         let (init, w, tx) = init_wait_test::<UTConfig>();
@@ -243,6 +267,47 @@ async fn test_wait_purged() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_wait_until_predicate() -> anyhow::Result<()> {
+    let leader_3 = || MetricsPredicate::<UTConfig>::new("leader is 3", |m| m.current_leader == Some(3));
+    let applied_2 = || MetricsPredicate::<UTConfig>::new("applied >= 2", |m| m.last_applied.index() >= Some(2));
+    let leader_4 = || MetricsPredicate::<UTConfig>::new("leader is 4", |m| m.current_leader == Some(4));
+
+    assert_eq!(
+        "((leader is 3 && applied >= 2) || leader is 4)",
+        leader_3().and(applied_2()).or(leader_4()).to_string()
+    );
+
+    let (init, w, tx) = init_wait_test::<UTConfig>();
+
+    let h = tokio::spawn(async move {
+        sleep(Duration::from_millis(10)).await;
+        let mut update = init.clone();
+        update.current_leader = Some(3);
+        update.last_applied = Some(log_id(1, 0, 2));
+        let rst = tx.send(update);
+        assert!(rst.is_ok());
+        // Keep the channel open, otherwise the error is shutdown instead of timeout.
+        tx
+    });
+
+    let got = w.until(MetricsPredicate::all([leader_3(), applied_2()]), None).await?;
+    let _tx = h.await?;
+    assert_eq!(Some(3), got.current_leader);
+
+    let got = w.until(MetricsPredicate::any([leader_4(), applied_2()]), None).await?;
+    assert_eq!(Some(3), got.current_leader);
+
+    // The timeout is overridden.
+    let res = w.until(leader_3().and(leader_4()), Some(Duration::from_millis(10))).await;
+    match res {
+        Err(WaitError::Timeout(t, _)) => assert_eq!(Duration::from_millis(10), t),
+        _ => panic!("expect WaitError::Timeout"),
+    }
+
+    Ok(())
+}
+
 pub(crate) type InitResult<C> = (RaftMetrics<C>, Wait<C>, WatchSenderOf<C, RaftMetrics<C>>);
 
 /// Build a initial state for testing of Wait:
@@ -261,13 +326,21 @@ where C: RaftTypeConfig {
         purged: None,
 
         current_leader: None,
+        leader_ready: false,
         millis_since_quorum_ack: None,
         last_quorum_acked: None,
         membership_config: Arc::new(StoredMembership::new(None, Membership::new(vec![btreeset! {}], None))),
         heartbeat: None,
 
         snapshot: None,
+        snapshot_building: false,
+        snapshot_receiving: None,
+        slow_io: 0,
+        snapshot_receive_timeouts: 0,
         replication: None,
+        snapshot_sending: None,
+        replication_breaker: None,
+        replication_status: None,
     };
     let (tx, rx) = C::watch_channel(init.clone());
     let w = Wait {
@@ -0,0 +1,185 @@
+/// The RPC protocol version and the optional features a node supports.
+///
+/// Before replicating to a target, a leader asks for the target's capabilities with
+/// [`RaftNetworkV2::capabilities()`], and only uses a feature both of them support. This way
+/// nodes of different versions, or built with different feature flags, can work together during
+/// a rolling upgrade.
+///
+/// Every RPC request carries the protocol version of the sender, e.g.,
+/// [`AppendEntriesRequest::protocol_version`]. A leader does not replicate to a target whose
+/// version is not compatible, see [`is_compatible()`](Self::is_compatible).
+///
+/// [`AppendEntriesRequest::protocol_version`]: crate::raft::AppendEntriesRequest::protocol_version
+/// [`RaftNetworkV2::capabilities()`]: crate::network::v2::RaftNetworkV2::capabilities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Capabilities {
+    /// The version of the RPC messages.
+    ///
+    /// `0` means the version is unknown, e.g., the target does not support negotiation.
+    pub protocol_version: u32,
+
+    /// The oldest version of the RPC messages of a peer this node can work with.
+    ///
+    /// `0` means the version is unknown.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_protocol_version: u32,
+
+    /// Whether compressed entries in an AppendEntries RPC can be decompressed.
+    pub entries_compression: bool,
+
+    /// Whether a compressed snapshot can be decompressed.
+    pub snapshot_compression: bool,
+}
+
+impl Capabilities {
+    /// The version of the RPC messages of this build.
+    ///
+    /// It is increased when a change to the RPC messages requires the peer to understand it:
+    ///
+    /// - 1: the initial version.
+    /// - 2: a follower may reply [`AppendEntriesResponse::Backpressure`].
+    ///
+    /// [`AppendEntriesResponse::Backpressure`]: crate::raft::AppendEntriesResponse::Backpressure
+    pub const PROTOCOL_VERSION: u32 = 2;
+
+    /// The oldest version of the RPC messages of a peer this build can work with.
+    ///
+    /// It is increased when the support of an old version is dropped.
+    pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+    /// The capabilities of this build.
+    pub fn local() -> Self {
+        Self {
+            protocol_version: Self::PROTOCOL_VERSION,
+            min_protocol_version: Self::MIN_PROTOCOL_VERSION,
+            entries_compression: cfg!(feature = "entries-compression"),
+            snapshot_compression: cfg!(feature = "snapshot-compression"),
+        }
+    }
+
+    /// The capabilities of a target that does not support negotiation.
+    ///
+    /// Snapshot compression is assumed not to be supported: a receiver built before it ignores
+    /// the `compressed` flag of a chunk, and would install the compressed bytes as the snapshot.
+    pub(crate) fn unknown(entries_compression: bool) -> Self {
+        Self {
+            protocol_version: 0,
+            min_protocol_version: 0,
+            entries_compression,
+            snapshot_compression: false,
+        }
+    }
+
+    /// Return the capabilities supported by both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            protocol_version: std::cmp::min(self.protocol_version, other.protocol_version),
+            min_protocol_version: std::cmp::max(self.min_protocol_version, other.min_protocol_version),
+            entries_compression: self.entries_compression && other.entries_compression,
+            snapshot_compression: self.snapshot_compression && other.snapshot_compression,
+        }
+    }
+
+    /// Whether a node with capabilities `self` can work with a peer with capabilities `other`.
+    ///
+    /// Each of them must be at least the oldest version the other one can work with. An unknown
+    /// version, i.e., `0`, is a node built before the version is introduced, and is compatible
+    /// with version 1.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        fn accepts(min: u32, version: u32) -> bool {
+            std::cmp::max(version, 1) >= min
+        }
+
+        accepts(self.min_protocol_version, other.protocol_version)
+            && accepts(other.min_protocol_version, self.protocol_version)
+    }
+
+    /// Whether a peer of `protocol_version` understands [`AppendEntriesResponse::Backpressure`].
+    ///
+    /// [`AppendEntriesResponse::Backpressure`]: crate::raft::AppendEntriesResponse::Backpressure
+    pub(crate) fn supports_backpressure(protocol_version: u32) -> bool {
+        protocol_version >= 2
+    }
+
+    /// Whether this node can handle a request sent by a peer of `protocol_version`.
+    pub(crate) fn accepts(&self, protocol_version: u32) -> bool {
+        self.is_compatible(&Self {
+            protocol_version,
+            ..Self::unknown(false)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capabilities;
+
+    #[test]
+    fn test_capabilities_intersect() {
+        let a = Capabilities {
+            protocol_version: 2,
+            min_protocol_version: 1,
+            entries_compression: true,
+            snapshot_compression: false,
+        };
+        let b = Capabilities {
+            protocol_version: 1,
+            min_protocol_version: 0,
+            entries_compression: true,
+            snapshot_compression: true,
+        };
+
+        assert_eq!(
+            Capabilities {
+                protocol_version: 1,
+                min_protocol_version: 1,
+                entries_compression: true,
+                snapshot_compression: false,
+            },
+            a.intersect(&b)
+        );
+        assert_eq!(a.intersect(&b), b.intersect(&a));
+    }
+
+    #[test]
+    fn test_capabilities_is_compatible() {
+        let v = |protocol_version, min_protocol_version| Capabilities {
+            protocol_version,
+            min_protocol_version,
+            ..Default::default()
+        };
+
+        let local = v(2, 2);
+
+        assert!(local.is_compatible(&v(2, 1)));
+        assert!(local.is_compatible(&v(3, 2)));
+
+        assert!(!local.is_compatible(&v(1, 1)), "peer is too old");
+        assert!(!local.is_compatible(&v(4, 3)), "peer requires a newer version");
+        assert!(!local.is_compatible(&v(0, 0)), "unknown version is version 1");
+
+        assert!(v(1, 1).is_compatible(&v(0, 0)));
+        assert!(v(1, 1).accepts(0));
+        assert!(!local.accepts(1));
+    }
+
+    #[test]
+    fn test_capabilities_unknown() {
+        let c = Capabilities::unknown(true);
+        assert_eq!(0, c.protocol_version);
+        assert!(c.entries_compression);
+        assert!(
+            !c.snapshot_compression,
+            "an unknown target may not decompress a snapshot"
+        );
+    }
+
+    #[test]
+    fn test_capabilities_supports_backpressure() {
+        assert!(!Capabilities::supports_backpressure(0));
+        assert!(!Capabilities::supports_backpressure(1));
+        assert!(Capabilities::supports_backpressure(2));
+        assert!(Capabilities::supports_backpressure(Capabilities::PROTOCOL_VERSION));
+    }
+}
@@ -0,0 +1,86 @@
+//! Gzip compression of snapshot chunks and AppendEntries payloads, enabled by feature flag
+//! `snapshot-compression` or `entries-compression`.
+
+use std::io;
+
+/// Compress a snapshot chunk or a serialized AppendEntries payload with gzip.
+///
+/// An application network implementation calls it to compress the entries of an AppendEntries
+/// RPC, if [`RPCOption::entries_compression_threshold()`] is reached.
+///
+/// It returns an error if neither feature `snapshot-compression` nor `entries-compression` is
+/// enabled.
+///
+/// [`RPCOption::entries_compression_threshold()`]: crate::network::RPCOption::entries_compression_threshold
+pub fn compress(data: &[u8]) -> Result<Vec<u8>, io::Error> {
+    #[cfg(any(feature = "snapshot-compression", feature = "entries-compression"))]
+    {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()
+    }
+
+    #[cfg(not(any(feature = "snapshot-compression", feature = "entries-compression")))]
+    {
+        let _ = data;
+        Err(not_enabled())
+    }
+}
+
+/// Decompress data compressed by [`compress()`].
+///
+/// It returns an error if neither feature `snapshot-compression` nor `entries-compression` is
+/// enabled.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, io::Error> {
+    #[cfg(any(feature = "snapshot-compression", feature = "entries-compression"))]
+    {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+        flate2::read::GzDecoder::new(data).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    #[cfg(not(any(feature = "snapshot-compression", feature = "entries-compression")))]
+    {
+        let _ = data;
+        Err(not_enabled())
+    }
+}
+
+#[cfg(not(any(feature = "snapshot-compression", feature = "entries-compression")))]
+fn not_enabled() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "feature `snapshot-compression` or `entries-compression` is not enabled",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compress;
+    use super::decompress;
+
+    #[cfg(any(feature = "snapshot-compression", feature = "entries-compression"))]
+    #[test]
+    fn test_compress_decompress() -> anyhow::Result<()> {
+        let data = vec![7u8; 4096];
+
+        let compressed = compress(&data)?;
+        assert!(compressed.len() < data.len());
+        assert_eq!(data, decompress(&compressed)?);
+
+        assert!(decompress(b"not gzip").is_err());
+
+        Ok(())
+    }
+
+    #[cfg(not(any(feature = "snapshot-compression", feature = "entries-compression")))]
+    #[test]
+    fn test_compression_not_enabled() {
+        assert_eq!(std::io::ErrorKind::Unsupported, compress(b"foo").unwrap_err().kind());
+        assert_eq!(std::io::ErrorKind::Unsupported, decompress(b"foo").unwrap_err().kind());
+    }
+}
@@ -0,0 +1,328 @@
+//! Wraps the application's network to collect [`RaftNetworkMetrics`].
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::async_runtime::watch::WatchSender;
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
+use crate::error::RPCError;
+use crate::error::RaftError;
+use crate::error::ReplicationClosed;
+use crate::error::StreamingError;
+use crate::metrics::RaftNetworkMetrics;
+use crate::network::v2::RaftNetworkV2;
+use crate::network::Backoff;
+use crate::network::Capabilities;
+use crate::network::RPCOption;
+use crate::network::RPCTypes;
+use crate::network::RaftNetworkFactory;
+use crate::network::SnapshotTransform;
+use crate::network::TraceContext;
+use crate::raft::message::TransferLeaderRequest;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::ClientWriteResponse;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::raft::SnapshotResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
+use crate::type_config::alias::WatchSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::Instant;
+use crate::LogId;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// Records the RPCs sent by all of the network clients of a Raft node.
+pub(crate) struct NetworkMetricsRecorder<C>
+where C: RaftTypeConfig
+{
+    tx: Arc<Mutex<WatchSenderOf<C, RaftNetworkMetrics<C>>>>,
+}
+
+impl<C> Clone for NetworkMetricsRecorder<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone() }
+    }
+}
+
+impl<C> NetworkMetricsRecorder<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(tx: WatchSenderOf<C, RaftNetworkMetrics<C>>) -> Self {
+        Self {
+            tx: Arc::new(Mutex::new(tx)),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RaftNetworkMetrics<C>)) {
+        let Ok(tx) = self.tx.lock() else {
+            return;
+        };
+
+        tx.send_if_modified(|m| {
+            f(m);
+            true
+        });
+    }
+
+    /// Run an RPC future and record its result.
+    ///
+    /// If the future is dropped before it returns, e.g., the caller's timeout expired, it is
+    /// recorded as a timeout.
+    async fn record<T, E>(
+        &self,
+        target: C::NodeId,
+        typ: RPCTypes,
+        entries: u64,
+        fu: impl Future<Output = Result<T, E>>,
+        is_timeout: impl FnOnce(&E) -> bool,
+    ) -> Result<T, E> {
+        self.update(|m| {
+            let x = m.get_mut(target, typ);
+            x.sent += 1;
+            x.entries += entries;
+        });
+
+        let mut guard = CancelGuard {
+            recorder: self,
+            target,
+            typ,
+            done: false,
+        };
+
+        let start = C::now();
+        let res = fu.await;
+        let latency = start.elapsed();
+        guard.done = true;
+
+        let (is_err, is_timeout) = match &res {
+            Ok(_) => (false, false),
+            Err(e) => (true, is_timeout(e)),
+        };
+
+        self.update(|m| {
+            let x = m.get_mut(target, typ);
+            if is_err {
+                x.errors += 1;
+            }
+            if is_timeout {
+                x.timeouts += 1;
+            }
+            x.latency.record(latency);
+        });
+
+        res
+    }
+}
+
+/// Records an RPC as timed out if it is dropped before it returns.
+struct CancelGuard<'a, C>
+where C: RaftTypeConfig
+{
+    recorder: &'a NetworkMetricsRecorder<C>,
+    target: C::NodeId,
+    typ: RPCTypes,
+    done: bool,
+}
+
+impl<'a, C> Drop for CancelGuard<'a, C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        self.recorder.update(|m| {
+            m.get_mut(self.target, self.typ).timeouts += 1;
+        });
+    }
+}
+
+/// A [`RaftNetworkFactory`] that creates clients recording their RPCs in [`RaftNetworkMetrics`].
+///
+/// The clients also inject the tracing context into every RPC, if the application's factory
+/// provides a [`TraceContext`].
+pub(crate) struct MeteredNetworkFactory<C, N>
+where C: RaftTypeConfig
+{
+    inner: N,
+    recorder: NetworkMetricsRecorder<C>,
+    trace_context: Option<Arc<dyn TraceContext>>,
+}
+
+impl<C, N> MeteredNetworkFactory<C, N>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(inner: N, recorder: NetworkMetricsRecorder<C>) -> Self
+    where N: RaftNetworkFactory<C> {
+        let trace_context = inner.trace_context();
+        Self {
+            inner,
+            recorder,
+            trace_context,
+        }
+    }
+}
+
+impl<C, N> RaftNetworkFactory<C> for MeteredNetworkFactory<C, N>
+where
+    C: RaftTypeConfig,
+    N: RaftNetworkFactory<C>,
+{
+    type Network = MeteredNetwork<C, N::Network>;
+
+    async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network {
+        MeteredNetwork {
+            inner: self.inner.new_client(target, node).await,
+            target,
+            recorder: self.recorder.clone(),
+            trace_context: self.trace_context.clone(),
+        }
+    }
+
+    fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+        self.inner.snapshot_transform()
+    }
+
+    fn trace_context(&self) -> Option<Arc<dyn TraceContext>> {
+        self.trace_context.clone()
+    }
+}
+
+/// Forwards every call to the application's network client, and records the RPCs.
+pub(crate) struct MeteredNetwork<C, Net>
+where C: RaftTypeConfig
+{
+    inner: Net,
+    target: C::NodeId,
+    recorder: NetworkMetricsRecorder<C>,
+    trace_context: Option<Arc<dyn TraceContext>>,
+}
+
+impl<C, Net> MeteredNetwork<C, Net>
+where C: RaftTypeConfig
+{
+    /// Fill the tracing context of the current span into `option`.
+    fn with_trace_context(&self, mut option: RPCOption) -> RPCOption {
+        if let Some(t) = &self.trace_context {
+            option.trace_context = t.inject(&tracing::Span::current());
+        }
+        option
+    }
+}
+
+fn is_rpc_timeout<C, E>(e: &RPCError<C, E>) -> bool
+where
+    C: RaftTypeConfig,
+    E: std::error::Error,
+{
+    matches!(e, RPCError::Timeout(_))
+}
+
+impl<C, Net> RaftNetworkV2<C> for MeteredNetwork<C, Net>
+where
+    C: RaftTypeConfig,
+    Net: RaftNetworkV2<C>,
+{
+    async fn append_entries(
+        &mut self,
+        rpc: AppendEntriesRequest<C>,
+        option: RPCOption,
+    ) -> Result<AppendEntriesResponse<C>, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        let entries = rpc.entries.len() as u64;
+        let fu = self.inner.append_entries(rpc, option);
+        self.recorder.record(self.target, RPCTypes::AppendEntries, entries, fu, is_rpc_timeout).await
+    }
+
+    async fn heartbeat(
+        &mut self,
+        rpc: HeartbeatRequest<C>,
+        option: RPCOption,
+    ) -> Result<HeartbeatResponse<C>, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.heartbeat(rpc, option);
+        self.recorder.record(self.target, RPCTypes::Heartbeat, 0, fu, is_rpc_timeout).await
+    }
+
+    async fn vote(&mut self, rpc: VoteRequest<C>, option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.vote(rpc, option);
+        self.recorder.record(self.target, RPCTypes::Vote, 0, fu, is_rpc_timeout).await
+    }
+
+    async fn pre_vote(&mut self, rpc: VoteRequest<C>, option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.pre_vote(rpc, option);
+        self.recorder.record(self.target, RPCTypes::PreVote, 0, fu, is_rpc_timeout).await
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote<C::NodeId>,
+        snapshot: Snapshot<C>,
+        cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, StreamingError<C>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.full_snapshot(vote, snapshot, cancel, option);
+        let is_timeout = |e: &StreamingError<C>| matches!(e, StreamingError::Timeout(_));
+        self.recorder.record(self.target, RPCTypes::InstallSnapshot, 0, fu, is_timeout).await
+    }
+
+    async fn target_snapshot_signature(
+        &mut self,
+        option: RPCOption,
+    ) -> Result<Option<SnapshotSignature<C>>, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        self.inner.target_snapshot_signature(option).await
+    }
+
+    async fn entries_compression_supported(&mut self, option: RPCOption) -> Result<bool, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        self.inner.entries_compression_supported(option).await
+    }
+
+    async fn capabilities(&mut self, option: RPCOption) -> Result<Capabilities, RPCError<C>> {
+        let option = self.with_trace_context(option);
+        self.inner.capabilities(option).await
+    }
+
+    async fn read_index(
+        &mut self,
+        option: RPCOption,
+    ) -> Result<Option<LogId<C::NodeId>>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.read_index(option);
+        self.recorder.record(self.target, RPCTypes::ReadIndex, 0, fu, is_rpc_timeout).await
+    }
+
+    async fn transfer_leader(&mut self, req: TransferLeaderRequest<C>, option: RPCOption) -> Result<(), RPCError<C>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.transfer_leader(req, option);
+        self.recorder.record(self.target, RPCTypes::TransferLeader, 0, fu, is_rpc_timeout).await
+    }
+
+    async fn forward_client_write(
+        &mut self,
+        app_data: C::D,
+        option: RPCOption,
+    ) -> Result<ClientWriteResponse<C>, RPCError<C, RaftError<C, ClientWriteError<C>>>> {
+        let option = self.with_trace_context(option);
+        let fu = self.inner.forward_client_write(app_data, option);
+        self.recorder.record(self.target, RPCTypes::ClientWrite, 0, fu, is_rpc_timeout).await
+    }
+
+    fn backoff(&self) -> Backoff {
+        self.inner.backoff()
+    }
+}
@@ -1,8 +1,16 @@
 //! The Raft network interface.
 
 mod backoff;
+mod capabilities;
+mod compression;
+pub(crate) mod metered;
 mod rpc_option;
 mod rpc_type;
+// The checksum is only used by the chunked snapshot transport that requires `tokio-rt`.
+#[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+mod snapshot_checksum;
+mod snapshot_transform;
+mod trace_context;
 
 pub mod v1;
 pub mod v2;
@@ -10,7 +18,13 @@ pub mod v2;
 pub mod snapshot_transport;
 
 pub use backoff::Backoff;
+pub use capabilities::Capabilities;
+pub use compression::compress;
+pub use compression::decompress;
 pub use rpc_option::RPCOption;
+pub(crate) use rpc_option::ReportSnapshotProgress;
 pub use rpc_type::RPCTypes;
+pub use snapshot_transform::SnapshotTransform;
+pub use trace_context::TraceContext;
 pub use v1::RaftNetwork;
 pub use v1::RaftNetworkFactory;
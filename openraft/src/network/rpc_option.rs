@@ -1,5 +1,16 @@
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::OptionalSend;
+use crate::OptionalSync;
+
+/// Receives the progress of sending a snapshot, to report it in the metrics.
+pub(crate) trait ReportSnapshotProgress: fmt::Debug + OptionalSend + OptionalSync {
+    /// `sent` bytes of the `total` bytes of the snapshot have been sent.
+    fn report(&self, sent: u64, total: u64);
+}
+
 /// An additional argument to the [`RaftNetwork`] methods to allow applications to customize
 /// networking behaviors.
 ///
@@ -13,6 +24,21 @@ pub struct RPCOption {
 
     /// The size of the snapshot chunk.
     pub(crate) snapshot_chunk_size: Option<usize>,
+
+    /// The max rate in bytes per second to transmit a snapshot.
+    pub(crate) snapshot_max_bytes_per_sec: Option<u64>,
+
+    /// Whether to compress snapshot chunks.
+    pub(crate) snapshot_compression: bool,
+
+    /// The size of the entries at or above which to compress them.
+    pub(crate) entries_compression_threshold: Option<u64>,
+
+    /// The tracing context of the sender.
+    pub(crate) trace_context: Vec<u8>,
+
+    /// Receives the progress of sending a snapshot.
+    pub(crate) snapshot_progress: Option<Arc<dyn ReportSnapshotProgress>>,
 }
 
 impl RPCOption {
@@ -20,6 +46,11 @@ impl RPCOption {
         Self {
             hard_ttl,
             snapshot_chunk_size: None,
+            snapshot_max_bytes_per_sec: None,
+            snapshot_compression: false,
+            entries_compression_threshold: None,
+            trace_context: vec![],
+            snapshot_progress: None,
         }
     }
 
@@ -51,4 +82,56 @@ impl RPCOption {
     pub fn snapshot_chunk_size(&self) -> Option<usize> {
         self.snapshot_chunk_size
     }
+
+    /// Get the max rate in bytes per second at which a snapshot should be transmitted.
+    ///
+    /// `None` means no limit. An application defined snapshot transport should throttle the
+    /// transmission to honor this rate, as the default chunked transport does.
+    pub fn snapshot_max_bytes_per_sec(&self) -> Option<u64> {
+        self.snapshot_max_bytes_per_sec
+    }
+
+    /// Whether snapshot chunks should be compressed for transport.
+    pub fn snapshot_compression(&self) -> bool {
+        self.snapshot_compression
+    }
+
+    /// Get the size in bytes of the serialized entries of an AppendEntries RPC, at or above which
+    /// they should be compressed for transport.
+    ///
+    /// It is `Some` only if [`Config::entries_compression_threshold`] is set and the target
+    /// reported it can decompress entries via
+    /// [`RaftNetworkV2::entries_compression_supported()`].
+    ///
+    /// [`Config::entries_compression_threshold`]: crate::Config::entries_compression_threshold
+    /// [`RaftNetworkV2::entries_compression_supported()`]: crate::network::v2::RaftNetworkV2::entries_compression_supported
+    pub fn entries_compression_threshold(&self) -> Option<u64> {
+        self.entries_compression_threshold
+    }
+
+    /// Get the tracing context of the span that sends this RPC, to send along with the request.
+    ///
+    /// It is filled by Openraft with [`TraceContext::inject()`] if
+    /// [`RaftNetworkFactory::trace_context()`] returns one, otherwise it is empty. The receiver
+    /// restores a span from it with [`Raft::trace_span()`].
+    ///
+    /// [`TraceContext::inject()`]: crate::network::TraceContext::inject
+    /// [`RaftNetworkFactory::trace_context()`]: crate::network::RaftNetworkFactory::trace_context
+    /// [`Raft::trace_span()`]: crate::Raft::trace_span
+    pub fn trace_context(&self) -> &[u8] {
+        &self.trace_context
+    }
+
+    /// Report that `sent` bytes of the `total` bytes of a snapshot have been sent.
+    ///
+    /// The progress is shown in [`RaftMetrics::snapshot_sending`]. An application defined snapshot
+    /// transport should call it after sending each chunk, as the default chunked transport does.
+    /// It does nothing if this option is not for sending a snapshot.
+    ///
+    /// [`RaftMetrics::snapshot_sending`]: crate::metrics::RaftMetrics::snapshot_sending
+    pub fn report_snapshot_progress(&self, sent: u64, total: u64) {
+        if let Some(p) = &self.snapshot_progress {
+            p.report(sent, total);
+        }
+    }
 }
@@ -1,14 +1,18 @@
 use std::fmt;
 
 #[derive(Debug, Clone, Copy)]
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
 #[derive(Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum RPCTypes {
     Vote,
+    PreVote,
     AppendEntries,
+    Heartbeat,
     InstallSnapshot,
     TransferLeader,
+    ReadIndex,
+    ClientWrite,
 }
 
 impl fmt::Display for RPCTypes {
@@ -0,0 +1,75 @@
+//! CRC-32 checksum of snapshot data, used to detect corruption during chunked snapshot transport.
+
+/// Lookup table of the reflected CRC-32(IEEE) polynomial `0xEDB88320`.
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+/// An incremental CRC-32 hasher.
+///
+/// Snapshot data is fed into it chunk by chunk, in the order of offset.
+#[derive(Debug, Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub(crate) struct Crc32 {
+    state: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc32 {
+    pub(crate) fn new() -> Self {
+        Self { state: 0xFFFF_FFFF }
+    }
+
+    /// Feed the next segment of data.
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        for b in data {
+            let i = ((self.state ^ (*b as u32)) & 0xFF) as usize;
+            self.state = TABLE[i] ^ (self.state >> 8);
+        }
+    }
+
+    /// Return the checksum of all data fed so far.
+    pub(crate) fn finalize(&self) -> u32 {
+        self.state ^ 0xFFFF_FFFF
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Crc32;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(0, Crc32::new().finalize());
+
+        let mut c = Crc32::new();
+        c.update(b"123456789");
+        assert_eq!(0xCBF4_3926, c.finalize());
+
+        // Feeding data in several segments produces the same checksum.
+        let mut c = Crc32::new();
+        c.update(b"1234");
+        c.update(b"");
+        c.update(b"56789");
+        assert_eq!(0xCBF4_3926, c.finalize());
+    }
+}
@@ -0,0 +1,33 @@
+//! Transform snapshot chunks before sending and after receiving, e.g., to encrypt them.
+
+use std::io;
+
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::SnapshotSegmentId;
+
+/// Transforms the bytes of every snapshot chunk sent by the chunk based snapshot transport.
+///
+/// The sender calls [`encode()`](Self::encode) on every chunk, after compressing it, before
+/// sending it to the target. The receiver calls [`decode()`](Self::decode) on every received
+/// chunk, before decompressing and writing it to `SnapshotData`.
+///
+/// It can be used to encrypt snapshot data in transit, without implementing a customized
+/// snapshot transport. Both ends must use the same transform:
+/// the sender gets it from [`RaftNetwork::snapshot_transform()`] and the receiver gets it from
+/// [`RaftNetworkFactory::snapshot_transform()`], or passes it explicitly to
+/// [`SnapshotTransport::receive_snapshot_with_transform()`].
+///
+/// `segment` identifies the chunk being transformed. It can be used, for example, to derive a
+/// unique nonce for every chunk. A chunk may be encoded more than once if it is re-sent.
+///
+/// [`RaftNetwork::snapshot_transform()`]: crate::network::RaftNetwork::snapshot_transform
+/// [`RaftNetworkFactory::snapshot_transform()`]: crate::network::RaftNetworkFactory::snapshot_transform
+/// [`SnapshotTransport::receive_snapshot_with_transform()`]: crate::network::snapshot_transport::SnapshotTransport::receive_snapshot_with_transform
+pub trait SnapshotTransform: OptionalSend + OptionalSync + 'static {
+    /// Transform a chunk to send.
+    fn encode(&self, segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error>;
+
+    /// Restore a received chunk that is transformed by [`encode()`](Self::encode).
+    fn decode(&self, segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error>;
+}
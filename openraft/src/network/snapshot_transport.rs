@@ -10,6 +10,7 @@ mod tokio_rt {
     use std::io::SeekFrom;
     use std::time::Duration;
 
+    use futures::future::Either;
     use futures::FutureExt;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
@@ -23,18 +24,31 @@ mod tokio_rt {
     use crate::error::RPCError;
     use crate::error::RaftError;
     use crate::error::ReplicationClosed;
+    use crate::error::SnapshotChecksumMismatch;
+    use crate::error::SnapshotDecode;
+    use crate::error::SnapshotDecompress;
+    use crate::error::SnapshotMismatch;
     use crate::error::StreamingError;
+    use crate::error::Unreachable;
+    use crate::network::compress;
+    use crate::network::decompress;
+    use crate::network::snapshot_checksum::Crc32;
+    use crate::network::Capabilities;
     use crate::network::RPCOption;
+    use crate::network::SnapshotTransform;
     use crate::raft::InstallSnapshotRequest;
     use crate::raft::SnapshotResponse;
     use crate::storage::Snapshot;
     use crate::type_config::TypeConfigExt;
     use crate::ErrorSubject;
     use crate::ErrorVerb;
+    use crate::Instant;
     use crate::OptionalSend;
     use crate::Raft;
     use crate::RaftNetwork;
     use crate::RaftTypeConfig;
+    use crate::SnapshotId;
+    use crate::SnapshotSegmentId;
     use crate::StorageError;
     use crate::ToStorageResult;
     use crate::Vote;
@@ -58,6 +72,18 @@ mod tokio_rt {
             let mut offset = 0;
             let end = snapshot.snapshot.seek(SeekFrom::End(0)).await.sto_res(subject_verb)?;
 
+            // For throttling: the time the transmission started and the bytes sent since then.
+            let start = C::now();
+            let mut sent_bytes = 0u64;
+
+            // Checksum of the data in range `[0, offset)`.
+            let mut checksum = Crc32::new();
+
+            // It is turned off if the target can not decompress a chunk.
+            let mut compression = option.snapshot_compression();
+
+            let transform = net.snapshot_transform();
+
             let mut c = std::pin::pin!(cancel);
             loop {
                 // If canceled, return at once
@@ -70,6 +96,8 @@ mod tokio_rt {
                 // Because network implementation does not yield.
                 C::sleep(Duration::from_millis(1)).await;
 
+                option.report_snapshot_progress(offset, end);
+
                 snapshot.snapshot.seek(SeekFrom::Start(offset)).await.sto_res(subject_verb)?;
 
                 // Safe unwrap(): this function is called only by default implementation of
@@ -86,12 +114,48 @@ mod tokio_rt {
                 let n_read = buf.len();
 
                 let done = (offset + n_read as u64) == end;
+
+                // The checksum including this chunk; It is committed only when this chunk is
+                // successfully sent, because a chunk may be re-sent upon error.
+                let mut next_checksum = checksum;
+                next_checksum.update(&buf);
+
+                let (data, compressed) = if compression {
+                    match compress(&buf) {
+                        Ok(compressed) => (compressed, true),
+                        Err(err) => {
+                            tracing::warn!(
+                                error = display(&err),
+                                "failed to compress snapshot chunk, send it as is"
+                            );
+                            compression = false;
+                            (buf, false)
+                        }
+                    }
+                } else {
+                    (buf, false)
+                };
+
+                let data = match &transform {
+                    Some(t) => {
+                        let segment = SnapshotSegmentId {
+                            id: snapshot.meta.snapshot_id.clone(),
+                            offset,
+                        };
+                        t.encode(&segment, data).sto_res(subject_verb)?
+                    }
+                    None => data,
+                };
+
                 let req = InstallSnapshotRequest {
                     vote,
                     meta: snapshot.meta.clone(),
                     offset,
-                    data: buf,
+                    data,
                     done,
+                    checksum: if done { Some(next_checksum.finalize()) } else { None },
+                    compressed,
+                    protocol_version: Capabilities::PROTOCOL_VERSION,
                 };
 
                 // Send the RPC over to the target.
@@ -104,7 +168,14 @@ mod tokio_rt {
                 );
 
                 #[allow(deprecated)]
-                let res = C::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone())).await;
+                let rpc = C::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone()));
+
+                // Do not wait for an in-flight chunk if canceled,
+                // e.g., the target is removed from the membership.
+                let res = match futures::future::select(c.as_mut(), std::pin::pin!(rpc)).await {
+                    Either::Left((err, _)) => return Err(err.into()),
+                    Either::Right((res, _)) => res,
+                };
 
                 let resp = match res {
                     Ok(outer_res) => match outer_res {
@@ -127,12 +198,56 @@ mod tokio_rt {
                                             //
                                             match snapshot_err {
                                                 InstallSnapshotError::SnapshotMismatch(mismatch) => {
-                                                    //
+                                                    let expect = &mismatch.expect;
+                                                    if expect.id == snapshot.meta.snapshot_id
+                                                        && expect.offset > 0
+                                                        && expect.offset <= end
+                                                    {
+                                                        // The target has already received part of this
+                                                        // snapshot, e.g., before it restarted.
+                                                        tracing::info!(
+                                                            mismatch = display(&mismatch),
+                                                            "snapshot mismatch, resume from offset {}",
+                                                            expect.offset
+                                                        );
+                                                        offset = expect.offset;
+                                                        checksum =
+                                                            checksum_of_prefix(snapshot.snapshot.as_mut(), offset)
+                                                                .await
+                                                                .sto_res(subject_verb)?;
+                                                    } else {
+                                                        tracing::warn!(
+                                                            mismatch = display(&mismatch),
+                                                            "snapshot mismatch, reset offset and retry"
+                                                        );
+                                                        offset = 0;
+                                                        checksum = Crc32::new();
+                                                    }
+                                                }
+                                                InstallSnapshotError::SnapshotChecksumMismatch(mismatch) => {
                                                     tracing::warn!(
                                                         mismatch = display(&mismatch),
-                                                        "snapshot mismatch, reset offset and retry"
+                                                        "snapshot checksum mismatch, reset offset and retry"
                                                     );
                                                     offset = 0;
+                                                    checksum = Crc32::new();
+                                                }
+                                                InstallSnapshotError::SnapshotDecompress(err) => {
+                                                    tracing::warn!(
+                                                        error = display(&err),
+                                                        "target can not decompress snapshot chunk, re-send it uncompressed"
+                                                    );
+                                                    compression = false;
+                                                }
+                                                InstallSnapshotError::SnapshotDecode(err) => {
+                                                    // Decoding the same chunk fails again, e.g., the
+                                                    // transforms of the nodes do not match. Give up, and
+                                                    // let the replication back off before retrying.
+                                                    tracing::error!(
+                                                        error = display(&err),
+                                                        "target can not decode snapshot chunk, stop sending"
+                                                    );
+                                                    return Err(StreamingError::Unreachable(Unreachable::new(&err)));
                                                 }
                                             }
                                         }
@@ -155,10 +270,33 @@ mod tokio_rt {
                 }
 
                 if done {
+                    option.report_snapshot_progress(end, end);
                     return Ok(SnapshotResponse::new(resp.vote));
                 }
 
                 offset += n_read as u64;
+                checksum = next_checksum;
+
+                // Sleep if it is sending faster than the rate limit.
+                if let Some(rate) = option.snapshot_max_bytes_per_sec() {
+                    sent_bytes += n_read as u64;
+
+                    let expected = Duration::from_secs_f64(sent_bytes as f64 / rate as f64);
+                    let elapsed = start.elapsed();
+                    if expected > elapsed {
+                        tracing::debug!(
+                            sent_bytes,
+                            rate,
+                            "snapshot transmission is throttled for {:?}",
+                            expected - elapsed
+                        );
+                        let sleep = C::sleep(expected - elapsed);
+                        if let Either::Left((err, _)) = futures::future::select(c.as_mut(), std::pin::pin!(sleep)).await
+                        {
+                            return Err(err.into());
+                        }
+                    }
+                }
             }
         }
 
@@ -167,40 +305,114 @@ mod tokio_rt {
             raft: &Raft<C>,
             req: InstallSnapshotRequest<C>,
         ) -> Result<Option<Snapshot<C>>, RaftError<C, InstallSnapshotError>> {
+            Self::receive_snapshot_with_transform(streaming, raft, req, raft.snapshot_transform()).await
+        }
+
+        async fn receive_snapshot_with_transform(
+            streaming: &mut Option<Streaming<C>>,
+            raft: &Raft<C>,
+            mut req: InstallSnapshotRequest<C>,
+            transform: Option<&dyn SnapshotTransform>,
+        ) -> Result<Option<Snapshot<C>>, RaftError<C, InstallSnapshotError>> {
+            if let Some(t) = transform {
+                let segment = SnapshotSegmentId {
+                    id: req.meta.snapshot_id.clone(),
+                    offset: req.offset,
+                };
+                let data = std::mem::take(&mut req.data);
+                req.data = t.decode(&segment, data).map_err(|e| {
+                    RaftError::APIError(InstallSnapshotError::SnapshotDecode(SnapshotDecode {
+                        snapshot_id: segment.id.clone(),
+                        offset: segment.offset,
+                        reason: e.to_string(),
+                    }))
+                })?;
+            }
+
+            if req.compressed {
+                req.data = decompress(&req.data).map_err(|e| {
+                    RaftError::APIError(InstallSnapshotError::SnapshotDecompress(SnapshotDecompress {
+                        snapshot_id: req.meta.snapshot_id.clone(),
+                        offset: req.offset,
+                        reason: e.to_string(),
+                    }))
+                })?;
+                req.compressed = false;
+            }
+
             let snapshot_id = &req.meta.snapshot_id;
             let snapshot_meta = req.meta.clone();
             let done = req.done;
+            let expected_checksum = req.checksum;
 
             tracing::info!(req = display(&req), "{}", func_name!());
 
             let curr_id = streaming.as_ref().map(|s| s.snapshot_id());
 
             if curr_id != Some(snapshot_id) {
-                if req.offset != 0 {
-                    let mismatch = InstallSnapshotError::SnapshotMismatch(crate::error::SnapshotMismatch {
-                        expect: crate::SnapshotSegmentId {
-                            id: snapshot_id.clone(),
-                            offset: 0,
-                        },
-                        got: crate::SnapshotSegmentId {
-                            id: snapshot_id.clone(),
-                            offset: req.offset,
-                        },
-                    });
-                    return Err(RaftError::APIError(mismatch));
-                }
-
-                // Changed to another stream. re-init snapshot state.
-                let snapshot_data = raft.begin_receiving_snapshot().await.map_err(|e| {
+                // Changed to another stream.
+                // Try to resume a partially received one, otherwise re-init snapshot state.
+                let resumed = raft.resume_receiving_snapshot(snapshot_id).await.map_err(|e| {
                     // Safe unwrap: `RaftError<Infallible>` is always a Fatal.
                     RaftError::Fatal(e.into_fatal().unwrap())
                 })?;
 
-                *streaming = Some(Streaming::new(snapshot_id.clone(), snapshot_data));
+                if let Some((received, mut snapshot_data)) = resumed {
+                    // Re-compute the checksum of the received data,
+                    // which also moves the cursor to the end of it.
+                    let checksum = checksum_of_prefix(snapshot_data.as_mut(), received)
+                        .await
+                        .map_err(|e| StorageError::read_snapshot(Some(snapshot_meta.signature()), &e))?;
+
+                    tracing::info!(
+                        snapshot_id = display(snapshot_id),
+                        received,
+                        "resume receiving snapshot"
+                    );
+
+                    *streaming = Some(Streaming {
+                        offset: received,
+                        snapshot_id: snapshot_id.clone(),
+                        checksum: Some(checksum),
+                        snapshot_data,
+                        updated_at: C::now(),
+                    });
+
+                    if req.offset != received {
+                        // Ask the leader to continue from where it has been received.
+                        return Err(RaftError::APIError(snapshot_mismatch(
+                            snapshot_id,
+                            received,
+                            req.offset,
+                        )));
+                    }
+                } else {
+                    if req.offset != 0 {
+                        return Err(RaftError::APIError(snapshot_mismatch(snapshot_id, 0, req.offset)));
+                    }
+
+                    let snapshot_data = raft.begin_receiving_snapshot().await.map_err(|e| {
+                        // Safe unwrap: `RaftError<Infallible>` is always a Fatal.
+                        RaftError::Fatal(e.into_fatal().unwrap())
+                    })?;
+
+                    *streaming = Some(Streaming::new(snapshot_id.clone(), snapshot_data));
+                }
             }
 
             {
                 let s = streaming.as_mut().unwrap();
+
+                if raft.config().strict_snapshot_offset && req.offset != 0 && req.offset != s.offset {
+                    // Do not leave a gap or overwrite received data.
+                    // Ask the leader to continue from where it has been received.
+                    return Err(RaftError::APIError(snapshot_mismatch(
+                        snapshot_id,
+                        s.offset,
+                        req.offset,
+                    )));
+                }
+
                 s.receive(req).await?;
             }
 
@@ -208,6 +420,32 @@ mod tokio_rt {
 
             if done {
                 let streaming = streaming.take().unwrap();
+
+                if let Some(expect) = expected_checksum {
+                    let got = streaming.checksum();
+                    if got != Some(expect) {
+                        // The received data is corrupted: discard the persisted data too,
+                        // otherwise it would be resumed and mismatch again.
+                        // The sender has to re-send from the beginning.
+                        let mismatch = SnapshotChecksumMismatch {
+                            snapshot_id: snapshot_meta.snapshot_id.clone(),
+                            expect,
+                            got,
+                        };
+                        tracing::warn!(mismatch = display(&mismatch), "drop received snapshot");
+
+                        drop(streaming);
+                        raft.discard_receiving_snapshot(&snapshot_meta.snapshot_id).await.map_err(|e| {
+                            // Safe unwrap: `RaftError<Infallible>` is always a Fatal.
+                            RaftError::Fatal(e.into_fatal().unwrap())
+                        })?;
+
+                        return Err(RaftError::APIError(InstallSnapshotError::SnapshotChecksumMismatch(
+                            mismatch,
+                        )));
+                    }
+                }
+
                 let mut data = streaming.into_snapshot_data();
 
                 data.as_mut()
@@ -223,6 +461,38 @@ mod tokio_rt {
         }
     }
 
+    fn snapshot_mismatch(snapshot_id: &SnapshotId, expect: u64, got: u64) -> InstallSnapshotError {
+        InstallSnapshotError::SnapshotMismatch(SnapshotMismatch {
+            expect: SnapshotSegmentId {
+                id: snapshot_id.clone(),
+                offset: expect,
+            },
+            got: SnapshotSegmentId {
+                id: snapshot_id.clone(),
+                offset: got,
+            },
+        })
+    }
+
+    /// Compute the checksum of the first `len` bytes of `data`, and leave the cursor at `len`.
+    async fn checksum_of_prefix<D>(data: &mut D, len: u64) -> Result<Crc32, std::io::Error>
+    where D: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin {
+        data.seek(SeekFrom::Start(0)).await?;
+
+        let mut checksum = Crc32::new();
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let n = std::cmp::min(remaining, buf.len() as u64) as usize;
+            data.read_exact(&mut buf[..n]).await?;
+            checksum.update(&buf[..n]);
+            remaining -= n as u64;
+        }
+
+        Ok(checksum)
+    }
+
     impl<C> Streaming<C>
     where
         C: RaftTypeConfig,
@@ -242,6 +512,17 @@ mod tokio_rt {
                     ));
                 }
                 self.offset = req.offset;
+
+                // Checksum can only be computed if data is written contiguously from the beginning.
+                self.checksum = None;
+            }
+
+            if req.offset == 0 {
+                self.checksum = Some(Crc32::new());
+            }
+
+            if let Some(c) = self.checksum.as_mut() {
+                c.update(&req.data);
             }
 
             // Write the next segment & update offset.
@@ -254,6 +535,7 @@ mod tokio_rt {
                 ));
             }
             self.offset += req.data.len() as u64;
+            self.updated_at = C::now();
             Ok(req.done)
         }
     }
@@ -268,10 +550,14 @@ use crate::error::InstallSnapshotError;
 use crate::error::RaftError;
 use crate::error::ReplicationClosed;
 use crate::error::StreamingError;
+use crate::network::snapshot_checksum::Crc32;
 use crate::network::RPCOption;
+use crate::network::SnapshotTransform;
 use crate::raft::InstallSnapshotRequest;
 use crate::raft::SnapshotResponse;
 use crate::storage::Snapshot;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::TypeConfigExt;
 use crate::OptionalSend;
 use crate::Raft;
 use crate::RaftNetwork;
@@ -296,6 +582,7 @@ pub trait SnapshotTransport<C: RaftTypeConfig> {
     /// `cancel` is a future that is polled by this function to check if the caller decides to
     /// cancel.
     /// It return `Ready` if the caller decide to cancel this snapshot transmission.
+    /// The chunk being sent is abandoned at once when it becomes `Ready`.
     // TODO: consider removing dependency on RaftNetwork
     async fn send_snapshot<Net>(
         net: &mut Net,
@@ -315,6 +602,10 @@ pub trait SnapshotTransport<C: RaftTypeConfig> {
     /// - The receiving state `streaming` is maintained by the caller.
     /// - And it depends on `Raft::begin_receiving_snapshot()` to create a `SnapshotData` for
     /// receiving data.
+    /// - It calls `Raft::resume_receiving_snapshot()` first, to open the `SnapshotData` for the
+    /// snapshot id, continuing from the data received before this node restarted.
+    /// - Every chunk is decoded with the transform returned by
+    /// [`RaftNetworkFactory::snapshot_transform()`], if there is one.
     ///
     /// Example usage:
     /// ```ignore
@@ -332,11 +623,28 @@ pub trait SnapshotTransport<C: RaftTypeConfig> {
     ///     }
     /// }
     /// ```
+    ///
+    /// [`RaftNetworkFactory::snapshot_transform()`]: crate::network::RaftNetworkFactory::snapshot_transform
     async fn receive_snapshot(
         streaming: &mut Option<Streaming<C>>,
         raft: &Raft<C>,
         req: InstallSnapshotRequest<C>,
     ) -> Result<Option<Snapshot<C>>, RaftError<C, InstallSnapshotError>>;
+
+    /// Receive a chunk of snapshot that is transformed by the sender with the same `transform`,
+    /// e.g., encrypted. If the snapshot is done receiving, return the snapshot.
+    ///
+    /// It is the same as [`Self::receive_snapshot()`] except that every chunk is decoded with
+    /// the given `transform` instead of the one provided by
+    /// [`RaftNetworkFactory::snapshot_transform()`].
+    ///
+    /// [`RaftNetworkFactory::snapshot_transform()`]: crate::network::RaftNetworkFactory::snapshot_transform
+    async fn receive_snapshot_with_transform(
+        streaming: &mut Option<Streaming<C>>,
+        raft: &Raft<C>,
+        req: InstallSnapshotRequest<C>,
+        transform: Option<&dyn SnapshotTransform>,
+    ) -> Result<Option<Snapshot<C>>, RaftError<C, InstallSnapshotError>>;
 }
 
 /// The Raft node is streaming in a snapshot from the leader.
@@ -351,8 +659,16 @@ where C: RaftTypeConfig
     /// The ID of the snapshot being written.
     snapshot_id: SnapshotId,
 
+    /// The checksum of the data written so far.
+    ///
+    /// It is `None` if the data is not written contiguously from offset 0.
+    checksum: Option<Crc32>,
+
     /// A handle to the snapshot writer.
     snapshot_data: Box<C::SnapshotData>,
+
+    /// The time when the last chunk is received.
+    updated_at: InstantOf<C>,
 }
 
 impl<C> Streaming<C>
@@ -362,7 +678,9 @@ where C: RaftTypeConfig
         Self {
             offset: 0,
             snapshot_id,
+            checksum: Some(Crc32::new()),
             snapshot_data,
+            updated_at: C::now(),
         }
     }
 
@@ -370,6 +688,24 @@ where C: RaftTypeConfig
         &self.snapshot_id
     }
 
+    /// The CRC-32 checksum of the data received so far, or `None` if it is unknown because the
+    /// data is not received contiguously from the beginning.
+    pub fn checksum(&self) -> Option<u32> {
+        self.checksum.as_ref().map(|c| c.finalize())
+    }
+
+    /// The number of bytes received.
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The time when the last chunk is received.
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(crate) fn updated_at(&self) -> InstantOf<C> {
+        self.updated_at
+    }
+
     /// Consumes the `Streaming` and returns the snapshot data.
     pub fn into_snapshot_data(self) -> Box<C::SnapshotData> {
         self.snapshot_data
@@ -378,17 +714,27 @@ where C: RaftTypeConfig
 
 #[cfg(test)]
 mod tests {
+    use std::io;
     use std::io::Cursor;
+    use std::sync::Arc;
+    use std::sync::Mutex;
     use std::time::Duration;
 
     use crate::engine::testing::UTConfig;
     use crate::error::InstallSnapshotError;
     use crate::error::RPCError;
     use crate::error::RaftError;
+    use crate::error::ReplicationClosed;
+    use crate::error::SnapshotDecode;
+    use crate::error::SnapshotDecompress;
     use crate::error::SnapshotMismatch;
+    use crate::error::StreamingError;
+    use crate::network::snapshot_checksum::Crc32;
     use crate::network::snapshot_transport::Chunked;
     use crate::network::snapshot_transport::SnapshotTransport;
     use crate::network::RPCOption;
+    use crate::network::ReportSnapshotProgress;
+    use crate::network::SnapshotTransform;
     use crate::raft::AppendEntriesRequest;
     use crate::raft::AppendEntriesResponse;
     use crate::raft::InstallSnapshotRequest;
@@ -399,12 +745,22 @@ mod tests {
     use crate::storage::SnapshotMeta;
     use crate::RaftNetwork;
     use crate::RaftTypeConfig;
+    use crate::SnapshotSegmentId;
     use crate::StoredMembership;
     use crate::Vote;
 
     struct Network {
         received_offset: Vec<u64>,
+        last_checksum: Option<u32>,
         match_cnt: u64,
+        /// The offset to ask the sender to continue from, in the mismatch error.
+        resume_offset: u64,
+        /// Whether each received chunk is compressed. A compressed chunk is always rejected.
+        received_compressed: Vec<bool>,
+        /// The data of each received chunk.
+        received_data: Vec<Vec<u8>>,
+        /// The transform applied to every chunk to send.
+        transform: Option<Arc<dyn SnapshotTransform>>,
     }
 
     impl<C> RaftNetwork<C> for Network
@@ -434,6 +790,18 @@ mod tests {
             // A fake implementation to test the Chunked::send_snapshot.
 
             self.received_offset.push(rpc.offset);
+            self.last_checksum = rpc.checksum;
+            self.received_compressed.push(rpc.compressed);
+            self.received_data.push(rpc.data.clone());
+
+            if rpc.compressed {
+                let err = RaftError::APIError(InstallSnapshotError::SnapshotDecompress(SnapshotDecompress {
+                    snapshot_id: rpc.meta.snapshot_id.clone(),
+                    offset: rpc.offset,
+                    reason: "not supported".to_string(),
+                }));
+                return Err(RPCError::RemoteError(crate::error::RemoteError::new(0, err)));
+            }
 
             // For the second last time, return a mismatch error.
             // Then return Ok for the reset of the time.
@@ -442,7 +810,7 @@ mod tests {
                 let mismatch = SnapshotMismatch {
                     expect: crate::SnapshotSegmentId {
                         id: rpc.meta.snapshot_id.clone(),
-                        offset: 0,
+                        offset: self.resume_offset,
                     },
                     got: crate::SnapshotSegmentId {
                         id: rpc.meta.snapshot_id.clone(),
@@ -455,6 +823,28 @@ mod tests {
                 Ok(InstallSnapshotResponse { vote: rpc.vote })
             }
         }
+
+        fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+            self.transform.clone()
+        }
+    }
+
+    /// Flip every bit of a chunk, and append the offset of the chunk.
+    struct Flip;
+
+    impl SnapshotTransform for Flip {
+        fn encode(&self, segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+            let mut data: Vec<u8> = data.into_iter().map(|b| !b).collect();
+            data.push(segment.offset as u8);
+            Ok(data)
+        }
+
+        fn decode(&self, segment: &SnapshotSegmentId, mut data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+            if data.pop() != Some(segment.offset as u8) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "offset mismatch"));
+            }
+            Ok(data.into_iter().map(|b| !b).collect())
+        }
     }
 
     /// Test that `Chunked` should reset the offset to 0 to re-send all data,
@@ -463,9 +853,14 @@ mod tests {
     async fn test_chunked_reset_offset_if_snapshot_id_mismatch() {
         let mut net = Network {
             received_offset: vec![],
+            last_checksum: None,
             // When match_cnt == 1, return a mismatch error.
             // For other times, return Ok.
             match_cnt: 4,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
         };
 
         let mut opt = RPCOption::new(Duration::from_millis(100));
@@ -490,5 +885,395 @@ mod tests {
         .unwrap();
 
         assert_eq!(net.received_offset, vec![0, 1, 2, 0, 1, 2]);
+
+        // The checksum is re-computed after the offset is reset.
+        let mut want = Crc32::new();
+        want.update(&[1, 2, 3]);
+        assert_eq!(net.last_checksum, Some(want.finalize()));
+    }
+
+    /// Test that `Chunked` should continue from the offset the target has received,
+    /// if a [`SnapshotMismatch`] error with a non-zero offset is received.
+    #[tokio::test]
+    async fn test_chunked_resume_from_received_offset() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            match_cnt: 4,
+            // The target has received 1 byte.
+            resume_offset: 1,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        let cancel = futures::future::pending();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(net.received_offset, vec![0, 1, 2, 1, 2]);
+
+        // The checksum covers the data received before resuming.
+        let mut want = Crc32::new();
+        want.update(&[1, 2, 3]);
+        assert_eq!(net.last_checksum, Some(want.finalize()));
+    }
+
+    /// Test that `Chunked` sends snapshot no faster than `snapshot_max_bytes_per_sec`.
+    #[tokio::test]
+    async fn test_chunked_throttle_by_max_bytes_per_sec() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            // Never return a mismatch error.
+            match_cnt: 0,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        opt.snapshot_max_bytes_per_sec = Some(100);
+        let cancel = futures::future::pending();
+
+        let start = std::time::Instant::now();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3, 4])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(net.received_offset, vec![0, 1, 2, 3]);
+
+        // 3 non-last chunks are throttled: 3 bytes at 100 bytes/sec takes at least 30 ms.
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    /// Records the reported progress of sending a snapshot.
+    #[derive(Debug, Default)]
+    struct Progress {
+        reported: Mutex<Vec<(u64, u64)>>,
+    }
+
+    impl ReportSnapshotProgress for Progress {
+        fn report(&self, sent: u64, total: u64) {
+            self.reported.lock().unwrap().push((sent, total));
+        }
+    }
+
+    /// Test that `Chunked` reports the progress before sending each chunk and when finished.
+    #[tokio::test]
+    async fn test_chunked_report_progress() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            // Never return a mismatch error.
+            match_cnt: 0,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
+        };
+
+        let progress = Arc::new(Progress::default());
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        opt.snapshot_progress = Some(progress.clone());
+        let cancel = futures::future::pending();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*progress.reported.lock().unwrap(), vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+    }
+
+    /// Test that `Chunked` re-sends a chunk uncompressed and stops compressing,
+    /// if the target can not decompress it.
+    #[tokio::test]
+    async fn test_chunked_fallback_to_uncompressed() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            match_cnt: 0,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        opt.snapshot_compression = true;
+        let cancel = futures::future::pending();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        if cfg!(feature = "snapshot-compression") {
+            assert_eq!(net.received_offset, vec![0, 0, 1, 2]);
+            assert_eq!(net.received_compressed, vec![true, false, false, false]);
+        } else {
+            // Without the feature, the chunk can not be compressed and is sent as is.
+            assert_eq!(net.received_offset, vec![0, 1, 2]);
+            assert_eq!(net.received_compressed, vec![false, false, false]);
+        }
+    }
+
+    /// Test that `Chunked` returns at once when canceled, without waiting for the throttled
+    /// transmission to finish.
+    #[tokio::test]
+    async fn test_chunked_cancel_while_throttled() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            match_cnt: 0,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: None,
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        opt.snapshot_max_bytes_per_sec = Some(1);
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            ReplicationClosed::new("target removed")
+        };
+
+        let start = std::time::Instant::now();
+
+        let res = Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await;
+
+        assert!(matches!(res, Err(StreamingError::Closed(_))));
+        assert_eq!(net.received_offset, vec![0]);
+
+        // Sending the second chunk has to wait for about 1 second without being canceled.
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    /// Test that `Chunked` encodes every chunk with the transform provided by the network.
+    #[tokio::test]
+    async fn test_chunked_transform() {
+        let mut net = Network {
+            received_offset: vec![],
+            last_checksum: None,
+            match_cnt: 0,
+            resume_offset: 0,
+            received_compressed: vec![],
+            received_data: vec![],
+            transform: Some(Arc::new(Flip)),
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(2);
+        let cancel = futures::future::pending();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(net.received_offset, vec![0, 2]);
+        assert_eq!(net.received_data, vec![vec![!1, !2, 0], vec![!3, 2]]);
+
+        // The checksum is computed on the data before being transformed.
+        let mut c = Crc32::new();
+        c.update(&[1, 2, 3]);
+        assert_eq!(net.last_checksum, Some(c.finalize()));
+
+        for (i, offset) in net.received_offset.iter().enumerate() {
+            let segment = SnapshotSegmentId {
+                id: "1-1-1-1".to_string(),
+                offset: *offset,
+            };
+            let decoded = Flip.decode(&segment, net.received_data[i].clone()).unwrap();
+            assert_eq!(decoded, vec![vec![1, 2], vec![3]][i]);
+        }
+    }
+
+    /// A transform that can not decode any chunk, e.g., the key does not match the sender's.
+    struct Reject;
+
+    impl SnapshotTransform for Reject {
+        fn encode(&self, _segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+            Ok(data)
+        }
+
+        fn decode(&self, _segment: &SnapshotSegmentId, _data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "wrong key"))
+        }
+    }
+
+    /// A receiver that decodes every chunk with its transform, as `Raft::install_snapshot()` does.
+    struct DecodingNetwork {
+        received_offset: Vec<u64>,
+        transform: Arc<dyn SnapshotTransform>,
+    }
+
+    impl<C> RaftNetwork<C> for DecodingNetwork
+    where C: RaftTypeConfig<NodeId = u64>
+    {
+        async fn append_entries(
+            &mut self,
+            _rpc: AppendEntriesRequest<C>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<C>, RPCError<C, RaftError<C>>> {
+            unimplemented!()
+        }
+
+        async fn vote(
+            &mut self,
+            _rpc: VoteRequest<C>,
+            _option: RPCOption,
+        ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>> {
+            unimplemented!()
+        }
+
+        async fn install_snapshot(
+            &mut self,
+            rpc: InstallSnapshotRequest<C>,
+            _option: RPCOption,
+        ) -> Result<InstallSnapshotResponse<C>, RPCError<C, RaftError<C, InstallSnapshotError>>> {
+            self.received_offset.push(rpc.offset);
+
+            let segment = SnapshotSegmentId {
+                id: rpc.meta.snapshot_id.clone(),
+                offset: rpc.offset,
+            };
+            if let Err(e) = self.transform.decode(&segment, rpc.data) {
+                let err = RaftError::APIError(InstallSnapshotError::SnapshotDecode(SnapshotDecode {
+                    snapshot_id: segment.id,
+                    offset: segment.offset,
+                    reason: e.to_string(),
+                }));
+                return Err(RPCError::RemoteError(crate::error::RemoteError::new(0, err)));
+            }
+
+            Ok(InstallSnapshotResponse { vote: rpc.vote })
+        }
+    }
+
+    /// Test that `Chunked` stops sending if the target can not decode a chunk, instead of
+    /// re-sending the same chunk forever.
+    #[tokio::test]
+    async fn test_chunked_stop_if_target_can_not_decode() {
+        let mut net = DecodingNetwork {
+            received_offset: vec![],
+            transform: Arc::new(Reject),
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(1);
+        let cancel = futures::future::pending();
+
+        let res = Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await;
+
+        assert!(
+            matches!(res, Err(StreamingError::Unreachable(_))),
+            "unexpected result: {:?}",
+            res
+        );
+        assert_eq!(net.received_offset, vec![0], "the chunk is sent only once");
     }
 }
@@ -0,0 +1,35 @@
+//! Propagate the tracing context of an RPC from the sender to the receiver.
+
+use crate::OptionalSend;
+use crate::OptionalSync;
+
+/// Converts between a [`tracing::Span`] and an opaque context sent along with every RPC, e.g., a
+/// W3C `traceparent`, so that a trace of a client write can be stitched together across nodes.
+///
+/// The sender calls [`inject()`](Self::inject) with the current span of the task that sends an
+/// RPC, such as a replication stream, and passes the result to the network client as
+/// [`RPCOption::trace_context()`]. The [`RaftNetworkV2`] implementation sends it along with the
+/// request.
+///
+/// The receiver gets a span from the received context with [`Raft::trace_span()`], which calls
+/// [`extract()`](Self::extract), and calls the [`Raft`] API, such as [`Raft::append_entries()`],
+/// within that span. Openraft then handles the request, including the storage IO it triggers,
+/// in that span.
+///
+/// Both ends get it from [`RaftNetworkFactory::trace_context()`].
+///
+/// [`RPCOption::trace_context()`]: crate::network::RPCOption::trace_context
+/// [`RaftNetworkV2`]: crate::network::v2::RaftNetworkV2
+/// [`Raft`]: crate::Raft
+/// [`Raft::trace_span()`]: crate::Raft::trace_span
+/// [`Raft::append_entries()`]: crate::Raft::append_entries
+/// [`RaftNetworkFactory::trace_context()`]: crate::network::RaftNetworkFactory::trace_context
+pub trait TraceContext: OptionalSend + OptionalSync + 'static {
+    /// Encode the context of `span` to send.
+    fn inject(&self, span: &tracing::Span) -> Vec<u8>;
+
+    /// Build a span from a received context encoded by [`inject()`](Self::inject).
+    ///
+    /// The returned span is usually a child of the remote span of the sender.
+    fn extract(&self, context: &[u8]) -> tracing::Span;
+}
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::network::v2::RaftNetworkV2;
+use crate::network::SnapshotTransform;
+use crate::network::TraceContext;
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
@@ -29,5 +34,49 @@ where C: RaftTypeConfig
     ///
     /// The method is intentionally async to give the implementation a chance to use asynchronous
     /// sync primitives to serialize access to the common internal object, if needed.
+    ///
+    /// ### Connection ownership
+    ///
+    /// The returned client is exclusively owned by the task that uses it, e.g., a replication
+    /// stream to `target`, and is dropped when the task quits. A replication stream creates its own
+    /// clients for log entries, snapshot and pipelined requests. Thus a client can keep a
+    /// persistent connection and perform a connect-time handshake when the first RPC is sent,
+    /// without a lock shared by all targets. A broken connection can be re-established by the
+    /// client itself upon the next RPC.
     async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network;
+
+    /// Return the transform to decode every received snapshot chunk, e.g., to decrypt snapshot
+    /// data.
+    ///
+    /// It is used by every chunk based receive path: [`Raft::install_snapshot()`] and
+    /// [`SnapshotTransport::receive_snapshot()`]. It must be the same transform the senders return
+    /// from [`RaftNetwork::snapshot_transform()`], see [`SnapshotTransform`].
+    ///
+    /// It is called once when the [`Raft`] is created.
+    /// By default it returns `None` and received snapshot chunks are used as is.
+    ///
+    /// [`Raft`]: crate::Raft
+    /// [`Raft::install_snapshot()`]: crate::Raft::install_snapshot
+    /// [`SnapshotTransport::receive_snapshot()`]: crate::network::snapshot_transport::SnapshotTransport::receive_snapshot
+    /// [`RaftNetwork::snapshot_transform()`]: crate::network::RaftNetwork::snapshot_transform
+    fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+        None
+    }
+
+    /// Return the propagator of the tracing context sent along with every RPC.
+    ///
+    /// Openraft injects the context of the current span into the [`RPCOption`] of every RPC sent
+    /// by a client of this factory, and [`Raft::trace_span()`] extracts a span from a received
+    /// context, see [`TraceContext`].
+    ///
+    /// It is called once when the [`Raft`] is created.
+    /// By default it returns `None` and no context is sent.
+    ///
+    /// [`RPCOption`]: crate::network::RPCOption
+    /// [`Raft`]: crate::Raft
+    /// [`Raft::trace_span()`]: crate::Raft::trace_span
+    #[since(version = "0.10.0")]
+    fn trace_context(&self) -> Option<Arc<dyn TraceContext>> {
+        None
+    }
 }
@@ -1,11 +1,14 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::error::RPCError;
 use crate::error::RaftError;
 use crate::network::rpc_option::RPCOption;
 use crate::network::Backoff;
+use crate::network::SnapshotTransform;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::VoteRequest;
@@ -50,6 +53,24 @@ where C: RaftTypeConfig
         option: RPCOption,
     ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>>;
 
+    /// Send a PreVote RPC to the target.
+    ///
+    /// The node received this message should pass it to [`Raft::pre_vote()`].
+    ///
+    /// By default it does not send anything and assumes the target grants the pre-vote, see
+    /// [`RaftNetworkV2::pre_vote()`].
+    ///
+    /// [`Raft::pre_vote()`]: crate::raft::Raft::pre_vote
+    /// [`RaftNetworkV2::pre_vote()`]: crate::network::v2::RaftNetworkV2::pre_vote
+    #[since(version = "0.10.0")]
+    async fn pre_vote(
+        &mut self,
+        rpc: VoteRequest<C>,
+        _option: RPCOption,
+    ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>> {
+        Ok(VoteResponse::new(rpc.vote, rpc.last_log_id, true))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
@@ -64,4 +85,14 @@ where C: RaftTypeConfig
     fn backoff(&self) -> Backoff {
         Backoff::new(std::iter::repeat(Duration::from_millis(500)))
     }
+
+    /// Return the transform applied to every snapshot chunk sent to the target, e.g., to encrypt
+    /// snapshot data.
+    ///
+    /// The target must decode the chunks with the same transform, see [`SnapshotTransform`].
+    ///
+    /// By default it returns `None` and snapshot chunks are sent as is.
+    fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+        None
+    }
 }
@@ -36,6 +36,10 @@ where
         RaftNetwork::<C>::vote(self, rpc, option).await.decompose_infallible()
     }
 
+    async fn pre_vote(&mut self, rpc: VoteRequest<C>, option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        RaftNetwork::<C>::pre_vote(self, rpc, option).await.decompose_infallible()
+    }
+
     async fn full_snapshot(
         &mut self,
         vote: Vote<C::NodeId>,
@@ -54,3 +58,70 @@ where
         RaftNetwork::<C>::backoff(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::error::InstallSnapshotError;
+    use crate::error::RPCError;
+    use crate::error::RaftError;
+    use crate::network::v2::RaftNetworkV2;
+    use crate::network::RPCOption;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::AppendEntriesResponse;
+    use crate::raft::InstallSnapshotRequest;
+    use crate::raft::InstallSnapshotResponse;
+    use crate::raft::VoteRequest;
+    use crate::raft::VoteResponse;
+    use crate::testing::log_id;
+    use crate::RaftNetwork;
+    use crate::Vote;
+
+    /// A network that does not implement pre-vote.
+    struct Network;
+
+    impl RaftNetwork<UTConfig> for Network {
+        async fn append_entries(
+            &mut self,
+            _rpc: AppendEntriesRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<UTConfig>, RPCError<UTConfig, RaftError<UTConfig>>> {
+            unimplemented!()
+        }
+
+        async fn install_snapshot(
+            &mut self,
+            _rpc: InstallSnapshotRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<InstallSnapshotResponse<UTConfig>, RPCError<UTConfig, RaftError<UTConfig, InstallSnapshotError>>>
+        {
+            unimplemented!()
+        }
+
+        async fn vote(
+            &mut self,
+            _rpc: VoteRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<VoteResponse<UTConfig>, RPCError<UTConfig, RaftError<UTConfig>>> {
+            unimplemented!()
+        }
+    }
+
+    /// If pre-vote is not implemented, it is granted, so that an election is not blocked.
+    #[tokio::test]
+    async fn test_pre_vote_not_implemented_is_granted() -> anyhow::Result<()> {
+        let mut net = Network;
+
+        let req = VoteRequest::new(Vote::new(2, 1), Some(log_id(1, 1, 3)));
+        let resp =
+            RaftNetworkV2::<UTConfig>::pre_vote(&mut net, req, RPCOption::new(Duration::from_millis(100))).await?;
+
+        assert!(resp.vote_granted);
+        assert_eq!(Vote::new(2, 1), resp.vote);
+        assert_eq!(Some(log_id(1, 1, 3)), resp.last_log_id);
+
+        Ok(())
+    }
+}
@@ -5,19 +5,28 @@ use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 use openraft_macros::since;
 
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
 use crate::error::RPCError;
+use crate::error::RaftError;
 use crate::error::ReplicationClosed;
 use crate::error::StreamingError;
 use crate::error::Unreachable;
 use crate::network::Backoff;
+use crate::network::Capabilities;
 use crate::network::RPCOption;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ClientWriteResponse;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
+use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
@@ -53,9 +62,58 @@ where C: RaftTypeConfig
         option: RPCOption,
     ) -> Result<AppendEntriesResponse<C>, RPCError<C>>;
 
+    /// Send a heartbeat to the target.
+    ///
+    /// The node received this message should pass it to [`Raft::heartbeat()`]. A heartbeat is
+    /// small and latency sensitive, a transport may send it with a higher priority than the other
+    /// RPCs.
+    ///
+    /// By default, it is sent as an AppendEntries RPC without entries, for compatibility with the
+    /// implementations before this method was added.
+    ///
+    /// [`Raft::heartbeat()`]: crate::raft::Raft::heartbeat
+    #[since(version = "0.10.0")]
+    async fn heartbeat(
+        &mut self,
+        rpc: HeartbeatRequest<C>,
+        option: RPCOption,
+    ) -> Result<HeartbeatResponse<C>, RPCError<C>> {
+        let req = AppendEntriesRequest {
+            vote: rpc.vote,
+            prev_log_id: None,
+            leader_commit: rpc.leader_commit,
+            entries: vec![],
+            protocol_version: rpc.protocol_version,
+        };
+
+        let resp = self.append_entries(req, option).await?;
+
+        let vote = match resp {
+            AppendEntriesResponse::HigherVote(vote) => vote,
+            _ => rpc.vote,
+        };
+        Ok(HeartbeatResponse::new(vote))
+    }
+
     /// Send a RequestVote RPC to the target.
     async fn vote(&mut self, rpc: VoteRequest<C>, option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>>;
 
+    /// Send a PreVote RPC to the target.
+    ///
+    /// A pre-vote is sent before starting an election if [`Config::enable_pre_vote`] is enabled.
+    /// The node received this message should pass it to [`Raft::pre_vote()`].
+    ///
+    /// This method provide a default implementation that does not send anything and assumes the
+    /// target grants the pre-vote. Thus if it is not implemented, a pre-vote always succeeds and
+    /// the node starts an election as if pre-vote is disabled.
+    ///
+    /// [`Config::enable_pre_vote`]: crate::Config::enable_pre_vote
+    /// [`Raft::pre_vote()`]: crate::raft::Raft::pre_vote
+    #[since(version = "0.10.0")]
+    async fn pre_vote(&mut self, rpc: VoteRequest<C>, _option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        Ok(VoteResponse::new(rpc.vote, rpc.last_log_id, true))
+    }
+
     /// Send a complete Snapshot to the target.
     ///
     /// This method is responsible to fragment the snapshot and send it to the target node.
@@ -72,7 +130,17 @@ where C: RaftTypeConfig
     ///
     /// `cancel` get `Ready` when the caller decides to cancel this snapshot transmission.
     ///
+    /// The snapshot data does not have to be sent through the Raft RPC channel.
+    /// An application can transfer it out of band, e.g., upload it to an object storage or serve
+    /// it via rsync, and send only the [`SnapshotMeta`] and a locator of the data to the target.
+    /// The target then fetches the data, builds a [`Snapshot`] and calls
+    /// [`Raft::install_full_snapshot()`].
+    /// See the [`raft-kv-memstore-opendal-snapshot-data`][opendal-example] example, in which
+    /// `SnapshotData` is a key of the snapshot in a remote storage.
+    ///
     /// [`Raft::install_full_snapshot()`]: crate::raft::Raft::install_full_snapshot
+    /// [`SnapshotMeta`]: crate::storage::SnapshotMeta
+    /// [opendal-example]: https://github.com/datafuselabs/openraft/tree/main/examples/raft-kv-memstore-opendal-snapshot-data
     async fn full_snapshot(
         &mut self,
         vote: Vote<C::NodeId>,
@@ -81,6 +149,87 @@ where C: RaftTypeConfig
         option: RPCOption,
     ) -> Result<SnapshotResponse<C>, StreamingError<C>>;
 
+    /// Get the signature of the current snapshot on the target node.
+    ///
+    /// Before sending a snapshot, Openraft calls this method to find out the snapshot the target
+    /// already has. If it returns `Some`, Openraft calls
+    /// [`RaftStateMachine::build_delta_snapshot()`] to build a snapshot with only the changes
+    /// since it, and sends that instead of the full snapshot to [`Self::full_snapshot()`].
+    ///
+    /// The target node should reply with [`Raft::snapshot_signature()`].
+    ///
+    /// By default, it returns `None` and the full snapshot is always sent.
+    ///
+    /// [`RaftStateMachine::build_delta_snapshot()`]: crate::storage::RaftStateMachine::build_delta_snapshot
+    /// [`Raft::snapshot_signature()`]: crate::raft::Raft::snapshot_signature
+    #[since(version = "0.10.0")]
+    async fn target_snapshot_signature(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<SnapshotSignature<C>>, RPCError<C>> {
+        Ok(None)
+    }
+
+    /// Ask the target whether it can decompress the compressed entries of an AppendEntries RPC.
+    ///
+    /// It is called by the default implementation of [`Self::capabilities()`], i.e., an
+    /// implementation that overrides [`Self::capabilities()`] does not need this method.
+    /// If the target supports it and [`Config::entries_compression_threshold`] is set, the
+    /// threshold is passed to [`Self::append_entries()`] via
+    /// [`RPCOption::entries_compression_threshold()`].
+    ///
+    /// By default, it returns `false` and entries are never compressed.
+    ///
+    /// [`Config::entries_compression_threshold`]: crate::Config::entries_compression_threshold
+    #[since(version = "0.10.0")]
+    async fn entries_compression_supported(&mut self, _option: RPCOption) -> Result<bool, RPCError<C>> {
+        Ok(false)
+    }
+
+    /// Ask the target for the RPC protocol version and the optional features it supports.
+    ///
+    /// The target node should reply with [`Raft::capabilities()`]. Openraft calls this method
+    /// before replicating logs or a snapshot to the target. It does not replicate to a target
+    /// whose protocol version is not compatible, see [`Capabilities::is_compatible()`], and uses a
+    /// negotiable feature, e.g., [`Config::entries_compression_threshold`] or
+    /// [`Config::snapshot_compression`], only if the target supports it. If it returns an error,
+    /// Openraft asks again before the next RPC.
+    ///
+    /// By default, it returns [`Capabilities`] with an unknown protocol version, built with
+    /// [`Self::entries_compression_supported()`], for compatibility with the implementations
+    /// before this method was added.
+    ///
+    /// [`Raft::capabilities()`]: crate::raft::Raft::capabilities
+    /// [`Config::entries_compression_threshold`]: crate::Config::entries_compression_threshold
+    /// [`Config::snapshot_compression`]: crate::Config::snapshot_compression
+    #[since(version = "0.10.0")]
+    async fn capabilities(&mut self, option: RPCOption) -> Result<Capabilities, RPCError<C>> {
+        let entries_compression = self.entries_compression_supported(option).await?;
+        Ok(Capabilities::unknown(entries_compression))
+    }
+
+    /// Ask the leader for a read index, i.e., the log id up to which the state machine should
+    /// apply to serve a linearizable read.
+    ///
+    /// It is sent by a follower or learner when [`Raft::read_index()`] is called on it.
+    /// The leader received this message should call [`Raft::get_read_log_id()`] and reply with
+    /// the returned `read_log_id`.
+    ///
+    /// This method provide a default implementation that just return [`Unreachable`] error, with
+    /// which [`Raft::read_index()`] only works on the leader.
+    ///
+    /// [`Raft::read_index()`]: crate::raft::Raft::read_index
+    /// [`Raft::get_read_log_id()`]: crate::raft::Raft::get_read_log_id
+    #[since(version = "0.10.0")]
+    async fn read_index(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<LogId<C::NodeId>>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        return Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "read_index not implemented",
+        ))));
+    }
+
     /// Send TransferLeader message to the target node.
     ///
     /// The node received this message should pass it to [`Raft::handle_transfer_leader()`].
@@ -97,6 +246,29 @@ where C: RaftTypeConfig
         ))));
     }
 
+    /// Forward a client write request to the leader.
+    ///
+    /// It is sent by a follower or learner when [`Raft::client_write()`] is called on it and
+    /// [`Config::forward_to_leader`] is enabled. The leader received this message should call
+    /// [`Raft::client_write()`] with `app_data` and reply with the returned result.
+    ///
+    /// This method provide a default implementation that just return [`Unreachable`] error, with
+    /// which the caller receives a [`ForwardToLeader`] error as if forwarding is disabled.
+    ///
+    /// [`Raft::client_write()`]: crate::raft::Raft::client_write
+    /// [`Config::forward_to_leader`]: crate::Config::forward_to_leader
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[since(version = "0.10.0")]
+    async fn forward_client_write(
+        &mut self,
+        _app_data: C::D,
+        _option: RPCOption,
+    ) -> Result<ClientWriteResponse<C>, RPCError<C, RaftError<C, ClientWriteError<C>>>> {
+        return Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "forward_client_write not implemented",
+        ))));
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
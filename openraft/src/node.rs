@@ -46,6 +46,13 @@ impl<T> NodeIdEssential for T where T: Sized
 /// A Raft node's ID.
 ///
 /// A `NodeId` uniquely identifies a node in the Raft cluster.
+///
+/// It is implemented for any type that satisfies the bounds, e.g., `u64`, `u128` or a UUID type.
+/// A node id is embedded in every [`LogId`] and [`Vote`], thus it is required to be `Copy`;
+/// a name or an address of a node belongs in the [`Node`] type.
+///
+/// [`LogId`]: crate::LogId
+/// [`Vote`]: crate::Vote
 #[cfg(feature = "serde")]
 pub trait NodeId: NodeIdEssential + serde::Serialize + for<'a> serde::Deserialize<'a> {}
 
@@ -70,7 +77,16 @@ impl<T> NodeEssential for T where T: Sized + OptionalSend + OptionalSync + Eq +
 ///
 /// For the most generic case `BasicNode` provides an example implementation including the node's
 /// network address, but the used `Node` implementation can be customized to include additional
-/// information.
+/// information, such as the zone or labels of a node.
+///
+/// The `Node` of every member is stored in [`Membership`], persisted in the membership log entry,
+/// and reported in [`RaftMetrics::membership_config`].
+/// It is passed to [`RaftNetworkFactory::new_client()`] when connecting to a node, so that a
+/// network implementation does not need another registry to resolve a node id to an address.
+///
+/// [`Membership`]: crate::Membership
+/// [`RaftMetrics::membership_config`]: crate::metrics::RaftMetrics::membership_config
+/// [`RaftNetworkFactory::new_client()`]: crate::network::RaftNetworkFactory::new_client
 #[cfg(feature = "serde")]
 pub trait Node: NodeEssential + serde::Serialize + for<'a> serde::Deserialize<'a> {}
 
@@ -34,6 +34,11 @@ where
     quorum_set: QS,
 
     learner_ids: Vec<C::NodeId>,
+
+    /// Whether it is a pre-vote, which does not update the vote on any node.
+    ///
+    /// A pre-vote candidate starts a real election when a quorum would grant its vote.
+    pre_vote: bool,
 }
 
 impl<C, QS> fmt::Display for Candidate<C, QS>
@@ -44,7 +49,8 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{{}@{}, last_log_id:{} progress:{}}}",
+            "{{{}{}@{}, last_log_id:{} progress:{}}}",
+            if self.pre_vote { "pre-vote:" } else { "" },
             self.vote,
             self.starting_time.display(),
             self.last_log_id.display(),
@@ -72,9 +78,19 @@ where
             progress: VecProgress::new(quorum_set.clone(), [], || false),
             quorum_set,
             learner_ids: learner_ids.into_iter().collect::<Vec<_>>(),
+            pre_vote: false,
         }
     }
 
+    /// Mark this candidate as running a pre-vote.
+    pub(crate) fn set_pre_vote(&mut self) {
+        self.pre_vote = true;
+    }
+
+    pub(crate) fn is_pre_vote(&self) -> bool {
+        self.pre_vote
+    }
+
     pub(crate) fn vote_ref(&self) -> &Vote<C::NodeId> {
         &self.vote
     }
@@ -107,6 +123,7 @@ where
         let vote = {
             let vote = *self.vote_ref();
             debug_assert!(!vote.is_committed());
+            debug_assert!(!self.pre_vote, "a pre-vote candidate can not become a leader");
             vote.into_committed()
         };
 
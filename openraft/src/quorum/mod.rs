@@ -2,6 +2,13 @@
 //! The most common quorum is **majority**.
 //! A quorum set is a collection of quorums, e.g. the quorum set of majority of `{a,b,c}` is `{a,b},
 //! {b,c}, {a,c}`.
+//!
+//! Openraft does not do majority math directly: both advancing the commit index(see
+//! [`Progress`](crate::progress::Progress)) and counting granted votes in an election consult a
+//! [`QuorumSet`]. [`EffectiveMembership`](crate::EffectiveMembership) implements it as a joint of
+//! majority quorum sets, one for each config in a joint membership.
+//! Other quorum rules, such as weighted voting or majority-of-majorities across regions, are
+//! supported by adding another `QuorumSet` implementation, without changing the callers.
 
 mod coherent;
 mod coherent_impl;
@@ -0,0 +1,86 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::storage::SnapshotMeta;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::SnapshotId;
+use crate::StoredMembership;
+use crate::Vote;
+
+/// A state change of a Raft node, delivered to the subscribers registered with
+/// [`Raft::subscribe()`].
+///
+/// [`Raft::subscribe()`]: crate::Raft::subscribe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaftEvent<C>
+where C: RaftTypeConfig
+{
+    /// This node became the leader with the `vote`.
+    BecameLeader { vote: Vote<C::NodeId> },
+
+    /// This node became a candidate and started an election with the `vote`.
+    BecameCandidate { vote: Vote<C::NodeId> },
+
+    /// This node became a follower, or a follower learned about a new leader.
+    ///
+    /// `leader_id` is `None` if the leader is not yet known.
+    BecameFollower { leader_id: Option<C::NodeId> },
+
+    /// This node became a learner, or a learner learned about a new leader.
+    ///
+    /// `leader_id` is `None` if the leader is not yet known.
+    BecameLearner { leader_id: Option<C::NodeId> },
+
+    /// Some events are dropped because the subscriber did not receive them in time.
+    ///
+    /// `skipped` is the number of the dropped events. The subscriber may read
+    /// [`Raft::metrics()`] to catch up with the current state.
+    ///
+    /// [`Raft::metrics()`]: crate::Raft::metrics
+    Lagged { skipped: u64 },
+
+    /// The effective membership changed.
+    MembershipChanged { membership: Arc<StoredMembership<C>> },
+
+    /// A snapshot is built by this node.
+    SnapshotBuilt { meta: SnapshotMeta<C> },
+
+    /// A snapshot received from the leader is installed.
+    SnapshotInstalled { meta: SnapshotMeta<C> },
+
+    /// The logs up to `upto`, inclusive, are purged.
+    LogPurged { upto: LogId<C::NodeId> },
+
+    /// A snapshot partially received from the leader is dropped, because no chunk is received
+    /// within [`Config::snapshot_receive_idle_timeout`].
+    ///
+    /// [`Config::snapshot_receive_idle_timeout`]: crate::Config::snapshot_receive_idle_timeout
+    SnapshotReceiveTimedOut { snapshot_id: SnapshotId },
+}
+
+impl<C> fmt::Display for RaftEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaftEvent::BecameLeader { vote } => write!(f, "BecameLeader: {}", vote),
+            RaftEvent::BecameCandidate { vote } => write!(f, "BecameCandidate: {}", vote),
+            RaftEvent::BecameFollower { leader_id } => {
+                write!(f, "BecameFollower: leader: {}", leader_id.display())
+            }
+            RaftEvent::BecameLearner { leader_id } => {
+                write!(f, "BecameLearner: leader: {}", leader_id.display())
+            }
+            RaftEvent::Lagged { skipped } => write!(f, "Lagged: skipped: {}", skipped),
+            RaftEvent::MembershipChanged { membership } => write!(f, "MembershipChanged: {}", membership),
+            RaftEvent::SnapshotBuilt { meta } => write!(f, "SnapshotBuilt: {}", meta),
+            RaftEvent::SnapshotInstalled { meta } => write!(f, "SnapshotInstalled: {}", meta),
+            RaftEvent::LogPurged { upto } => write!(f, "LogPurged: upto: {}", upto),
+            RaftEvent::SnapshotReceiveTimedOut { snapshot_id } => {
+                write!(f, "SnapshotReceiveTimedOut: {}", snapshot_id)
+            }
+        }
+    }
+}
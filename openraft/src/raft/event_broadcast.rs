@@ -0,0 +1,199 @@
+//! A bounded broadcast channel that delivers [`RaftEvent`]s from `RaftCore` to the subscribers.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::async_runtime::watch::WatchReceiver;
+use crate::async_runtime::watch::WatchSender;
+use crate::raft::RaftEvent;
+use crate::type_config::alias::WatchReceiverOf;
+use crate::type_config::alias::WatchSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::RaftTypeConfig;
+
+/// The recent events shared by the sender and all of the receivers.
+struct EventBuffer<C>
+where C: RaftTypeConfig
+{
+    /// The sequence number of the first event in `events`.
+    first_seq: u64,
+
+    events: VecDeque<RaftEvent<C>>,
+
+    capacity: usize,
+}
+
+impl<C> EventBuffer<C>
+where C: RaftTypeConfig
+{
+    /// The sequence number of the next event to push.
+    fn next_seq(&self) -> u64 {
+        self.first_seq + self.events.len() as u64
+    }
+}
+
+/// The sending end of the event channel, owned by `RaftCore`.
+///
+/// Sending never blocks: when the buffer is full the oldest event is dropped, and a receiver that
+/// has not yet received it gets a [`RaftEvent::Lagged`] instead.
+pub(crate) struct EventSender<C>
+where C: RaftTypeConfig
+{
+    buffer: Arc<Mutex<EventBuffer<C>>>,
+
+    /// Wakes up the receivers with the sequence number of the next event.
+    tx_seq: WatchSenderOf<C, u64>,
+}
+
+impl<C> EventSender<C>
+where C: RaftTypeConfig
+{
+    /// Create a sender and the [`EventSubscriber`] to create receivers from.
+    pub(crate) fn new(capacity: usize) -> (Self, EventSubscriber<C>) {
+        let buffer = EventBuffer {
+            first_seq: 0,
+            events: VecDeque::new(),
+            capacity: std::cmp::max(capacity, 1),
+        };
+        let buffer = Arc::new(Mutex::new(buffer));
+        let (tx_seq, rx_seq) = C::watch_channel(0);
+
+        let sender = Self {
+            buffer: buffer.clone(),
+            tx_seq,
+        };
+        let subscriber = EventSubscriber { buffer, rx_seq };
+
+        (sender, subscriber)
+    }
+
+    pub(crate) fn send(&self, event: RaftEvent<C>) {
+        let next_seq = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if buffer.events.len() >= buffer.capacity {
+                buffer.events.pop_front();
+                buffer.first_seq += 1;
+            }
+            buffer.events.push_back(event);
+            buffer.next_seq()
+        };
+
+        let _ = self.tx_seq.send(next_seq);
+    }
+}
+
+/// Creates [`RaftEventReceiver`]s, held by the [`Raft`](crate::Raft) handle.
+pub(crate) struct EventSubscriber<C>
+where C: RaftTypeConfig
+{
+    buffer: Arc<Mutex<EventBuffer<C>>>,
+    rx_seq: WatchReceiverOf<C, u64>,
+}
+
+impl<C> EventSubscriber<C>
+where C: RaftTypeConfig
+{
+    /// Create a receiver of the events sent after this call.
+    pub(crate) fn subscribe(&self) -> RaftEventReceiver<C> {
+        let next = self.buffer.lock().unwrap().next_seq();
+
+        RaftEventReceiver {
+            buffer: self.buffer.clone(),
+            rx_seq: self.rx_seq.clone(),
+            next,
+        }
+    }
+}
+
+/// Receives the [`RaftEvent`]s of a Raft node, returned by [`Raft::subscribe()`].
+///
+/// [`Raft::subscribe()`]: crate::Raft::subscribe
+pub struct RaftEventReceiver<C>
+where C: RaftTypeConfig
+{
+    buffer: Arc<Mutex<EventBuffer<C>>>,
+    rx_seq: WatchReceiverOf<C, u64>,
+
+    /// The sequence number of the next event to receive.
+    next: u64,
+}
+
+impl<C> RaftEventReceiver<C>
+where C: RaftTypeConfig
+{
+    /// Receive the next event, in the order they are emitted.
+    ///
+    /// It returns a [`RaftEvent::Lagged`] if some events are dropped before being received, and
+    /// `None` after the Raft node is shut down and all of the buffered events are received.
+    pub async fn recv(&mut self) -> Option<RaftEvent<C>> {
+        loop {
+            if let Some(event) = self.try_recv() {
+                return Some(event);
+            }
+
+            if self.rx_seq.changed().await.is_err() {
+                // The sender is dropped, return the events sent before that.
+                return self.try_recv();
+            }
+        }
+    }
+
+    /// Receive the next event if there is one, without waiting.
+    pub fn try_recv(&mut self) -> Option<RaftEvent<C>> {
+        let buffer = self.buffer.lock().unwrap();
+
+        if self.next < buffer.first_seq {
+            let skipped = buffer.first_seq - self.next;
+            self.next = buffer.first_seq;
+            return Some(RaftEvent::Lagged { skipped });
+        }
+
+        let event = buffer.events.get((self.next - buffer.first_seq) as usize)?.clone();
+        self.next += 1;
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EventSender;
+    use crate::engine::testing::UTConfig;
+    use crate::raft::RaftEvent;
+    use crate::testing::log_id;
+
+    fn purged(index: u64) -> RaftEvent<UTConfig> {
+        RaftEvent::LogPurged {
+            upto: log_id(1, 1, index),
+        }
+    }
+
+    #[test]
+    fn test_event_broadcast() {
+        let (tx, subscriber) = EventSender::<UTConfig>::new(2);
+
+        tx.send(purged(1));
+
+        let mut rx1 = subscriber.subscribe();
+        assert_eq!(
+            None,
+            rx1.try_recv(),
+            "only the events sent after subscribing are received"
+        );
+
+        tx.send(purged(2));
+        let mut rx2 = subscriber.subscribe();
+
+        tx.send(purged(3));
+        tx.send(purged(4));
+
+        assert_eq!(Some(RaftEvent::Lagged { skipped: 1 }), rx1.try_recv());
+        assert_eq!(Some(purged(3)), rx1.try_recv());
+        assert_eq!(Some(purged(4)), rx1.try_recv());
+        assert_eq!(None, rx1.try_recv());
+
+        assert_eq!(Some(purged(3)), rx2.try_recv());
+        assert_eq!(Some(purged(4)), rx2.try_recv());
+        assert_eq!(None, rx2.try_recv());
+    }
+}
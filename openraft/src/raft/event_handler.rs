@@ -0,0 +1,39 @@
+use crate::base::BoxFuture;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// Application callbacks invoked by `RaftCore` when this node acquires or loses the leadership.
+///
+/// A handler is registered with [`Raft::new_with_event_handler()`]. Unlike the events delivered
+/// by [`Raft::subscribe()`], the callbacks are awaited in `RaftCore`, right at the state
+/// transition: [`on_become_leader()`] returns before the new leader handles any client write,
+/// and [`on_step_down()`] returns before this node handles anything as a non-leader. Thus an
+/// application can start and stop its leader-only background jobs deterministically.
+///
+/// `RaftCore` is blocked while a callback runs, so a callback should return quickly, e.g., by
+/// spawning a task or signaling one.
+///
+/// A leader that is re-elected with a greater vote is reported as stepping down from the old
+/// vote and becoming leader with the new one.
+///
+/// [`Raft::new_with_event_handler()`]: crate::Raft::new_with_event_handler
+/// [`Raft::subscribe()`]: crate::Raft::subscribe
+/// [`on_become_leader()`]: RaftEventHandler::on_become_leader
+/// [`on_step_down()`]: RaftEventHandler::on_step_down
+pub trait RaftEventHandler<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Called when this node becomes the leader with the `vote`.
+    fn on_become_leader(&mut self, vote: Vote<C::NodeId>) -> BoxFuture<'_, ()> {
+        let _ = vote;
+        Box::pin(async {})
+    }
+
+    /// Called when this node is no longer the leader with the `vote`, including when it shuts
+    /// down as a leader.
+    fn on_step_down(&mut self, vote: Vote<C::NodeId>) -> BoxFuture<'_, ()> {
+        let _ = vote;
+        Box::pin(async {})
+    }
+}
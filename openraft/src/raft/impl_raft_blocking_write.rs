@@ -2,12 +2,21 @@
 //! Blocking mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use std::collections::BTreeSet;
+use std::time::Duration;
+
 use maplit::btreemap;
+use maplit::btreeset;
 
+use crate::async_runtime::watch::WatchReceiver;
 use crate::core::raft_msg::RaftMsg;
 use crate::display_ext::DisplayResult;
 use crate::error::ClientWriteError;
+use crate::error::Fatal;
+use crate::error::ForwardToLeader;
+use crate::error::LearnerLagging;
 use crate::error::RaftError;
+use crate::metrics::WaitError;
 use crate::raft::message::ClientWriteResult;
 use crate::raft::responder::OneshotResponder;
 use crate::raft::ClientWriteResponse;
@@ -15,6 +24,7 @@ use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::TypeConfigExt;
 use crate::ChangeMembers;
 use crate::Raft;
+use crate::RaftMetrics;
 use crate::RaftTypeConfig;
 
 /// Implement blocking mode write operations those reply on oneshot channel for communication
@@ -48,6 +58,15 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ///
     /// If it loses leadership or crashed before committing the second **uniform** config log, the
     /// cluster is left in the **joint** config.
+    ///
+    /// If [`Config::wait_learner_caught_up`] is enabled, it first blocks until every learner to
+    /// become a voter is up to date. If a learner does not catch up within
+    /// [`Config::wait_learner_caught_up_timeout`], it returns a
+    /// [`ChangeMembershipError::LearnerLagging`] error, without changing the membership.
+    ///
+    /// [`Config::wait_learner_caught_up`]: crate::Config::wait_learner_caught_up
+    /// [`Config::wait_learner_caught_up_timeout`]: crate::Config::wait_learner_caught_up_timeout
+    /// [`ChangeMembershipError::LearnerLagging`]: crate::error::ChangeMembershipError::LearnerLagging
     #[tracing::instrument(level = "info", skip_all)]
     pub async fn change_membership(
         &self,
@@ -56,6 +75,12 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         let changes: ChangeMembers<C> = members.into();
 
+        if self.inner.config.wait_learner_caught_up {
+            self.wait_learners_caught_up(&changes)
+                .await
+                .map_err(|e| RaftError::APIError(ClientWriteError::ChangeMembershipError(e.into())))?;
+        }
+
         tracing::info!(
             changes = debug(&changes),
             retain = display(retain),
@@ -173,6 +198,196 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
 
         Ok(resp)
     }
+
+    /// Update the stored [`Node`](crate::Node) of a member, e.g., its network address after it is
+    /// rescheduled to another host.
+    ///
+    /// The new node info is proposed in a single membership log, without entering a joint config,
+    /// and every node converges on it when the log is replicated.
+    /// If `id` is not yet a member, it is added as a learner, just like a non-blocking
+    /// [`add_learner()`](Self::add_learner).
+    ///
+    /// The node at the new address must be the same node with the same data, otherwise it may cause
+    /// a brain split. See:
+    /// [Update-Node](`crate::docs::cluster_control::dynamic_membership#update-node`)
+    #[tracing::instrument(level = "info", skip(self, node))]
+    pub async fn update_node(
+        &self,
+        id: C::NodeId,
+        node: C::Node,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = oneshot_channel::<C>();
+
+        let msg = RaftMsg::ChangeMembership {
+            changes: ChangeMembers::SetNodes(btreemap! {id=>node}),
+            retain: true,
+            tx,
+        };
+
+        self.inner.call_core(msg, rx).await
+    }
+
+    /// Remove a voter or learner from the cluster.
+    ///
+    /// If the node to remove is this leader itself, it can not remove itself: it transfers the
+    /// leadership to the most up-to-date voter, so that the cluster does not have to wait for an
+    /// election timeout to elect a new leader, and returns a [`ForwardToLeader`] error. The caller
+    /// should retry `remove_node()` on the new leader. A voter that does not take over the
+    /// leadership within `election_timeout_max`, e.g., a witness or an unreachable node, is
+    /// skipped and the next one is tried. If no voter takes over, the returned error has no
+    /// leader id, and the caller should retry after a new leader is elected.
+    ///
+    /// A voter is removed in two steps: it is first turned into a learner, so that it receives the
+    /// membership in which it is no longer a voter and never starts an election, and then it is
+    /// removed from the cluster. The removed node should be shut down by the application.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn remove_node(
+        &self,
+        id: C::NodeId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let timeout = Duration::from_millis(self.inner.config.election_timeout_max);
+
+        let metrics = self.metrics().borrow_watched().clone();
+        let membership = metrics.membership_config.membership();
+
+        if id == self.inner.id && metrics.current_leader == Some(id) {
+            let err = self.hand_off_leadership(&metrics, timeout).await?;
+            return Err(RaftError::APIError(ClientWriteError::ForwardToLeader(err)));
+        }
+
+        if membership.is_voter(&id) {
+            tracing::info!(target = display(id), "remove node: turn voter into learner");
+
+            let resp = self.change_membership(ChangeMembers::RemoveVoters(btreeset! {id}), true).await?;
+
+            // Let the removed voter know it is no longer a voter before it is removed.
+            // The removed node may be down, thus do not wait forever.
+            let wait_res = self
+                .wait(Some(timeout))
+                .metrics(
+                    |m| {
+                        let matched = m.replication.as_ref().and_then(|repl| repl.get(&id).copied().flatten());
+                        matched >= Some(resp.log_id)
+                    },
+                    "removed voter receives the membership",
+                )
+                .await;
+
+            tracing::info!(
+                target = display(id),
+                wait_res = display(DisplayResult(&wait_res)),
+                "waiting for the removed voter to receive the membership"
+            );
+        }
+
+        self.change_membership(ChangeMembers::RemoveNodes(btreeset! {id}), false).await
+    }
+
+    /// Transfer the leadership to another voter, trying the most up-to-date one first.
+    ///
+    /// It returns the new leader to forward the request to, or a [`ForwardToLeader`] without
+    /// leader if no voter takes over.
+    async fn hand_off_leadership(
+        &self,
+        metrics: &RaftMetrics<C>,
+        timeout: Duration,
+    ) -> Result<ForwardToLeader<C>, Fatal<C>> {
+        let me = self.inner.id;
+        let membership = metrics.membership_config.membership();
+
+        let matching = |x: &C::NodeId| metrics.replication.as_ref().and_then(|repl| repl.get(x).copied().flatten());
+
+        let mut targets = membership.voter_ids().filter(|x| *x != me).collect::<Vec<_>>();
+        targets.sort_by_key(|x| std::cmp::Reverse(matching(x)));
+
+        for target in targets {
+            tracing::info!(target = display(target), "remove leader: transfer leadership first");
+
+            self.trigger().transfer_leader(target).await?;
+
+            let wait_res = self
+                .wait(Some(timeout))
+                .metrics(
+                    |m| m.current_leader.is_some() && m.current_leader != Some(me),
+                    "leadership transferred",
+                )
+                .await;
+
+            if let Ok(m) = wait_res {
+                let leader_id = m.current_leader.unwrap();
+                let leader_node = m.membership_config.membership().get_node(&leader_id).cloned();
+                return Ok(ForwardToLeader {
+                    leader_id: Some(leader_id),
+                    leader_node,
+                });
+            }
+
+            tracing::warn!(
+                target = display(target),
+                "leadership is not transferred in time, try another voter"
+            );
+        }
+
+        Ok(ForwardToLeader::empty())
+    }
+
+    /// Block until every learner that is promoted to voter by `changes` is up to date.
+    ///
+    /// It returns at once if this node is not a leader. In this case the following membership
+    /// change just fails.
+    ///
+    /// It returns an error if a learner does not catch up within
+    /// [`Config::wait_learner_caught_up_timeout`](crate::Config::wait_learner_caught_up_timeout).
+    async fn wait_learners_caught_up(&self, changes: &ChangeMembers<C>) -> Result<(), LearnerLagging<C>> {
+        let voter_ids = match changes {
+            ChangeMembers::AddVoterIds(ids) => ids.clone(),
+            ChangeMembers::AddVoters(nodes) => nodes.keys().cloned().collect(),
+            ChangeMembers::ReplaceAllVoters(ids) => ids.clone(),
+            _ => return Ok(()),
+        };
+
+        let learner_ids: BTreeSet<_> =
+            self.metrics().borrow_watched().membership_config.membership().learner_ids().collect();
+
+        let timeout = self.inner.config.wait_learner_caught_up_timeout();
+
+        for id in voter_ids.intersection(&learner_ids) {
+            tracing::info!(
+                target = display(id),
+                timeout = debug(timeout),
+                "waiting for learner to catch up"
+            );
+
+            let wait_res = self
+                .wait(Some(timeout))
+                .metrics(
+                    |metrics| self.check_replication_upto_date(metrics, *id, None).is_ok(),
+                    "wait learner to catch up before promoting",
+                )
+                .await;
+
+            tracing::info!(
+                target = display(id),
+                wait_res = display(DisplayResult(&wait_res)),
+                "done waiting for learner to catch up"
+            );
+
+            if let Err(WaitError::Timeout(_, _)) = wait_res {
+                let matched =
+                    self.metrics().borrow_watched().replication.as_ref().and_then(|r| r.get(id).copied()).flatten();
+
+                return Err(LearnerLagging {
+                    node_id: *id,
+                    matched,
+                    timeout,
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn oneshot_channel<C>() -> (OneshotResponder<C>, OneshotReceiverOf<C, ClientWriteResult<C>>)
@@ -28,6 +28,14 @@ pub struct AppendEntriesRequest<C: RaftTypeConfig> {
 
     /// The leader's committed log id.
     pub leader_commit: Option<LogId<C::NodeId>>,
+
+    /// The RPC protocol version of the sender, see [`Capabilities`].
+    ///
+    /// It is `0` if the sender is built before the version is introduced.
+    ///
+    /// [`Capabilities`]: crate::network::Capabilities
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub protocol_version: u32,
 }
 
 impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C> {
@@ -37,6 +45,7 @@ impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C> {
             .field("prev_log_id", &self.prev_log_id)
             .field("entries", &self.entries)
             .field("leader_commit", &self.leader_commit)
+            .field("protocol_version", &self.protocol_version)
             .finish()
     }
 }
@@ -88,6 +97,20 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// [`RaftNetwork::append_entries`]: crate::network::RaftNetwork::append_entries
     PartialSuccess(Option<LogId<C::NodeId>>),
 
+    /// Successfully replicated all log entries to the target node, but the target node is
+    /// overloaded and asks the leader to slow down.
+    ///
+    /// It carries the apply lag of the target node, i.e., the number of logs accepted but not
+    /// yet applied to its state machine. It is returned instead of [`Self::Success`] when the lag
+    /// reaches [`Config::backpressure_apply_lag`].
+    ///
+    /// It is introduced in protocol version 2, and is only replied to a leader that sends the
+    /// request with [`AppendEntriesRequest::protocol_version`] 2 or later. An older leader always
+    /// gets [`Self::Success`] instead.
+    ///
+    /// [`Config::backpressure_apply_lag`]: crate::Config::backpressure_apply_lag
+    Backpressure { apply_lag: u64 },
+
     /// The first log id([`AppendEntriesRequest::prev_log_id`]) of the entries to send does not
     /// match on the remote target node.
     Conflict,
@@ -101,8 +124,12 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
 impl<C> AppendEntriesResponse<C>
 where C: RaftTypeConfig
 {
+    /// Return `true` if all log entries are accepted, including a [`Self::Backpressure`] reply.
     pub fn is_success(&self) -> bool {
-        matches!(*self, AppendEntriesResponse::Success)
+        matches!(
+            *self,
+            AppendEntriesResponse::Success | AppendEntriesResponse::Backpressure { .. }
+        )
     }
 
     pub fn is_conflict(&self) -> bool {
@@ -119,6 +146,9 @@ where C: RaftTypeConfig
             AppendEntriesResponse::PartialSuccess(m) => {
                 write!(f, "PartialSuccess({})", m.display())
             }
+            AppendEntriesResponse::Backpressure { apply_lag } => {
+                write!(f, "Backpressure(apply_lag: {})", apply_lag)
+            }
             AppendEntriesResponse::HigherVote(vote) => write!(f, "Higher vote, {}", vote),
             AppendEntriesResponse::Conflict => write!(f, "Conflict"),
         }
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::network::Capabilities;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// A heartbeat sent by the Leader to keep its leadership and to propagate the committed log id.
+///
+/// Unlike an [`AppendEntriesRequest`] with no entries, it carries no log position: the receiver
+/// only checks the `vote` and does not match or update its logs. Thus a transport can send it with
+/// a higher priority.
+///
+/// [`AppendEntriesRequest`]: crate::raft::AppendEntriesRequest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct HeartbeatRequest<C: RaftTypeConfig> {
+    pub vote: Vote<C::NodeId>,
+
+    /// The committed log id of the Leader.
+    pub leader_commit: Option<LogId<C::NodeId>>,
+
+    /// The RPC protocol version of the sender, see [`Capabilities`].
+    ///
+    /// It is `0` if the sender is built before the version is introduced.
+    ///
+    /// [`Capabilities`]: crate::network::Capabilities
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub protocol_version: u32,
+}
+
+impl<C> fmt::Display for HeartbeatRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{vote:{}, leader_commit:{}}}",
+            self.vote,
+            self.leader_commit.display()
+        )
+    }
+}
+
+impl<C> HeartbeatRequest<C>
+where C: RaftTypeConfig
+{
+    pub fn new(vote: Vote<C::NodeId>, leader_commit: Option<LogId<C::NodeId>>) -> Self {
+        Self {
+            vote,
+            leader_commit,
+            protocol_version: Capabilities::PROTOCOL_VERSION,
+        }
+    }
+}
+
+/// The response to a [`HeartbeatRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(derive_more::Display)]
+#[display("HeartbeatResponse{{vote:{}}}", vote)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct HeartbeatResponse<C: RaftTypeConfig> {
+    /// The vote of the receiver after handling the heartbeat.
+    ///
+    /// If it is not the vote in the request, the heartbeat is rejected because the receiver has
+    /// seen a higher vote.
+    pub vote: Vote<C::NodeId>,
+}
+
+impl<C> HeartbeatResponse<C>
+where C: RaftTypeConfig
+{
+    pub fn new(vote: Vote<C::NodeId>) -> Self {
+        Self { vote }
+    }
+}
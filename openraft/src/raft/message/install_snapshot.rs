@@ -21,18 +21,49 @@ pub struct InstallSnapshotRequest<C: RaftTypeConfig> {
 
     /// Will be `true` if this is the last chunk in the snapshot.
     pub done: bool,
+
+    /// The CRC-32 checksum of the entire snapshot data.
+    ///
+    /// It is only set in the last chunk, i.e., when `done` is `true`.
+    /// If it is set, the receiver verifies the received data against it before installing the
+    /// snapshot, and rejects a corrupted snapshot with
+    /// [`InstallSnapshotError::SnapshotChecksumMismatch`].
+    ///
+    /// [`InstallSnapshotError::SnapshotChecksumMismatch`]: crate::error::InstallSnapshotError::SnapshotChecksumMismatch
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checksum: Option<u32>,
+
+    /// Whether `data` is compressed with gzip.
+    ///
+    /// `offset` and `checksum` always refer to the uncompressed data.
+    /// A receiver that can not decompress it returns
+    /// [`InstallSnapshotError::SnapshotDecompress`], and the sender re-sends this chunk
+    /// uncompressed.
+    ///
+    /// [`InstallSnapshotError::SnapshotDecompress`]: crate::error::InstallSnapshotError::SnapshotDecompress
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compressed: bool,
+
+    /// The RPC protocol version of the sender, see [`Capabilities`].
+    ///
+    /// It is `0` if the sender is built before the version is introduced.
+    ///
+    /// [`Capabilities`]: crate::network::Capabilities
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub protocol_version: u32,
 }
 
 impl<C: RaftTypeConfig> fmt::Display for InstallSnapshotRequest<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "InstallSnapshotRequest {{ vote:{}, meta:{}, offset:{}, len:{}, done:{} }}",
+            "InstallSnapshotRequest {{ vote:{}, meta:{}, offset:{}, len:{}, done:{}, compressed:{} }}",
             self.vote,
             self.meta,
             self.offset,
             self.data.len(),
-            self.done
+            self.done,
+            self.compressed
         )
     }
 }
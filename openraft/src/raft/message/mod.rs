@@ -4,6 +4,7 @@
 //! and are also used by network layer to talk to other Raft nodes.
 
 mod append_entries;
+mod heartbeat;
 mod install_snapshot;
 mod transfer_leader;
 mod vote;
@@ -14,6 +15,8 @@ pub use append_entries::AppendEntriesRequest;
 pub use append_entries::AppendEntriesResponse;
 pub use client_write::ClientWriteResponse;
 pub use client_write::ClientWriteResult;
+pub use heartbeat::HeartbeatRequest;
+pub use heartbeat::HeartbeatResponse;
 pub use install_snapshot::InstallSnapshotRequest;
 pub use install_snapshot::InstallSnapshotResponse;
 pub use install_snapshot::SnapshotResponse;
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::display_ext::DisplayOptionExt;
+use crate::network::Capabilities;
 use crate::LogId;
 use crate::RaftTypeConfig;
 use crate::Vote;
@@ -19,6 +20,10 @@ where C: RaftTypeConfig
 
     /// The last log id the `to_node_id` node should at least have to become Leader.
     pub(crate) last_log_id: Option<LogId<C::NodeId>>,
+
+    /// The RPC protocol version of the sender.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) protocol_version: u32,
 }
 
 impl<C> TransferLeaderRequest<C>
@@ -29,6 +34,7 @@ where C: RaftTypeConfig
             from_leader: from,
             to_node_id: to,
             last_log_id,
+            protocol_version: Capabilities::PROTOCOL_VERSION,
         }
     }
 
@@ -48,6 +54,13 @@ where C: RaftTypeConfig
     pub fn last_log_id(&self) -> Option<&LogId<C::NodeId>> {
         self.last_log_id.as_ref()
     }
+
+    /// The RPC protocol version of the sender, see [`Capabilities`].
+    ///
+    /// It is `0` if the sender is built before the version is introduced.
+    pub fn protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
 }
 
 impl<C> fmt::Display for TransferLeaderRequest<C>
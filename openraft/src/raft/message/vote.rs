@@ -2,6 +2,7 @@ use std::borrow::Borrow;
 use std::fmt;
 
 use crate::display_ext::DisplayOptionExt;
+use crate::network::Capabilities;
 use crate::LogId;
 use crate::RaftTypeConfig;
 use crate::Vote;
@@ -12,6 +13,14 @@ use crate::Vote;
 pub struct VoteRequest<C: RaftTypeConfig> {
     pub vote: Vote<C::NodeId>,
     pub last_log_id: Option<LogId<C::NodeId>>,
+
+    /// The RPC protocol version of the sender, see [`Capabilities`].
+    ///
+    /// It is `0` if the sender is built before the version is introduced.
+    ///
+    /// [`Capabilities`]: crate::network::Capabilities
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub protocol_version: u32,
 }
 
 impl<C> fmt::Display for VoteRequest<C>
@@ -26,7 +35,11 @@ impl<C> VoteRequest<C>
 where C: RaftTypeConfig
 {
     pub fn new(vote: Vote<C::NodeId>, last_log_id: Option<LogId<C::NodeId>>) -> Self {
-        Self { vote, last_log_id }
+        Self {
+            vote,
+            last_log_id,
+            protocol_version: Capabilities::PROTOCOL_VERSION,
+        }
     }
 }
 
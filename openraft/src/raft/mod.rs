@@ -10,6 +10,9 @@
 
 #[cfg(test)]
 mod declare_raft_types_test;
+mod event;
+pub(crate) mod event_broadcast;
+mod event_handler;
 mod impl_raft_blocking_write;
 pub(crate) mod message;
 mod raft_inner;
@@ -28,10 +31,15 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use core_state::CoreState;
+pub use event::RaftEvent;
+pub use event_broadcast::RaftEventReceiver;
+pub use event_handler::RaftEventHandler;
 pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
 pub use message::ClientWriteResponse;
 pub use message::ClientWriteResult;
+pub use message::HeartbeatRequest;
+pub use message::HeartbeatResponse;
 pub use message::InstallSnapshotRequest;
 pub use message::InstallSnapshotResponse;
 pub use message::SnapshotResponse;
@@ -58,6 +66,8 @@ use crate::core::replication_lag;
 use crate::core::sm;
 use crate::core::sm::worker;
 use crate::core::RaftCore;
+use crate::core::SlowIO;
+use crate::core::SnapshotProgress;
 use crate::core::Tick;
 use crate::display_ext::DisplayOptionExt;
 use crate::engine::Engine;
@@ -65,23 +75,39 @@ use crate::engine::EngineConfig;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
 use crate::error::Fatal;
+use crate::error::ForceSetMembershipError;
+use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
 use crate::error::InvalidStateMachineType;
+use crate::error::LogNotApplied;
+use crate::error::LogNotInSnapshot;
+use crate::error::PurgeLogError;
 use crate::error::RaftError;
+use crate::error::ReadIndexError;
+use crate::error::StartupError;
+use crate::error::WriteTimeout;
 use crate::membership::IntoNodes;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
+use crate::metrics::RaftNetworkMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
+use crate::network::metered::MeteredNetworkFactory;
+use crate::network::metered::NetworkMetricsRecorder;
+use crate::network::Capabilities;
+use crate::network::SnapshotTransform;
+use crate::raft::event_broadcast::EventSender;
 use crate::raft::raft_inner::RaftInner;
 use crate::raft::responder::Responder;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
 use crate::raft::trigger::Trigger;
+use crate::raft_state::LogStateReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
 use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::ResponderReceiverOf;
@@ -95,6 +121,8 @@ use crate::OptionalSend;
 use crate::RaftNetworkFactory;
 use crate::RaftState;
 pub use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::SnapshotId;
 use crate::StorageHelper;
 use crate::Vote;
 
@@ -238,6 +266,94 @@ where C: RaftTypeConfig
         mut log_store: LS,
         mut state_machine: SM,
     ) -> Result<Self, Fatal<C>>
+    where
+        N: RaftNetworkFactory<C>,
+        LS: RaftLogStorage<C>,
+        SM: RaftStateMachine<C>,
+    {
+        log_store.set_fsync_policy(config.fsync_policy.clone()).await?;
+        let state = StorageHelper::new(&mut log_store, &mut state_machine).get_initial_state().await?;
+
+        Ok(Self::do_new(id, config, network, log_store, state_machine, state, None).await)
+    }
+
+    /// Create and spawn a new Raft task, after checking the consistency of the state in storage.
+    ///
+    /// It is the same as [`Raft::new()`], except that it checks the state loaded from the
+    /// [`RaftLogStorage`] and the [`RaftStateMachine`] before repairing or using it, and returns a
+    /// [`StartupError`] describing the inconsistency found, e.g., a log of a term greater than the
+    /// saved vote, or logs purged before being applied. Such a node must not join the cluster,
+    /// because it may have lost data it has acknowledged.
+    ///
+    /// See [`StorageHelper::get_checked_initial_state()`] for the checks.
+    ///
+    /// [`StorageHelper::get_checked_initial_state()`]: crate::StorageHelper::get_checked_initial_state
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
+    pub async fn new_checked<LS, N, SM>(
+        id: C::NodeId,
+        config: Arc<Config>,
+        network: N,
+        mut log_store: LS,
+        mut state_machine: SM,
+    ) -> Result<Self, StartupError<C>>
+    where
+        N: RaftNetworkFactory<C>,
+        LS: RaftLogStorage<C>,
+        SM: RaftStateMachine<C>,
+    {
+        log_store.set_fsync_policy(config.fsync_policy.clone()).await?;
+        let state = StorageHelper::new(&mut log_store, &mut state_machine).get_checked_initial_state().await?;
+
+        Ok(Self::do_new(id, config, network, log_store, state_machine, state, None).await)
+    }
+
+    /// Create and spawn a new Raft task, with a [`RaftEventHandler`] to be called when this node
+    /// becomes leader or steps down.
+    ///
+    /// The other arguments are the same as [`Raft::new()`].
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
+    pub async fn new_with_event_handler<LS, N, SM, H>(
+        id: C::NodeId,
+        config: Arc<Config>,
+        network: N,
+        mut log_store: LS,
+        mut state_machine: SM,
+        event_handler: H,
+    ) -> Result<Self, Fatal<C>>
+    where
+        N: RaftNetworkFactory<C>,
+        LS: RaftLogStorage<C>,
+        SM: RaftStateMachine<C>,
+        H: RaftEventHandler<C>,
+    {
+        log_store.set_fsync_policy(config.fsync_policy.clone()).await?;
+        let state = StorageHelper::new(&mut log_store, &mut state_machine).get_initial_state().await?;
+
+        let event_handler: Box<dyn RaftEventHandler<C>> = Box::new(event_handler);
+        Ok(Self::do_new(
+            id,
+            config,
+            network,
+            log_store,
+            state_machine,
+            state,
+            Some(event_handler),
+        )
+        .await)
+    }
+
+    /// Spawn a new Raft task with the `state` loaded from the storage.
+    async fn do_new<LS, N, SM>(
+        id: C::NodeId,
+        config: Arc<Config>,
+        network: N,
+        mut log_store: LS,
+        state_machine: SM,
+        state: RaftState<C>,
+        event_handler: Option<Box<dyn RaftEventHandler<C>>>,
+    ) -> Self
     where
         N: RaftNetworkFactory<C>,
         LS: RaftLogStorage<C>,
@@ -248,13 +364,10 @@ where C: RaftTypeConfig
         let (tx_metrics, rx_metrics) = C::watch_channel(RaftMetrics::new_initial(id));
         let (tx_data_metrics, rx_data_metrics) = C::watch_channel(RaftDataMetrics::default());
         let (tx_server_metrics, rx_server_metrics) = C::watch_channel(RaftServerMetrics::default());
+        let (tx_network_metrics, rx_network_metrics) = C::watch_channel(RaftNetworkMetrics::default());
         let (tx_shutdown, rx_shutdown) = C::oneshot();
 
-        let tick_handle = Tick::spawn(
-            Duration::from_millis(config.heartbeat_interval * 3 / 2),
-            tx_notify.clone(),
-            config.enable_tick,
-        );
+        let tick_handle = Tick::spawn(config.tick_interval(), tx_notify.clone(), config.enable_tick);
 
         let runtime_config = Arc::new(RuntimeConfig::new(&config));
 
@@ -268,23 +381,28 @@ where C: RaftTypeConfig
 
         let eng_config = EngineConfig::new(id, config.as_ref());
 
-        let state = {
-            let mut helper = StorageHelper::new(&mut log_store, &mut state_machine);
-            helper.get_initial_state().await?
-        };
-
         let engine = Engine::new(state, eng_config);
 
         let sm_span = tracing::span!(parent: &core_span, Level::DEBUG, "sm_worker");
 
+        let slow_io = SlowIO::new(&config);
+        let snapshot_receiving = SnapshotProgress::new();
+
         let sm_handle = worker::Worker::spawn(
             state_machine,
             log_store.get_log_reader().await,
             tx_notify.clone(),
+            slow_io.clone(),
+            config.clone(),
             sm_span,
         );
 
-        let core: RaftCore<C, N, LS> = RaftCore {
+        let snapshot_transform = network.snapshot_transform();
+        let trace_context = network.trace_context();
+        let network = MeteredNetworkFactory::new(network, NetworkMetricsRecorder::new(tx_network_metrics));
+        let (event_sender, event_subscriber) = EventSender::new(config.event_buffer_size as usize);
+
+        let core: RaftCore<C, MeteredNetworkFactory<C, N>, LS> = RaftCore {
             id,
             config: config.clone(),
             runtime_config: runtime_config.clone(),
@@ -295,10 +413,22 @@ where C: RaftTypeConfig
             engine,
 
             client_resp_channels: BTreeMap::new(),
+            client_write_deadlines: BTreeMap::new(),
 
             replications: Default::default(),
 
             heartbeat_handle: HeartbeatWorkersHandle::new(id, config.clone()),
+            slow_io,
+            snapshot_receiving: snapshot_receiving.clone(),
+            snapshot_receive_timeouts: 0,
+            storage_retry: None,
+            last_snapshot_at: C::now(),
+            snapshot_waiters: Vec::new(),
+            flush_waiters: Vec::new(),
+            event_sender,
+            event_handler,
+            forward_clients: Default::default(),
+            leading_vote: None,
             tx_api: tx_api.clone(),
             rx_api,
 
@@ -323,13 +453,18 @@ where C: RaftTypeConfig
             rx_metrics,
             rx_data_metrics,
             rx_server_metrics,
+            rx_network_metrics,
             tx_shutdown: std::sync::Mutex::new(Some(tx_shutdown)),
             core_state: std::sync::Mutex::new(CoreState::Running(core_handle)),
 
             snapshot: C::mutex(None),
+            snapshot_receiving,
+            snapshot_transform,
+            trace_context,
+            event_subscriber,
         };
 
-        Ok(Self { inner: Arc::new(inner) })
+        Self { inner: Arc::new(inner) }
     }
 
     /// Return a handle to update runtime config.
@@ -352,6 +487,36 @@ where C: RaftTypeConfig
         &self.inner.config
     }
 
+    /// Return the transform to decode received snapshot chunks, provided by
+    /// [`RaftNetworkFactory::snapshot_transform()`].
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(crate) fn snapshot_transform(&self) -> Option<&dyn SnapshotTransform> {
+        self.inner.snapshot_transform.as_deref()
+    }
+
+    /// Build a span from the tracing context sent along with an RPC by another node.
+    ///
+    /// The context is the [`RPCOption::trace_context()`] of the sender, encoded by the
+    /// [`TraceContext`] provided by [`RaftNetworkFactory::trace_context()`]. The application should
+    /// call the `Raft` API that handles the RPC, such as [`Self::append_entries()`], within the
+    /// returned span, e.g., with [`Instrument::instrument()`], so that the request is handled in
+    /// the trace of the sender.
+    ///
+    /// It returns a disabled span if there is no [`TraceContext`] or `context` is empty, in which
+    /// case the request is handled in the span of the caller.
+    ///
+    /// [`RPCOption::trace_context()`]: crate::network::RPCOption::trace_context
+    /// [`TraceContext`]: crate::network::TraceContext
+    /// [`RaftNetworkFactory::trace_context()`]: crate::network::RaftNetworkFactory::trace_context
+    /// [`Instrument::instrument()`]: tracing::Instrument::instrument
+    #[since(version = "0.10.0")]
+    pub fn trace_span(&self, context: &[u8]) -> tracing::Span {
+        match &self.inner.trace_context {
+            Some(t) if !context.is_empty() => t.extract(context),
+            _ => tracing::Span::none(),
+        }
+    }
+
     /// Return a handle to manually trigger raft actions, such as elect or build snapshot.
     ///
     /// Example:
@@ -367,24 +532,108 @@ where C: RaftTypeConfig
     ///
     /// These RPCs are sent by the cluster leader to replicate log entries (§5.3), and are also
     /// used as heartbeats (§5.2).
+    ///
+    /// The request is handled within the span of the caller. To link it to the trace of the
+    /// sender, call it within the span returned by [`Self::trace_span()`].
     #[tracing::instrument(level = "debug", skip(self, rpc))]
     pub async fn append_entries(&self, rpc: AppendEntriesRequest<C>) -> Result<AppendEntriesResponse<C>, RaftError<C>> {
         tracing::debug!(rpc = display(&rpc), "Raft::append_entries");
+        self.accepts_protocol_version(rpc.protocol_version);
+
+        let span = tracing::Span::current();
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::AppendEntries { rpc, tx, span }, rx).await
+    }
+
+    /// Submit a heartbeat sent by the Leader to this Raft node.
+    ///
+    /// It is sent by [`RaftNetworkV2::heartbeat()`]. Unlike [`Self::append_entries()`], it only
+    /// checks the vote and updates the committed log id, without matching the logs.
+    ///
+    /// [`RaftNetworkV2::heartbeat()`]: crate::network::v2::RaftNetworkV2::heartbeat
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, rpc))]
+    pub async fn heartbeat(&self, rpc: HeartbeatRequest<C>) -> Result<HeartbeatResponse<C>, RaftError<C>> {
+        tracing::debug!(rpc = display(&rpc), "Raft::heartbeat");
+        self.accepts_protocol_version(rpc.protocol_version);
 
+        let span = tracing::Span::current();
         let (tx, rx) = C::oneshot();
-        self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).await
+        self.inner.call_core(RaftMsg::Heartbeat { rpc, tx, span }, rx).await
     }
 
     /// Submit a VoteRequest (RequestVote in the spec) RPC to this Raft node.
     ///
     /// These RPCs are sent by cluster peers which are in candidate state attempting to gather votes
     /// (§5.2).
+    ///
+    /// The vote is not granted if the protocol version of the candidate is not compatible with
+    /// this node, see [`Capabilities::is_compatible()`].
     #[tracing::instrument(level = "debug", skip(self, rpc))]
     pub async fn vote(&self, rpc: VoteRequest<C>) -> Result<VoteResponse<C>, RaftError<C>> {
         tracing::info!(rpc = display(&rpc), "Raft::vote()");
 
+        if !self.accepts_protocol_version(rpc.protocol_version) {
+            return Ok(self.reject_vote().await?);
+        }
+
+        let span = tracing::Span::current();
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::RequestVote { rpc, tx, span }, rx).await
+    }
+
+    /// Submit a pre-vote request to this Raft node.
+    ///
+    /// It is sent by a peer that is about to start an election, to check if this node would grant
+    /// the vote, when [`Config::enable_pre_vote`] is enabled. Unlike [`Self::vote()`], it does not
+    /// update the vote of this node.
+    ///
+    /// [`Config::enable_pre_vote`]: crate::Config::enable_pre_vote
+    #[tracing::instrument(level = "debug", skip(self, rpc))]
+    pub async fn pre_vote(&self, rpc: VoteRequest<C>) -> Result<VoteResponse<C>, RaftError<C>> {
+        tracing::info!(rpc = display(&rpc), "Raft::pre_vote()");
+
+        if !self.accepts_protocol_version(rpc.protocol_version) {
+            return Ok(self.reject_vote().await?);
+        }
+
+        let span = tracing::Span::current();
         let (tx, rx) = C::oneshot();
-        self.inner.call_core(RaftMsg::RequestVote { rpc, tx }, rx).await
+        self.inner.call_core(RaftMsg::RequestPreVote { rpc, tx, span }, rx).await
+    }
+
+    /// Get the RPC protocol version and the optional features this node supports.
+    ///
+    /// A node should reply with it when it receives a request sent by
+    /// [`RaftNetworkV2::capabilities()`].
+    ///
+    /// [`RaftNetworkV2::capabilities()`]: crate::network::v2::RaftNetworkV2::capabilities
+    #[since(version = "0.10.0")]
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::local()
+    }
+
+    /// Check if a request sent by a peer of `protocol_version` can be handled by this node.
+    ///
+    /// A leader does not replicate to an incompatible node, thus receiving such a request means
+    /// the peer does not negotiate, and a warning is logged.
+    fn accepts_protocol_version(&self, protocol_version: u32) -> bool {
+        let local = self.capabilities();
+        let accepted = local.accepts(protocol_version);
+        if !accepted {
+            tracing::warn!(
+                "received a request of incompatible protocol version: {}, local: {}(min {})",
+                protocol_version,
+                local.protocol_version,
+                local.min_protocol_version
+            );
+        }
+        accepted
+    }
+
+    /// Build a response that does not grant the vote, without changing the state of this node.
+    async fn reject_vote(&self) -> Result<VoteResponse<C>, Fatal<C>> {
+        self.with_raft_state(|st| VoteResponse::new(st.vote_ref(), st.last_log_id().copied(), false)).await
     }
 
     /// Get the latest snapshot from the state machine.
@@ -400,6 +649,21 @@ where C: RaftTypeConfig
         self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
     }
 
+    /// Get the signature of the latest snapshot of this node, without reading the snapshot data.
+    ///
+    /// A node should reply with it when it receives a request sent by
+    /// [`RaftNetworkV2::target_snapshot_signature()`], so that the leader can send a delta snapshot
+    /// built since it. It returns `None` if there is no snapshot.
+    ///
+    /// [`RaftNetworkV2::target_snapshot_signature()`]: crate::network::v2::RaftNetworkV2::target_snapshot_signature
+    #[since(version = "0.10.0")]
+    pub async fn snapshot_signature(&self) -> Result<Option<SnapshotSignature<C>>, Fatal<C>> {
+        let signature = self
+            .with_raft_state(|st| st.snapshot_meta.last_log_id.map(|_| st.snapshot_meta.signature()))
+            .await?;
+        Ok(signature)
+    }
+
     /// Get a snapshot data for receiving snapshot from the leader.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn begin_receiving_snapshot(&self) -> Result<Box<SnapshotDataOf<C>>, RaftError<C, Infallible>> {
@@ -410,6 +674,45 @@ where C: RaftTypeConfig
         Ok(resp)
     }
 
+    /// Open the snapshot data to receive `snapshot_id` into, resuming a snapshot partially
+    /// received before.
+    ///
+    /// It returns the number of bytes already received, `0` if there is none, and the snapshot
+    /// data handle, or `None` if the state machine does not support resuming.
+    ///
+    /// See: [`RaftStateMachine::resume_receiving_snapshot()`]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn resume_receiving_snapshot(
+        &self,
+        snapshot_id: &SnapshotId,
+    ) -> Result<Option<(u64, Box<SnapshotDataOf<C>>)>, RaftError<C, Infallible>> {
+        tracing::info!(snapshot_id = display(snapshot_id), "Raft::resume_receiving_snapshot()");
+
+        let (tx, rx) = C::oneshot();
+        let msg = RaftMsg::ResumeReceivingSnapshot {
+            snapshot_id: snapshot_id.clone(),
+            tx,
+        };
+        let resp = self.inner.call_core(msg, rx).await?;
+        Ok(resp)
+    }
+
+    /// Discard the data of a partially received snapshot, so that it will be received from the
+    /// beginning, e.g., when the received data does not match the checksum.
+    ///
+    /// See: [`RaftStateMachine::discard_receiving_snapshot()`]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn discard_receiving_snapshot(&self, snapshot_id: &SnapshotId) -> Result<(), RaftError<C, Infallible>> {
+        tracing::info!(snapshot_id = display(snapshot_id), "Raft::discard_receiving_snapshot()");
+
+        let (tx, rx) = C::oneshot();
+        let msg = RaftMsg::DiscardReceivingSnapshot {
+            snapshot_id: snapshot_id.clone(),
+            tx,
+        };
+        self.inner.call_core(msg, rx).await
+    }
+
     /// Install a completely received snapshot to the state machine.
     ///
     /// This method is used to implement an application defined snapshot transmission.
@@ -423,8 +726,15 @@ where C: RaftTypeConfig
     ) -> Result<SnapshotResponse<C>, Fatal<C>> {
         tracing::info!("Raft::install_full_snapshot()");
 
+        let span = tracing::Span::current();
         let (tx, rx) = C::oneshot();
-        let res = self.inner.call_core(RaftMsg::InstallFullSnapshot { vote, snapshot, tx }, rx).await;
+        let msg = RaftMsg::InstallFullSnapshot {
+            vote,
+            snapshot,
+            tx,
+            span,
+        };
+        let res = self.inner.call_core(msg, rx).await;
         match res {
             Ok(x) => Ok(x),
             Err(e) => {
@@ -441,6 +751,8 @@ where C: RaftTypeConfig
     ///
     /// If receiving is finished `done == true`, it installs the snapshot to the state machine.
     /// Nothing will be done if the input snapshot is older than the state machine.
+    ///
+    /// Every chunk is decoded with [`RaftNetworkFactory::snapshot_transform()`] if it is provided.
     #[tracing::instrument(level = "debug", skip_all)]
     #[cfg(feature = "tokio-rt")]
     pub async fn install_snapshot(
@@ -453,6 +765,7 @@ where C: RaftTypeConfig
         use crate::async_runtime::mutex::Mutex;
 
         tracing::debug!(req = display(&req), "Raft::install_snapshot()");
+        self.accepts_protocol_version(req.protocol_version);
 
         let req_vote = req.vote;
         let my_vote = self.with_raft_state(|state| *state.vote_ref()).await?;
@@ -475,7 +788,22 @@ where C: RaftTypeConfig
             use crate::network::snapshot_transport::SnapshotTransport;
 
             let mut streaming = self.inner.snapshot.lock().await;
-            Chunked::receive_snapshot(&mut *streaming, self, req).await?
+            let prev_id = streaming.as_ref().map(|s| s.snapshot_id().clone());
+            let meta = req.meta.clone();
+
+            let res = Chunked::receive_snapshot(&mut *streaming, self, req).await;
+
+            let progress = &self.inner.snapshot_receiving;
+            if let Some(s) = streaming.as_ref() {
+                if Some(s.snapshot_id()) != prev_id.as_ref() {
+                    self.spawn_snapshot_receive_watchdog(s.snapshot_id().clone());
+                    progress.begin(&meta, s.offset());
+                }
+                progress.update(s.offset(), None);
+            } else {
+                progress.clear();
+            }
+            res?
         };
 
         if let Some(snapshot) = finished_snapshot {
@@ -485,6 +813,54 @@ where C: RaftTypeConfig
         Ok(resp)
     }
 
+    /// Drop the snapshot being received if no chunk is received for
+    /// [`Config::snapshot_receive_idle_timeout`], e.g., the Leader crashed while sending it.
+    #[cfg(feature = "tokio-rt")]
+    fn spawn_snapshot_receive_watchdog(&self, snapshot_id: SnapshotId) {
+        use crate::async_runtime::mutex::Mutex;
+        use crate::Instant;
+
+        let Some(timeout) = self.inner.config.snapshot_receive_idle_timeout() else {
+            return;
+        };
+
+        let inner = Arc::downgrade(&self.inner);
+
+        let _handle = C::spawn(async move {
+            loop {
+                C::sleep(timeout).await;
+
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+
+                let mut streaming = inner.snapshot.lock().await;
+
+                let Some(s) = streaming.as_ref() else {
+                    return;
+                };
+
+                if s.snapshot_id() != &snapshot_id {
+                    return;
+                }
+
+                let idle = s.updated_at().elapsed();
+                if idle >= timeout {
+                    tracing::warn!(
+                        snapshot_id = display(&snapshot_id),
+                        "no snapshot chunk received for {:?}, drop the partially received snapshot",
+                        idle
+                    );
+                    *streaming = None;
+                    inner.snapshot_receiving.clear();
+
+                    let _ = inner.send_msg(RaftMsg::SnapshotReceiveTimedOut { snapshot_id }).await;
+                    return;
+                }
+            }
+        });
+    }
+
     /// Get the ID of the current leader from this Raft node.
     ///
     /// This method is based on the Raft metrics system which does a good job at staying
@@ -495,6 +871,54 @@ where C: RaftTypeConfig
         self.metrics().borrow_watched().current_leader
     }
 
+    /// Returns `true` if this node believes it is the leader, according to the metrics.
+    ///
+    /// Like [`Raft::current_leader()`], it does not communicate with other nodes, and the
+    /// leadership may already be lost. Use it to route requests cheaply, and use
+    /// [`Raft::client_read()`] or [`Raft::ensure_linearizable()`] to guard against stale reads.
+    ///
+    /// It is not named `is_leader()`, because that is the name of the deprecated
+    /// [`Raft::is_leader()`], which confirms the leadership with a quorum.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn is_current_leader(&self) -> bool {
+        self.metrics().borrow_watched().state == ServerState::Leader
+    }
+
+    /// Ensures a read operation performed following this method is linearizable, with a fast path
+    /// rejecting the read on a node that is definitely not the leader.
+    ///
+    /// If this node is not a leader according to the metrics, it returns
+    /// `Err(CheckIsLeaderError::ForwardToLeader)` at once, with the leader this node knows about,
+    /// without sending any message to `RaftCore` or other nodes. Otherwise it is the same as
+    /// [`Raft::ensure_linearizable()`], and `Err(CheckIsLeaderError::QuorumNotEnough)` means this
+    /// node believes it is the leader but fails to confirm it with a quorum.
+    ///
+    /// This lets a frontend distinguish "definitely not leader", where it should redirect the
+    /// request, from "leader but not yet confirmed", where it may retry.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn client_read(&self) -> Result<Option<LogId<C::NodeId>>, RaftError<C, CheckIsLeaderError<C>>> {
+        let forward_to_leader = {
+            let m = self.metrics().borrow_watched();
+            if m.state == ServerState::Leader {
+                None
+            } else {
+                let leader_id = m.current_leader;
+                let leader_node = leader_id.and_then(|id| m.membership_config.membership().get_node(&id).cloned());
+                Some(ForwardToLeader { leader_id, leader_node })
+            }
+        };
+
+        if let Some(forward_to_leader) = forward_to_leader {
+            return Err(RaftError::APIError(CheckIsLeaderError::ForwardToLeader(
+                forward_to_leader,
+            )));
+        }
+
+        self.ensure_linearizable().await
+    }
+
     /// Check to ensure this node is still the cluster leader, in order to guard against stale reads
     /// (§8).
     ///
@@ -587,6 +1011,55 @@ where C: RaftTypeConfig
         Ok((read_log_id, applied))
     }
 
+    /// Ensures a read operation performed following this method are linearizable, on a leader, a
+    /// follower or a learner.
+    ///
+    /// On the leader it is the same as [`ensure_linearizable()`](Raft::ensure_linearizable).
+    /// On other nodes it asks the current leader for a read index with
+    /// [`RaftNetworkV2::read_index()`], then waits for the local state machine to apply up to it.
+    /// This way read requests can be served by any node to spread the read load.
+    ///
+    /// Returns:
+    /// - `Ok(read_log_id)` when the local state machine has applied up to `read_log_id`.
+    /// - `Err(ReadIndexError::ForwardToLeader)` if the leader is unknown, or the leader is changed.
+    /// - `Err(ReadIndexError::QuorumNotEnough)` if the leader fails to communicate with a quorum.
+    /// - `Err(ReadIndexError::RPCError)` if the leader can not be reached.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// my_raft.read_index().await?;
+    /// // Proceed with the state machine read
+    /// ```
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    ///
+    /// [`RaftNetworkV2::read_index()`]: crate::network::v2::RaftNetworkV2::read_index
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn read_index(&self) -> Result<Option<LogId<C::NodeId>>, RaftError<C, ReadIndexError<C>>> {
+        let read_log_id = match self.get_read_log_id().await {
+            Ok((read_log_id, _applied)) => read_log_id,
+            Err(RaftError::APIError(CheckIsLeaderError::ForwardToLeader(_))) => {
+                let (tx, rx) = C::oneshot();
+                self.inner.call_core(RaftMsg::ReadIndex { tx }, rx).await?
+            }
+            Err(RaftError::APIError(e)) => return Err(RaftError::APIError(e.into())),
+            Err(RaftError::Fatal(f)) => return Err(RaftError::Fatal(f)),
+        };
+
+        self.wait(None)
+            .applied_index_at_least(read_log_id.index(), "read_index")
+            .await
+            .map_err(|e| match e {
+                WaitError::Timeout(_, _) => {
+                    unreachable!("did not specify timeout")
+                }
+                WaitError::ShuttingDown => Fatal::Stopped,
+            })?;
+
+        Ok(read_log_id)
+    }
+
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
     ///
     /// It will be appended to the log, committed to the cluster, and then applied to the
@@ -601,10 +1074,19 @@ where C: RaftTypeConfig
     /// response. If it receives a command whose serial number has already been executed, it
     /// responds immediately without re-executing the request (§8). The
     /// [`RaftStateMachine::apply`] method is the perfect place to implement
-    /// this.
+    /// this, with the help of [`ClientSessions`].
     ///
     /// These are application specific requirements, and must be implemented by the application
     /// which is being built on top of Raft.
+    ///
+    /// If it is called on a non-leader, it returns a [`ForwardToLeader`] error, unless
+    /// [`Config::forward_to_leader`] is enabled, in which case the request is forwarded to the
+    /// leader with [`RaftNetworkV2::forward_client_write()`].
+    ///
+    /// [`ClientSessions`]: crate::storage::ClientSessions
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`Config::forward_to_leader`]: crate::Config::forward_to_leader
+    /// [`RaftNetworkV2::forward_client_write()`]: crate::network::v2::RaftNetworkV2::forward_client_write
     #[tracing::instrument(level = "debug", skip(self, app_data))]
     pub async fn client_write<E>(
         &self,
@@ -622,12 +1104,69 @@ where C: RaftTypeConfig
         Ok(client_write_response)
     }
 
+    /// Submit a mutating client request to Raft, and wait for its response until `timeout`.
+    ///
+    /// It is the same as [`Raft::client_write`], except that it returns a
+    /// [`ClientWriteError::Timeout`] error if the request is not applied within `timeout`, e.g.,
+    /// when the leader has lost the quorum.
+    ///
+    /// The deadline is passed to `RaftCore`, which fails the responder with the timeout error and
+    /// drops it when the deadline expires, thus a timed out write does not hold any resource.
+    ///
+    /// **A timeout does not mean the write is discarded**: the entry may have been appended, and it
+    /// may still be committed and applied after the timeout, with its response discarded. A client
+    /// that retries it should make it idempotent, e.g., with
+    /// [`ClientSessions`](crate::storage::ClientSessions).
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_with_timeout<E>(
+        &self,
+        app_data: C::D,
+        timeout: Duration,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
+        let deadline = C::now() + timeout;
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteWithDeadline {
+                app_data,
+                tx,
+                deadline,
+                timeout,
+            })
+            .await?;
+
+        // RaftCore checks the deadline on every tick.
+        // This timeout guarantees the caller returns in time, e.g., when tick is disabled.
+        let res: ClientWriteResult<C> = match C::timeout(timeout, self.inner.recv_msg(rx)).await {
+            Ok(res) => res?,
+            Err(_) => {
+                tracing::debug!(timeout = debug(timeout), "client write timeout");
+                return Err(RaftError::APIError(WriteTimeout { timeout }.into()));
+            }
+        };
+
+        let client_write_response = res.map_err(|e| RaftError::APIError(e))?;
+        Ok(client_write_response)
+    }
+
     /// Submit a mutating client request to Raft to update the state machine, returns an application
     /// defined response receiver [`Responder::Receiver`].
     ///
     /// `_ff` means fire and forget.
     ///
     /// It is same as [`Raft::client_write`] but does not wait for the response.
+    ///
+    /// It returns as soon as the request is sent to `RaftCore`, before it is appended to the log.
+    /// Thus the log id is unknown, and an error, such as [`ForwardToLeader`] if this node is not a
+    /// leader, is delivered only through the returned receiver. To return once the entry is
+    /// appended, with its log id, use [`Raft::client_write_submit()`] instead.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
     #[tracing::instrument(level = "debug", skip(self, app_data))]
     pub async fn client_write_ff(&self, app_data: C::D) -> Result<ResponderReceiverOf<C>, Fatal<C>> {
         let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
@@ -637,6 +1176,99 @@ where C: RaftTypeConfig
         Ok(rx)
     }
 
+    /// Submit a mutating client request to Raft, returns once the request is appended to the
+    /// leader's log, with the assigned log id and a receiver of the response.
+    ///
+    /// Unlike [`Raft::client_write`], it does not wait for the request to be applied. It returns
+    /// when the entry is flushed to the local log store, so that a pipeline can decouple
+    /// submitting requests from collecting responses:
+    /// - To wait for the entry to be committed, use [`Raft::wait()`] for the applied or committed
+    ///   index.
+    /// - To get the result of applying it to the state machine, await the returned
+    ///   [`Responder::Receiver`].
+    ///
+    /// It is not the fire-and-forget [`Raft::client_write_ff()`], which returns before the request
+    /// is appended, thus without a log id and without telling whether the request is accepted.
+    ///
+    /// If this node is not a leader, or the entry is truncated before it is flushed, it returns a
+    /// [`ForwardToLeader`] error. The request is not forwarded even if
+    /// [`Config::forward_to_leader`] is enabled.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`Config::forward_to_leader`]: crate::Config::forward_to_leader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_submit<E>(
+        &self,
+        app_data: C::D,
+    ) -> Result<(LogId<C::NodeId>, ResponderReceiverOf<C>), RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
+        let (log_id_tx, log_id_rx) = C::oneshot();
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteSubmit {
+                app_data,
+                tx,
+                log_id_tx,
+            })
+            .await?;
+
+        let log_id = self.inner.recv_msg(log_id_rx).await?;
+
+        if let Some(log_id) = log_id {
+            return Ok((log_id, rx));
+        }
+
+        // The request is rejected or truncated, the error is sent to the responder.
+        let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+        match res {
+            Ok(_) => unreachable!("a rejected request should not be applied"),
+            Err(e) => Err(RaftError::APIError(e)),
+        }
+    }
+
+    /// Submit a batch of mutating client requests to Raft, and wait for all of their responses.
+    ///
+    /// The requests are appended to the log in the given order, with a single message to
+    /// `RaftCore` and a single log IO, which amortizes the overhead per request for a
+    /// high-throughput producer. A response is returned for every request, in the same order.
+    ///
+    /// If this node is not a leader, every response is a [`ForwardToLeader`] error. The batch is
+    /// not forwarded even if [`Config::forward_to_leader`] is enabled.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`Config::forward_to_leader`]: crate::Config::forward_to_leader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_many<E>(&self, app_data: Vec<C::D>) -> Result<Vec<ClientWriteResult<C>>, Fatal<C>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let mut requests = Vec::with_capacity(app_data.len());
+        let mut receivers = Vec::with_capacity(app_data.len());
+
+        for d in app_data {
+            let (d, tx, rx) = ResponderOf::<C>::from_app_data(d);
+            requests.push((d, tx));
+            receivers.push(rx);
+        }
+
+        self.inner.send_msg(RaftMsg::ClientWriteMany { requests }).await?;
+
+        let mut results = Vec::with_capacity(receivers.len());
+        for rx in receivers {
+            let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+            results.push(res);
+        }
+
+        Ok(results)
+    }
+
     /// Handle the LeaderTransfer request from a Leader node.
     ///
     /// If this node is the `to` node, it resets the Leader lease and triggers an election when the
@@ -652,6 +1284,8 @@ where C: RaftTypeConfig
     /// [`RaftNetworkV2::transfer_leader`]: crate::network::v2::RaftNetworkV2::transfer_leader
     #[since(version = "0.10.0")]
     pub async fn handle_transfer_leader(&self, req: TransferLeaderRequest<C>) -> Result<(), Fatal<C>> {
+        self.accepts_protocol_version(req.protocol_version());
+
         // Reset the Leader lease at once and quit, if this is not the assigned next leader.
         // Only the assigned next Leader waits for the log to be flushed.
         if req.to_node_id == self.inner.id {
@@ -661,6 +1295,7 @@ where C: RaftTypeConfig
         let raft_msg = RaftMsg::HandleTransferLeader {
             from: req.from_leader,
             to: req.to_node_id,
+            span: tracing::Span::current(),
         };
 
         self.inner.send_msg(raft_msg).await?;
@@ -730,11 +1365,17 @@ where C: RaftTypeConfig
     ///
     /// This command should be called on pristine nodes — where the log index is 0 and the node is
     /// in Learner state — as if either of those constraints are false, it indicates that the
-    /// cluster is already formed and in motion. If `InitializeError::NotAllowed` is returned
-    /// from this function, it is safe to ignore, as it simply indicates that the cluster is
-    /// already up and running, which is ultimately the goal of this function. You can check
-    /// if the cluster is initialized with [`Raft::is_initialized()`] and then avoid re-initialize
-    /// it in case you want to get rid of this error.
+    /// cluster is already formed and in motion.
+    ///
+    /// It is idempotent: calling it on a node already initialized with the same membership config
+    /// does nothing and returns `Ok`, thus it is safe to call it on every boot. Otherwise, on an
+    /// initialized node it returns:
+    /// - [`InitializeError::AlreadyInitialized`] with the current membership config, if the node
+    ///   has a different membership config;
+    /// - [`InitializeError::NotAllowed`] with the current vote and last log id, if the node has
+    ///   voted or has logs but no membership config.
+    ///
+    /// You can check if the cluster is initialized with [`Raft::is_initialized()`].
     ///
     /// This command will work for single-node or multi-node cluster formation. This command
     /// should be called with all discovered nodes which need to be part of cluster, and as such
@@ -764,6 +1405,103 @@ where C: RaftTypeConfig
             .await
     }
 
+    /// Initialize an uninitialized node with a snapshot, e.g., restored from a backup of another
+    /// node, instead of with a membership config as [`Raft::initialize()`] does.
+    ///
+    /// The snapshot is installed in the same way as one replicated from the Leader:
+    /// the state machine is replaced with the snapshot, the membership config is updated to the
+    /// one included in the snapshot, and the logs included in the snapshot are purged.
+    /// This node does not start an election by itself; it works as a follower or learner
+    /// according to the membership config in the snapshot.
+    ///
+    /// It returns after the snapshot is installed.
+    /// If this node is already initialized, i.e., it has logs or a vote, it returns
+    /// [`InitializeError::NotAllowed`].
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn initialize_with_snapshot(
+        &self,
+        snapshot: Snapshot<C>,
+    ) -> Result<(), RaftError<C, InitializeError<C>>> {
+        tracing::info!(snapshot = display(&snapshot), "Raft::initialize_with_snapshot()");
+
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::InitializeWithSnapshot { snapshot, tx }, rx).await
+    }
+
+    /// **Unsafe**: forcibly set the membership config on this node, bypassing the consensus.
+    ///
+    /// This is a disaster recovery tool for when a quorum of the voters are permanently lost and
+    /// the cluster can not elect a leader or commit any log any more. Call it with the same
+    /// `members` on every surviving node: each of them appends a membership log with the given
+    /// voters and starts to elect. Once a leader is elected with the new membership, the
+    /// cluster works again and the lost nodes are no longer members.
+    ///
+    /// **This breaks the guarantees of Raft**: logs committed by the lost nodes but not yet
+    /// replicated to the survivors are lost, and if a node believed lost comes back, it may
+    /// form another cluster with its old membership. Use it only when the lost nodes will never
+    /// come back, e.g., their storage is destroyed.
+    ///
+    /// As a safety check, it is refused with [`ForceSetMembershipError::LeaderAlive`] if this
+    /// node is a leader or the lease of a known leader has not yet expired: in such case use
+    /// [`Raft::change_membership()`] instead. And `members` has to contain this node as a
+    /// voter, otherwise [`ForceSetMembershipError::NotInMembers`] is returned.
+    ///
+    /// It returns after the membership log is flushed to the local storage.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn force_set_membership<T>(&self, members: T) -> Result<(), RaftError<C, ForceSetMembershipError<C>>>
+    where T: IntoNodes<C::NodeId, C::Node> + Debug {
+        tracing::warn!(members = debug(&members), "Raft::force_set_membership()");
+
+        let (tx, rx) = C::oneshot();
+        self.inner
+            .call_core(
+                RaftMsg::ForceSetMembership {
+                    members: members.into_nodes(),
+                    tx,
+                },
+                rx,
+            )
+            .await
+    }
+
+    /// Purge logs up to and including the log at index `upto` at once, without waiting for
+    /// the snapshot policy to do it.
+    ///
+    /// It is meant for an application that backs up the logs elsewhere and wants to reclaim disk
+    /// space. Unlike [`Trigger::purge_log()`], which silently purges as many logs as possible, it
+    /// checks the bounds and returns:
+    /// - [`PurgeLogError::NotApplied`] if the log at `upto` is not yet applied;
+    /// - [`PurgeLogError::NotInSnapshot`] if the log at `upto` is not yet included in a snapshot.
+    ///
+    /// Logs that are already purged are ignored. Same as [`Trigger::purge_log()`], it returns once
+    /// the purge is scheduled: the logs may still be in use by a replication task, and are deleted
+    /// when it finishes.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn purge_log(&self, upto: u64) -> Result<(), RaftError<C, PurgeLogError<C>>> {
+        let (last_applied, snapshot_last_log_id) =
+            self.with_raft_state(|st| (st.io_applied().copied(), st.snapshot_last_log_id().copied())).await?;
+
+        if Some(upto) > last_applied.index() {
+            return Err(RaftError::APIError(LogNotApplied { upto, last_applied }.into()));
+        }
+
+        if Some(upto) > snapshot_last_log_id.index() {
+            return Err(RaftError::APIError(
+                LogNotInSnapshot {
+                    upto,
+                    snapshot_last_log_id,
+                }
+                .into(),
+            ));
+        }
+
+        self.trigger().purge_log(upto).await?;
+        Ok(())
+    }
+
     /// Returns Ok() with the latest known matched log id if it should quit waiting: leader change,
     /// node removed, or replication becomes upto date.
     ///
@@ -975,6 +1713,34 @@ where C: RaftTypeConfig
         self.inner.rx_server_metrics.clone()
     }
 
+    /// Get a handle to the network metrics channel.
+    ///
+    /// It reports the number, errors and latency of the RPCs sent to each target, collected by
+    /// wrapping the [`RaftNetworkFactory`] passed to [`Raft::new()`].
+    #[since(version = "0.10.0")]
+    pub fn network_metrics(&self) -> WatchReceiverOf<C, RaftNetworkMetrics<C>> {
+        self.inner.rx_network_metrics.clone()
+    }
+
+    /// Subscribe to the state changes of this node, such as becoming the leader or installing a
+    /// snapshot.
+    ///
+    /// Every subscriber receives all of the [`RaftEvent`]s emitted after it subscribes, in order,
+    /// so an application does not need to poll [`Raft::metrics()`] and diff the fields to detect
+    /// transitions. The events are emitted at the state transitions in `RaftCore`, thus a change
+    /// that is reverted soon is still delivered.
+    ///
+    /// The events are buffered in a channel of [`Config::event_buffer_size`] shared by all of the
+    /// subscribers. A subscriber that falls behind misses the oldest events and receives a
+    /// [`RaftEvent::Lagged`] instead, so that a slow subscriber never blocks `RaftCore` or grows
+    /// the memory without limit.
+    ///
+    /// [`Config::event_buffer_size`]: crate::Config::event_buffer_size
+    #[since(version = "0.10.0")]
+    pub fn subscribe(&self) -> RaftEventReceiver<C> {
+        self.inner.event_subscriber.subscribe()
+    }
+
     /// Get a handle to wait for the metrics to satisfy some condition.
     ///
     /// If `timeout` is `None`, then it will wait forever(10 years).
@@ -1006,9 +1772,36 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Wait until the log entry of `log_id` is applied to the local state machine, i.e.,
+    /// `last_applied >= log_id`.
+    ///
+    /// It is built on the metrics channel, see [`Raft::wait()`]. An application can implement
+    /// read-your-writes on a follower or learner: write on the leader, then wait for the returned
+    /// log id to be applied on the node to read from.
+    ///
+    /// `log_id` should be a committed log id, e.g., the one returned by [`Raft::client_write`].
+    /// If `timeout` is `None`, then it will wait forever(10 years).
+    #[since(version = "0.10.0")]
+    pub async fn wait_applied(
+        &self,
+        log_id: LogId<C::NodeId>,
+        timeout: Option<Duration>,
+    ) -> Result<RaftMetrics<C>, WaitError> {
+        self.wait(timeout)
+            .metrics(
+                |m| m.last_applied >= Some(log_id),
+                format!("wait_applied: last_applied >= {}", log_id),
+            )
+            .await
+    }
+
     /// Shutdown this Raft node.
     ///
-    /// It sends a shutdown signal and waits until `RaftCore` returns.
+    /// It sends a shutdown signal and waits until `RaftCore` returns. New requests are no longer
+    /// accepted. Before `RaftCore` returns, it stops the replication tasks and waits for the
+    /// logs already being applied, so that their clients receive the responses. The other pending
+    /// client requests receive a [`Fatal::Stopped`] error. A partially received snapshot is
+    /// discarded.
     pub async fn shutdown(&self) -> Result<(), JoinErrorOf<C>> {
         if let Some(tx) = self.inner.tx_shutdown.lock().unwrap().take() {
             // A failure to send means the RaftCore is already shutdown. Continue to check the task
@@ -1021,6 +1814,12 @@ where C: RaftTypeConfig
             let _ = join_handle.await;
         }
 
+        // Discard the partially received snapshot, it can not be installed any more.
+        let streaming = self.inner.snapshot.lock().await.take();
+        if let Some(s) = streaming {
+            tracing::info!("discard partially received snapshot: {}", s.snapshot_id());
+        }
+
         // TODO(xp): API change: replace `JoinError` with `Fatal`,
         //           to let the caller know the return value of RaftCore task.
         Ok(())
@@ -11,12 +11,17 @@ use crate::async_runtime::MpscUnboundedSender;
 use crate::config::RuntimeConfig;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::SnapshotProgress;
 use crate::core::TickHandle;
 use crate::error::Fatal;
 use crate::error::RaftError;
 use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftNetworkMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::network::SnapshotTransform;
+use crate::network::TraceContext;
 use crate::raft::core_state::CoreState;
+use crate::raft::event_broadcast::EventSubscriber;
 use crate::type_config::alias::AsyncRuntimeOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::MutexOf;
@@ -43,6 +48,7 @@ where C: RaftTypeConfig
     pub(in crate::raft) rx_metrics: WatchReceiverOf<C, RaftMetrics<C>>,
     pub(in crate::raft) rx_data_metrics: WatchReceiverOf<C, RaftDataMetrics<C>>,
     pub(in crate::raft) rx_server_metrics: WatchReceiverOf<C, RaftServerMetrics<C>>,
+    pub(in crate::raft) rx_network_metrics: WatchReceiverOf<C, RaftNetworkMetrics<C>>,
 
     pub(in crate::raft) tx_shutdown: std::sync::Mutex<Option<OneshotSenderOf<C, ()>>>,
     pub(in crate::raft) core_state: std::sync::Mutex<CoreState<C>>,
@@ -51,6 +57,19 @@ where C: RaftTypeConfig
     #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
     // This field will only be read when feature tokio-rt is on
     pub(in crate::raft) snapshot: MutexOf<C, Option<crate::network::snapshot_transport::Streaming<C>>>,
+
+    /// The progress of the snapshot being received, reported in the metrics by `RaftCore`.
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(in crate::raft) snapshot_receiving: SnapshotProgress<C>,
+
+    /// Decodes every received snapshot chunk, provided by the network factory.
+    pub(in crate::raft) snapshot_transform: Option<Arc<dyn SnapshotTransform>>,
+
+    /// Extracts the tracing context of received RPCs, provided by the network factory.
+    pub(in crate::raft) trace_context: Option<Arc<dyn TraceContext>>,
+
+    /// Creates the receivers of [`RaftEvent`](crate::raft::RaftEvent)s.
+    pub(in crate::raft) event_subscriber: EventSubscriber<C>,
 }
 
 impl<C> RaftInner<C>
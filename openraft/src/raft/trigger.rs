@@ -1,8 +1,13 @@
 //! Trigger an action to RaftCore by external caller.
 
+use openraft_macros::since;
+
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::raft_msg::RaftMsg;
 use crate::error::Fatal;
 use crate::raft::RaftInner;
+use crate::storage::SnapshotMeta;
+use crate::type_config::TypeConfigExt;
 use crate::RaftTypeConfig;
 
 /// Trigger is an interface to trigger an action to RaftCore by external caller.
@@ -18,8 +23,10 @@ use crate::RaftTypeConfig;
 ///
 /// ```ignore
 /// raft.trigger().heartbeat().await?;
+/// raft.trigger().tick().await?;
 /// raft.trigger().snapshot().await?;
-/// raft.trigger().purge_log().await?;
+/// raft.trigger().snapshot_and_wait().await?;
+/// raft.trigger().purge_log(100).await?;
 /// ```
 ///
 /// [`Raft::trigger()`]: crate::Raft::trigger
@@ -38,8 +45,13 @@ where C: RaftTypeConfig
 
     /// Trigger election at once and return at once.
     ///
+    /// It makes this node campaign immediately without waiting for the election timeout, e.g.,
+    /// for a management layer to move the leadership to this node, or to restore a leader at once
+    /// after fixing a network issue. This node must be a voter, otherwise it is ignored.
+    ///
     /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
     /// It is not affected by `Raft::enable_elect(false)`.
+    #[doc(alias = "trigger_election")]
     pub async fn elect(&self) -> Result<(), Fatal<C>> {
         self.raft_inner.send_external_command(ExternalCommand::Elect, "trigger_elect").await
     }
@@ -52,6 +64,22 @@ where C: RaftTypeConfig
         self.raft_inner.send_external_command(ExternalCommand::Heartbeat, "trigger_heartbeat").await
     }
 
+    /// Trigger a tick at once and return at once.
+    ///
+    /// A tick checks every timer in `RaftCore`: the election timeout, the heartbeat, the quorum,
+    /// building a snapshot and continuing a purge. It lets an application drive these timeouts from
+    /// an external tick source, e.g., a simulation clock, with the internal tick disabled by
+    /// [`Config::enable_tick`] or [`RuntimeConfigHandle::tick()`].
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    ///
+    /// [`Config::enable_tick`]: crate::Config::enable_tick
+    /// [`RuntimeConfigHandle::tick()`]: crate::raft::RuntimeConfigHandle::tick
+    #[since(version = "0.10.0")]
+    pub async fn tick(&self) -> Result<(), Fatal<C>> {
+        self.raft_inner.send_external_command(ExternalCommand::Tick, "trigger_tick").await
+    }
+
     /// Trigger to build a snapshot at once and return at once.
     ///
     /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
@@ -59,6 +87,25 @@ where C: RaftTypeConfig
         self.raft_inner.send_external_command(ExternalCommand::Snapshot, "trigger_snapshot").await
     }
 
+    /// Trigger to build a snapshot at once and wait for it to be built.
+    ///
+    /// It returns the meta of a snapshot that includes at least all of the logs applied to the
+    /// state machine when this method is called, regardless of the [`SnapshotPolicy`].
+    /// If a snapshot is being built when this method is called, it may wait for another one.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g. shut down or having storage error.
+    ///
+    /// [`SnapshotPolicy`]: crate::SnapshotPolicy
+    pub async fn snapshot_and_wait(&self) -> Result<SnapshotMeta<C>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::SnapshotAndWait { tx };
+        let res = self.raft_inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await;
+        res.map_err(|e| {
+            // Safe unwrap: `RaftError<Infallible>` is always a Fatal.
+            e.into_fatal().unwrap()
+        })
+    }
+
     /// Initiate the log purge up to and including the given `upto` log index.
     ///
     /// Logs that are not included in a snapshot will **NOT** be purged.
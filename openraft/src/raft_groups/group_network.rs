@@ -0,0 +1,244 @@
+use openraft_macros::add_async_trait;
+use openraft_macros::since;
+
+use crate::error::RPCError;
+use crate::network::v2::RaftNetworkV2;
+use crate::network::RPCOption;
+use crate::network::RaftNetworkFactory;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::raft_groups::GroupId;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+
+/// A network factory shared by all the Raft groups on a node.
+///
+/// It is the same as [`RaftNetworkFactory`], except that it is told which group a client is
+/// created for. A client should tag every RPC with the `group_id`, so that the receiving node can
+/// dispatch it to the member of that group, e.g., with [`RaftGroups::get()`]. The clients of
+/// different groups sending to the same target can share one connection.
+///
+/// [`RaftGroups::get()`]: crate::raft_groups::RaftGroups::get
+#[since(version = "0.10.0")]
+#[add_async_trait]
+pub trait SharedNetworkFactory<C, G>: OptionalSend + OptionalSync + 'static
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+{
+    /// Actual type of the network handling a single connection.
+    type Network: RaftNetworkV2<C>;
+
+    /// Create a new network instance sending RPCs of group `group_id` to the target node.
+    async fn new_client(&mut self, group_id: G, target: C::NodeId, node: &C::Node) -> Self::Network;
+
+    /// Send the heartbeats of several groups to the target node in one RPC.
+    ///
+    /// The receiving node should pass the batch to [`RaftGroups::handle_heartbeats()`],
+    /// and send back the responses. It returns a result for every group: a failure of one group,
+    /// e.g., the group is not on the target node, does not fail the heartbeats of other groups.
+    /// If the whole batch can not be delivered, every group gets the error.
+    ///
+    /// It is called by [`HeartbeatBatcher`], which collects the heartbeats sent to the same target
+    /// in a short window. By default, every heartbeat is sent one by one with
+    /// [`RaftNetworkV2::heartbeat()`] of a client of its group, which does not save any RPC.
+    ///
+    /// **Note**: if the clients created by [`new_client()`](Self::new_client) send heartbeats
+    /// through a [`HeartbeatBatcher`], this method must be implemented: the default would pass
+    /// every heartbeat back to the batcher, which calls this method again, without end.
+    ///
+    /// [`RaftGroups::handle_heartbeats()`]: crate::raft_groups::RaftGroups::handle_heartbeats
+    /// [`HeartbeatBatcher`]: crate::raft_groups::HeartbeatBatcher
+    async fn send_heartbeat_batch(
+        &mut self,
+        target: C::NodeId,
+        node: &C::Node,
+        batch: Vec<(G, HeartbeatRequest<C>)>,
+        option: RPCOption,
+    ) -> Vec<(G, Result<HeartbeatResponse<C>, RPCError<C>>)> {
+        let mut resps = Vec::with_capacity(batch.len());
+
+        for (group_id, rpc) in batch {
+            let mut client = self.new_client(group_id.clone(), target, node).await;
+            let res = client.heartbeat(rpc, option.clone()).await;
+            resps.push((group_id, res));
+        }
+
+        resps
+    }
+}
+
+/// A [`RaftNetworkFactory`] for one Raft group, which creates clients with a shared
+/// [`SharedNetworkFactory`].
+///
+/// Every group is given its own `GroupNetworkFactory` when creating the [`Raft`], while they
+/// share the inner factory, e.g., an `Arc` of a connection pool.
+///
+/// [`Raft`]: crate::Raft
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone)]
+pub struct GroupNetworkFactory<G, F> {
+    group_id: G,
+    inner: F,
+}
+
+impl<G, F> GroupNetworkFactory<G, F> {
+    /// Create a factory for the group `group_id`, creating clients with the shared `inner`.
+    pub fn new(group_id: G, inner: F) -> Self {
+        Self { group_id, inner }
+    }
+
+    /// Return the id of the group this factory creates clients for.
+    pub fn group_id(&self) -> &G {
+        &self.group_id
+    }
+}
+
+impl<C, G, F> RaftNetworkFactory<C> for GroupNetworkFactory<G, F>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+    F: SharedNetworkFactory<C, G>,
+{
+    type Network = F::Network;
+
+    async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network {
+        self.inner.new_client(self.group_id.clone(), target, node).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use anyerror::AnyError;
+
+    use super::GroupNetworkFactory;
+    use super::SharedNetworkFactory;
+    use crate::engine::testing::UTConfig;
+    use crate::error::RPCError;
+    use crate::error::ReplicationClosed;
+    use crate::error::StreamingError;
+    use crate::error::Unreachable;
+    use crate::network::v2::RaftNetworkV2;
+    use crate::network::RPCOption;
+    use crate::network::RaftNetworkFactory;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::AppendEntriesResponse;
+    use crate::raft::HeartbeatRequest;
+    use crate::raft::HeartbeatResponse;
+    use crate::raft::SnapshotResponse;
+    use crate::raft::VoteRequest;
+    use crate::raft::VoteResponse;
+    use crate::storage::Snapshot;
+    use crate::testing::router::RouterNetwork;
+    use crate::testing::Router;
+    use crate::OptionalSend;
+    use crate::Vote;
+
+    /// A shared factory that records the group id and target of every client it creates.
+    #[derive(Clone)]
+    struct Shared {
+        created: Arc<Mutex<Vec<(u64, u64)>>>,
+        router: Router<UTConfig>,
+    }
+
+    impl SharedNetworkFactory<UTConfig, u64> for Shared {
+        type Network = RouterNetwork<UTConfig>;
+
+        async fn new_client(&mut self, group_id: u64, target: u64, node: &()) -> Self::Network {
+            self.created.lock().unwrap().push((group_id, target));
+            self.router.new_client(target, node).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_group_network_factory() {
+        let shared = Shared {
+            created: Arc::new(Mutex::new(vec![])),
+            router: Router::new(),
+        };
+
+        let mut g1 = GroupNetworkFactory::new(1, shared.clone());
+        let mut g2 = GroupNetworkFactory::new(2, shared.clone());
+        assert_eq!(&1, g1.group_id());
+
+        let _ = RaftNetworkFactory::<UTConfig>::new_client(&mut g1, 5, &()).await;
+        let _ = RaftNetworkFactory::<UTConfig>::new_client(&mut g2, 5, &()).await;
+        let _ = RaftNetworkFactory::<UTConfig>::new_client(&mut g1, 6, &()).await;
+
+        assert_eq!(vec![(1, 5), (2, 5), (1, 6)], *shared.created.lock().unwrap());
+    }
+
+    /// A client of one group, that only sends heartbeats, and the group 3 is unreachable.
+    struct GroupClient {
+        group_id: u64,
+    }
+
+    impl RaftNetworkV2<UTConfig> for GroupClient {
+        async fn append_entries(
+            &mut self,
+            _rpc: AppendEntriesRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<UTConfig>, RPCError<UTConfig>> {
+            unreachable!("only heartbeat is sent")
+        }
+
+        async fn heartbeat(
+            &mut self,
+            rpc: HeartbeatRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<HeartbeatResponse<UTConfig>, RPCError<UTConfig>> {
+            if self.group_id == 3 {
+                return Err(RPCError::Unreachable(Unreachable::new(&AnyError::error("group 3"))));
+            }
+            Ok(HeartbeatResponse::new(rpc.vote))
+        }
+
+        async fn vote(
+            &mut self,
+            _rpc: VoteRequest<UTConfig>,
+            _option: RPCOption,
+        ) -> Result<VoteResponse<UTConfig>, RPCError<UTConfig>> {
+            unreachable!("only heartbeat is sent")
+        }
+
+        async fn full_snapshot(
+            &mut self,
+            _vote: Vote<u64>,
+            _snapshot: Snapshot<UTConfig>,
+            _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+            _option: RPCOption,
+        ) -> Result<SnapshotResponse<UTConfig>, StreamingError<UTConfig>> {
+            unreachable!("only heartbeat is sent")
+        }
+    }
+
+    struct PerGroup;
+
+    impl SharedNetworkFactory<UTConfig, u64> for PerGroup {
+        type Network = GroupClient;
+
+        async fn new_client(&mut self, group_id: u64, _target: u64, _node: &()) -> Self::Network {
+            GroupClient { group_id }
+        }
+    }
+
+    /// By default, a failing group does not fail the heartbeats of the other groups in the batch.
+    #[tokio::test]
+    async fn test_send_heartbeat_batch_default() {
+        let hb = |term| HeartbeatRequest::<UTConfig>::new(Vote::new_committed(term, 1), None);
+        let option = RPCOption::new(Duration::from_millis(100));
+
+        let resps = PerGroup.send_heartbeat_batch(5, &(), vec![(1, hb(1)), (3, hb(3)), (2, hb(2))], option).await;
+
+        assert_eq!(vec![1, 3, 2], resps.iter().map(|(g, _)| *g).collect::<Vec<_>>());
+        assert_eq!(Vote::new_committed(1, 1), resps[0].1.as_ref().unwrap().vote);
+        assert!(matches!(resps[1].1, Err(RPCError::Unreachable(_))));
+        assert_eq!(Vote::new_committed(2, 1), resps[2].1.as_ref().unwrap().vote);
+    }
+}
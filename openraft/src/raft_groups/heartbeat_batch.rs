@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use openraft_macros::since;
+
+use crate::error::NetworkError;
+use crate::error::RPCError;
+use crate::error::Unreachable;
+use crate::network::RPCOption;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::raft_groups::GroupId;
+use crate::raft_groups::SharedNetworkFactory;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::async_runtime::oneshot::OneshotSender;
+use crate::type_config::TypeConfigExt;
+use crate::RaftTypeConfig;
+
+type HeartbeatResult<C> = Result<HeartbeatResponse<C>, RPCError<C>>;
+
+/// The heartbeats to one target collected in the current window.
+struct Batch<C, G>
+where C: RaftTypeConfig
+{
+    node: C::Node,
+    option: RPCOption,
+    waiters: Vec<(G, HeartbeatRequest<C>, OneshotSenderOf<C, HeartbeatResult<C>>)>,
+}
+
+/// Coalesces the heartbeats of many Raft groups sent to the same node into one RPC.
+///
+/// The network client of every group calls [`heartbeat()`](Self::heartbeat) in its
+/// [`RaftNetworkV2::heartbeat()`] instead of sending the heartbeat itself. The first heartbeat to
+/// a target opens a window; the heartbeats to the same target in the window are sent together with
+/// [`SharedNetworkFactory::send_heartbeat_batch()`] when the window closes, and every caller
+/// receives its own response.
+///
+/// The window adds latency to every heartbeat, it should be much smaller than
+/// [`Config::heartbeat_interval`].
+///
+/// The `target` a heartbeat is batched by does not have to be the node the heartbeat is sent to.
+/// A single group whose learners are hosted by the same process can coalesce the heartbeats to
+/// these learners as well: use the learner id as the group id `G`, and the id of the process, e.g.,
+/// one of the learners, as the `target`.
+///
+/// [`RaftNetworkV2::heartbeat()`]: crate::network::v2::RaftNetworkV2::heartbeat
+/// [`Config::heartbeat_interval`]: crate::Config::heartbeat_interval
+#[since(version = "0.10.0")]
+pub struct HeartbeatBatcher<C, G, F>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+    F: SharedNetworkFactory<C, G> + Clone,
+{
+    window: Duration,
+    factory: F,
+    batches: Arc<Mutex<BTreeMap<C::NodeId, Batch<C, G>>>>,
+}
+
+impl<C, G, F> Clone for HeartbeatBatcher<C, G, F>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+    F: SharedNetworkFactory<C, G> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            window: self.window,
+            factory: self.factory.clone(),
+            batches: self.batches.clone(),
+        }
+    }
+}
+
+impl<C, G, F> HeartbeatBatcher<C, G, F>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+    F: SharedNetworkFactory<C, G> + Clone,
+{
+    /// Create a batcher that collects heartbeats for `window` before sending them with `factory`.
+    pub fn new(window: Duration, factory: F) -> Self {
+        Self {
+            window,
+            factory,
+            batches: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Send a heartbeat of group `group_id` to the target node in the next batch, and wait for its
+    /// response.
+    ///
+    /// The `option` of the first heartbeat in a batch is used for sending the batch.
+    pub async fn heartbeat(
+        &self,
+        group_id: G,
+        target: C::NodeId,
+        node: &C::Node,
+        rpc: HeartbeatRequest<C>,
+        option: RPCOption,
+    ) -> HeartbeatResult<C> {
+        let (tx, rx) = C::oneshot();
+
+        let is_first = {
+            let mut batches = self.batches.lock().unwrap();
+            let batch = batches.entry(target).or_insert_with(|| Batch {
+                node: node.clone(),
+                option,
+                waiters: vec![],
+            });
+            batch.waiters.push((group_id, rpc, tx));
+            batch.waiters.len() == 1
+        };
+
+        if is_first {
+            let this = self.clone();
+
+            #[allow(clippy::let_underscore_future)]
+            let _ = C::spawn(async move {
+                C::sleep(this.window).await;
+                this.flush(target).await;
+            });
+        }
+
+        match rx.await {
+            Ok(res) => res,
+            Err(_) => Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+                "heartbeat batch is dropped before sending",
+            )))),
+        }
+    }
+
+    /// Send the collected heartbeats to `target` and deliver the responses to the callers.
+    async fn flush(&self, target: C::NodeId) {
+        let Some(batch) = self.batches.lock().unwrap().remove(&target) else {
+            return;
+        };
+
+        let reqs = batch.waiters.iter().map(|(group_id, rpc, _)| (group_id.clone(), *rpc)).collect::<Vec<_>>();
+
+        tracing::debug!(target = display(target), "send a batch of {} heartbeats", reqs.len());
+
+        let mut factory = self.factory.clone();
+        let resps = factory.send_heartbeat_batch(target, &batch.node, reqs, batch.option).await;
+
+        let mut by_group = BTreeMap::<G, VecDeque<HeartbeatResult<C>>>::new();
+        for (group_id, res) in resps {
+            if let Err(e) = &res {
+                tracing::warn!(
+                    target = display(target),
+                    group_id = display(&group_id),
+                    error = display(e),
+                    "failed to send heartbeat in batch"
+                );
+            }
+            by_group.entry(group_id).or_default().push_back(res);
+        }
+
+        for (group_id, _, tx) in batch.waiters {
+            let res = match by_group.get_mut(&group_id).and_then(|x| x.pop_front()) {
+                Some(res) => res,
+                None => Err(RPCError::Network(NetworkError::new(&AnyError::error(format!(
+                    "no heartbeat response for group {}",
+                    group_id
+                ))))),
+            };
+            let _ = tx.send(res);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use super::HeartbeatBatcher;
+    use crate::engine::testing::UTConfig;
+    use crate::error::RPCError;
+    use crate::network::RPCOption;
+    use crate::network::RaftNetworkFactory;
+    use crate::raft::HeartbeatRequest;
+    use crate::raft::HeartbeatResponse;
+    use crate::raft_groups::SharedNetworkFactory;
+    use crate::testing::router::RouterNetwork;
+    use crate::testing::Router;
+    use crate::Vote;
+
+    /// Records the batches and accepts every heartbeat, except the ones to group 3.
+    #[derive(Clone)]
+    struct Recorder {
+        batches: Arc<Mutex<Vec<(u64, Vec<u64>)>>>,
+        router: Router<UTConfig>,
+    }
+
+    impl SharedNetworkFactory<UTConfig, u64> for Recorder {
+        type Network = RouterNetwork<UTConfig>;
+
+        async fn new_client(&mut self, _group_id: u64, target: u64, node: &()) -> Self::Network {
+            self.router.new_client(target, node).await
+        }
+
+        async fn send_heartbeat_batch(
+            &mut self,
+            target: u64,
+            _node: &(),
+            batch: Vec<(u64, HeartbeatRequest<UTConfig>)>,
+            _option: RPCOption,
+        ) -> Vec<(u64, Result<HeartbeatResponse<UTConfig>, RPCError<UTConfig>>)> {
+            self.batches.lock().unwrap().push((target, batch.iter().map(|(g, _)| *g).collect()));
+
+            let resps =
+                batch.into_iter().filter(|(g, _)| *g != 3).map(|(g, rpc)| (g, Ok(HeartbeatResponse::new(rpc.vote))));
+            resps.collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_batcher() {
+        let recorder = Recorder {
+            batches: Arc::new(Mutex::new(vec![])),
+            router: Router::new(),
+        };
+        let batcher = HeartbeatBatcher::new(Duration::from_millis(20), recorder.clone());
+
+        let hb = |term| HeartbeatRequest::<UTConfig>::new(Vote::new_committed(term, 1), None);
+        let option = || RPCOption::new(Duration::from_millis(100));
+
+        let (r1, r2, r3, r4) = tokio::join!(
+            batcher.heartbeat(1, 5, &(), hb(1), option()),
+            batcher.heartbeat(2, 5, &(), hb(2), option()),
+            batcher.heartbeat(3, 5, &(), hb(3), option()),
+            batcher.heartbeat(1, 6, &(), hb(4), option()),
+        );
+
+        assert_eq!(Vote::new_committed(1, 1), r1.unwrap().vote);
+        assert_eq!(Vote::new_committed(2, 1), r2.unwrap().vote);
+        assert!(matches!(r3, Err(RPCError::Network(_))), "no response for group 3");
+        assert_eq!(Vote::new_committed(4, 1), r4.unwrap().vote);
+
+        let mut batches = recorder.batches.lock().unwrap().clone();
+        batches.sort();
+        assert_eq!(vec![(5, vec![1, 2, 3]), (6, vec![1])], batches);
+    }
+}
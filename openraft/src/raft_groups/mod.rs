@@ -0,0 +1,36 @@
+//! A registry of the Raft groups in one process, with a shared tick, a shared transport and
+//! batched heartbeats.
+//!
+//! A sharded application runs one [`Raft`] per shard, i.e., a Raft group, and usually many groups
+//! share the same set of nodes. This module provides the pieces to share some resources among them:
+//!
+//! - [`RaftGroups`] is a registry of the local members of all groups, keyed by a group id. It
+//!   dispatches the incoming RPCs of a group, handles a batch of heartbeats for many groups at
+//!   once, and drives the timers of all groups with one shared tick instead of one timer per group.
+//!
+//! - [`GroupNetworkFactory`] binds a [`SharedNetworkFactory`] to one group, so that the clients of
+//!   all groups can multiplex their RPCs, tagged with the group id, over the same connection to a
+//!   node.
+//!
+//! - [`HeartbeatBatcher`] coalesces the heartbeats of all groups sent to the same node into one
+//!   RPC, sent with [`SharedNetworkFactory::send_heartbeat_batch()`].
+//!
+//! This is not a Multi-Raft implementation: only the timer, the transport and the heartbeats are
+//! shared. Every group is still a complete [`Raft`] instance: it spawns its own `RaftCore` task,
+//! state machine worker and replication tasks. Openraft does not provide per-group namespaces in a
+//! storage either: every group owns its own [`RaftLogStorage`] and [`RaftStateMachine`], and it is
+//! up to the application to store them apart, e.g., with a key prefix per group.
+//!
+//! [`Raft`]: crate::Raft
+//! [`RaftLogStorage`]: crate::storage::RaftLogStorage
+//! [`RaftStateMachine`]: crate::storage::RaftStateMachine
+
+mod group_network;
+mod heartbeat_batch;
+mod registry;
+
+pub use group_network::GroupNetworkFactory;
+pub use group_network::SharedNetworkFactory;
+pub use heartbeat_batch::HeartbeatBatcher;
+pub use registry::GroupId;
+pub use registry::RaftGroups;
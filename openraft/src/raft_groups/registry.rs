@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use futures::future::join_all;
+use openraft_macros::since;
+
+use crate::error::Fatal;
+use crate::error::RaftError;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::type_config::alias::JoinErrorOf;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::Raft;
+use crate::RaftTypeConfig;
+
+/// The id of a Raft group in a [`RaftGroups`].
+///
+/// It is implemented for any type that satisfies the bounds, such as `u64` or `String`.
+#[since(version = "0.10.0")]
+pub trait GroupId: Ord + Clone + fmt::Debug + fmt::Display + OptionalSend + OptionalSync + 'static {}
+
+impl<T> GroupId for T where T: Ord + Clone + fmt::Debug + fmt::Display + OptionalSend + OptionalSync + 'static {}
+
+/// A registry of the local members of many Raft groups.
+///
+/// The application creates a [`Raft`] for every group this node is a member of, and registers it
+/// with [`add_group()`](Self::add_group). A `RaftGroups` is cheap to clone, all the clones share
+/// the same registry.
+///
+/// Registering a group does not merge its tasks with the other groups: each [`Raft`] keeps
+/// running its own `RaftCore`, state machine worker and replication tasks.
+///
+/// ### Shared tick
+///
+/// By default every group runs its own timer. To drive all groups with one timer, create the
+/// groups with [`Config::enable_tick`] disabled, and call [`tick()`](Self::tick) periodically,
+/// e.g., every [`Config::tick_interval()`]. Elections and heartbeats of such a group are then
+/// driven only by this tick:
+///
+/// ```ignore
+/// loop {
+///     sleep(config.tick_interval()).await;
+///     raft_groups.tick().await;
+/// }
+/// ```
+///
+/// ### Coalesced heartbeats
+///
+/// A [`HeartbeatBatcher`] collects the heartbeats of all groups sent to the same node, and
+/// delivers them in one message. The receiving node handles the batch with
+/// [`handle_heartbeats()`](Self::handle_heartbeats).
+///
+/// [`HeartbeatBatcher`]: crate::raft_groups::HeartbeatBatcher
+/// [`Config::enable_tick`]: crate::Config::enable_tick
+/// [`Config::tick_interval()`]: crate::Config::tick_interval
+#[since(version = "0.10.0")]
+pub struct RaftGroups<C, G>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+{
+    groups: Arc<Mutex<BTreeMap<G, Raft<C>>>>,
+}
+
+impl<C, G> Clone for RaftGroups<C, G>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+{
+    fn clone(&self) -> Self {
+        Self {
+            groups: self.groups.clone(),
+        }
+    }
+}
+
+impl<C, G> Default for RaftGroups<C, G>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, G> RaftGroups<C, G>
+where
+    C: RaftTypeConfig,
+    G: GroupId,
+{
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            groups: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Register the local member of group `group_id`.
+    ///
+    /// It returns the previously registered member of the group, if any. The returned [`Raft`] is
+    /// not shut down.
+    pub fn add_group(&self, group_id: G, raft: Raft<C>) -> Option<Raft<C>> {
+        tracing::info!(group_id = display(&group_id), "RaftGroups: add group");
+        self.groups.lock().unwrap().insert(group_id, raft)
+    }
+
+    /// Unregister the local member of group `group_id` and return it.
+    ///
+    /// The returned [`Raft`] is not shut down.
+    pub fn remove_group(&self, group_id: &G) -> Option<Raft<C>> {
+        tracing::info!(group_id = display(group_id), "RaftGroups: remove group");
+        self.groups.lock().unwrap().remove(group_id)
+    }
+
+    /// Get the local member of group `group_id`, e.g., to dispatch an incoming RPC to it.
+    pub fn get(&self, group_id: &G) -> Option<Raft<C>> {
+        self.groups.lock().unwrap().get(group_id).cloned()
+    }
+
+    /// Return the ids of all registered groups, in ascending order.
+    pub fn group_ids(&self) -> Vec<G> {
+        self.groups.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Return the number of registered groups.
+    pub fn len(&self) -> usize {
+        self.groups.lock().unwrap().len()
+    }
+
+    /// Return `true` if there is no registered group.
+    pub fn is_empty(&self) -> bool {
+        self.groups.lock().unwrap().is_empty()
+    }
+
+    /// Trigger a tick on every group, see [`Trigger::tick()`].
+    ///
+    /// It returns the groups that failed with a [`Fatal`] error, e.g., a group that is shut down.
+    ///
+    /// [`Trigger::tick()`]: crate::raft::trigger::Trigger::tick
+    pub async fn tick(&self) -> Vec<(G, Fatal<C>)> {
+        let groups = self.snapshot();
+
+        let futs = groups.iter().map(|(_, raft)| async move { raft.trigger().tick().await });
+        let results = join_all(futs).await;
+
+        groups
+            .into_iter()
+            .zip(results)
+            .filter_map(|((group_id, _), res)| res.err().map(|e| (group_id, e)))
+            .collect()
+    }
+
+    /// Handle a batch of heartbeats sent to the groups on this node, concurrently.
+    ///
+    /// A response is returned for every heartbeat to a registered group, in the same order.
+    /// A heartbeat to an unknown group is ignored, and there is no response for it.
+    pub async fn handle_heartbeats(
+        &self,
+        heartbeats: Vec<(G, HeartbeatRequest<C>)>,
+    ) -> Vec<(G, Result<HeartbeatResponse<C>, RaftError<C>>)> {
+        let targets = {
+            let groups = self.groups.lock().unwrap();
+
+            heartbeats
+                .into_iter()
+                .filter_map(|(group_id, req)| match groups.get(&group_id) {
+                    Some(raft) => Some((group_id, raft.clone(), req)),
+                    None => {
+                        tracing::warn!(group_id = display(&group_id), "RaftGroups: heartbeat to unknown group");
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let futs = targets.into_iter().map(|(group_id, raft, req)| async move {
+            let res = raft.heartbeat(req).await;
+            (group_id, res)
+        });
+
+        join_all(futs).await
+    }
+
+    /// Unregister and shut down all groups.
+    ///
+    /// It returns the groups that failed to shut down.
+    pub async fn shutdown(&self) -> Vec<(G, JoinErrorOf<C>)> {
+        let groups = std::mem::take(&mut *self.groups.lock().unwrap());
+
+        tracing::info!("RaftGroups: shutdown {} groups", groups.len());
+
+        let futs = groups.iter().map(|(_, raft)| raft.shutdown());
+        let results = join_all(futs).await;
+
+        groups
+            .into_keys()
+            .zip(results)
+            .filter_map(|(group_id, res)| res.err().map(|e| (group_id, e)))
+            .collect()
+    }
+
+    /// Clone all registered groups, so that they are called without holding the lock.
+    fn snapshot(&self) -> Vec<(G, Raft<C>)> {
+        let groups = self.groups.lock().unwrap();
+        groups.iter().map(|(group_id, raft)| (group_id.clone(), raft.clone())).collect()
+    }
+}
@@ -63,6 +63,10 @@ pub(crate) struct IOState<C>
 where C: RaftTypeConfig
 {
     /// Whether it is building a snapshot
+    ///
+    /// Building a snapshot runs concurrently with applying logs, receiving or installing a
+    /// snapshot and replication. None of them cancels the building.
+    /// A built snapshot not greater than the current one is just ignored.
     building_snapshot: bool,
 
     /// Tracks the accepted, submitted and flushed IO to local storage.
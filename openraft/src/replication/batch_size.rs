@@ -0,0 +1,77 @@
+//! Defines the number of entries to send in an AppendEntries RPC.
+
+/// The number of entries to send in an AppendEntries RPC, adapting to the condition of the
+/// target.
+///
+/// If it is adaptive, it is halved when an RPC fails and doubled when an RPC succeeds, within
+/// `1..=max`. Otherwise, it is always `max`.
+#[derive(Clone, Debug)]
+pub(crate) struct BatchSize {
+    n: u64,
+    max: u64,
+    adaptive: bool,
+}
+
+impl BatchSize {
+    pub(crate) fn new(max: u64, adaptive: bool) -> Self {
+        Self { n: max, max, adaptive }
+    }
+
+    pub(crate) fn get(&self) -> u64 {
+        self.n
+    }
+
+    /// Send more entries in an RPC, when the target keeps up.
+    pub(crate) fn grow(&mut self) {
+        if self.adaptive {
+            self.n = std::cmp::min(self.n.saturating_mul(2), self.max);
+        }
+    }
+
+    /// Send less entries in an RPC, when an RPC to the target fails.
+    pub(crate) fn shrink(&mut self) {
+        if self.adaptive {
+            self.n = std::cmp::max(self.n / 2, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchSize;
+
+    #[test]
+    fn test_batch_size_adaptive() {
+        let mut b = BatchSize::new(10, true);
+        assert_eq!(10, b.get());
+
+        b.grow();
+        assert_eq!(10, b.get(), "no more than max");
+
+        b.shrink();
+        assert_eq!(5, b.get());
+        b.shrink();
+        b.shrink();
+        assert_eq!(1, b.get());
+        b.shrink();
+        assert_eq!(1, b.get(), "no less than 1");
+
+        b.grow();
+        assert_eq!(2, b.get());
+        b.grow();
+        b.grow();
+        b.grow();
+        assert_eq!(10, b.get());
+    }
+
+    #[test]
+    fn test_batch_size_not_adaptive() {
+        let mut b = BatchSize::new(10, false);
+
+        b.shrink();
+        assert_eq!(10, b.get());
+
+        b.grow();
+        assert_eq!(10, b.get());
+    }
+}
@@ -0,0 +1,121 @@
+//! Caches the capabilities of a replication target and limits how often they are asked.
+
+use crate::network::Backoff;
+use crate::network::Capabilities;
+use crate::type_config::alias::InstantOf;
+use crate::RaftTypeConfig;
+
+/// The capabilities of a replication target, asked with
+/// [`RaftNetworkV2::capabilities()`](crate::network::v2::RaftNetworkV2::capabilities).
+///
+/// The known capabilities are kept until the target becomes unreachable, when they are marked
+/// stale and asked again, because the target may come back with a different build. A failed
+/// probe is not retried until a backoff elapses, so that a retry to an unreachable target does
+/// not wait for a probe timeout every time; the stale capabilities are used meanwhile.
+pub(crate) struct CapabilitiesProbe<C>
+where C: RaftTypeConfig
+{
+    capabilities: Option<Capabilities>,
+
+    /// Whether `capabilities` should be asked again.
+    stale: bool,
+
+    /// The backoff of failed probes, reset when a probe succeeds.
+    backoff: Option<Backoff>,
+
+    /// A probe is not sent before this time.
+    next_probe: Option<InstantOf<C>>,
+}
+
+impl<C> CapabilitiesProbe<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            capabilities: None,
+            stale: true,
+            backoff: None,
+            next_probe: None,
+        }
+    }
+
+    /// The last known capabilities, or `None` if no probe has succeeded.
+    pub(crate) fn get(&self) -> Option<Capabilities> {
+        self.capabilities
+    }
+
+    /// Whether to ask the target for its capabilities at `now`.
+    pub(crate) fn should_probe(&self, now: InstantOf<C>) -> bool {
+        self.stale && !matches!(self.next_probe, Some(t) if now < t)
+    }
+
+    /// Record the capabilities returned by a probe.
+    pub(crate) fn probed(&mut self, capabilities: Capabilities) {
+        self.capabilities = Some(capabilities);
+        self.stale = false;
+        self.backoff = None;
+        self.next_probe = None;
+    }
+
+    /// Record a failed probe at `now`: the next probe is delayed by the next interval of the
+    /// backoff, which is created by `new_backoff` upon the first failure since the last success.
+    pub(crate) fn probe_failed(&mut self, now: InstantOf<C>, new_backoff: impl FnOnce() -> Backoff) {
+        let backoff = self.backoff.get_or_insert_with(new_backoff);
+        let delay = backoff.next().unwrap_or_default();
+        self.next_probe = Some(now + delay);
+    }
+
+    /// Mark the capabilities stale, e.g., when the target becomes unreachable.
+    pub(crate) fn invalidate(&mut self) {
+        self.stale = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::CapabilitiesProbe;
+    use crate::engine::testing::UTConfig;
+    use crate::network::Backoff;
+    use crate::network::Capabilities;
+    use crate::type_config::TypeConfigExt;
+
+    fn backoff() -> Backoff {
+        Backoff::new([100, 200].into_iter().map(Duration::from_millis))
+    }
+
+    #[test]
+    fn test_capabilities_probe() {
+        let now = UTConfig::<()>::now();
+        let ms = Duration::from_millis;
+
+        let mut p = CapabilitiesProbe::<UTConfig>::new();
+        assert_eq!(None, p.get());
+        assert!(p.should_probe(now));
+
+        // Failed probes are delayed by the backoff.
+        p.probe_failed(now, backoff);
+        assert!(!p.should_probe(now + ms(99)));
+        assert!(p.should_probe(now + ms(100)));
+
+        p.probe_failed(now + ms(100), backoff);
+        assert!(!p.should_probe(now + ms(299)));
+        assert!(p.should_probe(now + ms(300)));
+
+        // A known capabilities is not asked again until it is invalidated.
+        let c = Capabilities::local();
+        p.probed(c);
+        assert_eq!(Some(c), p.get());
+        assert!(!p.should_probe(now + ms(300)));
+
+        p.invalidate();
+        assert!(p.should_probe(now + ms(300)));
+        assert_eq!(Some(c), p.get(), "stale capabilities are used until a probe succeeds");
+
+        // The backoff restarts after a successful probe.
+        p.probe_failed(now + ms(300), backoff);
+        assert!(!p.should_probe(now + ms(399)));
+        assert!(p.should_probe(now + ms(400)));
+    }
+}
@@ -1,16 +1,22 @@
 //! Replication stream.
 
+mod batch_size;
 pub(crate) mod callbacks;
+mod capabilities_probe;
 pub(crate) mod hint;
 mod replication_session_id;
 pub(crate) mod request;
 pub(crate) mod response;
 
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyerror::AnyError;
+use batch_size::BatchSize;
 use futures::future::FutureExt;
+use rand::Rng;
 pub(crate) use replication_session_id::ReplicationSessionId;
 use request::Data;
 use request::Replicate;
@@ -24,27 +30,33 @@ use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::config::Config;
 use crate::core::notification::Notification;
 use crate::core::sm::handle::SnapshotReader;
+use crate::core::SnapshotProgress;
 use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
+use crate::entry::RaftPayload;
 use crate::error::HigherVote;
 use crate::error::PayloadTooLarge;
 use crate::error::RPCError;
 use crate::error::ReplicationClosed;
 use crate::error::ReplicationError;
 use crate::error::Timeout;
+use crate::error::Unreachable;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id_range::LogIdRange;
 use crate::network::v2::RaftNetworkV2;
 use crate::network::Backoff;
+use crate::network::Capabilities;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::replication::callbacks::SnapshotCallback;
+use crate::replication::capabilities_probe::CapabilitiesProbe;
 use crate::replication::hint::ReplicationHint;
 use crate::storage::RaftLogReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::Snapshot;
+use crate::type_config::alias::AsyncRuntimeOf;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::LogIdOf;
@@ -55,6 +67,7 @@ use crate::type_config::alias::MutexOf;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::async_runtime::mutex::Mutex;
+use crate::type_config::AsyncRuntime;
 use crate::type_config::TypeConfigExt;
 use crate::LogId;
 use crate::RaftLogId;
@@ -72,13 +85,24 @@ where C: RaftTypeConfig
 
     /// The channel used for communicating with the replication task.
     pub(crate) tx_repl: MpscUnboundedSenderOf<C, Replicate<C>>,
+
+    /// Whether the circuit breaker of this replication is open, i.e., the target has failed too
+    /// many times in a row.
+    pub(crate) breaker_open: Arc<AtomicBool>,
+
+    /// Whether this replication is backing off, i.e., waiting before sending the next RPC.
+    pub(crate) backing_off: Arc<AtomicBool>,
+
+    /// The progress of the snapshot being sent to the target.
+    pub(crate) snapshot_progress: SnapshotProgress<C>,
 }
 
 /// A task responsible for sending replication events to a target follower in the Raft cluster.
 ///
 /// NOTE: we do not stack replication requests to targets because this could result in
 /// out-of-order delivery. We always buffer until we receive a success response, then send the
-/// next payload from the buffer.
+/// next payload from the buffer. Except when [`Config::max_in_flight_appends`] is greater than 1:
+/// then several chunks of logs are sent at a time and an out-of-order delivery is retried.
 pub(crate) struct ReplicationCore<C, N, LS>
 where
     C: RaftTypeConfig,
@@ -108,6 +132,12 @@ where
     /// The `RaftNetwork` interface for replicating logs and heartbeat.
     network: N::Network,
 
+    /// Additional `RaftNetwork` instances for sending AppendEntries RPCs in parallel with
+    /// `network`.
+    ///
+    /// There are `max_in_flight_appends - 1` of them, and it is empty if pipelining is disabled.
+    pipeline_networks: Vec<N::Network>,
+
     /// Another `RaftNetwork` specific for snapshot replication.
     ///
     /// Snapshot transmitting is a long running task, and is processed in a separate task.
@@ -118,12 +148,42 @@ where
     /// It includes a cancel signaler and the join handle of the snapshot replication task.
     /// When ReplicationCore is dropped, this Sender is dropped, the snapshot task will be notified
     /// to quit.
+    /// E.g., when the membership changes, RaftCore drops all ReplicationCore, and an in-flight
+    /// snapshot transmission to a removed target is aborted without sending the rest chunks.
     snapshot_state: Option<(OneshotSenderOf<C, ()>, JoinHandleOf<C, ()>)>,
 
     /// The backoff policy if an [`Unreachable`](`crate::error::Unreachable`) error is returned.
     /// It will be reset to `None` when an successful response is received.
     backoff: Option<Backoff>,
 
+    /// The number of consecutive failed RPCs to the target.
+    failures: u64,
+
+    /// The capabilities of the target.
+    ///
+    /// It is asked before sending logs or a snapshot to the target.
+    capabilities: CapabilitiesProbe<C>,
+
+    /// Set when the target replies with [`AppendEntriesResponse::Backpressure`]: the next
+    /// AppendEntries is delayed for a `heartbeat_interval`.
+    throttled: bool,
+
+    /// Whether the circuit breaker is open: set when `failures` reaches
+    /// [`Config::replication_breaker_threshold`], and cleared on a successful RPC.
+    ///
+    /// It is shared with the [`ReplicationHandle`] to report metrics.
+    breaker_open: Arc<AtomicBool>,
+
+    /// Whether it is waiting for a backoff interval, or a pause asked by the target, to expire.
+    ///
+    /// It is shared with the [`ReplicationHandle`] to report metrics.
+    backing_off: Arc<AtomicBool>,
+
+    /// The progress of the snapshot being sent, updated by the snapshot sending task.
+    ///
+    /// It is shared with the [`ReplicationHandle`] to report metrics.
+    snapshot_progress: SnapshotProgress<C>,
+
     /// The [`RaftLogStorage::LogReader`] interface.
     log_reader: LS::LogReader,
 
@@ -145,6 +205,10 @@ where
     /// Appropriate number of entries to send.
     /// This is only used by AppendEntries RPC.
     entries_hint: ReplicationHint,
+
+    /// The number of entries to send in an AppendEntries RPC, adapting to the target's condition
+    /// if [`Config::enable_adaptive_payload`] is enabled.
+    batch_size: BatchSize,
 }
 
 impl<C, N, LS> ReplicationCore<C, N, LS>
@@ -164,6 +228,7 @@ where
         committed: Option<LogId<C::NodeId>>,
         matching: Option<LogId<C::NodeId>>,
         network: N::Network,
+        pipeline_networks: Vec<N::Network>,
         snapshot_network: N::Network,
         log_reader: LS::LogReader,
         snapshot_reader: SnapshotReader<C>,
@@ -180,14 +245,25 @@ where
 
         // other component to ReplicationStream
         let (tx_event, rx_event) = C::mpsc_unbounded();
+        let breaker_open = Arc::new(AtomicBool::new(false));
+        let backing_off = Arc::new(AtomicBool::new(false));
+        let snapshot_progress = SnapshotProgress::new();
+        let batch_size = BatchSize::new(config.max_payload_entries, config.enable_adaptive_payload);
 
         let this = Self {
             target,
             session_id,
             network,
+            pipeline_networks,
             snapshot_network: Arc::new(C::mutex(snapshot_network)),
             snapshot_state: None,
             backoff: None,
+            failures: 0,
+            capabilities: CapabilitiesProbe::new(),
+            throttled: false,
+            breaker_open: breaker_open.clone(),
+            backing_off: backing_off.clone(),
+            snapshot_progress: snapshot_progress.clone(),
             log_reader,
             snapshot_reader,
             config,
@@ -198,6 +274,7 @@ where
             weak_tx_event: tx_event.downgrade(),
             next_action: None,
             entries_hint: Default::default(),
+            batch_size,
         };
 
         let join_handle = C::spawn(this.main().instrument(span));
@@ -205,6 +282,9 @@ where
         ReplicationHandle {
             join_handle,
             tx_repl: tx_event,
+            breaker_open,
+            backing_off,
+            snapshot_progress,
         }
     }
 
@@ -236,7 +316,11 @@ where
                 }
                 Data::Logs(log) => {
                     log_data = Some(log);
-                    self.send_log_entries(log, true).await
+                    if self.pipeline_networks.is_empty() {
+                        self.send_log_entries(log, true).await
+                    } else {
+                        self.send_log_entries_pipelined(log).await
+                    }
                 }
                 Data::Snapshot(snap) => self.stream_snapshot(snap).await,
                 Data::SnapshotCallback(resp) => self.handle_snapshot_callback(resp),
@@ -248,14 +332,29 @@ where
                 Ok(next) => {
                     // reset backoff at once if replication succeeds
                     self.backoff = None;
+                    self.on_rpc_success();
 
                     // If the RPC was successful but not finished, continue.
                     if let Some(next) = next {
                         self.next_action = Some(next);
                     }
+
+                    // The target asked to slow down: give it some time to catch up.
+                    if self.throttled {
+                        self.throttled = false;
+                        let pause = Duration::from_millis(self.config.heartbeat_interval);
+                        self.backing_off.store(true, Ordering::Relaxed);
+                        self.backoff_drain_events(C::now() + pause).await?;
+                        self.backing_off.store(false, Ordering::Relaxed);
+                    }
                 }
                 Err(err) => {
-                    tracing::warn!(error=%err, "error replication to target={}", self.target);
+                    // Do not flood the log with errors of a target that is known to be down.
+                    if self.is_breaker_open() {
+                        tracing::debug!(error=%err, "error replication to target={}", self.target);
+                    } else {
+                        tracing::warn!(error=%err, "error replication to target={}", self.target);
+                    }
 
                     match err {
                         ReplicationError::Closed(closed) => {
@@ -276,11 +375,22 @@ where
                             return Ok(());
                         }
                         ReplicationError::RPCError(err) => {
-                            tracing::error!(err = display(&err), "RPCError");
+                            if !self.is_breaker_open() {
+                                tracing::error!(err = display(&err), "RPCError");
+                            }
 
                             let retry = match &err {
-                                RPCError::Timeout(_) => false,
+                                RPCError::Timeout(_) => {
+                                    self.batch_size.shrink();
+                                    self.on_rpc_failure();
+                                    false
+                                }
                                 RPCError::Unreachable(_unreachable) => {
+                                    self.on_rpc_failure();
+
+                                    // The target may come back with a different build.
+                                    self.capabilities.invalidate();
+
                                     // If there is an [`Unreachable`] error, we will backoff for a
                                     // period of time. Backoff will be reset if there is a
                                     // successful RPC is sent.
@@ -296,7 +406,11 @@ where
                                     self.next_action = Some(Data::Logs(log_data.unwrap()));
                                     true
                                 }
-                                RPCError::Network(_) => false,
+                                RPCError::Network(_) => {
+                                    self.batch_size.shrink();
+                                    self.on_rpc_failure();
+                                    false
+                                }
                                 RPCError::RemoteError(_) => false,
                             };
 
@@ -306,7 +420,7 @@ where
                                 // If there is no id, it is a heartbeat and do not need to notify RaftCore
                                 if need_notify {
                                     self.send_progress_error(err);
-                                } else {
+                                } else if !self.is_breaker_open() {
                                     tracing::warn!("heartbeat RPC failed, do not send any response to RaftCore");
                                 };
                             }
@@ -326,13 +440,80 @@ where
                 Duration::from_millis(500)
             });
 
+            self.backing_off.store(true, Ordering::Relaxed);
             self.backoff_drain_events(C::now() + duration).await?;
+            self.backing_off.store(false, Ordering::Relaxed);
         }
 
         self.drain_events().await?;
         Ok(())
     }
 
+    /// Record a failed RPC: open the circuit breaker if the target fails too many times in a row,
+    /// and start to back off if exponential backoff is enabled.
+    fn on_rpc_failure(&mut self) {
+        self.failures += 1;
+
+        let threshold = self.config.replication_breaker_threshold;
+        if threshold > 0 && self.failures >= threshold && !self.is_breaker_open() {
+            tracing::warn!(
+                failures = display(self.failures),
+                "replication to target={} failed too many times, open circuit breaker",
+                self.target
+            );
+            self.breaker_open.store(true, Ordering::Relaxed);
+        }
+
+        if self.config.replication_backoff_max > 0 && self.backoff.is_none() {
+            self.backoff = Some(self.exponential_backoff());
+        }
+    }
+
+    /// Record a successful RPC: reset the failure count and close the circuit breaker.
+    fn on_rpc_success(&mut self) {
+        self.failures = 0;
+
+        if self.is_breaker_open() {
+            tracing::info!("replication to target={} recovered, close circuit breaker", self.target);
+            self.breaker_open.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// The target accepted the logs but asked to slow down: send smaller batches, and pause before
+    /// the next one.
+    fn on_backpressure(&mut self, apply_lag: u64) {
+        tracing::debug!(
+            apply_lag = display(apply_lag),
+            "target={} is overloaded, slow down replication",
+            self.target
+        );
+
+        self.batch_size.shrink();
+        self.throttled = true;
+    }
+
+    fn is_breaker_open(&self) -> bool {
+        self.breaker_open.load(Ordering::Relaxed)
+    }
+
+    /// Build an exponential backoff with jitter, from `heartbeat_interval` up to
+    /// `replication_backoff_max`.
+    ///
+    /// Every interval is randomized to `[d/2, d]`, so that the retries to a recovered target
+    /// from several leaders or streams do not happen at the same time.
+    fn exponential_backoff(&self) -> Backoff {
+        let max = Duration::from_millis(self.config.replication_backoff_max);
+        let min = std::cmp::min(Duration::from_millis(self.config.heartbeat_interval), max);
+
+        let intervals = std::iter::successors(Some(min), move |d| Some(std::cmp::min(*d * 2, max)));
+
+        Backoff::new(intervals.map(|d| {
+            let millis = d.as_millis() as u64;
+            let jittered = AsyncRuntimeOf::<C>::thread_rng().gen_range(millis / 2..=millis);
+            Duration::from_millis(jittered)
+        }))
+    }
+
     /// When a [`PayloadTooLarge`] error is received, update the hint for the next several RPC.
     fn update_hint(&mut self, too_large: &PayloadTooLarge) {
         const DEFAULT_ENTRIES_HINT_TTL: u64 = 10;
@@ -341,10 +522,16 @@ where
             RPCTypes::Vote => {
                 unreachable!("Vote RPC should not be too large")
             }
+            RPCTypes::PreVote => {
+                unreachable!("PreVote RPC should not be too large")
+            }
             RPCTypes::AppendEntries => {
                 self.entries_hint = ReplicationHint::new(too_large.entries_hint(), DEFAULT_ENTRIES_HINT_TTL);
                 tracing::debug!(entries_hint = debug(&self.entries_hint), "updated entries hint");
             }
+            RPCTypes::Heartbeat => {
+                unreachable!("Heartbeat RPC should not be too large")
+            }
             RPCTypes::InstallSnapshot => {
                 // TODO: handle too large
                 tracing::error!("InstallSnapshot RPC is too large, but it is not supported yet");
@@ -352,6 +539,12 @@ where
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader RPC should not be too large")
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("ReadIndex RPC should not be too large")
+            }
+            RPCTypes::ClientWrite => {
+                unreachable!("ClientWrite RPC is not sent by replication")
+            }
         }
     }
 
@@ -381,12 +574,8 @@ where
                 let start = rng.prev.next_index();
                 let end = rng.last.next_index();
 
-                if let Some(hint) = self.entries_hint.get() {
-                    let hint_end = start + hint;
-                    (start, std::cmp::min(end, hint_end))
-                } else {
-                    (start, end)
-                }
+                let n = self.entries_hint.get().unwrap_or(self.batch_size.get());
+                (start, std::cmp::min(end, start + n))
             };
 
             if start == end {
@@ -395,7 +584,8 @@ where
                 (vec![], r)
             } else {
                 // limited_get_log_entries will return logs smaller than the range [start, end).
-                let logs = self.log_reader.limited_get_log_entries(start, end).await?;
+                let mut logs = self.log_reader.limited_get_log_entries(start, end).await?;
+                self.limit_payload_bytes(&mut logs);
 
                 let first = *logs.first().map(|x| x.get_log_id()).unwrap();
                 let last = *logs.last().map(|x| x.get_log_id()).unwrap();
@@ -423,6 +613,7 @@ where
             prev_log_id: sending_range.prev,
             leader_commit: self.committed,
             entries: logs,
+            protocol_version: Capabilities::PROTOCOL_VERSION,
         };
 
         // Send the payload.
@@ -433,21 +624,13 @@ where
             self.config.heartbeat_interval
         );
 
-        let the_timeout = Duration::from_millis(self.config.heartbeat_interval);
-        let option = RPCOption::new(the_timeout);
+        let the_timeout = self.config.append_entries_timeout();
+        let option = self.append_entries_option(the_timeout).await?;
         let res = C::timeout(the_timeout, self.network.append_entries(payload, option)).await;
 
         tracing::debug!("append_entries res: {:?}", res);
 
-        let append_res = res.map_err(|_e| {
-            let to = Timeout {
-                action: RPCTypes::AppendEntries,
-                id: self.session_id.vote_ref().leader_id().voted_for().unwrap(),
-                target: self.target,
-                timeout: the_timeout,
-            };
-            RPCError::Timeout(to)
-        })?; // return Timeout error
+        let append_res = res.map_err(|_e| self.append_entries_timeout(the_timeout))?; // return Timeout error
 
         let append_resp = append_res?;
 
@@ -463,6 +646,19 @@ where
 
                 let matching = sending_range.last;
                 if has_payload {
+                    self.batch_size.grow();
+                    self.notify_progress(ReplicationResult(Ok(matching)));
+                    Ok(self.next_action_to_send(matching, log_ids))
+                } else {
+                    Ok(None)
+                }
+            }
+            AppendEntriesResponse::Backpressure { apply_lag } => {
+                self.notify_heartbeat_progress(leader_time);
+
+                let matching = sending_range.last;
+                if has_payload {
+                    self.on_backpressure(apply_lag);
                     self.notify_progress(ReplicationResult(Ok(matching)));
                     Ok(self.next_action_to_send(matching, log_ids))
                 } else {
@@ -512,6 +708,257 @@ where
         }
     }
 
+    /// Send the logs in `log_ids` with up to `max_in_flight_appends` AppendEntries RPCs in flight.
+    ///
+    /// The logs are split into chunks of at most `max_payload_entries` entries. Every chunk is sent
+    /// through its own network instance, without waiting for the response to the previous one.
+    ///
+    /// Responses are handled in the order the chunks are sent: the replication stops at the first
+    /// chunk that is not fully accepted, and the following responses are discarded. A chunk that
+    /// arrives at the target before its predecessor is rejected with a conflict, which is not a
+    /// real conflict: the logs since the last matching log are sent again.
+    /// Only a conflict of the first chunk is reported to RaftCore.
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn send_log_entries_pipelined(
+        &mut self,
+        log_ids: LogIdRange<C>,
+    ) -> Result<Option<Data<C>>, ReplicationError<C>> {
+        tracing::debug!(log_id_range = display(log_ids), "send_log_entries_pipelined");
+
+        // Fall back to one RPC at a time when the payload size is being limited.
+        if self.entries_hint.is_active() {
+            return self.send_log_entries(log_ids, true).await;
+        }
+
+        // Build one request for every chunk.
+        let mut requests = vec![];
+        {
+            let max_in_flight = self.pipeline_networks.len() + 1;
+            let end = log_ids.last.next_index();
+
+            let mut prev = log_ids.prev;
+            while prev < log_ids.last && requests.len() < max_in_flight {
+                let start = prev.next_index();
+                let chunk_end = std::cmp::min(start + self.batch_size.get(), end);
+
+                let mut logs = self.log_reader.limited_get_log_entries(start, chunk_end).await?;
+                self.limit_payload_bytes(&mut logs);
+                let Some(last) = logs.last().map(|x| *x.get_log_id()) else {
+                    break;
+                };
+
+                let sending_range = LogIdRange::new(prev, Some(last));
+                let payload = AppendEntriesRequest {
+                    vote: *self.session_id.vote_ref(),
+                    prev_log_id: prev,
+                    leader_commit: self.committed,
+                    entries: logs,
+                    protocol_version: Capabilities::PROTOCOL_VERSION,
+                };
+                requests.push((sending_range, payload));
+
+                prev = Some(last);
+            }
+        }
+
+        let leader_time = C::now();
+        let the_timeout = self.config.append_entries_timeout();
+        let option = self.append_entries_option(the_timeout).await?;
+
+        let responses = {
+            let networks = std::iter::once(&mut self.network).chain(self.pipeline_networks.iter_mut());
+
+            let sending = requests.into_iter().zip(networks).map(|((sending_range, payload), network)| {
+                let option = option.clone();
+                async move {
+                    tracing::debug!(payload = display(&payload), "start sending pipelined append_entries");
+
+                    let res = C::timeout(the_timeout, network.append_entries(payload, option)).await;
+                    (sending_range, res)
+                }
+            });
+
+            futures::future::join_all(sending).await
+        };
+
+        let mut matching = None;
+
+        for (i, (sending_range, res)) in responses.into_iter().enumerate() {
+            tracing::debug!(
+                req = display(&sending_range),
+                res = debug(&res),
+                "pipelined append_entries resp"
+            );
+
+            let append_resp = match res {
+                Ok(Ok(resp)) => resp,
+                Ok(Err(rpc_err)) if i == 0 => return Err(rpc_err.into()),
+                Err(_timeout) if i == 0 => return Err(self.append_entries_timeout(the_timeout).into()),
+                // The logs sent before are accepted, retry the rest.
+                _ => break,
+            };
+
+            match append_resp {
+                AppendEntriesResponse::Success => {
+                    matching = Some(sending_range.last);
+                }
+                AppendEntriesResponse::Backpressure { apply_lag } => {
+                    // Do not count on the following chunks, the target is busy.
+                    self.on_backpressure(apply_lag);
+                    matching = Some(sending_range.last);
+                    break;
+                }
+                AppendEntriesResponse::PartialSuccess(m) => {
+                    Self::debug_assert_partial_success(&sending_range, &m);
+                    matching = Some(m);
+                    break;
+                }
+                AppendEntriesResponse::HigherVote(vote) => {
+                    tracing::debug!(%vote, "append entries failed. converting to follower");
+
+                    return Err(ReplicationError::HigherVote(HigherVote {
+                        higher: vote,
+                        sender_vote: *self.session_id.vote_ref(),
+                    }));
+                }
+                AppendEntriesResponse::Conflict => {
+                    if i == 0 {
+                        let conflict = sending_range.prev;
+                        debug_assert!(conflict.is_some(), "prev_log_id=None never conflict");
+
+                        self.notify_heartbeat_progress(leader_time);
+                        self.notify_progress(ReplicationResult(Err(conflict.unwrap())));
+                        return Ok(None);
+                    }
+
+                    // Delivered before the previous chunk; resend from the last matching.
+                    break;
+                }
+            }
+        }
+
+        let Some(matching) = matching else {
+            // No logs are sent.
+            return Ok(None);
+        };
+
+        if !self.throttled {
+            self.batch_size.grow();
+        }
+        self.notify_heartbeat_progress(leader_time);
+        self.notify_progress(ReplicationResult(Ok(matching)));
+        Ok(self.next_action_to_send(matching, log_ids))
+    }
+
+    /// Build the [`RPCOption`] for an AppendEntries RPC.
+    ///
+    /// If [`Config::entries_compression_threshold`] is set, the threshold is passed only if the
+    /// target can decompress entries.
+    ///
+    /// It returns an error if the protocol version of the target is not compatible.
+    async fn append_entries_option(&mut self, timeout: Duration) -> Result<RPCOption, RPCError<C>> {
+        let mut option = RPCOption::new(timeout);
+
+        let capabilities = self.target_capabilities(timeout).await;
+        self.check_compatible(capabilities.as_ref())?;
+
+        let threshold = self.config.entries_compression_threshold;
+        if threshold > 0 && capabilities.map(|c| c.entries_compression) == Some(true) {
+            option.entries_compression_threshold = Some(threshold);
+        }
+
+        Ok(option)
+    }
+
+    /// Check if the protocol version of the target is compatible with this node.
+    ///
+    /// A target whose capabilities are unknown is assumed to be compatible, as it was before the
+    /// negotiation is introduced. An incompatible target is treated as unreachable, thus it is
+    /// asked again after a backoff, e.g., when it is upgraded.
+    fn check_compatible(&self, capabilities: Option<&Capabilities>) -> Result<(), RPCError<C>> {
+        let Some(capabilities) = capabilities else {
+            return Ok(());
+        };
+
+        let local = Capabilities::local();
+        if local.is_compatible(capabilities) {
+            return Ok(());
+        }
+
+        let err = AnyError::error(format!(
+            "incompatible protocol version of target={}: target: {}(min {}), local: {}(min {})",
+            self.target,
+            capabilities.protocol_version,
+            capabilities.min_protocol_version,
+            local.protocol_version,
+            local.min_protocol_version
+        ));
+        Err(RPCError::Unreachable(Unreachable::new(&err)))
+    }
+
+    /// Get the capabilities of the target, asking the target if it is not yet known or stale.
+    ///
+    /// It returns `None` if the target has never answered. A failed probe is retried after a
+    /// backoff, not on every call.
+    async fn target_capabilities(&mut self, timeout: Duration) -> Option<Capabilities> {
+        if self.capabilities.should_probe(C::now()) {
+            let res = C::timeout(timeout, self.network.capabilities(RPCOption::new(timeout))).await;
+
+            match res {
+                Ok(Ok(capabilities)) => {
+                    tracing::info!(
+                        capabilities = debug(&capabilities),
+                        "target={} capabilities",
+                        self.target
+                    );
+                    self.capabilities.probed(capabilities);
+                }
+                Ok(Err(err)) => {
+                    tracing::warn!(error = display(&err), "failed to get capabilities of target");
+                    self.capabilities.probe_failed(C::now(), || self.network.backoff());
+                }
+                Err(_timeout) => {
+                    tracing::warn!("timeout while getting capabilities of target");
+                    self.capabilities.probe_failed(C::now(), || self.network.backoff());
+                }
+            }
+        }
+
+        self.capabilities.get()
+    }
+
+    /// Remove the trailing entries from `logs` so that the total payload size does not exceed
+    /// [`Config::max_payload_bytes`].
+    ///
+    /// At least one entry is kept, even if it is larger than the limit.
+    fn limit_payload_bytes(&self, logs: &mut Vec<C::Entry>) {
+        let max = self.config.max_payload_bytes;
+        if max == 0 {
+            return;
+        }
+
+        let mut size = 0;
+        let n = logs
+            .iter()
+            .position(|ent| {
+                size += ent.payload_size();
+                size > max
+            })
+            .unwrap_or(logs.len());
+
+        logs.truncate(std::cmp::max(n, 1));
+    }
+
+    /// Build a timeout error for an AppendEntries RPC.
+    fn append_entries_timeout(&self, timeout: Duration) -> RPCError<C> {
+        RPCError::Timeout(Timeout {
+            action: RPCTypes::AppendEntries,
+            id: self.session_id.vote_ref().leader_id().voted_for().unwrap(),
+            target: self.target,
+            timeout,
+        })
+    }
+
     /// Send the error result to RaftCore.
     /// RaftCore will then submit another replication command.
     fn send_progress_error(&mut self, err: RPCError<C>) {
@@ -750,16 +1197,27 @@ where
             Some(x) => x,
         };
 
+        let capabilities = self.target_capabilities(self.config.install_snapshot_timeout()).await;
+        self.check_compatible(capabilities.as_ref())?;
+
         let mut option = RPCOption::new(self.config.install_snapshot_timeout());
         option.snapshot_chunk_size = Some(self.config.snapshot_max_chunk_size as usize);
+        option.snapshot_max_bytes_per_sec = self.config.snapshot_max_bytes_per_sec();
+        if self.config.snapshot_compression {
+            // Only compress if the target says it can decompress: an older receiver ignores the
+            // `compressed` flag and would install the compressed bytes.
+            option.snapshot_compression = capabilities.map(|c| c.snapshot_compression) == Some(true);
+        }
 
         let (tx_cancel, rx_cancel) = C::oneshot();
 
         let jh = C::spawn(Self::send_snapshot(
             self.snapshot_network.clone(),
+            self.snapshot_reader.clone(),
             *self.session_id.vote_ref(),
             snapshot,
             option,
+            self.snapshot_progress.clone(),
             rx_cancel,
             self.weak_tx_event.clone(),
         ));
@@ -772,17 +1230,71 @@ where
         Ok(None)
     }
 
+    /// Return a delta snapshot to send instead of the full `snapshot`, if the target already has a
+    /// snapshot and the state machine can build a delta since it.
+    ///
+    /// It runs in the snapshot sending task, thus the RPC to the target does not block
+    /// replicating logs. Any failure falls back to sending the full snapshot.
+    async fn delta_or_full_snapshot(
+        net: &mut N::Network,
+        snapshot_reader: &SnapshotReader<C>,
+        snapshot: Snapshot<C>,
+        option: &RPCOption,
+    ) -> Snapshot<C> {
+        let res = C::timeout(option.hard_ttl(), net.target_snapshot_signature(option.clone())).await;
+
+        let base = match res {
+            Ok(Ok(Some(base))) => base,
+            Ok(Ok(None)) => return snapshot,
+            Ok(Err(err)) => {
+                tracing::warn!(error = display(&err), "failed to get snapshot signature of target");
+                return snapshot;
+            }
+            Err(_timeout) => {
+                tracing::warn!("timeout while getting snapshot signature of target");
+                return snapshot;
+            }
+        };
+
+        if base.last_log_id >= snapshot.meta.last_log_id {
+            // The target is not behind this snapshot, there is no delta to build.
+            return snapshot;
+        }
+
+        let delta = match snapshot_reader.get_delta_snapshot(base).await {
+            Ok(Some(delta)) => delta,
+            Ok(None) => return snapshot,
+            Err(reason) => {
+                tracing::warn!(
+                    error = display(&reason),
+                    "failed to get delta snapshot from state machine"
+                );
+                return snapshot;
+            }
+        };
+
+        tracing::info!("send delta snapshot: meta:{}", delta.meta);
+        delta
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn send_snapshot(
         network: Arc<MutexOf<C, N::Network>>,
+        snapshot_reader: SnapshotReader<C>,
         vote: Vote<C::NodeId>,
         snapshot: Snapshot<C>,
-        option: RPCOption,
+        mut option: RPCOption,
+        progress: SnapshotProgress<C>,
         cancel: OneshotReceiverOf<C, ()>,
         weak_tx: MpscUnboundedWeakSenderOf<C, Replicate<C>>,
     ) {
+        let mut net = network.lock().await;
+
+        let snapshot = Self::delta_or_full_snapshot(&mut net, &snapshot_reader, snapshot, &option).await;
         let meta = snapshot.meta.clone();
 
-        let mut net = network.lock().await;
+        progress.begin(&meta, 0);
+        option.snapshot_progress = Some(Arc::new(progress.clone()));
 
         let start_time = C::now();
 
@@ -792,6 +1304,7 @@ where
         };
 
         let res = net.full_snapshot(vote, snapshot, cancel, option).await;
+        progress.clear();
         if let Err(e) = &res {
             tracing::warn!(error = display(e), "failed to send snapshot");
         }
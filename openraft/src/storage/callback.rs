@@ -5,11 +5,15 @@ use std::io;
 use crate::async_runtime::MpscUnboundedSender;
 use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::core::notification::Notification;
+use crate::core::SlowIO;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::MpscUnboundedWeakSenderOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::async_runtime::oneshot::OneshotSender;
+use crate::type_config::TypeConfigExt;
 use crate::ErrorSubject;
 use crate::ErrorVerb;
+use crate::Instant;
 use crate::LogId;
 use crate::RaftTypeConfig;
 use crate::StorageError;
@@ -27,6 +31,9 @@ where C: RaftTypeConfig
     notification: Notification<C>,
 
     tx: MpscUnboundedWeakSenderOf<C, Notification<C>>,
+
+    /// Checks if the IO is slow, and the time the IO is submitted.
+    slow_io: Option<(SlowIO, InstantOf<C>)>,
 }
 
 impl<C> IOFlushed<C>
@@ -36,9 +43,16 @@ where C: RaftTypeConfig
         Self {
             notification: notify,
             tx,
+            slow_io: None,
         }
     }
 
+    /// Check the time from now until the IO completes with `slow_io`.
+    pub(crate) fn with_slow_io(mut self, slow_io: SlowIO) -> Self {
+        self.slow_io = Some((slow_io, C::now()));
+        self
+    }
+
     #[deprecated(since = "0.10.0", note = "Use `io_completed` instead")]
     pub fn log_io_completed(self, result: Result<(), io::Error>) {
         self.io_completed(result)
@@ -67,6 +81,10 @@ where C: RaftTypeConfig
             }
             Ok(_) => {
                 tracing::debug!("{}: IOFlushed completed: {}", func_name!(), self.notification);
+
+                if let Some((slow_io, submitted_at)) = &self.slow_io {
+                    slow_io.check(format_args!("flush {}", self.notification), submitted_at.elapsed());
+                }
                 tx.send(self.notification)
             }
         };
@@ -87,6 +105,7 @@ where C: RaftTypeConfig
             }
             Notification::HigherVote { .. }
             | Notification::StorageError { .. }
+            | Notification::StorageRetry
             | Notification::ReplicationProgress { .. }
             | Notification::HeartbeatProgress { .. }
             | Notification::StateMachine { .. }
@@ -0,0 +1,250 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Identifies a client write request for deduplication: the client that sent it and the sequence
+/// number the client assigned to it.
+///
+/// A client assigns monotonically increasing sequence numbers to its requests, and reuses the
+/// same sequence number when it retries a request, e.g., after a timeout or a
+/// [`ForwardToLeader`] error.
+///
+/// [`ForwardToLeader`]: crate::error::ForwardToLeader
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClientSession<K> {
+    /// The id of the client.
+    pub client_id: K,
+
+    /// The sequence number of the request in this client session.
+    pub seq: u64,
+}
+
+impl<K> ClientSession<K> {
+    pub fn new(client_id: K, seq: u64) -> Self {
+        Self { client_id, seq }
+    }
+}
+
+impl<K> fmt::Display for ClientSession<K>
+where K: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.client_id, self.seq)
+    }
+}
+
+/// The result of looking up a request in [`ClientSessions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionLookup<'a, R> {
+    /// The request has not yet been applied.
+    New,
+
+    /// The request is the last applied one of its client, with the response of it.
+    Applied(&'a R),
+
+    /// The request is older than the last applied one of its client. It has been applied, or has
+    /// been skipped by the client, but its response is no longer kept.
+    Stale {
+        /// The last applied sequence number of the client.
+        last_seq: u64,
+    },
+}
+
+/// Error returned by [`ClientSessions::apply()`] when a request is older than the last applied
+/// one of its client, and its response is unknown.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("stale client request: seq {seq} is older than the last applied seq {last_seq}")]
+pub struct StaleRequest {
+    /// The sequence number of the request.
+    pub seq: u64,
+
+    /// The last applied sequence number of the client.
+    pub last_seq: u64,
+}
+
+/// The highest applied sequence number and its response of every client session, for a state
+/// machine to provide exactly-once semantics for retried client writes (§6.3).
+///
+/// A client may retry a write whose response is lost, and the retried request is appended to the
+/// log again. The state machine should then return the response of the first application instead
+/// of applying it twice. To do this, the application embeds a [`ClientSession`] in its request
+/// type [`RaftTypeConfig::D`], and the state machine embeds a `ClientSessions` and applies every
+/// such request through [`ClientSessions::apply()`]:
+///
+/// ```ignore
+/// let resp = sm.sessions.apply(&req.session, || sm.data.update(&req));
+/// ```
+///
+/// [`Raft::client_write()`] does not deduplicate requests by itself: it does not know about the
+/// session a request belongs to. A retried request is appended to the log again, and it is the
+/// state machine that skips it when applying.
+///
+/// Because every node must make the same decision, `ClientSessions` is part of the state
+/// machine: it must be included in the snapshot and restored when a snapshot is installed.
+///
+/// Only the response of the last request is kept for each client. Thus a client must not send a
+/// request before the previous one has responded. A request with a sequence number lower than the
+/// last applied one is [`SessionLookup::Stale`]: its response is unknown.
+///
+/// [`RaftTypeConfig::D`]: crate::RaftTypeConfig::D
+/// [`Raft::client_write()`]: crate::Raft::client_write
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClientSessions<K, R>
+where K: Ord
+{
+    /// The last applied sequence number and its response, by client id.
+    sessions: BTreeMap<K, (u64, R)>,
+}
+
+impl<K, R> Default for ClientSessions<K, R>
+where K: Ord
+{
+    fn default() -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K, R> ClientSessions<K, R>
+where
+    K: Ord + Clone,
+    R: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a request of a client session, or return the cached response if it is a duplicate.
+    ///
+    /// `apply` is called to apply the request to the state machine only if `session.seq` is
+    /// greater than the last applied sequence number of this client. Its response is recorded
+    /// and returned.
+    ///
+    /// If the request is a retry of the last applied one, the cached response is returned.
+    /// If it is older than that, it is not applied and a [`StaleRequest`] error is returned,
+    /// because its response is no longer kept.
+    pub fn apply(&mut self, session: &ClientSession<K>, apply: impl FnOnce() -> R) -> Result<R, StaleRequest> {
+        match self.lookup(session) {
+            SessionLookup::New => {}
+            SessionLookup::Applied(resp) => return Ok(resp.clone()),
+            SessionLookup::Stale { last_seq } => {
+                return Err(StaleRequest {
+                    seq: session.seq,
+                    last_seq,
+                })
+            }
+        }
+
+        let resp = apply();
+        self.sessions.insert(session.client_id.clone(), (session.seq, resp.clone()));
+        Ok(resp)
+    }
+
+    /// Look up the request of this session: whether it is applied and the response of it.
+    pub fn lookup(&self, session: &ClientSession<K>) -> SessionLookup<'_, R> {
+        let Some((seq, resp)) = self.sessions.get(&session.client_id) else {
+            return SessionLookup::New;
+        };
+
+        match session.seq.cmp(seq) {
+            Ordering::Greater => SessionLookup::New,
+            Ordering::Equal => SessionLookup::Applied(resp),
+            Ordering::Less => SessionLookup::Stale { last_seq: *seq },
+        }
+    }
+
+    /// Return the highest applied sequence number of a client.
+    pub fn last_seq(&self, client_id: &K) -> Option<u64> {
+        self.sessions.get(client_id).map(|(seq, _)| *seq)
+    }
+
+    /// Remove a client session, e.g., when the client is expired by the application.
+    ///
+    /// It must be done deterministically on every node, usually by applying a log entry.
+    pub fn remove(&mut self, client_id: &K) -> Option<(u64, R)> {
+        self.sessions.remove(client_id)
+    }
+
+    /// Return the number of client sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Return `true` if there is no client session.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientSession;
+    use super::ClientSessions;
+    use super::SessionLookup;
+    use super::StaleRequest;
+
+    #[test]
+    fn test_client_sessions_apply() -> Result<(), StaleRequest> {
+        let mut sessions = ClientSessions::<&str, u64>::new();
+        let mut applied = 0;
+
+        let resp = sessions.apply(&ClientSession::new("a", 1), || {
+            applied += 1;
+            10
+        })?;
+        assert_eq!(10, resp);
+        assert_eq!(1, applied);
+        assert_eq!(Some(1), sessions.last_seq(&"a"));
+
+        // A retry is not applied again.
+        let resp = sessions.apply(&ClientSession::new("a", 1), || {
+            applied += 1;
+            11
+        })?;
+        assert_eq!(10, resp);
+        assert_eq!(1, applied);
+
+        // Another client is independent.
+        let resp = sessions.apply(&ClientSession::new("b", 1), || {
+            applied += 1;
+            20
+        })?;
+        assert_eq!(20, resp);
+        assert_eq!(2, applied);
+
+        // The next request is applied.
+        let resp = sessions.apply(&ClientSession::new("a", 2), || {
+            applied += 1;
+            12
+        })?;
+        assert_eq!(12, resp);
+        assert_eq!(3, applied);
+        assert_eq!(
+            SessionLookup::Applied(&12),
+            sessions.lookup(&ClientSession::new("a", 2))
+        );
+        assert_eq!(SessionLookup::New, sessions.lookup(&ClientSession::new("a", 3)));
+
+        // The response of an older request is not kept: it is neither applied nor answered with
+        // the response of a later request.
+        assert_eq!(
+            SessionLookup::Stale { last_seq: 2 },
+            sessions.lookup(&ClientSession::new("a", 1))
+        );
+        let res = sessions.apply(&ClientSession::new("a", 1), || {
+            applied += 1;
+            13
+        });
+        assert_eq!(Err(StaleRequest { seq: 1, last_seq: 2 }), res);
+        assert_eq!(3, applied);
+
+        assert_eq!(2, sessions.len());
+        assert_eq!(Some((2, 12)), sessions.remove(&"a"));
+        assert_eq!(None, sessions.last_seq(&"a"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use anyerror::AnyError;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::storage::IOFlushed;
+use crate::storage::LogState;
+use crate::storage::RaftLogStorage;
+use crate::ErrorSubject;
+use crate::ErrorVerb;
+use crate::FsyncPolicy;
+use crate::LogId;
+use crate::LogIdOptionExt;
+use crate::OptionalSend;
+use crate::RaftLogId;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::Vote;
+
+/// A [`RaftLogStorage`] wrapper that checks the invariants of the calls to the wrapped log store.
+///
+/// It is meant to be used in tests, or in a debug build, to catch bugs early: an invalid call
+/// made by Openraft is rejected with a descriptive [`StorageError`] instead of being passed to
+/// the wrapped log store. It checks:
+///
+/// - Appended logs are consecutive and follow the last log id, i.e., there is no hole.
+/// - The vote and the committed log id never regress.
+/// - Purged logs only move forward, and purged logs are never truncated.
+/// - Committed logs are never truncated.
+///
+/// It tracks the state with the results of the wrapped log store, thus a log store that does not
+/// report its state correctly in [`get_log_state()`] is also detected.
+///
+/// [`get_log_state()`]: RaftLogStorage::get_log_state
+pub struct DefensiveLogStore<C, LS>
+where C: RaftTypeConfig
+{
+    inner: LS,
+
+    vote: Option<Vote<C::NodeId>>,
+    committed: Option<LogId<C::NodeId>>,
+    last_purged_log_id: Option<LogId<C::NodeId>>,
+    last_log_id: Option<LogId<C::NodeId>>,
+}
+
+impl<C, LS> DefensiveLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+{
+    /// Wrap a log store, loading its current state to check the following calls against.
+    pub async fn new(mut inner: LS) -> Result<Self, StorageError<C>> {
+        let log_state = inner.get_log_state().await?;
+        let vote = inner.get_log_reader().await.read_vote().await?;
+        let committed = inner.read_committed().await?;
+
+        Ok(Self {
+            inner,
+            vote,
+            committed,
+            last_purged_log_id: log_state.last_purged_log_id,
+            last_log_id: log_state.last_log_id,
+        })
+    }
+
+    /// Unwrap and return the wrapped log store.
+    pub fn into_inner(self) -> LS {
+        self.inner
+    }
+
+    /// Check that the entries to append follow the last log id, and return the new last log id.
+    fn check_append(&self, entries: &[C::Entry]) -> Result<Option<LogId<C::NodeId>>, StorageError<C>> {
+        let mut last = self.last_log_id;
+        for ent in entries.iter() {
+            let log_id = *ent.get_log_id();
+
+            if log_id.index != last.next_index() || Some(log_id) <= last {
+                return Err(defensive_error(
+                    ErrorSubject::Log(log_id),
+                    ErrorVerb::Write,
+                    format!(
+                        "appended log must follow the last log id: {} -> {}",
+                        last.display(),
+                        log_id
+                    ),
+                ));
+            }
+            last = Some(log_id);
+        }
+        Ok(last)
+    }
+
+    async fn reload_log_state(&mut self) -> Result<(), StorageError<C>> {
+        let log_state = self.inner.get_log_state().await?;
+        self.last_purged_log_id = log_state.last_purged_log_id;
+        self.last_log_id = log_state.last_log_id;
+        Ok(())
+    }
+}
+
+fn defensive_error<C>(subject: ErrorSubject<C>, verb: ErrorVerb, msg: String) -> StorageError<C>
+where C: RaftTypeConfig {
+    StorageError::new(subject, verb, AnyError::error(format!("defensive check: {}", msg)))
+}
+
+impl<C, LS> RaftLogStorage<C> for DefensiveLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+{
+    type LogReader = LS::LogReader;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
+        self.inner.get_log_state().await
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        self.inner.get_log_reader().await
+    }
+
+    async fn set_fsync_policy(&mut self, policy: FsyncPolicy) -> Result<(), StorageError<C>> {
+        self.inner.set_fsync_policy(policy).await
+    }
+
+    async fn save_vote(&mut self, vote: &Vote<C::NodeId>) -> Result<(), StorageError<C>> {
+        if !(Some(vote) >= self.vote.as_ref()) {
+            return Err(defensive_error(
+                ErrorSubject::Vote,
+                ErrorVerb::Write,
+                format!("vote must not regress: {} -> {}", self.vote.display(), vote),
+            ));
+        }
+
+        self.inner.save_vote(vote).await?;
+        self.vote = Some(*vote);
+        Ok(())
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogId<C::NodeId>>) -> Result<(), StorageError<C>> {
+        if committed < self.committed {
+            return Err(defensive_error(
+                ErrorSubject::Logs,
+                ErrorVerb::Write,
+                format!(
+                    "committed must not regress: {} -> {}",
+                    self.committed.display(),
+                    committed.display()
+                ),
+            ));
+        }
+
+        self.inner.save_committed(committed).await?;
+        self.committed = committed;
+        Ok(())
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogId<C::NodeId>>, StorageError<C>> {
+        self.inner.read_committed().await
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+
+        let last = self.check_append(&entries)?;
+
+        self.inner.append(entries, callback).await?;
+        self.last_log_id = last;
+        Ok(())
+    }
+
+    async fn try_append(
+        &mut self,
+        entries: Vec<C::Entry>,
+        callback: IOFlushed<C>,
+    ) -> Result<(), (StorageError<C>, Vec<C::Entry>)> {
+        let last = self.check_append(&entries).map_err(|e| (e, vec![]))?;
+
+        self.inner.try_append(entries, callback).await?;
+        self.last_log_id = last;
+        Ok(())
+    }
+
+    async fn truncate(&mut self, log_id: LogId<C::NodeId>) -> Result<(), StorageError<C>> {
+        if log_id.index < self.last_purged_log_id.next_index() {
+            return Err(defensive_error(
+                ErrorSubject::Log(log_id),
+                ErrorVerb::Delete,
+                format!(
+                    "must not truncate purged logs: truncate since {}, last purged: {}",
+                    log_id,
+                    self.last_purged_log_id.display()
+                ),
+            ));
+        }
+
+        if Some(log_id.index) <= self.committed.index() {
+            return Err(defensive_error(
+                ErrorSubject::Log(log_id),
+                ErrorVerb::Delete,
+                format!(
+                    "must not truncate committed logs: truncate since {}, committed: {}",
+                    log_id,
+                    self.committed.display()
+                ),
+            ));
+        }
+
+        self.inner.truncate(log_id).await?;
+        self.reload_log_state().await
+    }
+
+    async fn purge(&mut self, log_id: LogId<C::NodeId>) -> Result<(), StorageError<C>> {
+        if Some(log_id) < self.last_purged_log_id {
+            return Err(defensive_error(
+                ErrorSubject::Log(log_id),
+                ErrorVerb::Delete,
+                format!(
+                    "purged log id must not regress: {} -> {}",
+                    self.last_purged_log_id.display(),
+                    log_id
+                ),
+            ));
+        }
+
+        self.inner.purge(log_id).await?;
+        self.reload_log_state().await
+    }
+}
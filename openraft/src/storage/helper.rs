@@ -2,11 +2,13 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Duration;
 
+use openraft_macros::since;
 use validit::Valid;
 
 use crate::display_ext::DisplayOptionExt;
 use crate::engine::LogIdList;
 use crate::entry::RaftPayload;
+use crate::error::StartupError;
 use crate::log_id::RaftLogId;
 use crate::raft_state::IOState;
 use crate::storage::RaftLogStorage;
@@ -170,6 +172,59 @@ where
         })
     }
 
+    /// Get Raft's state information from storage, like [`get_initial_state()`], and check the
+    /// consistency of the stored state first.
+    ///
+    /// It returns a [`StartupError`] describing the inconsistency if the storage is corrupted,
+    /// before anything is repaired, e.g., before re-applying the committed logs.
+    ///
+    /// [`get_initial_state()`]: Self::get_initial_state
+    #[since(version = "0.10.0")]
+    pub async fn get_checked_initial_state(&mut self) -> Result<RaftState<C>, StartupError<C>> {
+        let mut log_reader = self.log_store.get_log_reader().await;
+        let vote = log_reader.read_vote().await?.unwrap_or_default();
+
+        let committed = self.log_store.read_committed().await?;
+        let st = self.log_store.get_log_state().await?;
+        let (last_applied, _) = self.state_machine.applied_state().await?;
+
+        let last_purged = st.last_purged_log_id;
+        let last_log_id = st.last_log_id;
+
+        if last_log_id < last_purged {
+            return Err(StartupError::LogBeforePurged {
+                last_purged,
+                last_log_id,
+            });
+        }
+
+        if let Some(log_id) = &last_log_id {
+            if log_id.leader_id.term > vote.leader_id().get_term() {
+                return Err(StartupError::LogAheadOfVote { vote, last_log_id });
+            }
+        }
+
+        if committed > last_applied && committed > last_log_id {
+            return Err(StartupError::CommittedNotInLog { committed, last_log_id });
+        }
+
+        if last_purged > last_applied {
+            return Err(StartupError::PurgedNotApplied {
+                last_purged,
+                last_applied,
+            });
+        }
+
+        let snapshot = self.state_machine.get_current_snapshot().await?;
+        let snapshot = snapshot.and_then(|s| s.meta.last_log_id);
+        if snapshot > last_applied {
+            return Err(StartupError::SnapshotAheadOfApplied { snapshot, last_applied });
+        }
+
+        let state = self.get_initial_state().await?;
+        Ok(state)
+    }
+
     /// Returns the last 2 membership config found in log or state machine.
     ///
     /// A raft node needs to store at most 2 membership config log:
@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::storage::RaftLogReader;
+use crate::OptionalSend;
+use crate::RaftLogId;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::Vote;
+
+/// A bounded in-memory cache of the most recently appended log entries, indexed by log index.
+///
+/// It is shared by a [`RaftLogStorage`] implementation and the log readers it returns: the
+/// storage feeds it in [`append()`] and keeps it consistent in [`truncate()`] and [`purge()`];
+/// a [`CachedLogReader`] consults it before reading the storage. Since every replication task
+/// reads the same recent logs, a cache hit saves a storage read per follower.
+///
+/// It holds at most `capacity` entries; the oldest are evicted first.
+///
+/// A cache hit clones the cached entries, thus an application with large payloads should keep
+/// them in a cheaply cloneable buffer, such as `bytes::Bytes`.
+///
+/// [`RaftLogStorage`]: crate::storage::RaftLogStorage
+/// [`append()`]: crate::storage::RaftLogStorage::append
+/// [`truncate()`]: crate::storage::RaftLogStorage::truncate
+/// [`purge()`]: crate::storage::RaftLogStorage::purge
+pub struct LogCache<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<LogCacheInner<C>>>,
+}
+
+struct LogCacheInner<C>
+where C: RaftTypeConfig
+{
+    capacity: usize,
+
+    /// Consecutive entries, the last one is the last appended.
+    entries: VecDeque<C::Entry>,
+}
+
+impl<C> Clone for LogCache<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> LogCache<C>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+{
+    /// Create a cache that holds at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogCacheInner {
+                capacity,
+                entries: VecDeque::with_capacity(capacity),
+            })),
+        }
+    }
+
+    /// Add entries just appended to the log.
+    ///
+    /// If an entry does not follow the last cached one, the cache is reset.
+    pub fn append<'a>(&self, entries: impl IntoIterator<Item = &'a C::Entry>)
+    where C::Entry: 'a {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.capacity == 0 {
+            return;
+        }
+
+        for ent in entries {
+            let index = ent.get_log_id().index;
+
+            let expected = inner.entries.back().map(|x| x.get_log_id().index + 1);
+            if expected.is_some() && expected != Some(index) {
+                inner.entries.clear();
+            }
+
+            if inner.entries.len() == inner.capacity {
+                inner.entries.pop_front();
+            }
+            inner.entries.push_back(ent.clone());
+        }
+    }
+
+    /// Remove entries at and after `since`, i.e., `[since, +oo)`.
+    pub fn truncate(&self, since: u64) {
+        let mut inner = self.inner.lock().unwrap();
+
+        while inner.entries.back().map(|x| x.get_log_id().index >= since).unwrap_or(false) {
+            inner.entries.pop_back();
+        }
+    }
+
+    /// Remove entries at and before `upto`, i.e., `(-oo, upto]`.
+    pub fn purge(&self, upto: u64) {
+        let mut inner = self.inner.lock().unwrap();
+
+        while inner.entries.front().map(|x| x.get_log_id().index <= upto).unwrap_or(false) {
+            inner.entries.pop_front();
+        }
+    }
+
+    /// Get the cached entries in range `[start, end)`.
+    ///
+    /// It returns `None` if the entry at `start` is not cached. Otherwise it returns the entries
+    /// from `start` up to `end` or the last cached entry, whichever comes first.
+    pub fn get(&self, start: u64, end: u64) -> Option<Vec<C::Entry>> {
+        let inner = self.inner.lock().unwrap();
+
+        let first = inner.entries.front()?.get_log_id().index;
+        if start < first || start >= end {
+            return None;
+        }
+
+        let offset = (start - first) as usize;
+        if offset >= inner.entries.len() {
+            return None;
+        }
+
+        let n = std::cmp::min((end - start) as usize, inner.entries.len() - offset);
+        Some(inner.entries.range(offset..offset + n).cloned().collect())
+    }
+}
+
+/// A [`RaftLogReader`] that reads the recently appended entries from a [`LogCache`], and the
+/// others from the wrapped reader.
+///
+/// A [`RaftLogStorage`] implementation returns it from [`get_log_reader()`], sharing its
+/// [`LogCache`] with all the readers.
+///
+/// [`RaftLogStorage`]: crate::storage::RaftLogStorage
+/// [`get_log_reader()`]: crate::storage::RaftLogStorage::get_log_reader
+pub struct CachedLogReader<C, LR>
+where C: RaftTypeConfig
+{
+    cache: LogCache<C>,
+    inner: LR,
+}
+
+impl<C, LR> CachedLogReader<C, LR>
+where C: RaftTypeConfig
+{
+    pub fn new(cache: LogCache<C>, inner: LR) -> Self {
+        Self { cache, inner }
+    }
+}
+
+impl<C, LR> RaftLogReader<C> for CachedLogReader<C, LR>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    LR: RaftLogReader<C>,
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, StorageError<C>> {
+        let start = match range.start_bound() {
+            Bound::Included(x) => Some(*x),
+            Bound::Excluded(x) => x.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => x.checked_add(1),
+            Bound::Excluded(x) => Some(*x),
+            // The cache does not know if it has all the logs.
+            Bound::Unbounded => None,
+        };
+
+        // Only a fully cached range is served from the cache.
+        if let (Some(start), Some(end)) = (start, end) {
+            if let Some(entries) = self.cache.get(start, end) {
+                if entries.len() as u64 == end - start {
+                    return Ok(entries);
+                }
+            }
+        }
+
+        self.inner.try_get_log_entries(range).await
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<Vote<C::NodeId>>, StorageError<C>> {
+        self.inner.read_vote().await
+    }
+
+    async fn limited_get_log_entries(&mut self, start: u64, end: u64) -> Result<Vec<C::Entry>, StorageError<C>> {
+        // It is allowed to return only the first part of the range.
+        if let Some(entries) = self.cache.get(start, end) {
+            return Ok(entries);
+        }
+
+        self.inner.limited_get_log_entries(start, end).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogCache;
+    use crate::engine::testing::UTConfig;
+    use crate::testing::blank_ent;
+    use crate::RaftLogId;
+
+    fn indexes(entries: Option<Vec<crate::Entry<UTConfig>>>) -> Option<Vec<u64>> {
+        entries.map(|v| v.iter().map(|x| x.get_log_id().index).collect())
+    }
+
+    #[test]
+    fn test_log_cache() -> anyhow::Result<()> {
+        let cache = LogCache::<UTConfig>::new(3);
+        assert_eq!(None, indexes(cache.get(1, 2)));
+
+        let entries = (1..=4).map(|i| blank_ent::<UTConfig>(1, 1, i)).collect::<Vec<_>>();
+        cache.append(&entries);
+
+        // The oldest is evicted.
+        assert_eq!(None, indexes(cache.get(1, 5)));
+        assert_eq!(Some(vec![2, 3, 4]), indexes(cache.get(2, 5)));
+        assert_eq!(Some(vec![3, 4]), indexes(cache.get(3, 10)), "up to the last cached");
+        assert_eq!(Some(vec![3]), indexes(cache.get(3, 4)));
+        assert_eq!(None, indexes(cache.get(5, 6)));
+        assert_eq!(None, indexes(cache.get(3, 3)));
+
+        cache.truncate(4);
+        assert_eq!(Some(vec![2, 3]), indexes(cache.get(2, 5)));
+
+        cache.purge(2);
+        assert_eq!(None, indexes(cache.get(2, 5)));
+        assert_eq!(Some(vec![3]), indexes(cache.get(3, 5)));
+
+        // Not consecutive: reset.
+        cache.append(&[blank_ent::<UTConfig>(2, 1, 7)]);
+        assert_eq!(None, indexes(cache.get(3, 5)));
+        assert_eq!(Some(vec![7]), indexes(cache.get(7, 8)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_cache_capacity_0() -> anyhow::Result<()> {
+        let cache = LogCache::<UTConfig>::new(0);
+        cache.append(&[blank_ent::<UTConfig>(1, 1, 1)]);
+        assert_eq!(None, indexes(cache.get(1, 2)));
+
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
 //! The Raft storage interface and data types.
 
 mod callback;
+mod client_session;
+mod defensive;
 mod helper;
+mod log_cache;
 mod log_reader_ext;
 mod log_state;
+mod response_stream;
 mod snapshot;
 mod snapshot_meta;
 mod snapshot_signature;
@@ -13,9 +17,18 @@ pub use self::callback::IOFlushed;
 pub use self::callback::LogApplied;
 #[allow(deprecated)]
 pub use self::callback::LogFlushed;
+pub use self::client_session::ClientSession;
+pub use self::client_session::ClientSessions;
+pub use self::client_session::SessionLookup;
+pub use self::client_session::StaleRequest;
+pub use self::defensive::DefensiveLogStore;
 pub use self::helper::StorageHelper;
+pub use self::log_cache::CachedLogReader;
+pub use self::log_cache::LogCache;
 pub use self::log_reader_ext::RaftLogReaderExt;
 pub use self::log_state::LogState;
+pub use self::response_stream::ResponseSink;
+pub use self::response_stream::ResponseStream;
 pub use self::snapshot::Snapshot;
 pub use self::snapshot_meta::SnapshotMeta;
 pub use self::snapshot_signature::SnapshotSignature;
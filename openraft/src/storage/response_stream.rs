@@ -0,0 +1,197 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::async_runtime::MpscReceiver;
+use crate::async_runtime::MpscSender;
+use crate::type_config::alias::MpscReceiverOf;
+use crate::type_config::alias::MpscSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// A response that a state machine produces in parts after [`RaftStateMachine::apply()`] returns,
+/// for requests with large results such as scans or exports.
+///
+/// Instead of building the whole result in [`RaftTypeConfig::R`], which is buffered in `RaftCore`
+/// until it is sent to the client, the state machine embeds a `ResponseStream` in the response,
+/// and sends the items through the paired [`ResponseSink`], e.g., from a spawned task:
+///
+/// ```ignore
+/// let (sink, stream) = ResponseStream::channel(64);
+/// let rows = self.data.range_snapshot(&req.range);
+/// C::spawn(async move {
+///     for row in rows {
+///         if sink.send(row).await.is_err() {
+///             break; // The client has dropped the stream.
+///         }
+///     }
+/// });
+/// responses.push(Response::Scan(stream));
+/// ```
+///
+/// The items must be read from a consistent view of the state machine taken in `apply()`, because
+/// the state machine keeps applying logs while the items are sent. The channel is bounded, so a
+/// slow client applies backpressure to the producer rather than to the state machine.
+///
+/// A stream only exists on the local node: with feature `serde`, serializing or deserializing it
+/// returns an error. Thus a network that serializes the response refuses to send it back for a
+/// request forwarded to the leader with [`Config::forward_to_leader`], and the client receives an
+/// error instead of a silently empty stream.
+///
+/// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+/// [`Config::forward_to_leader`]: crate::Config::forward_to_leader
+#[since(version = "0.10.0")]
+pub struct ResponseStream<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    rx: MpscReceiverOf<C, T>,
+}
+
+/// The sending half of a [`ResponseStream`].
+#[since(version = "0.10.0")]
+pub struct ResponseSink<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    tx: MpscSenderOf<C, T>,
+}
+
+impl<C, T> ResponseStream<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    /// Create a stream and its sink, which buffers at most `buffer` items not yet received.
+    pub fn channel(buffer: usize) -> (ResponseSink<C, T>, Self) {
+        let (tx, rx) = C::mpsc(buffer);
+        (ResponseSink { tx }, Self { rx })
+    }
+
+    /// Receive the next item, or `None` if all the items are received.
+    ///
+    /// The stream ends when every [`ResponseSink`] is dropped.
+    pub async fn next(&mut self) -> Option<T> {
+        self.rx.recv().await
+    }
+
+    /// Receive all the remaining items.
+    pub async fn collect(mut self) -> Vec<T> {
+        let mut items = vec![];
+        while let Some(item) = self.next().await {
+            items.push(item);
+        }
+        items
+    }
+}
+
+impl<C, T> ResponseSink<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    /// Send an item to the stream, wait if the buffer of the stream is full.
+    ///
+    /// It returns the item back if the stream is dropped.
+    pub async fn send(&self, item: T) -> Result<(), T> {
+        self.tx.send(item).await.map_err(|e| e.0)
+    }
+}
+
+impl<C, T> Clone for ResponseSink<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone() }
+    }
+}
+
+impl<C, T> fmt::Debug for ResponseStream<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseStream").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C, T> serde::Serialize for ResponseStream<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer {
+        Err(serde::ser::Error::custom(
+            "ResponseStream can not be serialized: it only exists on the local node",
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C, T> serde::Deserialize<'de> for ResponseStream<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        Err(serde::de::Error::custom(
+            "ResponseStream can not be deserialized: it only exists on the local node",
+        ))
+    }
+}
+
+impl<C, T> fmt::Debug for ResponseSink<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseSink").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseStream;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    #[tokio::test]
+    async fn test_response_stream() {
+        let (sink, stream) = ResponseStream::<UTConfig, u64>::channel(2);
+
+        let h = UTConfig::<()>::spawn(async move {
+            for i in 0..10 {
+                sink.send(i).await.unwrap();
+            }
+        });
+
+        assert_eq!((0..10).collect::<Vec<_>>(), stream.collect().await);
+        h.await.unwrap();
+
+        let (sink, stream) = ResponseStream::<UTConfig, u64>::channel(2);
+        drop(stream);
+        assert_eq!(Err(3), sink.send(3).await);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_response_stream_serde() {
+        let (_sink, stream) = ResponseStream::<UTConfig, u64>::channel(2);
+
+        let res = serde_json::to_string(&stream);
+        assert!(res.is_err(), "a stream can not be serialized");
+
+        let res = serde_json::from_str::<ResponseStream<UTConfig, u64>>("null");
+        assert!(res.is_err(), "a stream can not be deserialized");
+    }
+}
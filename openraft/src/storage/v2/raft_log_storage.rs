@@ -1,7 +1,9 @@
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::storage::IOFlushed;
 use crate::storage::LogState;
+use crate::FsyncPolicy;
 use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
@@ -45,8 +47,30 @@ where C: RaftTypeConfig
     ///
     /// The method is intentionally async to give the implementation a chance to use asynchronous
     /// primitives to serialize access to the common internal object, if needed.
+    ///
+    /// Every replication task gets its own reader to read the logs to send to its target. To
+    /// reduce the storage reads when targets are up to date, the reader may serve the recently
+    /// appended entries from a cache, e.g., a [`CachedLogReader`] with a [`LogCache`] fed by
+    /// [`Self::append()`].
+    ///
+    /// [`CachedLogReader`]: crate::storage::CachedLogReader
+    /// [`LogCache`]: crate::storage::LogCache
     async fn get_log_reader(&mut self) -> Self::LogReader;
 
+    /// Set the policy of syncing appended logs to disk, i.e., [`Config::fsync_policy`].
+    ///
+    /// It is called once when a Raft node starts, before any other method. The log store applies
+    /// it in [`Self::append()`]; the `callback` of `append()` must still be called in order, and
+    /// only when the entries are synced as the policy requires.
+    ///
+    /// The default implementation ignores the policy, i.e., the log store decides when to sync.
+    ///
+    /// [`Config::fsync_policy`]: crate::Config::fsync_policy
+    #[since(version = "0.10.0")]
+    async fn set_fsync_policy(&mut self, _policy: FsyncPolicy) -> Result<(), StorageError<C>> {
+        Ok(())
+    }
+
     /// Save vote to storage.
     ///
     /// ### To ensure correctness:
@@ -96,11 +120,42 @@ where C: RaftTypeConfig
     ///
     /// - There must not be a **hole** in logs. Because Raft only examine the last log id to ensure
     ///   correctness.
+    ///
+    /// - The callbacks must be called in the order the entries are appended.
+    ///
+    /// ### Group commit
+    ///
+    /// `RaftCore` keeps processing messages and submitting more entries while an append is being
+    /// flushed; the entries are counted as accepted by this node only when the `callback` is
+    /// called. Thus an implementation does not have to fsync on every call: it can buffer the
+    /// entries of several calls, flush them with one fsync, and then call the held callbacks in
+    /// order. This way fsync latency does not limit the throughput.
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
         I::IntoIter: OptionalSend;
 
+    /// Append log entries like [`append()`](Self::append), but give the entries back if it fails,
+    /// so that they can be appended again.
+    ///
+    /// `RaftCore` calls it instead of `append()` if [`Config::storage_retry_max`] is not 0, and
+    /// retries the append later if the error is [transient] and all the entries are given back.
+    /// The `callback` must not be called if an error is returned.
+    ///
+    /// By default it calls `append()`, and the entries are not given back, i.e., a failed append is
+    /// not retried.
+    ///
+    /// [`Config::storage_retry_max`]: crate::Config::storage_retry_max
+    /// [transient]: crate::StorageError::is_transient
+    #[since(version = "0.10.0")]
+    async fn try_append(
+        &mut self,
+        entries: Vec<C::Entry>,
+        callback: IOFlushed<C>,
+    ) -> Result<(), (StorageError<C>, Vec<C::Entry>)> {
+        self.append(entries, callback).await.map_err(|e| (e, vec![]))
+    }
+
     /// Truncate logs since `log_id`, inclusive
     ///
     /// ### To ensure correctness:
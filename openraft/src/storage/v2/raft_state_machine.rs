@@ -2,11 +2,13 @@ use openraft_macros::add_async_trait;
 
 use crate::storage::Snapshot;
 use crate::storage::SnapshotMeta;
+use crate::storage::SnapshotSignature;
 use crate::LogId;
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 use crate::StoredMembership;
 
@@ -63,6 +65,11 @@ where C: RaftTypeConfig
     /// - An implementation with persistent snapshot: `apply()` does not have to persist state on
     ///   disk. But every snapshot has to be persistent. And when starting up the application, the
     ///   state machine should be rebuilt from the last snapshot.
+    ///
+    /// A large response, such as the result of a scan, does not have to be built before returning:
+    /// it can be sent in parts through a [`ResponseStream`] embedded in the response.
+    ///
+    /// [`ResponseStream`]: crate::storage::ResponseStream
     async fn apply<I>(&mut self, entries: I) -> Result<Vec<C::R>, StorageError<C>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
@@ -88,6 +95,47 @@ where C: RaftTypeConfig
     /// [sto]: crate::docs::getting_started#3-implement-raftlogstorage-and-raftstatemachine
     async fn begin_receiving_snapshot(&mut self) -> Result<Box<C::SnapshotData>, StorageError<C>>;
 
+    /// Open the snapshot `snapshot_id` for receiving by chunks, resuming a transmission
+    /// interrupted by a restart of this node.
+    ///
+    /// Openraft calls this method every time it starts receiving a snapshot by chunks, including
+    /// the first time, so that an implementation can key the received data by `snapshot_id`:
+    ///
+    /// - It returns `Some((received, data))`, where `received` is the number of bytes already
+    ///   persisted for `snapshot_id`, or `0` if there is none, and `data` is a writable handle that
+    ///   contains these bytes. The data written to `data` should be persisted along with
+    ///   `snapshot_id`, so that it can be found after a restart. `received` must not be greater
+    ///   than the size of the data that is actually persisted. If `received > 0`, the leader is
+    ///   asked to continue sending from `received`, instead of from the beginning.
+    /// - It returns `None` if resuming is not supported; Openraft then calls
+    ///   [`Self::begin_receiving_snapshot`] to receive the snapshot from the beginning.
+    ///
+    /// Persisted data of other snapshot ids is no longer needed and can be removed.
+    ///
+    /// By default, it returns `None` and an interrupted snapshot transmission always restarts from
+    /// the beginning.
+    async fn resume_receiving_snapshot(
+        &mut self,
+        snapshot_id: &SnapshotId,
+    ) -> Result<Option<(u64, Box<C::SnapshotData>)>, StorageError<C>> {
+        let _ = snapshot_id;
+        Ok(None)
+    }
+
+    /// Discard the data of a partially received snapshot, e.g., when the received data does not
+    /// match the checksum.
+    ///
+    /// After this method returns, [`Self::resume_receiving_snapshot`] must not return any data
+    /// for `snapshot_id`, i.e., it returns `None` or an offset of `0`, so that the snapshot will
+    /// be received again from offset 0.
+    ///
+    /// By default, it does nothing, which is correct only if [`Self::resume_receiving_snapshot`] is
+    /// not implemented.
+    async fn discard_receiving_snapshot(&mut self, snapshot_id: &SnapshotId) -> Result<(), StorageError<C>> {
+        let _ = snapshot_id;
+        Ok(())
+    }
+
     /// Install a snapshot which has finished streaming from the leader.
     ///
     /// Before this method returns:
@@ -118,5 +166,37 @@ where C: RaftTypeConfig
     /// A proper snapshot implementation will store last-applied-log-id and the
     /// last-applied-membership config as part of the snapshot, which should be decoded for
     /// creating this method's response data.
+    ///
+    /// On a leader, this method is called once for every follower or learner that needs a
+    /// snapshot, and every returned handle is read independently by one replication task.
+    /// The snapshot itself is built only once, thus the returned handles should share the
+    /// underlying data, e.g., open the same snapshot file again or share an in-memory buffer with
+    /// an `Arc`, instead of copying it.
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>>;
+
+    /// Build a snapshot that contains only the changes since the snapshot `base`.
+    ///
+    /// Openraft calls this method before sending a snapshot to a target node that already has
+    /// the snapshot `base`, as reported by
+    /// [`RaftNetworkV2::target_snapshot_signature()`][target_snapshot_signature].
+    /// The returned snapshot is sent instead of the current full snapshot.
+    ///
+    /// The `meta` of the returned snapshot describes the state after the delta is applied, e.g.,
+    /// `meta.last_log_id` is the last log id included.
+    /// The application encodes the delta in `SnapshotData`, so that [`Self::install_snapshot`] on
+    /// the target node can tell it from a full snapshot and apply it on top of `base`.
+    ///
+    /// It returns `None` if a delta can not be built, e.g., `base` is unknown or too old, in which
+    /// case the full snapshot is sent.
+    ///
+    /// By default, it returns `None` and the full snapshot is always sent.
+    ///
+    /// [target_snapshot_signature]: crate::network::v2::RaftNetworkV2::target_snapshot_signature
+    async fn build_delta_snapshot(
+        &mut self,
+        base: &SnapshotSignature<C>,
+    ) -> Result<Option<Snapshot<C>>, StorageError<C>> {
+        let _ = base;
+        Ok(None)
+    }
 }
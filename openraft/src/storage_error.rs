@@ -26,7 +26,7 @@ where C: RaftTypeConfig
             Ok(x) => Ok(x),
             Err(e) => {
                 let (subject, verb) = f();
-                let io_err = StorageError::new(subject, verb, AnyError::new(&e));
+                let io_err = StorageError::from_io_error(subject, verb, e);
                 Err(io_err)
             }
         }
@@ -96,16 +96,34 @@ where C: RaftTypeConfig
         Some(self)
     }
 
+    /// Build a `StorageError` from an `io::Error`.
+    ///
+    /// Errors of kind `Interrupted`, `WouldBlock` and `TimedOut` are [transient].
+    ///
+    /// [transient]: StorageError::is_transient
     pub fn from_io_error(subject: ErrorSubject<C>, verb: ErrorVerb, io_error: std::io::Error) -> Self {
-        StorageError::new(subject, verb, AnyError::new(&io_error))
+        let transient = is_transient_io_error(&io_error);
+        StorageError::new(subject, verb, AnyError::new(&io_error)).with_transient(transient)
     }
 }
 
+/// Whether an `io::Error` may succeed if the operation is retried.
+fn is_transient_io_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::Interrupted | std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+    )
+}
+
 /// Error that occurs when operating the store.
 ///
 /// It indicates a data crash.
 /// An application returning this error will shutdown the Openraft node immediately to prevent
-/// further damage.
+/// further damage, unless the error is [transient] and [`Config::storage_retry_max`] allows
+/// `RaftCore` to retry the operation.
+///
+/// [transient]: StorageError::is_transient
+/// [`Config::storage_retry_max`]: crate::Config::storage_retry_max
 #[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct StorageError<C>
@@ -115,6 +133,10 @@ where C: RaftTypeConfig
     verb: ErrorVerb,
     source: AnyError,
     backtrace: Option<String>,
+
+    /// Whether the operation may succeed if retried.
+    #[cfg_attr(feature = "serde", serde(default))]
+    transient: bool,
 }
 
 impl<C> fmt::Display for StorageError<C>
@@ -134,9 +156,31 @@ where C: RaftTypeConfig
             verb,
             source: source.into(),
             backtrace: anyerror::backtrace_str(),
+            transient: false,
         }
     }
 
+    /// Build a transient error, such as a disk being briefly full or an interrupted IO.
+    ///
+    /// `RaftCore` retries the operation that returns a transient error, if it is configured to by
+    /// [`Config::storage_retry_max`], instead of shutting down.
+    ///
+    /// [`Config::storage_retry_max`]: crate::Config::storage_retry_max
+    pub fn new_transient(subject: ErrorSubject<C>, verb: ErrorVerb, source: impl Into<AnyError>) -> Self {
+        Self::new(subject, verb, source).with_transient(true)
+    }
+
+    /// Mark this error as transient or not.
+    pub fn with_transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
+
+    /// Returns `true` if the operation may succeed if retried.
+    pub fn is_transient(&self) -> bool {
+        self.transient
+    }
+
     pub fn write_log_entry(log_id: LogId<C::NodeId>, source: impl Into<AnyError>) -> Self {
         Self::new(ErrorSubject::Log(log_id), ErrorVerb::Write, source)
     }
@@ -90,6 +90,16 @@ where
 
 /// Test suite to ensure a `RaftStore` impl works as expected.
 ///
+/// A store implementation runs all the cases with [`Suite::test_all()`], e.g.:
+///
+/// ```ignore
+/// #[tokio::test]
+/// async fn test_store() -> Result<(), StorageError<TypeConfig>> {
+///     Suite::test_all(MyStoreBuilder {}).await?;
+///     Ok(())
+/// }
+/// ```
+///
 /// Additional traits are required to be implemented by the store builder for testing:
 /// - `C::D` and `C::R` requires `Debug` for debugging.
 /// - `C::NodeId` requires `From<u64>` to build a node id.
@@ -154,6 +164,7 @@ where
         run_test(builder, Self::purge_logs_upto_20).await?;
         run_test(builder, Self::delete_logs_since_11).await?;
         run_test(builder, Self::delete_logs_since_0).await?;
+        run_test(builder, Self::delete_conflicting_logs_and_append).await?;
         run_test(builder, Self::append_to_log).await?;
         run_test(builder, Self::snapshot_meta).await?;
 
@@ -1078,6 +1089,30 @@ where
         Ok(())
     }
 
+    /// A follower deletes the logs conflicting with the leader and appends the leader's logs.
+    pub async fn delete_conflicting_logs_and_append(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
+        Self::feed_10_logs_vote_self(&mut store).await?;
+
+        store.truncate(log_id_0(1, 5)).await?;
+        append(&mut store, [blank_ent_0::<C>(2, 5), blank_ent_0::<C>(2, 6)]).await?;
+
+        let logs = store.try_get_log_entries(4..100).await?;
+        assert_eq!(
+            vec![log_id_0(1, 4), log_id_0(2, 5), log_id_0(2, 6)],
+            logs.iter().map(|x| *x.get_log_id()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            LogState {
+                last_purged_log_id: None,
+                last_log_id: Some(log_id_0(2, 6)),
+            },
+            store.get_log_state().await?
+        );
+
+        Ok(())
+    }
+
     pub async fn append_to_log(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
         Self::feed_10_logs_vote_self(&mut store).await?;
 
@@ -2,6 +2,8 @@
 
 pub mod common;
 pub mod log;
+pub mod router;
 pub mod runtime;
 
 pub use common::*;
+pub use router::Router;
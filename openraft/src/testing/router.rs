@@ -0,0 +1,308 @@
+//! An in-process network connecting the Raft nodes in the same process, for testing.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyerror::AnyError;
+
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
+use crate::error::RPCError;
+use crate::error::RaftError;
+use crate::error::RemoteError;
+use crate::error::ReplicationClosed;
+use crate::error::StreamingError;
+use crate::error::Unreachable;
+use crate::network::v2::RaftNetworkV2;
+use crate::network::Capabilities;
+use crate::network::RPCOption;
+use crate::network::RaftNetworkFactory;
+use crate::raft::message::TransferLeaderRequest;
+use crate::raft::responder::OneshotResponder;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::ClientWriteResponse;
+use crate::raft::HeartbeatRequest;
+use crate::raft::HeartbeatResponse;
+use crate::raft::SnapshotResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotSignature;
+use crate::LogId;
+use crate::OptionalSend;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// A [`RaftNetworkFactory`] that delivers RPCs to the [`Raft`] nodes in the same process.
+///
+/// It lets an application spin up a multi-node cluster in a unit test, without a real transport:
+/// create the router, pass [`Router::for_node()`] to [`Raft::new()`] of every node, and register
+/// the nodes with [`Router::add()`]. A node can be isolated with [`Router::set_unreachable()`] to
+/// emulate a network partition.
+///
+/// ```ignore
+/// let router = Router::new();
+///
+/// for id in [1, 2, 3] {
+///     let (log_store, sm) = new_store();
+///     let raft = Raft::new(id, config.clone(), router.for_node(id), log_store, sm).await?;
+///     router.add(id, raft);
+/// }
+///
+/// router.get(&1).unwrap().initialize(btreeset! {1, 2, 3}).await?;
+/// ```
+///
+/// A client write to a follower is forwarded to the leader with
+/// [`RaftNetworkV2::forward_client_write()`], which requires the [`OneshotResponder`].
+pub struct Router<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<RouterInner<C>>>,
+
+    /// The node that sends RPCs through this router, if it is returned by
+    /// [`Router::for_node()`].
+    source: Option<C::NodeId>,
+}
+
+struct RouterInner<C>
+where C: RaftTypeConfig
+{
+    nodes: BTreeMap<C::NodeId, Raft<C>>,
+
+    /// The nodes that can not send or receive any RPC.
+    unreachable: BTreeSet<C::NodeId>,
+}
+
+impl<C> Clone for Router<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            source: self.source,
+        }
+    }
+}
+
+impl<C> Default for Router<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Router<C>
+where C: RaftTypeConfig
+{
+    /// Create a router without any node.
+    pub fn new() -> Self {
+        let inner = RouterInner {
+            nodes: BTreeMap::new(),
+            unreachable: BTreeSet::new(),
+        };
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+            source: None,
+        }
+    }
+
+    /// Return a router sharing the same nodes, to be used as the network of node `id`.
+    ///
+    /// RPCs sent through it are not delivered if `id` is unreachable. Without it, only the RPCs
+    /// that carry a vote, such as `append_entries`, are blocked by an unreachable sender.
+    pub fn for_node(&self, id: C::NodeId) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            source: Some(id),
+        }
+    }
+
+    /// Register a node so that it can receive RPCs.
+    ///
+    /// If there is already a node with the same id, it is replaced and returned.
+    pub fn add(&self, id: C::NodeId, raft: Raft<C>) -> Option<Raft<C>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.nodes.insert(id, raft)
+    }
+
+    /// Remove a node from the router and return it.
+    ///
+    /// RPCs sent to a removed node return [`Unreachable`].
+    pub fn remove(&self, id: &C::NodeId) -> Option<Raft<C>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.nodes.remove(id)
+    }
+
+    /// Get a registered node.
+    pub fn get(&self, id: &C::NodeId) -> Option<Raft<C>> {
+        let inner = self.inner.lock().unwrap();
+        inner.nodes.get(id).cloned()
+    }
+
+    /// Return the ids of all of the registered nodes.
+    pub fn node_ids(&self) -> Vec<C::NodeId> {
+        let inner = self.inner.lock().unwrap();
+        inner.nodes.keys().copied().collect()
+    }
+
+    /// Make a node unable to send or receive any RPC, or restore it.
+    ///
+    /// An RPC from or to an unreachable node returns [`Unreachable`].
+    pub fn set_unreachable(&self, id: C::NodeId, unreachable: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        if unreachable {
+            inner.unreachable.insert(id);
+        } else {
+            inner.unreachable.remove(&id);
+        }
+    }
+
+    /// Get the target node if both the source and the target are reachable.
+    fn route(&self, source: Option<C::NodeId>, target: C::NodeId) -> Result<Raft<C>, Unreachable> {
+        let inner = self.inner.lock().unwrap();
+
+        for id in source.iter().chain([&target]) {
+            if inner.unreachable.contains(id) {
+                return Err(unreachable(format!("node {} is unreachable", id)));
+            }
+        }
+
+        inner
+            .nodes
+            .get(&target)
+            .cloned()
+            .ok_or_else(|| unreachable(format!("node {} is not found", target)))
+    }
+}
+
+fn unreachable(msg: impl fmt::Display) -> Unreachable {
+    Unreachable::new(&AnyError::error(msg.to_string()))
+}
+
+impl<C> RaftNetworkFactory<C> for Router<C>
+where C: RaftTypeConfig<Responder = OneshotResponder<C>>
+{
+    type Network = RouterNetwork<C>;
+
+    async fn new_client(&mut self, target: C::NodeId, _node: &C::Node) -> Self::Network {
+        RouterNetwork {
+            target,
+            router: self.clone(),
+        }
+    }
+}
+
+/// A client sending RPCs to one target through a [`Router`].
+pub struct RouterNetwork<C>
+where C: RaftTypeConfig
+{
+    target: C::NodeId,
+    router: Router<C>,
+}
+
+impl<C> RouterNetwork<C>
+where C: RaftTypeConfig
+{
+    /// Get the target node, the source is the leader or candidate in `vote`.
+    fn route(&self, vote: &Vote<C::NodeId>) -> Result<Raft<C>, Unreachable> {
+        let source = self.router.source.or(vote.leader_id().voted_for());
+        self.router.route(source, self.target)
+    }
+
+    /// Get the target node for an RPC that does not carry a vote.
+    fn route_from_source(&self) -> Result<Raft<C>, Unreachable> {
+        self.router.route(self.router.source, self.target)
+    }
+}
+
+impl<C> RaftNetworkV2<C> for RouterNetwork<C>
+where C: RaftTypeConfig<Responder = OneshotResponder<C>>
+{
+    async fn append_entries(
+        &mut self,
+        rpc: AppendEntriesRequest<C>,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse<C>, RPCError<C>> {
+        let raft = self.route(&rpc.vote)?;
+        raft.append_entries(rpc).await.map_err(|e| RPCError::Unreachable(unreachable(e)))
+    }
+
+    async fn heartbeat(
+        &mut self,
+        rpc: HeartbeatRequest<C>,
+        _option: RPCOption,
+    ) -> Result<HeartbeatResponse<C>, RPCError<C>> {
+        let raft = self.route(&rpc.vote)?;
+        raft.heartbeat(rpc).await.map_err(|e| RPCError::Unreachable(unreachable(e)))
+    }
+
+    async fn vote(&mut self, rpc: VoteRequest<C>, _option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        let raft = self.route(&rpc.vote)?;
+        raft.vote(rpc).await.map_err(|e| RPCError::Unreachable(unreachable(e)))
+    }
+
+    async fn pre_vote(&mut self, rpc: VoteRequest<C>, _option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+        let raft = self.route(&rpc.vote)?;
+        raft.pre_vote(rpc).await.map_err(|e| RPCError::Unreachable(unreachable(e)))
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote<C::NodeId>,
+        snapshot: Snapshot<C>,
+        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, StreamingError<C>> {
+        let raft = self.route(&vote)?;
+        raft.install_full_snapshot(vote, snapshot)
+            .await
+            .map_err(|e| StreamingError::Unreachable(unreachable(e)))
+    }
+
+    async fn target_snapshot_signature(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<SnapshotSignature<C>>, RPCError<C>> {
+        let raft = self.route_from_source()?;
+        let signature = raft.snapshot_signature().await.map_err(|e| RPCError::Unreachable(unreachable(e)))?;
+        Ok(signature)
+    }
+
+    async fn capabilities(&mut self, _option: RPCOption) -> Result<Capabilities, RPCError<C>> {
+        let raft = self.route_from_source()?;
+        Ok(raft.capabilities())
+    }
+
+    async fn read_index(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<LogId<C::NodeId>>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        let raft = self.route_from_source()?;
+        let (read_log_id, _applied) =
+            raft.get_read_log_id().await.map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))?;
+        Ok(read_log_id)
+    }
+
+    async fn transfer_leader(&mut self, req: TransferLeaderRequest<C>, _option: RPCOption) -> Result<(), RPCError<C>> {
+        let raft = self.route(req.from_leader())?;
+        raft.handle_transfer_leader(req).await.map_err(|e| RPCError::Unreachable(unreachable(e)))
+    }
+
+    async fn forward_client_write(
+        &mut self,
+        app_data: C::D,
+        _option: RPCOption,
+    ) -> Result<ClientWriteResponse<C>, RPCError<C, RaftError<C, ClientWriteError<C>>>> {
+        let raft = self.route_from_source()?;
+        raft.client_write(app_data)
+            .await
+            .map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))
+    }
+}
@@ -69,9 +69,17 @@ pub trait RaftTypeConfig:
 
     /// Snapshot data for exposing a snapshot for reading & writing.
     ///
+    /// Openraft itself does not read or write it, thus no IO trait is required.
+    /// It can be a file, a key in an object storage, or a list of in-memory chunks.
+    /// Only the chunk based transport used by [`RaftNetwork`] requires it to be
+    /// `AsyncRead + AsyncWrite + AsyncSeek + Unpin`.
+    /// An implementation that can not seek should use [`RaftNetworkV2`] to send a snapshot.
+    ///
     /// See the [storage chapter of the guide][sto] for details on log compaction / snapshotting.
     ///
     /// [sto]: crate::docs::getting_started#3-implement-raftlogstorage-and-raftstatemachine
+    /// [`RaftNetwork`]: crate::network::v1::RaftNetwork
+    /// [`RaftNetworkV2`]: crate::network::v2::RaftNetworkV2
     type SnapshotData: OptionalSend + 'static;
 
     /// Asynchronous runtime type.
@@ -1,18 +1,20 @@
 #![deny(unused_crate_dependencies)]
 #![deny(unused_qualifications)]
 
+mod snapshot_data;
 #[cfg(test)]
 mod test;
 
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::io::Cursor;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use openraft::alias::SnapshotDataOf;
+use openraft::storage::ClientSession;
+use openraft::storage::ClientSessions;
 use openraft::storage::IOFlushed;
 use openraft::storage::LogState;
 use openraft::storage::RaftLogReader;
@@ -20,17 +22,22 @@ use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftSnapshotBuilder;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::storage::SnapshotSignature;
 use openraft::Entry;
 use openraft::EntryPayload;
+use openraft::ErrorSubject;
+use openraft::ErrorVerb;
 use openraft::LogId;
 use openraft::OptionalSend;
 use openraft::RaftLogId;
+use openraft::SnapshotId;
 use openraft::SnapshotMeta;
 use openraft::StorageError;
 use openraft::StoredMembership;
 use openraft::Vote;
 use serde::Deserialize;
 use serde::Serialize;
+pub use snapshot_data::MemSnapshotData;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
 
@@ -79,6 +86,7 @@ openraft::declare_raft_types!(
         D = ClientRequest,
         R = ClientResponse,
         Node = (),
+        SnapshotData = MemSnapshotData,
 );
 
 /// The application snapshot type which the `MemStore` works with.
@@ -99,6 +107,12 @@ pub struct MemStoreStateMachine {
 
     /// The current status of a client by ID.
     pub client_status: HashMap<String, String>,
+
+    /// The last applied request of every client, to apply a retried request only once, or `None`
+    /// if deduplication is disabled.
+    ///
+    /// A request is identified by [`ClientRequest::client`] and [`ClientRequest::serial`].
+    pub client_sessions: Option<ClientSessions<String, ClientResponse>>,
 }
 
 #[derive(Debug, Clone)]
@@ -110,12 +124,18 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+    SaveVote,
+    AppendLog,
+    Apply,
 }
 
-/// Block operations for testing purposes.
+/// Block or fail operations for testing purposes.
 #[derive(Clone, Debug, Default)]
 pub struct BlockConfig {
     inner: Arc<Mutex<BTreeMap<BlockOperation, Duration>>>,
+
+    /// The number of the next calls of an operation that fail with a transient error.
+    failing: Arc<Mutex<BTreeMap<BlockOperation, u64>>>,
 }
 
 impl BlockConfig {
@@ -133,6 +153,32 @@ impl BlockConfig {
     pub fn clear_blocking(&mut self, block: BlockOperation) {
         self.inner.lock().unwrap().remove(&block);
     }
+
+    /// Let the next `n` calls of an operation fail with a transient error, for testing purposes.
+    pub fn set_failing(&self, op: BlockOperation, n: u64) {
+        self.failing.lock().unwrap().insert(op, n);
+    }
+
+    /// Get the number of the calls of an operation that are still going to fail.
+    pub fn get_failing(&self, op: &BlockOperation) -> u64 {
+        self.failing.lock().unwrap().get(op).copied().unwrap_or_default()
+    }
+
+    /// Return a transient error if the call of an operation should fail.
+    fn check_failing(
+        &self,
+        op: BlockOperation,
+        subject: ErrorSubject<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        let mut failing = self.failing.lock().unwrap();
+        let Some(n) = failing.get_mut(&op).filter(|n| **n > 0) else {
+            return Ok(());
+        };
+        *n -= 1;
+
+        let err = std::io::Error::new(std::io::ErrorKind::Interrupted, format!("injected failure of {:?}", op));
+        Err(StorageError::new_transient(subject, ErrorVerb::Write, &err))
+    }
 }
 
 /// An in-memory log storage implementing the `RaftLogStorage` trait.
@@ -177,6 +223,16 @@ pub struct MemStateMachine {
 
     /// Block operations for testing purposes.
     pub block: BlockConfig,
+
+    /// The bases of the delta snapshots built, or `None` if building delta snapshot is disabled.
+    ///
+    /// For testing purposes.
+    delta_bases: Mutex<Option<Vec<SnapshotSignature<TypeConfig>>>>,
+
+    /// The id and the persisted data of the snapshot being received.
+    ///
+    /// It is kept when `Raft` restarts on this state machine, thus receiving can be resumed.
+    receiving: Mutex<Option<(SnapshotId, Arc<Mutex<Vec<u8>>>)>>,
 }
 
 impl MemStateMachine {
@@ -189,9 +245,37 @@ impl MemStateMachine {
             snapshot_idx: Arc::new(Mutex::new(0)),
             current_snapshot,
             block,
+            delta_bases: Mutex::new(None),
+            receiving: Mutex::new(None),
         }
     }
 
+    /// Let [`RaftStateMachine::build_delta_snapshot()`] build a delta snapshot.
+    ///
+    /// This method is only used for testing purposes.
+    pub fn enable_delta_snapshot(&self) {
+        let mut bases = self.delta_bases.lock().unwrap();
+        bases.get_or_insert_with(Vec::new);
+    }
+
+    /// Let [`RaftStateMachine::apply()`] apply a retried client request only once, by
+    /// [`ClientSessions`].
+    ///
+    /// It must be enabled on every node before any request is applied, and it is carried to
+    /// other nodes by snapshot. This method is only used for testing purposes.
+    pub async fn enable_client_sessions(&self) {
+        let mut sm = self.sm.write().await;
+        sm.client_sessions.get_or_insert_with(ClientSessions::new);
+    }
+
+    /// Return the bases of the delta snapshots built so far.
+    ///
+    /// This method is only used for testing purposes.
+    pub fn delta_snapshot_bases(&self) -> Vec<SnapshotSignature<TypeConfig>> {
+        let bases = self.delta_bases.lock().unwrap();
+        bases.clone().unwrap_or_default()
+    }
+
     /// Remove the current snapshot.
     ///
     /// This method is only used for testing purposes.
@@ -302,7 +386,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
 
         Ok(Snapshot {
             meta,
-            snapshot: Box::new(Cursor::new(data)),
+            snapshot: Box::new(MemSnapshotData::new(data)),
         })
     }
 }
@@ -343,6 +427,8 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip(self))]
     async fn save_vote(&mut self, vote: &Vote<MemNodeId>) -> Result<(), StorageError<TypeConfig>> {
         tracing::debug!(?vote, "save_vote");
+        self.block.check_failing(BlockOperation::SaveVote, ErrorSubject::Vote)?;
+
         let mut h = self.vote.write().await;
 
         *h = Some(*vote);
@@ -363,6 +449,13 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<TypeConfig>) -> Result<(), StorageError<TypeConfig>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend {
+        self.block.check_failing(BlockOperation::AppendLog, ErrorSubject::Logs)?;
+
+        if let Some(d) = self.block.get_blocking(&BlockOperation::AppendLog) {
+            tracing::info!(?d, "block appending log");
+            tokio::time::sleep(d).await;
+        }
+
         let mut log = self.log.write().await;
         for entry in entries {
             let s =
@@ -374,6 +467,18 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
         Ok(())
     }
 
+    async fn try_append(
+        &mut self,
+        entries: Vec<Entry<TypeConfig>>,
+        callback: IOFlushed<TypeConfig>,
+    ) -> Result<(), (StorageError<TypeConfig>, Vec<Entry<TypeConfig>>)> {
+        if let Err(e) = self.block.check_failing(BlockOperation::AppendLog, ErrorSubject::Logs) {
+            return Err((e, entries));
+        }
+
+        self.append(entries, callback).await.map_err(|e| (e, vec![]))
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn truncate(&mut self, log_id: LogId<MemNodeId>) -> Result<(), StorageError<TypeConfig>> {
         tracing::debug!("delete_log: [{:?}, +oo)", log_id);
@@ -434,6 +539,8 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
         I::IntoIter: OptionalSend,
     {
+        self.block.check_failing(BlockOperation::Apply, ErrorSubject::StateMachine)?;
+
         let mut res = Vec::new();
 
         let mut sm = self.sm.write().await;
@@ -446,8 +553,21 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             match entry.payload {
                 EntryPayload::Blank => res.push(ClientResponse(None)),
                 EntryPayload::Normal(ref data) => {
-                    let previous = sm.client_status.insert(data.client.clone(), data.status.clone());
-                    res.push(ClientResponse(previous));
+                    let st = &mut *sm;
+                    let client_status = &mut st.client_status;
+                    let mut update = || ClientResponse(client_status.insert(data.client.clone(), data.status.clone()));
+
+                    let resp = match st.client_sessions.as_mut() {
+                        None => update(),
+                        Some(sessions) => {
+                            let session = ClientSession::new(data.client.clone(), data.serial);
+                            sessions.apply(&session, update).unwrap_or_else(|stale| {
+                                tracing::warn!(%session, error = display(&stale), "skip stale client request");
+                                ClientResponse(None)
+                            })
+                        }
+                    };
+                    res.push(resp);
                 }
                 EntryPayload::Membership(ref mem) => {
                     sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
@@ -464,7 +584,42 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn begin_receiving_snapshot(&mut self) -> Result<Box<SnapshotDataOf<TypeConfig>>, StorageError<TypeConfig>> {
-        Ok(Box::new(Cursor::new(Vec::new())))
+        Ok(Box::new(MemSnapshotData::new(Vec::new())))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn resume_receiving_snapshot(
+        &mut self,
+        snapshot_id: &SnapshotId,
+    ) -> Result<Option<(u64, Box<SnapshotDataOf<TypeConfig>>)>, StorageError<TypeConfig>> {
+        let mut receiving = self.receiving.lock().unwrap();
+
+        let persisted = match &*receiving {
+            Some((id, persisted)) if id == snapshot_id => persisted.clone(),
+            _ => {
+                // The data of another snapshot is no longer needed.
+                let persisted = Arc::new(Mutex::new(Vec::new()));
+                *receiving = Some((snapshot_id.clone(), persisted.clone()));
+                persisted
+            }
+        };
+
+        let data = MemSnapshotData::persisted(persisted);
+        let received = data.get_ref().len() as u64;
+
+        tracing::info!(received, "open snapshot data for receiving");
+
+        Ok(Some((received, Box::new(data))))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn discard_receiving_snapshot(&mut self, snapshot_id: &SnapshotId) -> Result<(), StorageError<TypeConfig>> {
+        let mut receiving = self.receiving.lock().unwrap();
+
+        if receiving.as_ref().map(|(id, _)| id) == Some(snapshot_id) {
+            *receiving = None;
+        }
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self, snapshot))]
@@ -498,6 +653,14 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             *sm = new_sm;
         }
 
+        // The received data is no longer needed once the snapshot is installed.
+        {
+            let mut receiving = self.receiving.lock().unwrap();
+            if receiving.as_ref().map(|(id, _)| id) == Some(&meta.snapshot_id) {
+                *receiving = None;
+            }
+        }
+
         // Update current snapshot.
         let mut current_snapshot = self.current_snapshot.write().await;
         *current_snapshot = Some(new_snapshot);
@@ -511,10 +674,28 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
                 let data = snapshot.data.clone();
                 Ok(Some(Snapshot {
                     meta: snapshot.meta.clone(),
-                    snapshot: Box::new(Cursor::new(data)),
+                    snapshot: Box::new(MemSnapshotData::new(data)),
                 }))
             }
             None => Ok(None),
         }
     }
+
+    /// The snapshot data of `MemStore` is the entire state machine, thus the current snapshot is
+    /// returned as the delta: installing it on top of `base` results in the same state.
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn build_delta_snapshot(
+        &mut self,
+        base: &SnapshotSignature<TypeConfig>,
+    ) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        {
+            let mut bases = self.delta_bases.lock().unwrap();
+            let Some(bases) = bases.as_mut() else {
+                return Ok(None);
+            };
+            bases.push(base.clone());
+        }
+
+        self.get_current_snapshot().await
+    }
 }
@@ -0,0 +1,95 @@
+use std::io;
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncSeek;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+/// The snapshot data of `MemStore`: an in-memory buffer.
+///
+/// The data of a snapshot being received is also written through to a buffer held by the state
+/// machine, which survives a restart of the node like a file on disk does. Thus receiving a
+/// snapshot can be resumed after restart.
+#[derive(Debug, Default)]
+pub struct MemSnapshotData {
+    cursor: Cursor<Vec<u8>>,
+
+    /// The buffer written data is persisted to, if the snapshot is being received.
+    persisted: Option<Arc<Mutex<Vec<u8>>>>,
+}
+
+impl MemSnapshotData {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self {
+            cursor: Cursor::new(data),
+            persisted: None,
+        }
+    }
+
+    /// Create a snapshot data that contains the data in `persisted` and writes through to it.
+    pub(crate) fn persisted(persisted: Arc<Mutex<Vec<u8>>>) -> Self {
+        let data = persisted.lock().unwrap().clone();
+        Self {
+            cursor: Cursor::new(data),
+            persisted: Some(persisted),
+        }
+    }
+
+    pub fn get_ref(&self) -> &Vec<u8> {
+        self.cursor.get_ref()
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.cursor.into_inner()
+    }
+}
+
+impl AsyncRead for MemSnapshotData {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().cursor).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MemSnapshotData {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let pos = this.cursor.position() as usize;
+        let res = Pin::new(&mut this.cursor).poll_write(cx, buf);
+
+        if let (Poll::Ready(Ok(n)), Some(persisted)) = (&res, &this.persisted) {
+            let mut persisted = persisted.lock().unwrap();
+            if persisted.len() < pos + n {
+                persisted.resize(pos + n, 0);
+            }
+            persisted[pos..pos + n].copy_from_slice(&buf[..*n]);
+        }
+
+        res
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().cursor).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().cursor).poll_shutdown(cx)
+    }
+}
+
+impl AsyncSeek for MemSnapshotData {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.get_mut().cursor).start_seek(position)
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.get_mut().cursor).poll_complete(cx)
+    }
+}
@@ -29,6 +29,7 @@ use openraft::AnyError;
 use openraft::Entry;
 use openraft::EntryPayload;
 use openraft::ErrorVerb;
+use openraft::FsyncPolicy;
 use openraft::LogId;
 use openraft::OptionalSend;
 use openraft::RaftLogId;
@@ -130,6 +131,9 @@ impl RocksStateMachine {
 #[derive(Debug, Clone)]
 pub struct RocksLogStore {
     db: Arc<DB>,
+
+    /// Whether to sync the WAL after appending logs. It is disabled by `FsyncPolicy::Never`.
+    fsync: bool,
 }
 
 type StorageResult<T> = Result<T, StorageError<TypeConfig>>;
@@ -171,6 +175,7 @@ mod meta {
 
     pub(crate) struct LastPurged {}
     pub(crate) struct Vote {}
+    pub(crate) struct Committed {}
 
     impl StoreMeta for LastPurged {
         const KEY: &'static str = "last_purged_log_id";
@@ -188,6 +193,14 @@ mod meta {
             ErrorSubject::Vote
         }
     }
+    impl StoreMeta for Committed {
+        const KEY: &'static str = "committed";
+        type Value = Option<LogId<RocksNodeId>>;
+
+        fn subject(_v: Option<&Self::Value>) -> ErrorSubject<TypeConfig> {
+            ErrorSubject::Store
+        }
+    }
 }
 
 impl RocksLogStore {
@@ -344,10 +357,26 @@ impl RaftLogStorage<TypeConfig> for RocksLogStore {
         Ok(())
     }
 
+    async fn save_committed(&mut self, committed: Option<LogId<RocksNodeId>>) -> StorageResult<()> {
+        // It does not need to be persisted at once: a committed log id lost on crash is recovered
+        // from the leader.
+        self.put_meta::<meta::Committed>(&committed)
+    }
+
+    async fn read_committed(&mut self) -> StorageResult<Option<LogId<RocksNodeId>>> {
+        Ok(self.get_meta::<meta::Committed>()?.flatten())
+    }
+
     async fn get_log_reader(&mut self) -> Self::LogReader {
         self.clone()
     }
 
+    async fn set_fsync_policy(&mut self, policy: FsyncPolicy) -> StorageResult<()> {
+        // Other policies are served by syncing on every append.
+        self.fsync = policy != FsyncPolicy::Never;
+        Ok(())
+    }
+
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<TypeConfig>) -> Result<(), StorageError<TypeConfig>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + Send {
         for entry in entries {
@@ -362,7 +391,9 @@ impl RaftLogStorage<TypeConfig> for RocksLogStore {
                 .map_err(|e| StorageError::write_logs(&e))?;
         }
 
-        self.db.flush_wal(true).map_err(|e| StorageError::write_logs(&e))?;
+        if self.fsync {
+            self.db.flush_wal(true).map_err(|e| StorageError::write_logs(&e))?;
+        }
 
         // If there is error, the callback will be dropped.
         callback.io_completed(Ok(()));
@@ -516,7 +547,13 @@ pub async fn new<P: AsRef<Path>>(db_path: P) -> (RocksLogStore, RocksStateMachin
     let db = DB::open_cf_descriptors(&db_opts, db_path, vec![meta, sm_meta, logs]).unwrap();
 
     let db = Arc::new(db);
-    (RocksLogStore { db: db.clone() }, RocksStateMachine::new(db).await)
+    (
+        RocksLogStore {
+            db: db.clone(),
+            fsync: true,
+        },
+        RocksStateMachine::new(db).await,
+    )
 }
 
 fn read_logs_err(e: impl Error + 'static) -> StorageError<TypeConfig> {
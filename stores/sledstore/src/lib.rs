@@ -354,6 +354,29 @@ impl SledStore {
         Ok(Some(v))
     }
 
+    async fn set_committed_(&self, committed: &Option<LogId<ExampleNodeId>>) -> StorageResult<()> {
+        let store_tree = store(&self.db);
+        let val = serde_json::to_vec(committed).unwrap();
+        store_tree.insert(b"committed", val).map_err(write_err)?;
+
+        // It does not need to be flushed at once:
+        // a committed log id lost on crash is recovered from the leader.
+        Ok(())
+    }
+
+    fn get_committed_(&self) -> StorageResult<Option<LogId<ExampleNodeId>>> {
+        let store_tree = store(&self.db);
+        let val = store_tree.get(b"committed").map_err(read_err)?;
+        let ivec = if let Some(t) = val {
+            t
+        } else {
+            return Ok(None);
+        };
+
+        let v = serde_json::from_slice(&ivec).map_err(read_err)?;
+        Ok(v)
+    }
+
     fn get_current_snapshot_(&self) -> StorageResult<Option<ExampleSnapshot>> {
         let store_tree = store(&self.db);
         let ivec = store_tree.get(b"snapshot").map_err(read_snap_err)?;
@@ -496,6 +519,14 @@ impl RaftLogStorage<TypeConfig> for Arc<SledStore> {
         self.set_vote_(vote).await
     }
 
+    async fn save_committed(&mut self, committed: Option<LogId<ExampleNodeId>>) -> StorageResult<()> {
+        self.set_committed_(&committed).await
+    }
+
+    async fn read_committed(&mut self) -> StorageResult<Option<LogId<ExampleNodeId>>> {
+        self.get_committed_()
+    }
+
     async fn get_log_reader(&mut self) -> Self::LogReader {
         self.clone()
     }
@@ -4,6 +4,7 @@ use std::time::Duration;
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::network::v2::RaftNetworkV2;
+use openraft::network::Capabilities;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::VoteRequest;
@@ -51,6 +52,7 @@ async fn append_sees_higher_vote() -> Result<()> {
                 VoteRequest {
                     vote: Vote::new(10, 1),
                     last_log_id: Some(LogId::new(CommittedLeaderId::new(10, 1), 5)),
+                    protocol_version: Capabilities::PROTOCOL_VERSION,
                 },
                 option,
             )
@@ -3,6 +3,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::network::Capabilities;
 use openraft::raft::AppendEntriesRequest;
 use openraft::storage::RaftLogStorage;
 use openraft::testing::blank_ent;
@@ -50,6 +51,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: None,
         entries: vec![],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -64,6 +66,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: None,
         entries: vec![blank_ent(0, 0, 0)],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -77,6 +80,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
         entries: vec![],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -98,6 +102,7 @@ async fn append_conflicts() -> Result<()> {
         ],
         // this set the last_applied to 2
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req()).await?;
@@ -122,6 +127,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 1)),
         entries: vec![blank_ent(1, 0, 2)],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -138,6 +144,7 @@ async fn append_conflicts() -> Result<()> {
         entries: vec![blank_ent(2, 0, 3)],
         // this set the last_applied to 2
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -152,6 +159,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2000)),
         entries: vec![],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -167,6 +175,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(3, 0), 3)),
         entries: vec![],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -182,6 +191,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
         entries: vec![blank_ent(2, 0, 3), blank_ent(2, 0, 4), blank_ent(2, 0, 5)],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -197,6 +207,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(2, 0), 3)),
         entries: vec![blank_ent(3, 0, 4)],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -213,6 +224,7 @@ async fn append_conflicts() -> Result<()> {
         prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 200)),
         entries: vec![],
         leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     let resp = r0.append_entries(req).await?;
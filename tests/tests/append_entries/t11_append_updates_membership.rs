@@ -3,6 +3,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::network::Capabilities;
 use openraft::raft::AppendEntriesRequest;
 use openraft::testing::blank_ent;
 use openraft::CommittedLeaderId;
@@ -62,6 +63,7 @@ async fn append_updates_membership() -> Result<()> {
                 blank_ent(1, 0, 5),
             ],
             leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+            protocol_version: Capabilities::PROTOCOL_VERSION,
         };
 
         let resp = r0.append_entries(req).await?;
@@ -78,6 +80,7 @@ async fn append_updates_membership() -> Result<()> {
             prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
             entries: vec![blank_ent(2, 0, 3)],
             leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+            protocol_version: Capabilities::PROTOCOL_VERSION,
         };
 
         let resp = r0.append_entries(req).await?;
@@ -7,15 +7,20 @@ mod fixtures;
 // The number indicate the preferred running order for these case.
 // See ./README.md
 
+mod t10_client_write_forward;
 mod t10_client_writes;
 mod t11_client_reads;
+mod t12_purge_log;
 mod t12_trigger_purge_log;
 mod t13_begin_receiving_snapshot;
 mod t13_get_snapshot;
 mod t13_install_full_snapshot;
 mod t13_trigger_snapshot;
+mod t13_trigger_snapshot_and_wait;
 mod t14_transfer_leader;
 mod t16_with_raft_state;
 mod t16_with_state_machine;
+mod t17_response_stream;
+mod t18_client_write_retry;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With `Config::forward_to_leader` enabled, a client write sent to a follower is forwarded to
+/// the leader and the leader's response is returned.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_forward() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            forward_to_leader: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write to follower 1, forwarded to leader 0");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let resp = n1.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index);
+        assert_eq!(Some(&1), router.get_rpc_count().get(&RPCTypes::ClientWrite));
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "applied on leader").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "applied on follower").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- leader is removed, follower responds with ForwardToLeader"
+    );
+    {
+        router.remove_node(0);
+
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write(ClientRequest::make_request("foo", 2)).await;
+
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::ForwardToLeader(fwd)) => {
+                assert_eq!(Some(0), fwd.leader_id);
+            }
+            _ => panic!("expect ForwardToLeader, got: {:?}", err),
+        }
+    }
+
+    Ok(())
+}
+
+/// A forwarded client write that the leader does not respond in time has an unknown outcome: it
+/// is not reported as `ForwardToLeader`, because the leader may still apply it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_forward_unknown_outcome() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            forward_to_leader: true,
+            forward_to_leader_timeout: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- leader 0 can not replicate, the forwarded write times out"
+    );
+    {
+        router.set_unreachable(1, true);
+        router.set_unreachable(2, true);
+
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write(ClientRequest::make_request("foo", 1)).await;
+        log_index += 1;
+
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::ForwardUnknownOutcome(e)) => {
+                assert_eq!(0, e.leader_id);
+            }
+            _ => panic!("expect ForwardUnknownOutcome, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- the write is still applied after recovery");
+    {
+        router.set_unreachable(1, false);
+        router.set_unreachable(2, false);
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "applied on leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -1,9 +1,13 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use futures::prelude::*;
 use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::WriteTimeout;
 use openraft::raft::ClientWriteResponse;
+use openraft::storage::RaftLogStorage;
 use openraft::CommittedLeaderId;
 use openraft::Config;
 use openraft::LogId;
@@ -97,3 +101,194 @@ async fn client_write_ff() -> Result<()> {
 
     Ok(())
 }
+
+/// Test Raft::client_write_many,
+///
+/// The requests are appended in order and a response is returned for each of them.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_many() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let reqs = (1..=3).map(|serial| ClientRequest::make_request("foo", serial)).collect();
+    let results = n0.client_write_many(reqs).await?;
+    assert_eq!(3, results.len());
+
+    let mut prev = None;
+    for (i, res) in results.into_iter().enumerate() {
+        let got = res?;
+        assert_eq!(log_index + 1 + i as u64, got.log_id.index);
+        assert_eq!(prev.as_deref(), got.response().0.as_deref());
+        prev = Some(format!("request-{}", i + 1));
+    }
+
+    tracing::info!("--- a follower responds with ForwardToLeader for every request");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let reqs = (4..=5).map(|serial| ClientRequest::make_request("foo", serial)).collect();
+        let results = n1.client_write_many(reqs).await?;
+        assert_eq!(2, results.len());
+        for res in results {
+            assert!(matches!(res, Err(ClientWriteError::ForwardToLeader(_))));
+        }
+    }
+
+    Ok(())
+}
+
+/// Test Raft::client_write_submit,
+///
+/// It returns the assigned log id once the entry is flushed to the leader's log store, before the
+/// request is applied, and the response is received later via the returned `Responder::Receiver`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_submit() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let (log_id, resp_rx) = n0.client_write_submit(ClientRequest::make_request("foo", 2)).await?;
+    assert_eq!(log_index + 1, log_id.index);
+
+    let (mut sto0, _sm0) = router.get_storage_handle(&0)?;
+    let state = sto0.get_log_state().await?;
+    assert!(
+        state.last_log_id >= Some(log_id),
+        "the entry is flushed: {:?}",
+        state.last_log_id
+    );
+
+    let got: ClientWriteResponse<TypeConfig> = resp_rx.await??;
+    assert_eq!(log_id, got.log_id);
+
+    tracing::info!("--- a follower responds with ForwardToLeader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write_submit(ClientRequest::make_request("foo", 3)).await;
+        let err = res.unwrap_err();
+        assert!(err.forward_to_leader().is_some(), "got: {}", err);
+    }
+
+    Ok(())
+}
+
+/// Test Raft::client_write_with_timeout,
+///
+/// A write that can not be committed because the quorum is lost returns a timeout error.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let timeout = Duration::from_millis(500);
+
+    let got = n0.client_write_with_timeout(ClientRequest::make_request("foo", 1), timeout).await?;
+    assert_eq!(log_index + 1, got.log_id.index);
+
+    tracing::info!("--- isolate followers, the write can not be committed");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let res = n0.client_write_with_timeout(ClientRequest::make_request("foo", 2), timeout).await;
+        let err = res.unwrap_err();
+        assert_eq!(
+            Some(&ClientWriteError::Timeout(WriteTimeout { timeout })),
+            err.api_error()
+        );
+    }
+
+    Ok(())
+}
+
+/// Test Raft::client_write_with_timeout with tick enabled,
+///
+/// RaftCore fails the write when the deadline expires, but the entry is still committed and
+/// applied when the quorum is restored.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_timeout_applied_after_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            // Isolated followers must not elect, which may discard the entry.
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let timeout = Duration::from_millis(500);
+
+    tracing::info!("--- isolate followers, the write times out");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let res = n0.client_write_with_timeout(ClientRequest::make_request("foo", 1), timeout).await;
+        let err = res.unwrap_err();
+        assert_eq!(
+            Some(&ClientWriteError::Timeout(WriteTimeout { timeout })),
+            err.api_error()
+        );
+    }
+
+    tracing::info!("--- restore followers, the timed out write is applied");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        router
+            .wait(&0, Some(Duration::from_millis(2_000)))
+            .applied_index(Some(log_index + 1), "applied")
+            .await?;
+
+        let got = n0.client_write_with_timeout(ClientRequest::make_request("foo", 2), timeout).await?;
+        assert_eq!(log_index + 2, got.log_id.index);
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use std::time::Duration;
 use anyerror::AnyError;
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::error::CheckIsLeaderError;
 use openraft::error::NetworkError;
 use openraft::error::RPCError;
 use openraft::Config;
@@ -183,6 +184,222 @@ async fn get_read_log_id() -> Result<()> {
     Ok(())
 }
 
+/// With lease read enabled, the leader serves reads without sending heartbeats within the lease.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lease_read() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            enable_lease_read: true,
+            max_clock_drift: 100,
+            heartbeat_interval: 100,
+            election_timeout_min: 1000,
+            election_timeout_max: 1100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a quorum acks a heartbeat, then isolate node 1 and 2");
+    {
+        n0.trigger().heartbeat().await?;
+        // Wait for the heartbeat to be acked.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+    }
+
+    tracing::info!(log_index, "--- ensure_linearizable succeeds within the lease");
+    {
+        router.ensure_linearizable(0).await?;
+    }
+
+    tracing::info!(log_index, "--- ensure_linearizable fails after the lease expires");
+    {
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let rst = router.ensure_linearizable(0).await;
+        assert!(rst.is_err());
+    }
+
+    Ok(())
+}
+
+/// While transferring leadership, the leader does not serve reads with the lease, because the
+/// followers have given up the lease and a new leader may be elected at any time.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lease_read_disabled_when_transferring_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            enable_lease_read: true,
+            max_clock_drift: 100,
+            heartbeat_interval: 100,
+            election_timeout_min: 1000,
+            election_timeout_max: 1100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a quorum acks a heartbeat, then isolate node 1 and 2");
+    {
+        n0.trigger().heartbeat().await?;
+        // Wait for the heartbeat to be acked.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+    }
+
+    tracing::info!(log_index, "--- ensure_linearizable succeeds within the lease");
+    {
+        router.ensure_linearizable(0).await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- ensure_linearizable fails within the lease when transferring leader"
+    );
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        let rst = router.ensure_linearizable(0).await;
+        assert!(rst.is_err(), "quorum can not be reached");
+    }
+
+    Ok(())
+}
+
+/// A follower or a learner serves a linearizable read with `Raft::read_index()`, by getting a read
+/// log id from the leader and waiting for its state machine to apply up to it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn read_index_on_non_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- write to leader");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+    }
+
+    for id in [0, 1, 3] {
+        tracing::info!(log_index, "--- read_index on node {}", id);
+
+        let n = router.get_raft_handle(&id)?;
+        let read_log_id = n.read_index().await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+
+        let applied = n.metrics().borrow().last_applied;
+        assert!(applied.index() >= Some(log_index));
+    }
+
+    tracing::info!(log_index, "--- read_index fails if the leader is unreachable");
+    {
+        router.remove_node(0);
+        let n3 = router.get_raft_handle(&3)?;
+        let rst = n3.read_index().await;
+        tracing::debug!(?rst, "read_index with leader removed");
+        assert!(rst.is_err());
+    }
+
+    Ok(())
+}
+
+/// `Raft::client_read()` rejects a read on a non-leader at once with `ForwardToLeader`, and
+/// returns `QuorumNotEnough` on a leader that can not confirm its leadership.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_read_fast_path() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    assert!(n0.is_current_leader());
+    assert!(!n1.is_current_leader());
+    assert_eq!(Some(0), n1.current_leader().await);
+
+    tracing::info!(log_index, "--- client_read on leader");
+    {
+        let read_log_id = n0.client_read().await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+    }
+
+    tracing::info!(log_index, "--- client_read on follower returns ForwardToLeader");
+    {
+        let err = n1.client_read().await.unwrap_err();
+        match err.api_error() {
+            Some(CheckIsLeaderError::ForwardToLeader(fwd)) => {
+                assert_eq!(Some(0), fwd.leader_id);
+            }
+            _ => panic!("expect ForwardToLeader, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- client_read on leader without quorum returns QuorumNotEnough"
+    );
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let err = n0.client_read().await.unwrap_err();
+        match err.api_error() {
+            Some(CheckIsLeaderError::QuorumNotEnough(_)) => {}
+            _ => panic!("expect QuorumNotEnough, got: {:?}", err),
+        }
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(200))
 }
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::LogNotApplied;
+use openraft::error::LogNotInSnapshot;
+use openraft::error::PurgeLogError;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Call `Raft::purge_log()` to purge logs, it refuses to purge logs not applied or not in a
+/// snapshot.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn purge_log() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            // Disable building snapshot by policy.
+            snapshot_policy: SnapshotPolicy::Never,
+            // Disable auto purge by policy.
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write some logs and build a snapshot on node-0");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 write logs").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+    }
+
+    let snapshot_index = log_index;
+
+    tracing::info!(log_index, "--- write another bunch of logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 write logs").await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- can not purge logs not applied");
+    {
+        let res = n0.purge_log(log_index + 1).await;
+        assert_eq!(
+            PurgeLogError::NotApplied(LogNotApplied {
+                upto: log_index + 1,
+                last_applied: Some(log_id(1, 0, log_index)),
+            }),
+            res.unwrap_err().into_api_error().unwrap()
+        );
+    }
+
+    tracing::info!(log_index, "--- can not purge logs not in snapshot");
+    {
+        let res = n0.purge_log(log_index).await;
+        assert_eq!(
+            PurgeLogError::NotInSnapshot(LogNotInSnapshot {
+                upto: log_index,
+                snapshot_last_log_id: Some(log_id(1, 0, snapshot_index)),
+            }),
+            res.unwrap_err().into_api_error().unwrap()
+        );
+    }
+
+    tracing::info!(log_index, "--- purge logs in snapshot");
+    {
+        n0.purge_log(snapshot_index).await?;
+
+        router
+            .wait(&0, timeout())
+            .purged(
+                Some(log_id(1, 0, snapshot_index)),
+                format_args!("node-0 purged upto {}", snapshot_index),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::CommittedLeaderId;
+use openraft::Config;
+use openraft::LogId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Manually build a snapshot with `Raft::trigger().snapshot_and_wait()`, and it returns when the
+/// snapshot is built.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn trigger_snapshot_and_wait() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- send some logs");
+    {
+        router.client_request_many(0, "0", 10).await?;
+        log_index += 10;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 write logs").await?;
+    }
+
+    tracing::info!(log_index, "--- build snapshot on node-0 and wait");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let meta = n0.trigger().snapshot_and_wait().await?;
+
+        let want = LogId::new(CommittedLeaderId::new(1, 0), log_index);
+        assert_eq!(Some(want), meta.last_log_id);
+
+        let snapshot = n0.get_snapshot().await?.unwrap();
+        assert_eq!(Some(want), snapshot.meta.last_log_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
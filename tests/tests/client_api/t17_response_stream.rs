@@ -0,0 +1,170 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::ResponseStream;
+use openraft::storage::Snapshot;
+use openraft::testing::Router;
+use openraft::Config;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::RaftSnapshotBuilder;
+use openraft::SnapshotMeta;
+use openraft::StorageError;
+use openraft::StoredMembership;
+
+use crate::fixtures::ut_harness;
+
+openraft::declare_raft_types!(
+    StreamConfig:
+        D = u64,
+        R = StreamResponse,
+);
+
+type TC = StreamConfig;
+type Err = StorageError<TC>;
+
+/// The response to a request `n`: a stream of `0..n`, or `None` for a blank or membership log.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct StreamResponse {
+    items: Option<ResponseStream<TC, u64>>,
+}
+
+/// A state machine that responds to a request `n` with a stream of `0..n`, sent from a spawned
+/// task.
+#[derive(Clone, Default)]
+struct StreamStateMachine {
+    last_applied: Option<LogId<u64>>,
+    last_membership: StoredMembership<TC>,
+}
+
+impl RaftSnapshotBuilder<TC> for StreamStateMachine {
+    async fn build_snapshot(&mut self) -> Result<Snapshot<TC>, Err> {
+        Ok(Snapshot {
+            meta: SnapshotMeta {
+                last_log_id: self.last_applied,
+                last_membership: self.last_membership.clone(),
+                snapshot_id: "stream".to_string(),
+            },
+            snapshot: Box::new(Cursor::new(vec![])),
+        })
+    }
+}
+
+impl RaftStateMachine<TC> for StreamStateMachine {
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogId<u64>>, StoredMembership<TC>), Err> {
+        Ok((self.last_applied, self.last_membership.clone()))
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<StreamResponse>, Err>
+    where
+        I: IntoIterator<Item = Entry<TC>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let mut res = vec![];
+
+        for entry in entries {
+            self.last_applied = Some(entry.log_id);
+
+            let items = match entry.payload {
+                EntryPayload::Blank => None,
+                EntryPayload::Normal(n) => {
+                    let (sink, stream) = ResponseStream::channel(2);
+                    tokio::spawn(async move {
+                        for i in 0..n {
+                            if sink.send(i).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                    Some(stream)
+                }
+                EntryPayload::Membership(m) => {
+                    self.last_membership = StoredMembership::new(Some(entry.log_id), m);
+                    None
+                }
+            };
+
+            res.push(StreamResponse { items });
+        }
+
+        Ok(res)
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<Box<Cursor<Vec<u8>>>, Err> {
+        Ok(Box::new(Cursor::new(vec![])))
+    }
+
+    async fn install_snapshot(&mut self, meta: &SnapshotMeta<TC>, _snapshot: Box<Cursor<Vec<u8>>>) -> Result<(), Err> {
+        self.last_applied = meta.last_log_id;
+        self.last_membership = meta.last_membership.clone();
+        Ok(())
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TC>>, Err> {
+        Ok(None)
+    }
+}
+
+/// A state machine returns a [`ResponseStream`] to `Raft::client_write()`, and the client receives
+/// every item. The stream refuses to be serialized, so that it is not forwarded to another node
+/// as a silently empty stream.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn response_stream() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = Router::<TC>::new();
+
+    tracing::info!("--- initializing cluster of 1 node");
+    let raft = {
+        let log_store = memstore::LogStore::<TC>::default();
+        let raft = Raft::new(1, config, router.clone(), log_store, StreamStateMachine::default()).await?;
+        router.add(1, raft.clone());
+
+        raft.initialize(btreeset! {1}).await?;
+        raft.wait(timeout()).current_leader(1, "node-1 is leader").await?;
+        raft
+    };
+
+    tracing::info!("--- write a request, the response is streamed back");
+    let resp = {
+        let resp = raft.client_write(5).await?;
+        let items = resp.data.items.expect("a normal log responds with a stream");
+        assert_eq!(vec![0, 1, 2, 3, 4], items.collect().await);
+
+        raft.client_write(3).await?
+    };
+
+    tracing::info!("--- a response with a stream can not be sent to another node");
+    {
+        let res = serde_json::to_string(&resp.data);
+        assert!(res.is_err(), "a stream can not be serialized");
+    }
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A retried client write is applied only once by a state machine that deduplicates requests
+/// with `ClientSessions`, and the sessions are carried to another node by snapshot.
+///
+/// What does this test do?
+///
+/// - build a single node cluster whose state machine deduplicates client requests.
+/// - write two requests, then retry the second one with different data: the response of the first
+///   application is returned, and the retry does not change the state.
+/// - build a snapshot, purge the logs, and add a learner that receives the snapshot.
+/// - retry again: the learner, whose sessions are restored from the snapshot, skips it too.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_retry() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+    sm0.enable_client_sessions().await;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    // The same `client` and `serial` as a previous request, but different data.
+    let retry = ClientRequest {
+        status: "retry".to_string(),
+        ..ClientRequest::make_request("foo", 2)
+    };
+
+    tracing::info!(log_index, "--- write 2 requests, then retry the second one");
+    {
+        let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        assert_eq!(None, resp.data.0);
+
+        let resp = n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+        assert_eq!(Some("request-1".to_string()), resp.data.0);
+
+        let resp = n0.client_write(retry.clone()).await?;
+        log_index += 3;
+        assert_eq!(
+            Some("request-1".to_string()),
+            resp.data.0,
+            "the response of the first application is returned"
+        );
+
+        let state = sm0.get_state_machine().await;
+        assert_eq!(Some(&"request-2".to_string()), state.client_status.get("foo"));
+    }
+
+    tracing::info!(log_index, "--- a request older than the last applied one is skipped");
+    {
+        let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+        assert_eq!(None, resp.data.0);
+
+        let state = sm0.get_state_machine().await;
+        assert_eq!(Some(&"request-2".to_string()), state.client_status.get("foo"));
+    }
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs");
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- add a learner, which receives the sessions by snapshot");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index - 1), "learner-1 snapshot").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 catch up").await?;
+
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        let sessions = sm1.get_state_machine().await.client_sessions;
+        assert_eq!(Some(2), sessions.and_then(|s| s.last_seq(&"foo".to_string())));
+    }
+
+    tracing::info!(log_index, "--- retry on the leader, the learner skips it too");
+    {
+        let resp = n0.client_write(retry).await?;
+        log_index += 1;
+        assert_eq!(Some("request-1".to_string()), resp.data.0);
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 applies the retry").await?;
+
+        for id in [0, 1] {
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let state = sm.get_state_machine().await;
+            assert_eq!(
+                Some(&"request-2".to_string()),
+                state.client_status.get("foo"),
+                "node-{} applies the request only once",
+                id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
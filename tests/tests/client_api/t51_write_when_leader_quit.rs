@@ -6,6 +6,7 @@ use maplit::btreeset;
 use openraft::error::ClientWriteError;
 use openraft::error::ForwardToLeader;
 use openraft::error::RaftError;
+use openraft::network::Capabilities;
 use openraft::raft::AppendEntriesRequest;
 use openraft::testing::log_id;
 use openraft::Config;
@@ -71,6 +72,7 @@ async fn write_when_leader_quit_and_log_revert() -> Result<()> {
 
                 entries: vec![],
                 leader_commit: None,
+                protocol_version: Capabilities::PROTOCOL_VERSION,
             })
             .await?;
 
@@ -148,6 +150,7 @@ async fn write_when_leader_switched() -> Result<()> {
 
                 // Inform node-0 to commit the pending log.
                 leader_commit: Some(log_id(1, 0, log_index + 1)),
+                protocol_version: Capabilities::PROTOCOL_VERSION,
             })
             .await?;
 
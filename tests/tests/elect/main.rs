@@ -9,3 +9,8 @@ mod fixtures;
 
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
+mod t12_elect_pre_vote;
+mod t13_leader_check_quorum;
+mod t14_elect_priority;
+mod t15_elect_simulated_time;
+mod t16_elect_external_tick;
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With pre-vote enabled, an isolated node does not increase its term,
+/// and does not disturb the leader when it rejoins.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_pre_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_pre_vote: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+    let leader_vote = n0.metrics().borrow().vote;
+
+    tracing::info!(log_index, "--- isolate node 2, it runs pre-vote but does not elect");
+    {
+        router.set_network_error(2, true);
+
+        // Wait for several election timeouts.
+        tokio::time::sleep(Duration::from_millis(config.election_timeout_max * 5)).await;
+
+        let n2 = router.get_raft_handle(&2)?;
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(
+            leader_vote.leader_id().term,
+            m.vote.leader_id().term,
+            "term is not increased"
+        );
+        assert_eq!(ServerState::Follower, m.state);
+    }
+
+    tracing::info!(log_index, "--- restore node 2, the leader is not disturbed");
+    {
+        router.set_network_error(2, false);
+
+        n0.wait(timeout()).log(Some(log_index), "node 0 keeps leading, log is not changed").await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).current_leader(0, "node 2 follows node 0").await?;
+
+        assert_eq!(leader_vote, n0.metrics().borrow().vote);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
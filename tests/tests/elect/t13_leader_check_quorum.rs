@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With check-quorum enabled, a leader partitioned from a quorum steps down.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_check_quorum() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_check_quorum: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+    tracing::info!(log_index, "--- isolate node 0, it steps down");
+    {
+        router.set_network_error(0, true);
+
+        n0.wait(timeout()).metrics(|m| m.state != ServerState::Leader, "node 0 steps down").await?;
+    }
+
+    tracing::info!(log_index, "--- node 1 or 2 becomes the new leader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "a new leader is elected",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3000))
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// When the leader is gone, the node with the highest election priority becomes the next leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_priority() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_election_priority: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let high_priority_config = Arc::new(
+        Config {
+            election_priority: 2,
+            max_election_priority: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create node 0,1 with priority 0, node 2 with priority 2");
+    {
+        router.new_raft_node(0).await;
+        router.new_raft_node(1).await;
+
+        let (log_store, sm) = router.new_store();
+        router.new_raft_node_with_config(2, high_priority_config, log_store, sm).await;
+    }
+
+    tracing::info!("--- initialize cluster on node 0");
+    {
+        router.initialize(0).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+    }
+
+    tracing::info!("--- isolate node 0, node 2 becomes the new leader");
+    {
+        router.set_network_error(0, true);
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "a new leader is elected",
+            )
+            .await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(Some(2), m.current_leader, "node 2 has the highest priority");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3000))
+}
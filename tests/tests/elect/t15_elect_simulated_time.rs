@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness_sim;
+use crate::fixtures::RaftRouter;
+
+/// A partitioned leader is replaced after the election timeout, on virtual time.
+///
+/// The timeouts are several seconds long, but the test does not sleep for real: the paused clock
+/// jumps to the next timer whenever the cluster is idle.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness_sim)]
+async fn elect_simulated_time() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 1_000,
+            election_timeout_min: 3_000,
+            election_timeout_max: 4_000,
+            enable_check_quorum: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let wall_start = std::time::Instant::now();
+    let virtual_start = tokio::time::Instant::now();
+
+    tracing::info!(log_index, "--- isolate node 0, node 1 or 2 becomes the new leader");
+    {
+        router.set_network_error(0, true);
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "a new leader is elected",
+            )
+            .await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).metrics(|m| m.state != ServerState::Leader, "node 0 steps down").await?;
+    }
+
+    tracing::info!(log_index, "--- the election took several seconds of virtual time only");
+    {
+        let virtual_elapsed = virtual_start.elapsed();
+        let wall_elapsed = wall_start.elapsed();
+
+        // The last heartbeat from node 0 is sent at most one heartbeat interval before isolating.
+        assert!(
+            virtual_elapsed >= Duration::from_millis(3_000 - 1_000),
+            "a new leader can not be elected before the election timeout, but took {:?}",
+            virtual_elapsed
+        );
+        assert!(
+            wall_elapsed < virtual_elapsed,
+            "wall clock {:?} should be shorter than virtual time {:?}",
+            wall_elapsed,
+            virtual_elapsed
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(30_000))
+}
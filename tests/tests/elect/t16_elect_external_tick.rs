@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With the internal tick disabled, a follower detects the election timeout only when an
+/// external tick source calls `Trigger::tick()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_external_tick() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- disable tick on node 1 and 2, isolate node 0");
+    {
+        n1.runtime_config().tick(false);
+        n2.runtime_config().tick(false);
+
+        router.set_network_error(0, true);
+
+        // Wait for several election timeouts.
+        tokio::time::sleep(Duration::from_millis(config.election_timeout_max * 5)).await;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, m.state, "node 1 does not elect without tick");
+        assert_eq!(Some(0), m.current_leader);
+    }
+
+    tracing::info!(log_index, "--- tick node 1 externally, it elects");
+    {
+        n1.trigger().tick().await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
@@ -22,24 +22,35 @@ use anyerror::AnyError;
 use anyhow::Context;
 use lazy_static::lazy_static;
 use maplit::btreeset;
+use openraft::error::decompose::DecomposeResult;
 use openraft::error::CheckIsLeaderError;
 use openraft::error::ClientWriteError;
 use openraft::error::Fatal;
 use openraft::error::Infallible;
+use openraft::error::InstallSnapshotError;
 use openraft::error::NetworkError;
 use openraft::error::PayloadTooLarge;
 use openraft::error::RPCError;
 use openraft::error::RaftError;
+use openraft::error::RemoteError;
 use openraft::error::ReplicationClosed;
 use openraft::error::StreamingError;
 use openraft::error::Unreachable;
 use openraft::metrics::Wait;
+use openraft::network::snapshot_transport::Chunked;
+use openraft::network::snapshot_transport::SnapshotTransport;
+use openraft::network::Capabilities;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
+use openraft::network::SnapshotTransform;
+use openraft::network::TraceContext;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::AppendEntriesResponse;
 use openraft::raft::ClientWriteResponse;
+use openraft::raft::HeartbeatRequest;
+use openraft::raft::HeartbeatResponse;
 use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::InstallSnapshotResponse;
 use openraft::raft::SnapshotResponse;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::VoteRequest;
@@ -47,6 +58,7 @@ use openraft::raft::VoteResponse;
 use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::storage::SnapshotSignature;
 use openraft::Config;
 use openraft::LogId;
 use openraft::LogIdOptionExt;
@@ -56,6 +68,7 @@ use openraft::Raft;
 use openraft::RaftLogId;
 use openraft::RaftLogReader;
 use openraft::RaftMetrics;
+use openraft::RaftNetwork;
 use openraft::RaftState;
 use openraft::RaftTypeConfig;
 use openraft::ServerState;
@@ -72,6 +85,7 @@ use openraft_memstore::TypeConfig as MemConfig;
 use pretty_assertions::assert_eq;
 #[allow(unused_imports)]
 use pretty_assertions::assert_ne;
+use tracing::Instrument;
 use tracing_appender::non_blocking::WorkerGuard;
 
 use crate::fixtures::logging::init_file_logging;
@@ -111,6 +125,40 @@ where
     res
 }
 
+/// Create a harness that runs a test in a single-threaded tokio runtime with virtual time.
+///
+/// The clock is paused: it does not move with the wall clock, but jumps to the next timer as soon
+/// as all tasks are idle. Thus a test that waits for several election timeouts finishes without
+/// really sleeping.
+///
+/// The runs are not deterministic: the randomized election timeouts and backoffs are drawn from
+/// an unseeded [`AsyncRuntime::thread_rng()`](openraft::AsyncRuntime::thread_rng). A test must
+/// not depend on which node times out first.
+///
+/// Because `RaftCore` reads time only through [`AsyncRuntime`](openraft::AsyncRuntime), and
+/// [`TokioRuntime`](openraft::TokioRuntime) uses `tokio::time`, a cluster built by [`RaftRouter`]
+/// runs on virtual time without any change.
+pub fn ut_harness_sim<F, Fut>(f: F) -> anyhow::Result<()>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = anyhow::Result<()>> + 'static,
+{
+    #[allow(clippy::let_unit_value)]
+    let _g = init_default_ut_tracing();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("Failed building the Runtime");
+
+    let res = rt.block_on(f());
+    if let Err(e) = &res {
+        tracing::error!("simulation error: {:?}", e);
+    }
+    res
+}
+
 pub fn init_default_ut_tracing() {
     static START: Once = Once::new();
 
@@ -211,13 +259,25 @@ impl RPCErrorType {
                 RPCTypes::Vote => {
                     unreachable!("Vote RPC should not be too large")
                 }
+                RPCTypes::PreVote => {
+                    unreachable!("PreVote RPC should not be too large")
+                }
                 RPCTypes::AppendEntries => PayloadTooLarge::new_entries_hint(*entries_hint).into(),
+                RPCTypes::Heartbeat => {
+                    unreachable!("Heartbeat RPC should not be too large")
+                }
                 RPCTypes::InstallSnapshot => {
                     unreachable!("InstallSnapshot RPC should not be too large")
                 }
                 RPCTypes::TransferLeader => {
                     unreachable!("TransferLeader RPC should not be too large")
                 }
+                RPCTypes::ReadIndex => {
+                    unreachable!("ReadIndex RPC should not be too large")
+                }
+                RPCTypes::ClientWrite => {
+                    unreachable!("ClientWrite RPC should not be too large")
+                }
             },
         }
     }
@@ -232,6 +292,7 @@ pub enum RPCRequest<C: RaftTypeConfig>
 where C::SnapshotData: fmt::Debug
 {
     AppendEntries(AppendEntriesRequest<C>),
+    Heartbeat(HeartbeatRequest<C>),
     InstallSnapshot(InstallSnapshotRequest<C>),
     InstallFullSnapshot(Snapshot<C>),
     Vote(VoteRequest<C>),
@@ -244,6 +305,7 @@ where C::SnapshotData: fmt::Debug
     pub fn get_type(&self) -> RPCTypes {
         match self {
             RPCRequest::AppendEntries(_) => RPCTypes::AppendEntries,
+            RPCRequest::Heartbeat(_) => RPCTypes::Heartbeat,
             RPCRequest::InstallSnapshot(_) => RPCTypes::InstallSnapshot,
             RPCRequest::InstallFullSnapshot(_) => RPCTypes::InstallSnapshot,
             RPCRequest::Vote(_) => RPCTypes::Vote,
@@ -285,6 +347,16 @@ pub struct TypedRaftRouter {
 
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<Mutex<HashMap<RPCTypes, RPCPreHook>>>,
+
+    /// If set, snapshots are sent in chunks encoded by it, and decoded by the target node.
+    snapshot_transform: Option<Arc<dyn SnapshotTransform>>,
+
+    /// If set, the tracing context is sent along with every RPC and restored by the target node.
+    trace_context: Option<Arc<dyn TraceContext>>,
+
+    /// The capabilities a node replies with instead of its own, to emulate a node of another
+    /// version.
+    capabilities: Arc<Mutex<HashMap<MemNodeId, Capabilities>>>,
 }
 
 /// Default `RaftRouter` for memstore.
@@ -293,6 +365,8 @@ pub type RaftRouter = TypedRaftRouter;
 pub struct Builder {
     config: Arc<Config>,
     send_delay: u64,
+    snapshot_transform: Option<Arc<dyn SnapshotTransform>>,
+    trace_context: Option<Arc<dyn TraceContext>>,
 }
 
 impl Builder {
@@ -301,6 +375,18 @@ impl Builder {
         self
     }
 
+    /// Send snapshots in chunks with [`Chunked`] transport, encoded and decoded by `transform`.
+    pub fn snapshot_transform(mut self, transform: Arc<dyn SnapshotTransform>) -> Self {
+        self.snapshot_transform = Some(transform);
+        self
+    }
+
+    /// Send the tracing context along with every RPC, injected and extracted by `trace_context`.
+    pub fn trace_context(mut self, trace_context: Arc<dyn TraceContext>) -> Self {
+        self.trace_context = Some(trace_context);
+        self
+    }
+
     pub fn build(self) -> TypedRaftRouter {
         let send_delay = {
             let send_delay = env::var("OPENRAFT_NETWORK_SEND_DELAY").ok();
@@ -320,13 +406,21 @@ impl Builder {
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
             rpc_pre_hook: Default::default(),
+            snapshot_transform: self.snapshot_transform,
+            trace_context: self.trace_context,
+            capabilities: Default::default(),
         }
     }
 }
 
 impl TypedRaftRouter {
     pub fn builder(config: Arc<Config>) -> Builder {
-        Builder { config, send_delay: 0 }
+        Builder {
+            config,
+            send_delay: 0,
+            snapshot_transform: None,
+            trace_context: None,
+        }
     }
 
     /// Create a new instance.
@@ -479,7 +573,20 @@ impl TypedRaftRouter {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn new_raft_node_with_sto(&mut self, id: MemNodeId, log_store: MemLogStore, sm: MemStateMachine) {
-        let node = Raft::new(id, self.config.clone(), self.clone(), log_store.clone(), sm.clone()).await.unwrap();
+        let config = self.config.clone();
+        self.new_raft_node_with_config(id, config, log_store, sm).await
+    }
+
+    /// Create and register a new Raft node with a config different from the router's.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn new_raft_node_with_config(
+        &mut self,
+        id: MemNodeId,
+        config: Arc<Config>,
+        log_store: MemLogStore,
+        sm: MemStateMachine,
+    ) {
+        let node = Raft::new(id, config, self.clone(), log_store.clone(), sm.clone()).await.unwrap();
         let mut rt = self.nodes.lock().unwrap();
         rt.insert(id, (node, log_store, sm));
     }
@@ -550,6 +657,16 @@ impl TypedRaftRouter {
         }
     }
 
+    /// Set the capabilities node `id` replies with, or unset it to reply with its own.
+    pub fn set_capabilities(&self, id: MemNodeId, capabilities: Option<Capabilities>) {
+        let mut caps = self.capabilities.lock().unwrap();
+        if let Some(capabilities) = capabilities {
+            caps.insert(id, capabilities);
+        } else {
+            caps.remove(&id);
+        }
+    }
+
     /// Set a hook function to be called when before an RPC is sent to target node.
     pub fn set_rpc_pre_hook<F>(&self, rpc_type: RPCTypes, hook: F)
     where F: Fn(&TypedRaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static {
@@ -1001,6 +1118,14 @@ impl RaftNetworkFactory<MemConfig> for TypedRaftRouter {
             owner: self.clone(),
         }
     }
+
+    fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+        self.snapshot_transform.clone()
+    }
+
+    fn trace_context(&self) -> Option<Arc<dyn TraceContext>> {
+        self.trace_context.clone()
+    }
 }
 
 pub struct RaftRouterNetwork {
@@ -1013,7 +1138,7 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
     async fn append_entries(
         &mut self,
         mut rpc: AppendEntriesRequest<MemConfig>,
-        _option: RPCOption,
+        option: RPCOption,
     ) -> Result<AppendEntriesResponse<MemConfig>, RPCError<MemConfig>> {
         let from_id = rpc.vote.leader_id().voted_for().unwrap();
 
@@ -1057,7 +1182,7 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
-        let resp = node.append_entries(rpc).await;
+        let resp = node.append_entries(rpc).instrument(node.trace_span(option.trace_context())).await;
 
         tracing::debug!("append_entries: recv resp from id={} {:?}", self.target, resp);
         let resp = resp.map_err(|e| {
@@ -1070,7 +1195,9 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         // If entries are truncated by quota, return an partial success response.
         if let Some(truncated) = truncated {
             match resp {
-                AppendEntriesResponse::Success => Ok(AppendEntriesResponse::PartialSuccess(truncated)),
+                AppendEntriesResponse::Success | AppendEntriesResponse::Backpressure { .. } => {
+                    Ok(AppendEntriesResponse::PartialSuccess(truncated))
+                }
                 _ => Ok(resp),
             }
         } else {
@@ -1078,12 +1205,38 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         }
     }
 
+    /// Send a heartbeat to the target Raft node.
+    async fn heartbeat(
+        &mut self,
+        rpc: HeartbeatRequest<MemConfig>,
+        option: RPCOption,
+    ) -> Result<HeartbeatResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = rpc.vote.leader_id().voted_for().unwrap();
+
+        self.owner.count_rpc(RPCTypes::Heartbeat);
+        self.owner.call_rpc_pre_hook(rpc, from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.heartbeat(rpc).instrument(node.trace_span(option.trace_context())).await;
+        let resp = resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })?;
+
+        Ok(resp)
+    }
+
     async fn full_snapshot(
         &mut self,
         vote: Vote<MemNodeId>,
         snapshot: Snapshot<MemConfig>,
-        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
-        _option: RPCOption,
+        cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        option: RPCOption,
     ) -> Result<SnapshotResponse<MemConfig>, StreamingError<MemConfig>> {
         let from_id = vote.leader_id().voted_for().unwrap();
 
@@ -1092,9 +1245,19 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
 
+        if let Some(transform) = self.owner.snapshot_transform.clone() {
+            let mut net = ChunkedRouterNetwork {
+                target: self.target,
+                owner: self.owner.clone(),
+                transform,
+            };
+            let resp = Chunked::send_snapshot(&mut net, vote, snapshot, cancel, option).await;
+            return resp.decompose_infallible();
+        }
+
         let node = self.owner.get_raft_handle(&self.target)?;
 
-        let resp = node.install_full_snapshot(vote, snapshot).await;
+        let resp = node.install_full_snapshot(vote, snapshot).instrument(node.trace_span(option.trace_context())).await;
         let resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
                 "error: {} target={}",
@@ -1109,7 +1272,7 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
     async fn vote(
         &mut self,
         rpc: VoteRequest<MemConfig>,
-        _option: RPCOption,
+        option: RPCOption,
     ) -> Result<VoteResponse<MemConfig>, RPCError<MemConfig>> {
         let from_id = rpc.vote.leader_id().voted_for().unwrap();
 
@@ -1120,7 +1283,33 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
-        let resp = node.vote(rpc).await;
+        let resp = node.vote(rpc).instrument(node.trace_span(option.trace_context())).await;
+        let resp = resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })?;
+
+        Ok(resp)
+    }
+
+    /// Send a PreVote RPC to the target Raft node.
+    async fn pre_vote(
+        &mut self,
+        rpc: VoteRequest<MemConfig>,
+        option: RPCOption,
+    ) -> Result<VoteResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = rpc.vote.leader_id().voted_for().unwrap();
+
+        self.owner.count_rpc(RPCTypes::PreVote);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.pre_vote(rpc).instrument(node.trace_span(option.trace_context())).await;
         let resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
                 "error: {} target={}",
@@ -1134,7 +1323,7 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
     async fn transfer_leader(
         &mut self,
         rpc: TransferLeaderRequest<MemConfig>,
-        _option: RPCOption,
+        option: RPCOption,
     ) -> Result<(), RPCError<MemConfig>> {
         let from_id = rpc.from_leader().leader_id().voted_for().unwrap();
 
@@ -1145,7 +1334,7 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
-        let resp = node.handle_transfer_leader(rpc).await;
+        let resp = node.handle_transfer_leader(rpc).instrument(node.trace_span(option.trace_context())).await;
         resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
                 "error: {} target={}",
@@ -1153,6 +1342,114 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
             ))))
         })
     }
+
+    /// Get a read log id from the target Raft node, which is supposed to be the leader.
+    async fn read_index(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<LogId<MemNodeId>>, RPCError<MemConfig, RaftError<MemConfig, CheckIsLeaderError<MemConfig>>>>
+    {
+        self.owner.count_rpc(RPCTypes::ReadIndex);
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let (read_log_id, _applied) =
+            node.get_read_log_id().await.map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))?;
+
+        Ok(read_log_id)
+    }
+
+    /// Forward a client write request to the target Raft node, which is supposed to be the leader.
+    async fn forward_client_write(
+        &mut self,
+        app_data: ClientRequest,
+        _option: RPCOption,
+    ) -> Result<ClientWriteResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig, ClientWriteError<MemConfig>>>>
+    {
+        self.owner.count_rpc(RPCTypes::ClientWrite);
+        self.owner.rand_send_delay().await;
+
+        // The request is not delivered if the target is not found.
+        let node = self.owner.get_raft_handle(&self.target).map_err(|e| RPCError::Unreachable(Unreachable::new(&e)))?;
+
+        node.client_write(app_data)
+            .await
+            .map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))
+    }
+
+    async fn capabilities(&mut self, _option: RPCOption) -> Result<Capabilities, RPCError<MemConfig>> {
+        self.owner.rand_send_delay().await;
+
+        if let Some(capabilities) = self.owner.capabilities.lock().unwrap().get(&self.target) {
+            return Ok(*capabilities);
+        }
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+        Ok(node.capabilities())
+    }
+
+    async fn target_snapshot_signature(
+        &mut self,
+        _option: RPCOption,
+    ) -> Result<Option<SnapshotSignature<MemConfig>>, RPCError<MemConfig>> {
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let signature = node.snapshot_signature().await;
+        let signature = signature.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })?;
+
+        Ok(signature)
+    }
+}
+
+/// Sends snapshot chunks built by [`Chunked`] transport to the target, which receives them with
+/// [`Raft::install_snapshot()`].
+struct ChunkedRouterNetwork {
+    target: MemNodeId,
+    owner: TypedRaftRouter,
+    transform: Arc<dyn SnapshotTransform>,
+}
+
+impl RaftNetwork<MemConfig> for ChunkedRouterNetwork {
+    async fn append_entries(
+        &mut self,
+        _rpc: AppendEntriesRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig>>> {
+        unreachable!("only snapshot chunks are sent")
+    }
+
+    async fn vote(
+        &mut self,
+        _rpc: VoteRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<VoteResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig>>> {
+        unreachable!("only snapshot chunks are sent")
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        rpc: InstallSnapshotRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<InstallSnapshotResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig, InstallSnapshotError>>>
+    {
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        node.install_snapshot(rpc)
+            .await
+            .map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))
+    }
+
+    fn snapshot_transform(&self) -> Option<Arc<dyn SnapshotTransform>> {
+        Some(self.transform.clone())
+    }
 }
 
 pub enum ValueTest<T> {
@@ -9,8 +9,14 @@ mod fixtures;
 
 mod t10_initialization;
 mod t11_shutdown;
+mod t12_testing_router;
+mod t13_event_handler;
+mod t14_raft_groups;
 mod t50_follower_restart_does_not_interrupt;
+mod t50_initialize_with_snapshot_restart;
 mod t50_single_follower_restart;
 mod t50_single_leader_restart_re_apply_logs;
+mod t50_single_voter_elect_at_startup;
+mod t50_startup_check_storage;
 mod t90_issue_607_single_restart;
 mod t90_issue_920_non_voter_leader_restart;
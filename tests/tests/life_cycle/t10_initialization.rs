@@ -4,9 +4,11 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use maplit::btreeset;
+use openraft::error::AlreadyInitialized;
 use openraft::error::InitializeError;
 use openraft::error::NotAllowed;
 use openraft::error::NotInMembers;
+use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftStateMachine;
 use openraft::CommittedLeaderId;
 use openraft::Config;
@@ -216,9 +218,9 @@ async fn initialize_err_target_not_include_target() -> anyhow::Result<()> {
 
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
-async fn initialize_err_not_allowed() -> anyhow::Result<()> {
-    // Initialize a node with membership config that does not include the target node that accepts
-    // the `initialize` request.
+async fn initialize_again() -> anyhow::Result<()> {
+    // Initializing an initialized node again is a no-op with the same membership config, and is
+    // refused with a different one.
 
     let config = Arc::new(Config::default().validate()?);
     let mut router = RaftRouter::new(config.clone());
@@ -238,20 +240,30 @@ async fn initialize_err_not_allowed() -> anyhow::Result<()> {
         n0.wait(timeout()).log_index(Some(1), "init").await?;
     }
 
-    tracing::info!("--- Initialize node 0 again, not allowed");
+    tracing::info!("--- Initialize node 0 again with the same membership, Ok");
     {
         let n0 = router.get_raft_handle(&0)?;
-        let res = n0.initialize(btreeset! {0}).await;
+        n0.initialize(btreeset! {0}).await?;
+
+        n0.wait(timeout()).log_index(Some(1), "no new log").await?;
+    }
+
+    tracing::info!("--- Initialize node 0 again with a different membership, not allowed");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let res = n0.initialize(btreeset! {0, 1}).await;
         assert!(res.is_err(), "expect error but: {:?}", res);
         let err = res.unwrap_err();
 
         assert_eq!(
-            InitializeError::NotAllowed(NotAllowed {
-                last_log_id: Some(LogId {
-                    leader_id: CommittedLeaderId::new(1, 0),
-                    index: 1
-                }),
-                vote: Vote::new_committed(1, 0)
+            InitializeError::AlreadyInitialized(AlreadyInitialized {
+                membership: StoredMembership::new(
+                    Some(LogId {
+                        leader_id: CommittedLeaderId::new(0, 0),
+                        index: 0
+                    }),
+                    Membership::new(vec![btreeset! {0}], None)
+                ),
             }),
             err.into_api_error().unwrap()
         );
@@ -260,6 +272,34 @@ async fn initialize_err_not_allowed() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn initialize_err_not_allowed() -> anyhow::Result<()> {
+    // A node that has voted but has no membership config is not allowed to initialize.
+
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let (mut log_store, sm) = router.new_store();
+    log_store.save_vote(&Vote::new(1, 1)).await?;
+    router.new_raft_node_with_sto(0, log_store, sm).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let res = n0.initialize(btreeset! {0}).await;
+    assert!(res.is_err(), "expect error but: {:?}", res);
+    let err = res.unwrap_err();
+
+    assert_eq!(
+        InitializeError::NotAllowed(NotAllowed {
+            last_log_id: None,
+            vote: Vote::new(1, 1)
+        }),
+        err.into_api_error().unwrap()
+    );
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
@@ -1,10 +1,12 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::error::Fatal;
 use openraft::Config;
 use openraft::ServerState;
+use openraft_memstore::ClientRequest;
 
 use crate::fixtures::ut_harness;
 use crate::fixtures::RaftRouter;
@@ -111,3 +113,53 @@ async fn return_error_after_shutdown() -> Result<()> {
 
     Ok(())
 }
+
+/// Shutting down a node with in-flight client writes resolves every write, either with a response
+/// or with a Fatal::Stopped error.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_resolves_in_flight_writes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- send writes and shutdown");
+    let handles = (0..100)
+        .map(|i| {
+            let n0 = n0.clone();
+            tokio::spawn(async move {
+                n0.client_write(ClientRequest {
+                    client: "foo".to_string(),
+                    serial: i,
+                    status: format!("request-{}", i),
+                })
+                .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    n0.shutdown().await?;
+
+    tracing::info!(log_index, "--- every write is resolved");
+    {
+        for h in handles {
+            let res = tokio::time::timeout(Duration::from_millis(1_000), h).await??;
+            if let Err(err) = res {
+                assert_eq!(Fatal::Stopped, err.into_fatal().unwrap());
+            }
+        }
+    }
+
+    Ok(())
+}
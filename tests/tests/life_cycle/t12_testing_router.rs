@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::v2::RaftNetworkV2;
+use openraft::network::RPCOption;
+use openraft::network::RaftNetworkFactory;
+use openraft::testing::Router;
+use openraft::Config;
+use openraft::Raft;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::TypeConfig;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+
+use crate::fixtures::ut_harness;
+
+/// A cluster connected by the [`Router`] shipped in `openraft::testing` elects a leader,
+/// replicates logs, forwards a client write to the leader, and elects another leader when the
+/// current one is isolated.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn testing_router() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            forward_to_leader: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = Router::<TypeConfig>::new();
+
+    tracing::info!("--- start 3 nodes");
+    for id in [0, 1, 2] {
+        let (log_store, sm) = openraft_memstore::new_mem_store();
+        let raft = Raft::new(id, config.clone(), router.for_node(id), log_store, sm).await?;
+        router.add(id, raft);
+    }
+    assert_eq!(vec![0, 1, 2], router.node_ids());
+
+    let n0 = router.get(&0).unwrap();
+    n0.initialize(btreeset! {0,1,2}).await?;
+    n0.wait(timeout()).state(ServerState::Leader, "node 0 is leader").await?;
+
+    tracing::info!("--- write a log");
+    let log_index = {
+        let req = ClientRequest {
+            client: "foo".to_string(),
+            serial: 1,
+            status: "bar".to_string(),
+        };
+        let resp = n0.client_write(req).await?;
+        resp.log_id.index
+    };
+
+    for id in [0, 1, 2] {
+        let raft = router.get(&id).unwrap();
+        raft.wait(timeout()).applied_index(Some(log_index), "log is applied").await?;
+    }
+
+    tracing::info!("--- a write to a follower is forwarded to the leader");
+    {
+        let n1 = router.get(&1).unwrap();
+        let req = ClientRequest {
+            client: "foo".to_string(),
+            serial: 2,
+            status: "bar".to_string(),
+        };
+        let resp = n1.client_write(req).await?;
+        assert_eq!(log_index + 1, resp.log_id.index);
+    }
+
+    tracing::info!("--- isolate node 0, another node becomes leader");
+    {
+        router.set_unreachable(0, true);
+
+        let option = RPCOption::new(Duration::from_millis(1_000));
+
+        let mut from_0 = router.for_node(0).new_client(1, &()).await;
+        let res = from_0.capabilities(option.clone()).await;
+        assert!(res.is_err(), "an unreachable node can not send RPC");
+
+        let mut from_2 = router.for_node(2).new_client(1, &()).await;
+        from_2.capabilities(option).await?;
+
+        let n1 = router.get(&1).unwrap();
+        n1.wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "a new leader is elected",
+            )
+            .await?;
+    }
+
+    for id in router.node_ids() {
+        router.remove(&id).unwrap().shutdown().await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
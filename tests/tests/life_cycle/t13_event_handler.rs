@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::base::BoxFuture;
+use openraft::raft::RaftEventHandler;
+use openraft::testing::Router;
+use openraft::Config;
+use openraft::Raft;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::TypeConfig;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+
+use crate::fixtures::ut_harness;
+
+/// Records the callbacks that are called.
+#[derive(Clone, Default)]
+struct Recorder {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl RaftEventHandler<TypeConfig> for Recorder {
+    fn on_become_leader(&mut self, vote: Vote<u64>) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.calls.lock().unwrap().push(format!("become_leader: {}", vote));
+        })
+    }
+
+    fn on_step_down(&mut self, vote: Vote<u64>) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.calls.lock().unwrap().push(format!("step_down: {}", vote));
+        })
+    }
+}
+
+/// The event handler is called when a node becomes leader and when the leader shuts down.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn event_handler() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = Router::<TypeConfig>::new();
+    let recorder = Recorder::default();
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let n0 = Raft::new_with_event_handler(0, config.clone(), router.clone(), log_store, sm, recorder.clone()).await?;
+    router.add(0, n0.clone());
+
+    tracing::info!("--- no callback before initialization");
+    {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(recorder.calls.lock().unwrap().is_empty());
+    }
+
+    tracing::info!("--- become leader");
+    {
+        n0.initialize(btreeset! {0}).await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 is leader").await?;
+
+        let req = ClientRequest {
+            client: "foo".to_string(),
+            serial: 1,
+            status: "bar".to_string(),
+        };
+        n0.client_write(req).await?;
+
+        let vote = n0.metrics().borrow().vote;
+        assert_eq!(
+            vec![format!("become_leader: {}", vote)],
+            *recorder.calls.lock().unwrap()
+        );
+    }
+
+    tracing::info!("--- step down when shutting down");
+    {
+        let vote = n0.metrics().borrow().vote;
+        router.remove(&0).unwrap().shutdown().await?;
+
+        assert_eq!(
+            vec![format!("become_leader: {}", vote), format!("step_down: {}", vote)],
+            *recorder.calls.lock().unwrap()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
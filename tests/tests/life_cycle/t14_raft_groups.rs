@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::raft::HeartbeatRequest;
+use openraft::raft_groups::RaftGroups;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+use tokio::time::sleep;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Register the members of two groups in a `RaftGroups`, drive them with a shared tick, dispatch a
+/// batch of heartbeats and shut them down together.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn raft_groups() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let groups = RaftGroups::<TypeConfig, u64>::new();
+
+    tracing::info!("--- create a single node cluster for group 1 and 2");
+    let mut routers = vec![];
+    for group_id in [1, 2] {
+        let mut router = RaftRouter::new(config.clone());
+        router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+        let prev = groups.add_group(group_id, router.get_raft_handle(&0)?);
+        assert!(prev.is_none());
+
+        routers.push(router);
+    }
+
+    assert_eq!(vec![1, 2], groups.group_ids());
+    assert_eq!(2, groups.len());
+
+    tracing::info!("--- a shared tick drives every group");
+    {
+        let failures = groups.tick().await;
+        assert!(failures.is_empty());
+
+        groups
+            .get(&2)
+            .unwrap()
+            .wait(timeout())
+            .state(ServerState::Leader, "group 2 is still leading")
+            .await?;
+    }
+
+    tracing::info!("--- handle a batch of heartbeats, the unknown group is ignored");
+    {
+        let stale = HeartbeatRequest::new(Vote::new_committed(0, 0), None);
+        let resps = groups.handle_heartbeats(vec![(1, stale), (3, stale), (2, stale)]).await;
+
+        assert_eq!(vec![1, 2], resps.iter().map(|(g, _)| *g).collect::<Vec<_>>());
+
+        for (group_id, resp) in resps {
+            let leader_vote = groups.get(&group_id).unwrap().metrics().borrow().vote;
+            assert_eq!(
+                leader_vote, resp?.vote,
+                "group {} rejects the stale heartbeat",
+                group_id
+            );
+        }
+    }
+
+    tracing::info!("--- shutdown all groups");
+    {
+        let failures = groups.shutdown().await;
+        assert!(failures.is_empty());
+        assert!(groups.is_empty());
+
+        for router in routers.iter() {
+            let m = router.get_metrics(&0)?;
+            assert_eq!(ServerState::Shutdown, m.state);
+        }
+    }
+
+    Ok(())
+}
+
+/// With the internal tick disabled, elections and heartbeats are driven only by
+/// `RaftGroups::tick()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn raft_groups_tick_drives_timers() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize a cluster of 3 nodes, register every node in its RaftGroups");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let mut registries = vec![];
+    for id in [0, 1, 2] {
+        let groups = RaftGroups::<TypeConfig, u64>::new();
+        groups.add_group(1, router.get_raft_handle(&id)?);
+        registries.push(groups);
+    }
+
+    let heartbeats = || router.get_rpc_count().get(&RPCTypes::Heartbeat).copied().unwrap_or_default();
+
+    tracing::info!("--- without tick, the leader sends no heartbeat");
+    {
+        let before = heartbeats();
+        sleep(Duration::from_millis(500)).await;
+        assert_eq!(before, heartbeats(), "no heartbeat without tick");
+    }
+
+    tracing::info!("--- a tick on the leader sends heartbeats");
+    {
+        let before = heartbeats();
+        let failures = registries[0].tick().await;
+        assert!(failures.is_empty());
+
+        sleep(Duration::from_millis(200)).await;
+        assert!(heartbeats() > before, "tick sends heartbeats");
+    }
+
+    tracing::info!("--- isolate the leader, without tick no follower elects");
+    {
+        router.set_network_error(0, true);
+
+        sleep(Duration::from_millis(1_000)).await;
+        for id in [1, 2] {
+            let m = router.get_metrics(&id)?;
+            assert_eq!(
+                ServerState::Follower,
+                m.state,
+                "node {} does not elect without tick",
+                id
+            );
+        }
+    }
+
+    tracing::info!("--- tick the followers, one of them is elected");
+    {
+        let followers = vec![registries[1].clone(), registries[2].clone()];
+        let ticker = tokio::spawn(async move {
+            loop {
+                for groups in followers.iter() {
+                    groups.tick().await;
+                }
+                sleep(Duration::from_millis(50)).await;
+            }
+        });
+
+        router
+            .wait(&1, Some(Duration::from_millis(5_000)))
+            .metrics(
+                |m| m.current_leader == Some(1) || m.current_leader == Some(2),
+                "node 1 or 2 is elected",
+            )
+            .await?;
+
+        ticker.abort();
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::storage::RaftLogStorage;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Initialize a node with a snapshot and restart it.
+///
+/// Installing the snapshot does not grant any leader: the vote stays the default both in memory and
+/// in the store, and there is no leader until an election is done.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn initialize_with_snapshot_restart() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let snap;
+    let log_index;
+
+    tracing::info!("--- build a snapshot on another cluster");
+    {
+        let mut router = RaftRouter::new(config.clone());
+        let mut index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+        index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&0, timeout()).applied_index(Some(index), "write logs").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, index), "build snapshot").await?;
+
+        snap = n0.get_snapshot().await?.unwrap();
+        log_index = index;
+    }
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!(log_index, "--- initialize a new node-0 with the snapshot");
+    {
+        router.new_raft_node(0).await;
+        let n0 = router.get_raft_handle(&0)?;
+        n0.initialize_with_snapshot(snap).await?;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot installed").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Vote::default(), m.vote, "vote is not changed");
+        assert_eq!(None, m.current_leader, "no leader is granted");
+        assert_ne!(ServerState::Leader, m.state);
+    }
+
+    tracing::info!(log_index, "--- restart node-0");
+    {
+        let (n0, mut sto, sm) = router.remove_node(0).unwrap();
+        n0.shutdown().await?;
+
+        assert_eq!(
+            Vote::default(),
+            sto.read_vote().await?.unwrap_or_default(),
+            "persisted vote is not changed"
+        );
+
+        router.new_raft_node_with_sto(0, sto, sm).await;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot loaded").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Vote::default(), m.vote, "in-memory vote is the persisted one");
+        assert_eq!(None, m.current_leader, "no leader after restart");
+    }
+
+    tracing::info!(log_index, "--- node-0 becomes leader by a normal election");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().elect().await?;
+
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 elected").await?;
+        router.wait(&0, timeout()).current_leader(0, "node-0 is the leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::storage::RaftLogStorage;
+use openraft::Config;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The only voter elects itself at startup, without waiting for a tick or an election timeout.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn single_voter_elect_at_startup() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- stop node-0, restart it as a follower");
+    {
+        let (node, mut sto, sm) = router.remove_node(0).unwrap();
+        node.shutdown().await?;
+        let v = sto.read_vote().await?.unwrap_or_default();
+
+        // Set a non-committed vote so that the node restarts as a follower.
+        sto.save_vote(&Vote::new(v.leader_id.get_term() + 1, v.leader_id.voted_for().unwrap())).await?;
+
+        router.new_raft_node_with_sto(0, sto, sm).await;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 becomes leader without tick").await?;
+
+        // Leader blank log
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- write to 1 log, committed without replication");
+    {
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 works").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::StartupError;
+use openraft::storage::RaftLogStorage;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Raft;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::MemLogStore;
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::new_checked()` restarts a node with consistent storage, and refuses to start a node
+/// whose storage is corrupted.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn startup_check_storage() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let (router, mut sto, sm, log_index) = stopped_node(config.clone(), false).await?;
+
+    tracing::info!(log_index, "--- restart node-0 with consistent storage");
+    {
+        let raft = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await?;
+        raft.wait(timeout()).applied_index_at_least(Some(log_index), "node-0 restarted").await?;
+        raft.shutdown().await?;
+    }
+
+    tracing::info!(log_index, "--- lose the vote, restarting node-0 is refused");
+    {
+        sto.save_vote(&Vote::new(0, 0)).await?;
+
+        let res = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().expect("the saved vote is behind the logs");
+
+        assert!(
+            matches!(err, StartupError::LogAheadOfVote { vote, .. } if vote == Vote::new(0, 0)),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// The last log is before the last purged log.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn startup_check_log_before_purged() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let (router, mut sto, sm, log_index) = stopped_node(config.clone(), false).await?;
+
+    tracing::info!(log_index, "--- purge with a greater term than the last log");
+    {
+        sto.purge(log_id(2, 0, 1)).await?;
+
+        let res = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().expect("the last log is before the last purged");
+
+        assert_eq!(
+            StartupError::LogBeforePurged {
+                last_purged: Some(log_id(2, 0, 1)),
+                last_log_id: Some(log_id(1, 0, log_index)),
+            },
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// The committed log id is neither applied nor in the log.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn startup_check_committed_not_in_log() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let (router, mut sto, sm, log_index) = stopped_node(config.clone(), false).await?;
+
+    tracing::info!(log_index, "--- save a committed log id after the last log");
+    {
+        sto.save_committed(Some(log_id(1, 0, log_index + 10))).await?;
+
+        let res = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().expect("the committed log is lost");
+
+        assert_eq!(
+            StartupError::CommittedNotInLog {
+                committed: Some(log_id(1, 0, log_index + 10)),
+                last_log_id: Some(log_id(1, 0, log_index)),
+            },
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// Logs are purged before being applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn startup_check_purged_not_applied() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let (router, mut sto, sm, log_index) = stopped_node(config.clone(), false).await?;
+
+    tracing::info!(log_index, "--- lose the state machine and purge a log");
+    {
+        sm.clear_state_machine().await;
+        sto.purge(log_id(1, 0, 1)).await?;
+
+        let res = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().expect("a purged log is not applied");
+
+        assert_eq!(
+            StartupError::PurgedNotApplied {
+                last_purged: Some(log_id(1, 0, 1)),
+                last_applied: None,
+            },
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// The snapshot includes logs that are not applied to the state machine.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn startup_check_snapshot_ahead_of_applied() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let (router, sto, sm, log_index) = stopped_node(config.clone(), true).await?;
+
+    tracing::info!(log_index, "--- lose the state machine but keep the snapshot");
+    {
+        sm.clear_state_machine().await;
+
+        let res = Raft::new_checked(0, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().expect("the snapshot is ahead of the state machine");
+
+        assert_eq!(
+            StartupError::SnapshotAheadOfApplied {
+                snapshot: Some(log_id(1, 0, log_index)),
+                last_applied: None,
+            },
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// Bring up a cluster of 1 node, write 2 logs, optionally build a snapshot, then stop the node and
+/// return its storage.
+async fn stopped_node(
+    config: Arc<Config>,
+    build_snapshot: bool,
+) -> anyhow::Result<(RaftRouter, MemLogStore, MemStateMachine, u64)> {
+    let mut router = RaftRouter::new(config);
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write to 2 logs");
+    {
+        router.client_request_many(0, "foo", 2).await?;
+        log_index += 2;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write 2 logs").await?;
+    }
+
+    if build_snapshot {
+        tracing::info!(log_index, "--- build snapshot");
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+    }
+
+    let (node, sto, sm) = router.remove_node(0).unwrap();
+    node.shutdown().await?;
+
+    Ok((router, sto, sm, log_index))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
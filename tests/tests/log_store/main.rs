@@ -8,3 +8,6 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_defensive_log_store;
+mod t30_storage_retry;
+mod t40_slow_io;
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use openraft::storage::DefensiveLogStore;
+use openraft::storage::RaftLogStorage;
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::blank_ent;
+use openraft::testing::log_id;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+
+/// `DefensiveLogStore` rejects the calls that break the log store invariants.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn defensive_log_store() -> Result<()> {
+    let (log_store, _sm) = openraft_memstore::new_mem_store();
+    let mut ls = DefensiveLogStore::new(log_store).await?;
+
+    tracing::info!("--- vote must not regress");
+    {
+        ls.save_vote(&Vote::new(2, 1)).await?;
+        ls.save_vote(&Vote::new(2, 1)).await?;
+
+        let res = ls.save_vote(&Vote::new(1, 1)).await;
+        assert!(res.is_err());
+    }
+
+    tracing::info!("--- appended logs must not leave a hole");
+    {
+        ls.blocking_append([blank_ent::<TypeConfig>(1, 0, 0), blank_ent::<TypeConfig>(1, 0, 1)]).await?;
+
+        let res = ls.blocking_append([blank_ent::<TypeConfig>(1, 0, 3)]).await;
+        assert!(res.is_err(), "index 2 is missing");
+
+        let res = ls.blocking_append([blank_ent::<TypeConfig>(0, 0, 2)]).await;
+        assert!(res.is_err(), "log id must be greater than the last");
+
+        ls.blocking_append([blank_ent::<TypeConfig>(1, 0, 2), blank_ent::<TypeConfig>(1, 0, 3)]).await?;
+    }
+
+    tracing::info!("--- purged logs must not be truncated");
+    {
+        ls.purge(log_id(1, 0, 1)).await?;
+
+        let res = ls.truncate(log_id(1, 0, 1)).await;
+        assert!(res.is_err());
+
+        ls.truncate(log_id(1, 0, 3)).await?;
+        ls.blocking_append([blank_ent::<TypeConfig>(2, 0, 3)]).await?;
+    }
+
+    tracing::info!("--- committed logs must not be truncated");
+    {
+        ls.blocking_append([blank_ent::<TypeConfig>(2, 0, 4), blank_ent::<TypeConfig>(2, 0, 5)]).await?;
+        ls.save_committed(Some(log_id(2, 0, 4))).await?;
+
+        let res = ls.truncate(log_id(2, 0, 4)).await;
+        assert!(res.is_err(), "committed log must not be truncated");
+
+        let res = ls.truncate(log_id(2, 0, 3)).await;
+        assert!(res.is_err(), "log before committed must not be truncated");
+
+        ls.truncate(log_id(2, 0, 5)).await?;
+    }
+
+    tracing::info!("--- purged log id must not regress");
+    {
+        let res = ls.purge(log_id(1, 0, 0)).await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::Fatal;
+use openraft::Config;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A transient error of appending logs is retried without blocking `RaftCore`: the leader keeps
+/// sending heartbeats while waiting for the retry, and no follower starts an election.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_retry_append_does_not_block() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            storage_retry_max: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let (_ls, sm) = router.get_storage_handle(&0)?;
+    let vote = router.get_metrics(&1)?.vote;
+
+    tracing::info!(
+        log_index,
+        "--- appending on the leader fails 3 times, retried in 50+100+200 ms, longer than election timeout"
+    );
+    {
+        sm.block.set_failing(BlockOperation::AppendLog, 3);
+
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write is applied").await?;
+        }
+        assert_eq!(0, sm.block.get_failing(&BlockOperation::AppendLog));
+
+        for id in [1, 2] {
+            let m = router.get_metrics(&id)?;
+            assert_eq!(vote, m.vote, "node {} does not elect while the leader retries", id);
+        }
+    }
+
+    Ok(())
+}
+
+/// A transient error of applying logs is retried by the state machine worker.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_retry_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            storage_retry_max: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (_ls, sm) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- applying fails 2 times");
+    {
+        sm.block.set_failing(BlockOperation::Apply, 2);
+
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write is applied").await?;
+        assert_eq!(0, sm.block.get_failing(&BlockOperation::Apply));
+
+        let state = sm.get_state_machine().await;
+        assert_eq!(Some("request-0"), state.client_status.get("foo").map(|x| x.as_str()));
+    }
+
+    Ok(())
+}
+
+/// When the retries are used up, the node shuts down with the storage error.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_retry_exhausted() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            storage_retry_max: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (_ls, sm) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- appending fails 2 times, more than the retries");
+    {
+        sm.block.set_failing(BlockOperation::AppendLog, 2);
+
+        let res = router.send_client_request(0, ClientRequest::make_request("foo", 1)).await;
+        assert!(res.is_err());
+
+        let m = router
+            .wait(&0, timeout())
+            .metrics(|m| m.running_state.is_err(), "node is shut down by the storage error")
+            .await?;
+
+        let Err(Fatal::StorageError(e)) = m.running_state else {
+            panic!("expect a storage error, got: {:?}", m.running_state);
+        };
+        assert!(e.is_transient());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Every storage IO is checked once against `slow_io_threshold`, from submit to flush.
+///
+/// What does this test do?
+///
+/// - build a single node cluster.
+/// - fail saving the vote several times, so that it is retried after a backoff longer than the
+///   threshold, and assert that the IO is not counted as slow.
+/// - block appending logs for longer than the threshold, and assert that the append is counted
+///   once.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn slow_io() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            slow_io_threshold: 100,
+            storage_retry_max: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (_ls, sm) = router.get_storage_handle(&0)?;
+    let n0 = router.get_raft_handle(&0)?;
+    let slow_io = router.get_metrics(&0)?.slow_io;
+
+    tracing::info!(
+        log_index,
+        "--- saving vote fails 3 times, retried in 50+100+200 ms, the backoff is not counted"
+    );
+    {
+        let term = router.get_metrics(&0)?.current_term;
+
+        sm.block.set_failing(BlockOperation::SaveVote, 3);
+        n0.trigger().elect().await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.current_term > term && m.state == ServerState::Leader,
+                "re-elected in a new term",
+            )
+            .await?;
+        log_index += 1;
+        let m = router.wait(&0, timeout()).applied_index(Some(log_index), "blank log is applied").await?;
+
+        assert_eq!(0, sm.block.get_failing(&BlockOperation::SaveVote));
+        assert_eq!(slow_io, m.slow_io, "no IO is slow");
+    }
+
+    tracing::info!(log_index, "--- appending logs takes 200 ms, counted once");
+    {
+        sm.block.set_blocking(BlockOperation::AppendLog, Duration::from_millis(200));
+
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write is applied").await?;
+
+        let m = router.get_metrics(&0)?;
+        assert_eq!(slow_io + 1, m.slow_io, "a slow append is counted once");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -213,6 +213,192 @@ async fn change_with_turn_removed_voter_to_learner() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// With `wait_learner_caught_up` enabled, `change_membership` does not promote a lagging learner
+/// until it catches up.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_with_wait_learner_caught_up() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            wait_learner_caught_up: true,
+            replication_lag_threshold: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- isolate learner 1 and write to leader");
+    {
+        router.set_network_error(1, true);
+        log_index += router.client_request_many(0, "foo", 10).await?;
+    }
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- change_membership blocks while learner 1 is lagging");
+    let h = {
+        let leader = leader.clone();
+        tokio::spawn(async move { leader.change_membership([0, 1], false).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!h.is_finished());
+    assert_eq!(
+        btreeset! {0},
+        leader.metrics().borrow().membership_config.membership().voter_ids().collect()
+    );
+
+    tracing::info!(log_index, "--- restore learner 1, change_membership finishes");
+    {
+        router.set_network_error(1, false);
+        h.await??;
+        log_index += 2;
+
+        leader.wait(timeout()).voter_ids([0, 1], "1 becomes voter").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+    }
+
+    Ok(())
+}
+
+/// With `wait_learner_caught_up` enabled, `change_membership` returns an error if a learner does
+/// not catch up within `wait_learner_caught_up_timeout`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_with_wait_learner_caught_up_timeout() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            wait_learner_caught_up: true,
+            wait_learner_caught_up_timeout: 500,
+            replication_lag_threshold: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- isolate learner 1 and write to leader");
+    {
+        router.set_network_error(1, true);
+        log_index += router.client_request_many(0, "foo", 10).await?;
+    }
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- change_membership fails because learner 1 is lagging");
+    {
+        let res = leader.change_membership([0, 1], false).await;
+        let raft_err = res.unwrap_err();
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerLagging(err)) => {
+                assert_eq!(1, err.node_id);
+                assert_eq!(Duration::from_millis(500), err.timeout);
+            }
+            _ => {
+                unreachable!("expect ChangeMembershipError::LearnerLagging, got: {}", raft_err)
+            }
+        }
+
+        assert_eq!(
+            btreeset! {0},
+            leader.metrics().borrow().membership_config.membership().voter_ids().collect(),
+            "membership is not changed"
+        );
+    }
+
+    Ok(())
+}
+
+/// `update_node()` proposes a single membership log without changing voters.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn update_node() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- update node 1");
+    {
+        let leader = router.get_raft_handle(&0)?;
+        let res = leader.update_node(1, ()).await?;
+        log_index += 1;
+
+        assert_eq!(Some(log_index), res.log_id.index());
+
+        for node_id in [0, 1, 2, 3] {
+            let m = router.wait(&node_id, timeout()).applied_index(Some(log_index), "update node applied").await?;
+            assert_eq!(Some(log_index), m.membership_config.log_id().index());
+            assert_eq!(
+                btreeset! {0,1,2},
+                m.membership_config.membership().voter_ids().collect(),
+                "voters are not changed"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// With `check_voters_reachable`, a membership change is rejected if the reachable voters are not a
+/// quorum of the new membership.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_with_check_voters_reachable() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            check_voters_reachable: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1,2}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate learner 1 and 2, wait for the leader lease to expire"
+    );
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+    }
+
+    tracing::info!(log_index, "--- promoting 1 and 2 is rejected");
+    {
+        let res = leader.change_membership([0, 1, 2], false).await;
+        let raft_err = res.unwrap_err();
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::VotersUnreachable(err)) => {
+                assert_eq!(btreeset! {1,2}, err.unreachable);
+            }
+            _ => {
+                unreachable!("expect VotersUnreachable")
+            }
+        }
+    }
+
+    tracing::info!(log_index, "--- restore learner 1, promoting 1 and 2 is accepted");
+    {
+        router.set_network_error(1, false);
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner 1 is reachable").await?;
+
+        leader.change_membership([0, 1, 2], false).await?;
+        leader.wait(timeout()).voter_ids([0, 1, 2], "1 and 2 become voters").await?;
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
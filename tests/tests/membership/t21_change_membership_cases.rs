@@ -61,6 +61,12 @@ async fn m012_change_m456() -> anyhow::Result<()> {
     change_from_to(btreeset! {0, 1, 2}, btreeset! {4,5,6}).await
 }
 
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn m012_change_m0345() -> anyhow::Result<()> {
+    change_from_to(btreeset! {0, 1, 2}, btreeset! {0,3,4,5}).await
+}
+
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn m01234_change_m0123() -> anyhow::Result<()> {
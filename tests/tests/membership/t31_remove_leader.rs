@@ -256,6 +256,86 @@ async fn remove_leader_access_new_cluster() -> Result<()> {
     Ok(())
 }
 
+/// `remove_node()` on the leader transfers the leadership first, and the caller retries on the new
+/// leader to remove the old one.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn remove_node_hands_off_leadership() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- remove leader 0, it transfers the leadership");
+    let new_leader = {
+        let err = n0.remove_node(0).await.unwrap_err();
+        let fwd = err.forward_to_leader().cloned().expect("ForwardToLeader");
+
+        let new_leader = fwd.leader_id.unwrap();
+        assert_ne!(0, new_leader);
+        new_leader
+    };
+
+    tracing::info!(log_index, "--- remove node 0 on the new leader {}", new_leader);
+    {
+        let n = router.get_raft_handle(&new_leader)?;
+        n.remove_node(0).await?;
+
+        let m = n.wait(timeout()).voter_ids([1, 2], "node 0 is removed").await?;
+        assert!(m.membership_config.membership().get_node(&0).is_none());
+    }
+
+    tracing::info!(log_index, "--- the removed node 0 knows it is no longer a voter");
+    {
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| !m.membership_config.membership().is_voter(&0),
+                "node 0 receives the membership without it as a voter",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// `remove_node()` of a follower on the leader does not hand off leadership: the follower is turned
+/// into a learner first, so that it knows it is removed, and then removed from the cluster.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn remove_node_follower() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- remove follower 2 on leader 0");
+    {
+        n0.remove_node(2).await?;
+
+        let m = n0.wait(timeout()).voter_ids([0, 1], "node 2 is removed").await?;
+        assert!(m.membership_config.membership().get_node(&2).is_none());
+        assert_eq!(Some(0), m.current_leader, "leadership is not transferred");
+    }
+
+    tracing::info!(log_index, "--- the removed node 2 knows it is no longer a voter");
+    {
+        router
+            .wait(&2, timeout())
+            .metrics(
+                |m| !m.membership_config.membership().is_voter(&2),
+                "node 2 receives the membership without it as a voter",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(3_000))
 }
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A witness stores only log ids and memberships, and never becomes the leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn witness() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let witness_config = Arc::new(
+        Config {
+            witness: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1 and witness 2");
+    let mut log_index = {
+        router.new_raft_node(0).await;
+        router.new_raft_node(1).await;
+
+        let (log_store, sm) = router.new_store();
+        router.new_raft_node_with_config(2, witness_config, log_store, sm).await;
+
+        router.initialize(0).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+        // membership log and blank log
+        1
+    };
+
+    tracing::info!(log_index, "--- write to leader, the witness stores blank logs");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait_for_log(&btreeset! {0,1,2}, Some(log_index), timeout(), "write 10 logs").await?;
+
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+        let logs = sto2.try_get_log_entries(..).await?;
+        assert_eq!(log_index + 1, logs.len() as u64);
+
+        for ent in logs {
+            assert!(
+                !matches!(ent.payload, EntryPayload::Normal(_)),
+                "witness does not store app data: {}",
+                ent
+            );
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- isolate leader 0, node 1 becomes the leader, not the witness"
+    );
+    {
+        router.set_network_error(0, true);
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        assert_eq!(Some(1), n2.metrics().borrow().current_leader);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ForceSetMembershipError;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// When a quorum of voters are lost, the survivor regains availability with
+/// `force_set_membership()`, and it is refused while the leader is alive.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn force_set_membership() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- refused when the leader is alive");
+    {
+        let err = n2.force_set_membership(btreeset! {2}).await.unwrap_err();
+        let err = err.into_api_error().unwrap();
+        assert!(matches!(err, ForceSetMembershipError::LeaderAlive(_)), "{}", err);
+    }
+
+    tracing::info!(log_index, "--- lose node 0 and 1, node 2 can not elect");
+    {
+        let (n0, _sto0, _sm0) = router.remove_node(0).unwrap();
+        n0.shutdown().await?;
+
+        let (n1, _sto1, _sm1) = router.remove_node(1).unwrap();
+        n1.shutdown().await?;
+
+        // Wait for the leader lease to expire.
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        let err = n2.force_set_membership(btreeset! {1}).await.unwrap_err();
+        let err = err.into_api_error().unwrap();
+        assert!(matches!(err, ForceSetMembershipError::NotInMembers(_)), "{}", err);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- force node 2 to be the only voter, it becomes the leader"
+    );
+    {
+        n2.force_set_membership(btreeset! {2}).await?;
+
+        n2.wait(timeout()).state(ServerState::Leader, "node 2 becomes leader").await?;
+        n2.wait(timeout()).voter_ids([2], "node 2 is the only voter").await?;
+
+        // the forced membership log and the blank log
+        log_index += 2;
+
+        log_index += router.client_request_many(2, "foo", 10).await?;
+        n2.wait(timeout()).applied_index(Some(log_index), "node 2 applies logs").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -9,8 +9,12 @@ mod fixtures;
 
 mod t10_current_leader;
 mod t10_leader_last_ack;
+mod t10_leader_ready;
+mod t10_network_metrics;
 mod t10_purged;
 mod t10_server_metrics_and_data_metrics;
+mod t10_subscribe_events;
 mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
+mod t30_replication_status;
 mod t40_metrics_wait;
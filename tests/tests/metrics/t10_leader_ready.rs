@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A leader becomes ready when the blank log of its term is committed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_ready() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).leader_ready("leader 0 is ready").await?;
+
+    tracing::info!(log_index, "--- a follower is never ready");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        assert!(!n1.metrics().borrow().leader_ready);
+    }
+
+    Ok(())
+}
+
+/// With blank log disabled, a leader becomes ready when the first application log of its term is
+/// committed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_ready_without_blank_log() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_blank_log: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster of 0,1,2");
+    {
+        router.new_raft_node(0).await;
+        router.new_raft_node(1).await;
+        router.new_raft_node(2).await;
+
+        router.initialize(0).await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- leader is established without appending a blank log");
+    {
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(0), m.last_log_index, "only the initial membership log");
+        assert!(!m.leader_ready);
+    }
+
+    tracing::info!("--- the first application log makes the leader ready");
+    {
+        router.client_request(0, "foo", 1).await?;
+
+        n0.wait(timeout()).leader_ready("leader 0 is ready").await?;
+        assert_eq!(Some(1), n0.metrics().borrow().last_log_index);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::metrics::RaftNetworkMetrics;
+use openraft::network::RPCTypes;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+#[allow(unused_imports)]
+use pretty_assertions::assert_ne;
+use tokio::time::sleep;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The RPCs sent by a leader are recorded in the network metrics, regardless of the transport.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn network_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "applied").await?;
+
+        let m = n0.network_metrics().borrow().clone();
+        for target in [1, 2] {
+            let ae = m.get(&target, RPCTypes::AppendEntries).unwrap();
+            assert!(ae.sent > 0);
+            assert!(ae.entries >= 10, "at least 10 entries are sent to {}: {}", target, ae);
+            assert_eq!(0, ae.errors);
+            assert!(ae.latency.count() > 0);
+        }
+        assert!(
+            m.get(&0, RPCTypes::AppendEntries).is_none(),
+            "no RPC is sent to the leader itself"
+        );
+    }
+
+    tracing::info!(log_index, "--- errors are recorded");
+    {
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, _req, _from, target| {
+            if target == 1 {
+                let any_err = AnyError::error("network failure");
+                Err(RPCError::Network(NetworkError::new(&any_err)))
+            } else {
+                Ok(())
+            }
+        });
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "applied without node 1").await?;
+
+        let rx = n0.network_metrics();
+        let errors_recorded = |m: &RaftNetworkMetrics<TypeConfig>| {
+            m.get(&1, RPCTypes::AppendEntries).map(|x| x.errors > 0).unwrap_or(false)
+        };
+
+        let wait_errors = async {
+            while !errors_recorded(&rx.borrow()) {
+                sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(timeout().unwrap(), wait_errors).await.expect("errors to node 1 are recorded");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
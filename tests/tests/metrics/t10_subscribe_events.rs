@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftEvent;
+use openraft::raft::RaftEventReceiver;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The subscribers registered with `Raft::subscribe()` receive the state changes.
+///
+/// What does this test do?
+///
+/// - subscribe to node 0 and node 1 of a cluster.
+/// - add a learner, assert that both receive the membership change.
+/// - build a snapshot on node 0, assert that it receives the snapshot and purge events.
+/// - elect node 1, assert that node 0 receives the step down and node 1 receives every transition
+///   in order.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn subscribe_events() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+    let mut rx0 = n0.subscribe();
+    let mut rx1 = n1.subscribe();
+
+    tracing::info!(log_index, "--- add learner 3, membership changes on every node");
+    {
+        router.new_raft_node(3).await;
+        n0.add_learner(3, (), true).await?;
+        log_index += 1;
+
+        for rx in [&mut rx0, &mut rx1] {
+            recv_until(rx, |e| match e {
+                RaftEvent::MembershipChanged { membership } => membership.log_id().map(|x| x.index) == Some(log_index),
+                _ => false,
+            })
+            .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- build snapshot on node 0, logs are purged");
+    {
+        n0.trigger().snapshot().await?;
+
+        recv_until(&mut rx0, |e| match e {
+            RaftEvent::SnapshotBuilt { meta } => meta.last_log_id == Some(log_id(1, 0, log_index)),
+            _ => false,
+        })
+        .await?;
+
+        recv_until(&mut rx0, |e| {
+            e == &RaftEvent::LogPurged {
+                upto: log_id(1, 0, log_index),
+            }
+        })
+        .await?;
+    }
+
+    tracing::info!(log_index, "--- elect node 1, node 0 steps down");
+    {
+        // Let the leader lease expire
+        tokio::time::sleep(Duration::from_millis(700)).await;
+
+        n1.trigger().elect().await?;
+
+        recv_until(&mut rx0, |e| e == &RaftEvent::BecameFollower { leader_id: Some(1) }).await?;
+
+        let candidate = recv_until(&mut rx1, |e| matches!(e, RaftEvent::BecameCandidate { .. })).await?;
+        let leader = recv_until(&mut rx1, |e| matches!(e, RaftEvent::BecameLeader { .. })).await?;
+
+        let RaftEvent::BecameCandidate { vote } = candidate else {
+            unreachable!()
+        };
+        assert_eq!(Vote::new(2, 1), vote);
+        assert_eq!(
+            RaftEvent::BecameLeader {
+                vote: Vote::new_committed(2, 1)
+            },
+            leader
+        );
+    }
+
+    Ok(())
+}
+
+/// Receive events until one satisfies `pred`, or timeout.
+async fn recv_until(
+    rx: &mut RaftEventReceiver<TypeConfig>,
+    pred: impl Fn(&RaftEvent<TypeConfig>) -> bool,
+) -> Result<RaftEvent<TypeConfig>> {
+    let fu = async {
+        loop {
+            let ev = rx.recv().await.expect("subscriber is not closed");
+            tracing::info!("received event: {}", ev);
+            if pred(&ev) {
+                return ev;
+            }
+        }
+    };
+
+    let ev = tokio::time::timeout(timeout(), fu).await?;
+    Ok(ev)
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
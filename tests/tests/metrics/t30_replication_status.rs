@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::ReplicationState;
+use openraft::testing::log_id;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports the status of the replication to every follower and learner.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_status() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- all targets are up to date");
+    {
+        let m = router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let status = m.replication_status.as_ref().unwrap();
+                    status.values().all(|s| s.matched == Some(log_id(1, 0, log_index)))
+                },
+                "all targets matched",
+            )
+            .await?;
+
+        let status = m.replication_status.unwrap();
+        assert_eq!(btreeset! {1,2,3}, status.keys().copied().collect());
+
+        for s in status.values() {
+            assert_eq!(log_index + 1, s.next_index);
+            assert_eq!(0, s.inflight);
+            assert!(s.last_contact.is_some());
+            assert_eq!(ReplicationState::LineRate, s.state);
+        }
+    }
+
+    tracing::info!(log_index, "--- node 3 is unreachable, replication to it backs off");
+    {
+        router.set_unreachable(3, true);
+
+        router.client_request_many(0, "0", 1).await?;
+        log_index += 1;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let status = m.replication_status.as_ref().unwrap();
+                    status[&3].state == ReplicationState::BackingOff
+                        && status[&3].matched < Some(log_id(1, 0, log_index))
+                },
+                "node 3 backing off",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- node 3 is reachable again, replication to it recovers");
+    {
+        router.set_unreachable(3, false);
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let status = m.replication_status.as_ref().unwrap();
+                    status[&3].state == ReplicationState::LineRate
+                        && status[&3].matched == Some(log_id(1, 0, log_index))
+                },
+                "node 3 caught up",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
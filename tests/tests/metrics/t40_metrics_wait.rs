@@ -6,6 +6,8 @@ use maplit::btreeset;
 use openraft::metrics::WaitError;
 use openraft::Config;
 use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
 
 use crate::fixtures::ut_harness;
 use crate::fixtures::RaftRouter;
@@ -65,6 +67,49 @@ async fn metrics_wait() -> Result<()> {
     Ok(())
 }
 
+/// Test Raft::wait_applied(): write on the leader, then wait on a follower for the log to be
+/// applied, to read the write.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn wait_applied() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+
+    let m = n1.wait_applied(resp.log_id, timeout()).await?;
+    assert!(m.last_applied >= Some(resp.log_id));
+
+    let (_sto, sm) = router.get_storage_handle(&1)?;
+    assert_eq!(
+        Some(&"request-1".to_string()),
+        sm.get_state_machine().await.client_status.get("foo")
+    );
+
+    tracing::info!("--- wait for a log that is not written and timeout");
+    {
+        let mut log_id = resp.log_id;
+        log_id.index += 1;
+
+        let res = n1.wait_applied(log_id, Some(Duration::from_millis(100))).await;
+        assert!(matches!(res, Err(WaitError::Timeout(_, _))));
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
@@ -7,6 +7,13 @@ mod fixtures;
 mod t10_append_entries_partial_success;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
+mod t51_append_entries_max_payload_bytes;
 mod t51_append_entries_too_large;
+mod t52_append_entries_pipeline;
+mod t53_replication_breaker;
+mod t54_heartbeat_not_blocked_by_replication;
+mod t55_append_entries_backpressure;
 #[cfg(feature = "loosen-follower-log-revert")]
 mod t60_feature_loosen_follower_log_revert;
+mod t70_trace_context;
+mod t71_protocol_version;
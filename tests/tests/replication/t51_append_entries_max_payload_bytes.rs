@@ -0,0 +1,91 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::AppendEntriesRequest;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::RPCTypes;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With `max_payload_bytes` set, the entries to replicate are split into several AppendEntries
+/// RPCs by the size of the application data.
+///
+/// The JSON encoding of a test request is about 46 bytes, thus an RPC contains at most 2 of them
+/// with a limit of 100 bytes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn append_entries_max_payload_bytes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_payload_bytes: 100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n = 10u64;
+
+    tracing::info!(log_index, "--- write {} entries to leader", n);
+    {
+        log_index += router.client_request_many(0, "0", n as usize).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), format!("{} writes", n)).await?;
+    }
+
+    let max_normal = Arc::new(AtomicU64::new(0));
+    let rpc_with_normal = Arc::new(AtomicU64::new(0));
+
+    let mx = max_normal.clone();
+    let cnt = rpc_with_normal.clone();
+
+    tracing::info!(log_index, "--- count normal entries in RPCs to node-1");
+    {
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _id, target| {
+            let r: AppendEntriesRequest<_> = req.try_into().unwrap();
+            if target == 1 {
+                let normal = r.entries.iter().filter(|ent| matches!(ent.payload, EntryPayload::Normal(_))).count();
+                if normal > 0 {
+                    mx.fetch_max(normal as u64, Ordering::Relaxed);
+                    cnt.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Ok(())
+        });
+    }
+
+    tracing::info!(log_index, "--- add node-1 as learner");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "1 node added").await?;
+    }
+
+    assert_eq!(
+        2,
+        max_normal.load(Ordering::Relaxed),
+        "an RPC contains at most 2 normal entries"
+    );
+    assert!(
+        rpc_with_normal.load(Ordering::Relaxed) >= n / 2,
+        "10 normal entries are sent in at least 5 RPCs"
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With `max_in_flight_appends > 1`, a lagging follower catches up with several AppendEntries RPCs
+/// in flight.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn append_entries_pipeline() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_payload_entries: 2,
+            max_in_flight_appends: 4,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- isolate learner 3 and write 50 entries");
+    {
+        router.set_network_error(3, true);
+
+        log_index += router.client_request_many(0, "0", 50).await?;
+        router.wait_for_log(&btreeset! {0,1,2}, Some(log_index), timeout(), "write 50 logs").await?;
+    }
+
+    tracing::info!(log_index, "--- restore learner 3, it catches up");
+    {
+        router.set_network_error(3, false);
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner 3 catches up").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The replication circuit breaker to a failing follower opens after
+/// `replication_breaker_threshold` failures, and closes when the follower recovers.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_breaker() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            replication_backoff_max: 200,
+            replication_breaker_threshold: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- isolate node 2, the circuit breaker to it opens");
+    {
+        router.set_network_error(2, true);
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.replication_breaker.as_ref().map(|b| b.contains(&2)).unwrap_or(false),
+                "circuit breaker to node 2 is open",
+            )
+            .await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(btreeset! {2}), m.replication_breaker);
+    }
+
+    tracing::info!(log_index, "--- restore node 2, the circuit breaker to it closes");
+    {
+        router.set_network_error(2, false);
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.replication_breaker.as_ref().map(|b| b.is_empty()).unwrap_or(false),
+                "circuit breaker to node 2 is closed",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
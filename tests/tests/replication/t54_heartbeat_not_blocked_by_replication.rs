@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::testing::log_id;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// Heartbeats are sent by a dedicated task: they keep being acknowledged by a follower while
+/// replicating logs to it fails.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn heartbeat_not_blocked_by_replication() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- replicating logs to node 1 fails, write logs");
+    {
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, req, _id, target| {
+            let RPCRequest::AppendEntries(req) = req else {
+                return Ok(());
+            };
+
+            if target == 1 && !req.entries.is_empty() {
+                let any_err = AnyError::error("replication failure");
+                Err(RPCError::Network(NetworkError::new(&any_err)))
+            } else {
+                Ok(())
+            }
+        });
+
+        router.client_request_many(0, "0", 10).await?;
+        log_index += 10;
+
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 receives logs").await?;
+    }
+
+    tracing::info!(log_index, "--- node 1 still acknowledges heartbeats");
+    {
+        let now = TypeConfig::now();
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let heartbeat = m.heartbeat.as_ref().expect("node 0 is the leader");
+                    let node1 = heartbeat.get(&1).unwrap();
+                    node1.map(|t| *t >= now).unwrap_or(false)
+                },
+                "node 1 acknowledged heartbeat",
+            )
+            .await?;
+
+        let m = router.get_metrics(&1)?;
+        assert!(
+            m.last_applied < Some(log_id(1, 0, log_index)),
+            "node 1 does not receive logs"
+        );
+        assert_eq!(Some(0), m.current_leader, "node 1 still follows node 0");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::RPCTypes;
+use openraft::Config;
+use tokio::time::Instant;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// A leader pauses replication to a target that replies `Backpressure`.
+///
+/// What does this test do?
+///
+/// - build a cluster of a leader and a learner whose apply lag reaches `backpressure_apply_lag`
+///   upon every append.
+/// - write some logs, each sent in its own AppendEntries RPC.
+/// - assert that the leader waits for a `heartbeat_interval` before sending the next RPC.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn append_entries_backpressure() -> Result<()> {
+    let heartbeat_interval = 200;
+
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            heartbeat_interval,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            max_payload_entries: 1,
+            backpressure_apply_lag: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    {
+        let sent = sent.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, target| {
+            if let RPCRequest::AppendEntries(req) = req {
+                if target == 1 && !req.entries.is_empty() {
+                    sent.lock().unwrap().push(Instant::now());
+                }
+            }
+            Ok(())
+        });
+    }
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- write logs, replication to the learner is throttled");
+    {
+        sent.lock().unwrap().clear();
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "replicated to learner").await?;
+
+        let sent = sent.lock().unwrap();
+        assert!(sent.len() >= 5, "one log per AppendEntries: {}", sent.len());
+
+        for w in sent.windows(2) {
+            let gap = w[1] - w[0];
+            assert!(
+                gap >= Duration::from_millis(heartbeat_interval),
+                "the leader pauses after a Backpressure reply: gap: {:?}",
+                gap
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
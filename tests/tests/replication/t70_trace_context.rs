@@ -0,0 +1,128 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::RPCTypes;
+use openraft::network::TraceContext;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Stamp every sent RPC with a unique context and record every received context.
+#[derive(Default)]
+struct Stamp {
+    injected: AtomicU64,
+    extracted: Mutex<Vec<Vec<u8>>>,
+}
+
+impl TraceContext for Stamp {
+    fn inject(&self, _span: &tracing::Span) -> Vec<u8> {
+        let n = self.injected.fetch_add(1, Ordering::Relaxed);
+        n.to_le_bytes().to_vec()
+    }
+
+    fn extract(&self, context: &[u8]) -> tracing::Span {
+        self.extracted.lock().unwrap().push(context.to_vec());
+        tracing::info_span!("remote", context = debug(context))
+    }
+}
+
+/// The tracing context is sent along with every RPC and restored by the receiver.
+///
+/// What does this test do?
+///
+/// - build a cluster of 3 nodes whose network sends the context injected by a `TraceContext`.
+/// - write some logs.
+/// - assert that every vote, pre-vote, append-entries and heartbeat RPC delivers a context that is
+///   injected by the sender.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn trace_context() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let stamp = Arc::new(Stamp::default());
+    let mut router = RaftRouter::builder(config.clone()).trace_context(stamp.clone()).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs and send heartbeats");
+    {
+        router.client_request_many(0, "0", 10).await?;
+        log_index += 10;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "write logs").await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().heartbeat().await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- every received RPC carries a context injected by the sender"
+    );
+    {
+        let count = || {
+            let rpc_count = router.get_rpc_count();
+            [
+                RPCTypes::Vote,
+                RPCTypes::PreVote,
+                RPCTypes::AppendEntries,
+                RPCTypes::Heartbeat,
+            ]
+            .iter()
+            .map(|typ| rpc_count.get(typ).copied().unwrap_or_default())
+            .sum::<u64>()
+        };
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(1_000);
+        loop {
+            let extracted = stamp.extracted.lock().unwrap().len() as u64;
+            if extracted == count() {
+                break;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "every RPC is received with a context: extracted: {}, sent: {}",
+                extracted,
+                count()
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let rpc_count = router.get_rpc_count();
+        assert!(rpc_count.get(&RPCTypes::Vote).copied().unwrap_or_default() > 0);
+        assert!(rpc_count.get(&RPCTypes::Heartbeat).copied().unwrap_or_default() > 0);
+
+        let injected = stamp.injected.load(Ordering::Relaxed);
+        for context in stamp.extracted.lock().unwrap().iter() {
+            let n = u64::from_le_bytes(context.as_slice().try_into()?);
+            assert!(n < injected, "context {} is injected by a sender", n);
+        }
+    }
+
+    tracing::info!(log_index, "--- an empty context restores no span");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        assert!(n0.trace_span(&[]).is_disabled());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
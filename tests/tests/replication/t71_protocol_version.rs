@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::Capabilities;
+use openraft::network::RPCTypes;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// A leader does not replicate to a target of an incompatible protocol version, and every request
+/// carries the protocol version of the sender.
+///
+/// What does this test do?
+///
+/// - build a single node cluster and add a learner that requires a newer protocol version.
+/// - write some logs, assert that nothing is replicated to the learner.
+/// - upgrade the learner, assert that logs are replicated and the requests carry the version.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn protocol_version() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let versions = Arc::new(Mutex::new(Vec::new()));
+    {
+        let versions = versions.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, target| {
+            if let RPCRequest::AppendEntries(req) = req {
+                if target == 1 {
+                    versions.lock().unwrap().push(req.protocol_version);
+                }
+            }
+            Ok(())
+        });
+    }
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add a learner that requires a newer protocol version");
+    {
+        let newer = Capabilities {
+            protocol_version: Capabilities::PROTOCOL_VERSION + 1,
+            min_protocol_version: Capabilities::PROTOCOL_VERSION + 1,
+            ..Capabilities::local()
+        };
+        assert!(!Capabilities::local().is_compatible(&newer));
+
+        router.new_raft_node(1).await;
+        router.set_capabilities(1, Some(newer));
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let m1 = router.get_metrics(&1)?;
+        assert_eq!(
+            None, m1.last_log_index,
+            "no log is replicated to an incompatible target"
+        );
+        assert!(
+            versions.lock().unwrap().is_empty(),
+            "no AppendEntries is sent to node-1"
+        );
+    }
+
+    tracing::info!(log_index, "--- upgrade the learner, replication resumes");
+    {
+        router.set_capabilities(1, None);
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "replicated to upgraded node-1").await?;
+
+        let versions = versions.lock().unwrap();
+        assert!(!versions.is_empty());
+        assert!(
+            versions.iter().all(|v| *v == Capabilities::PROTOCOL_VERSION),
+            "every request carries the version of the sender: {:?}",
+            versions
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
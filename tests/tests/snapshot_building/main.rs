@@ -8,3 +8,4 @@ mod t10_build_snapshot;
 mod t35_building_snapshot_does_not_block_append;
 mod t35_building_snapshot_does_not_block_apply;
 mod t60_snapshot_policy_never;
+mod t61_snapshot_policy_interval;
@@ -4,6 +4,7 @@ use std::time::Duration;
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::network::v2::RaftNetworkV2;
+use openraft::network::Capabilities;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
@@ -139,6 +140,7 @@ async fn build_snapshot() -> Result<()> {
                     prev_log_id: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
                     entries: vec![],
                     leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+                    protocol_version: Capabilities::PROTOCOL_VERSION,
                 },
                 option,
             )
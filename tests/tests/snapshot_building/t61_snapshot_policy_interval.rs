@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::CommittedLeaderId;
+use openraft::Config;
+use openraft::LogId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A time based snapshot policy builds a snapshot periodically, even if only a few logs are
+/// written.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster with `SnapshotPolicy::Interval`.
+/// - write a few logs, far less than the default logs based threshold.
+/// - assert that a snapshot including these logs is built after the interval.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_policy_interval() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Any(vec![
+                SnapshotPolicy::LogsSinceLast(5000),
+                SnapshotPolicy::Interval(Duration::from_millis(500)),
+            ]),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write a few logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        router
+            .wait(&0, timeout())
+            .applied_index(Some(log_index), format_args!("write log upto {}", log_index))
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- wait for snapshot to be built by interval");
+    {
+        router
+            .wait(&0, Some(Duration::from_millis(3_000)))
+            .snapshot(
+                LogId::new(CommittedLeaderId::new(1, 0), log_index),
+                "snapshot is built by interval",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -6,6 +6,8 @@ mod fixtures;
 
 mod t10_api_install_snapshot;
 mod t10_api_install_snapshot_with_lower_vote;
+mod t11_api_install_snapshot_corrupted_chunk;
+mod t11_api_install_snapshot_strict_offset;
 mod t20_startup_snapshot;
 mod t30_purge_in_snapshot_logs;
 mod t31_snapshot_overrides_membership;
@@ -16,4 +18,9 @@ mod t50_snapshot_line_rate_to_snapshot;
 mod t50_snapshot_when_lacking_log;
 mod t51_after_snapshot_add_learner_and_request_a_log;
 mod t60_snapshot_chunk_size;
+mod t60_snapshot_delta;
+mod t60_snapshot_transform;
+mod t61_snapshot_transfer_metrics;
+mod t62_snapshot_receive_idle_timeout;
+mod t63_resume_snapshot_after_restart;
 mod t90_issue_808_snapshot_to_unreachable_node_should_not_block;
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::network::Capabilities;
 use openraft::raft::InstallSnapshotRequest;
 use openraft::storage::SnapshotMeta;
 use openraft::testing::log_id;
@@ -48,6 +49,9 @@ async fn snapshot_arguments() -> Result<()> {
         offset: 0,
         data: vec![1, 2, 3],
         done: false,
+        checksum: None,
+        compressed: false,
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     tracing::info!(log_index, "--- only allow to begin a new session when offset is 0");
@@ -98,5 +102,18 @@ async fn snapshot_arguments() -> Result<()> {
         req.meta.snapshot_id = "ss2".into();
         n.0.install_snapshot(req).await?;
     }
+
+    tracing::info!("-- finish with a mismatched checksum, the snapshot is rejected");
+    {
+        let mut req = make_req();
+        req.meta.snapshot_id = "ss3".into();
+        req.done = true;
+        req.checksum = Some(0);
+        let res = n.0.install_snapshot(req).await;
+        assert_eq!(
+            "snapshot checksum mismatch, snapshot_id: ss3, expect: 0, got: Some(1438416925)",
+            res.unwrap_err().to_string()
+        );
+    }
     Ok(())
 }
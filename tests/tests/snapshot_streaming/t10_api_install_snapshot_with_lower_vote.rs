@@ -1,8 +1,8 @@
-use std::io::Cursor;
 use std::sync::Arc;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::network::Capabilities;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::InstallSnapshotRequest;
 use openraft::storage::Snapshot;
@@ -10,6 +10,7 @@ use openraft::storage::SnapshotMeta;
 use openraft::testing::log_id;
 use openraft::Config;
 use openraft::Vote;
+use openraft_memstore::MemSnapshotData;
 
 use crate::fixtures::ut_harness;
 use crate::fixtures::RaftRouter;
@@ -45,6 +46,9 @@ async fn install_snapshot_lower_vote() -> Result<()> {
         offset: 0,
         data: vec![1, 2, 3],
         done: false,
+        checksum: None,
+        compressed: false,
+        protocol_version: Capabilities::PROTOCOL_VERSION,
     };
 
     tracing::info!(log_index, "--- force the vote on target node to be higher");
@@ -55,6 +59,7 @@ async fn install_snapshot_lower_vote() -> Result<()> {
                 prev_log_id: None,
                 entries: vec![],
                 leader_commit: None,
+                protocol_version: Capabilities::PROTOCOL_VERSION,
             })
             .await;
         let vote = n0.with_raft_state(|st| *st.vote_ref()).await?;
@@ -81,7 +86,7 @@ async fn install_snapshot_lower_vote() -> Result<()> {
         let got = n0
             .install_full_snapshot(Vote::new_committed(1, 1), Snapshot {
                 meta: Default::default(),
-                snapshot: Box::new(Cursor::new(vec![])),
+                snapshot: Box::new(MemSnapshotData::default()),
             })
             .await?;
         assert_eq!(Vote::new_committed(2, 1), got.vote);
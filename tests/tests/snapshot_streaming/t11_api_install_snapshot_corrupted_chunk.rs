@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::Capabilities;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::storage::SnapshotMeta;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// API test: a snapshot with a corrupted chunk is discarded and has to be received from offset 0.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster.
+/// - send a snapshot in 2 chunks, the second one is corrupted, and the checksum mismatches.
+/// - the partially received data is discarded: resuming from the corrupted chunk is rejected.
+/// - re-send the snapshot from offset 0 and it is accepted.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_corrupted_chunk() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = 0;
+
+    tracing::info!(log_index, "--- initializing cluster");
+    log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    // CRC-32 of [1, 2, 3, 4, 5, 6]
+    let checksum = 2180413220;
+
+    let n = router.remove_node(0).unwrap();
+    let make_req = |offset: u64, data: Vec<u8>, done: bool| InstallSnapshotRequest {
+        // force it to be a follower
+        vote: Vote::new_committed(2, 1),
+        meta: SnapshotMeta {
+            snapshot_id: "ss1".into(),
+            last_log_id: Some(log_id(1, 0, 0)),
+            last_membership: Default::default(),
+        },
+        offset,
+        data,
+        done,
+        checksum: if done { Some(checksum) } else { None },
+        compressed: false,
+        protocol_version: Capabilities::PROTOCOL_VERSION,
+    };
+
+    tracing::info!(log_index, "--- send ss1:[0,3) and a corrupted ss1:[3,6)");
+    {
+        n.0.install_snapshot(make_req(0, vec![1, 2, 3], false)).await?;
+
+        let res = n.0.install_snapshot(make_req(3, vec![4, 0, 6], true)).await;
+        assert_eq!(
+            "snapshot checksum mismatch, snapshot_id: ss1, expect: 2180413220, got: Some(4236346209)",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    tracing::info!(log_index, "--- the corrupted data is discarded, it can not be resumed");
+    {
+        let res = n.0.install_snapshot(make_req(3, vec![4, 5, 6], true)).await;
+        assert_eq!(
+            "snapshot segment id mismatch, expect: ss1+0, got: ss1+3",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    tracing::info!(log_index, "--- re-send from offset 0");
+    {
+        n.0.install_snapshot(make_req(0, vec![1, 2, 3], false)).await?;
+        n.0.install_snapshot(make_req(3, vec![4, 5, 6], true)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::Capabilities;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::storage::SnapshotMeta;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// API test: with `strict_snapshot_offset` enabled, install_snapshot rejects a chunk whose offset
+/// does not follow the last received one.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster with `strict_snapshot_offset` enabled.
+/// - send install_snapshot request with contiguous and non-contiguous offsets.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_strict_offset() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_tick: false,
+            strict_snapshot_offset: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = 0;
+
+    tracing::info!(log_index, "--- initializing cluster");
+    log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n = router.remove_node(0).unwrap();
+    let make_req = |offset: u64| InstallSnapshotRequest {
+        // force it to be a follower
+        vote: Vote::new_committed(2, 1),
+        meta: SnapshotMeta {
+            snapshot_id: "ss1".into(),
+            last_log_id: Some(log_id(1, 0, 0)),
+            last_membership: Default::default(),
+        },
+        offset,
+        data: vec![1, 2, 3],
+        done: false,
+        checksum: None,
+        compressed: false,
+        protocol_version: Capabilities::PROTOCOL_VERSION,
+    };
+
+    tracing::info!(log_index, "--- install and write ss1:[0,3)");
+    {
+        n.0.install_snapshot(make_req(0)).await?;
+    }
+
+    tracing::info!(log_index, "--- continue write with a gap is rejected");
+    {
+        let res = n.0.install_snapshot(make_req(8)).await;
+        assert_eq!(
+            "snapshot segment id mismatch, expect: ss1+3, got: ss1+8",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    tracing::info!(log_index, "--- re-write received data is rejected");
+    {
+        let res = n.0.install_snapshot(make_req(1)).await;
+        assert_eq!(
+            "snapshot segment id mismatch, expect: ss1+3, got: ss1+1",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    tracing::info!(log_index, "--- continue write from the expected offset");
+    {
+        n.0.install_snapshot(make_req(3)).await?;
+    }
+
+    tracing::info!(log_index, "--- restart from offset 0 is allowed");
+    {
+        n.0.install_snapshot(make_req(0)).await?;
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use std::time::Duration;
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::network::v2::RaftNetworkV2;
+use openraft::network::Capabilities;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
@@ -97,6 +98,7 @@ async fn snapshot_overrides_membership() -> Result<()> {
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {2,3}], None)),
                 }],
                 leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
+                protocol_version: Capabilities::PROTOCOL_VERSION,
             };
             let option = RPCOption::new(Duration::from_millis(1_000));
 
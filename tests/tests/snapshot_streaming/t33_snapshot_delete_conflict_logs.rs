@@ -4,6 +4,7 @@ use std::time::Duration;
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::network::v2::RaftNetworkV2;
+use openraft::network::Capabilities;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
@@ -116,6 +117,7 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                 },
             ],
             leader_commit: Some(LogId::new(CommittedLeaderId::new(1, 0), 2)),
+            protocol_version: Capabilities::PROTOCOL_VERSION,
         };
         let option = RPCOption::new(Duration::from_millis(1_000));
 
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The leader sends a delta snapshot to a follower that already has an older snapshot.
+///
+/// What does this test do?
+///
+/// - build a cluster of a leader and a learner, both build a snapshot.
+/// - isolate the learner, write more logs to the leader, build a snapshot and purge the logs.
+/// - restore the learner and assert that the leader builds a delta snapshot since the snapshot on
+///   the learner, and the learner installs it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_delta() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+    let (_, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- send just enough logs to build a snapshot on both nodes");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "leader snapshot").await?;
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index), "learner snapshot").await?;
+    }
+
+    let base = n1.snapshot_signature().await?.expect("learner has a snapshot");
+    assert_eq!(Some(log_id(1, 0, log_index)), base.last_log_id);
+
+    tracing::info!(log_index, "--- isolate learner, build snapshot and purge logs");
+    {
+        sm0.enable_delta_snapshot();
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "leader snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- restore learner, it receives a delta snapshot");
+    {
+        router.set_network_error(1, false);
+
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index), "learner installs snapshot").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner catches up").await?;
+
+        assert_eq!(
+            vec![base],
+            sm0.delta_snapshot_bases(),
+            "delta is built since learner snapshot"
+        );
+    }
+
+    Ok(())
+}
+
+/// The leader sends the full snapshot if the target does not have a snapshot to build a delta
+/// since.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_delta_fallback_to_full() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_, sm0) = router.get_storage_handle(&0)?;
+    sm0.enable_delta_snapshot();
+
+    tracing::info!(log_index, "--- build snapshot and purge logs");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner without snapshot");
+    {
+        router.new_raft_node(1).await;
+        assert_eq!(None, router.get_raft_handle(&1)?.snapshot_signature().await?);
+
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index - 1), "learner-1 snapshot").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "sync all data to learner-1").await?;
+
+        assert!(sm0.delta_snapshot_bases().is_empty(), "no delta is built");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
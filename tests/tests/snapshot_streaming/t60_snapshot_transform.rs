@@ -0,0 +1,107 @@
+use std::io;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::SnapshotTransform;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::SnapshotSegmentId;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Flip every bit of a chunk and append the low byte of the offset; count the calls.
+#[derive(Default)]
+struct Flip {
+    encoded: AtomicU64,
+    decoded: AtomicU64,
+}
+
+impl SnapshotTransform for Flip {
+    fn encode(&self, segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        self.encoded.fetch_add(1, Ordering::Relaxed);
+
+        let mut data: Vec<u8> = data.into_iter().map(|b| !b).collect();
+        data.push(segment.offset as u8);
+        Ok(data)
+    }
+
+    fn decode(&self, segment: &SnapshotSegmentId, mut data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        self.decoded.fetch_add(1, Ordering::Relaxed);
+
+        if data.pop() != Some(segment.offset as u8) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "offset mismatch"));
+        }
+        Ok(data.into_iter().map(|b| !b).collect())
+    }
+}
+
+/// Snapshot chunks encoded by a non-identity transform are decoded by the receiver.
+///
+/// What does this test do?
+///
+/// - build a single node cluster whose nodes send and receive snapshots in chunks with a transform.
+/// - send enough requests to the node that a snapshot is built, and purge the logs.
+/// - add learner and assert that it installs the snapshot: every chunk is decoded by the default
+///   receive path `Raft::install_snapshot()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_transform() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            snapshot_max_chunk_size: 10,
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let flip = Arc::new(Flip::default());
+    let mut router = RaftRouter::builder(config.clone()).snapshot_transform(flip.clone()).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- send just enough logs to trigger snapshot");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner to receive the transformed snapshot");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index - 1), "learner-1 snapshot").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "sync all data to learner-1").await?;
+
+        let encoded = flip.encoded.load(Ordering::Relaxed);
+        let decoded = flip.decoded.load(Ordering::Relaxed);
+        assert!(encoded > 1, "snapshot is sent in more than one chunk, got: {}", encoded);
+        assert_eq!(encoded, decoded, "every sent chunk is decoded");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
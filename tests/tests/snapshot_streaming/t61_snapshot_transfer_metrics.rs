@@ -0,0 +1,129 @@
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::SnapshotTransform;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::SnapshotSegmentId;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Send chunks as is; it makes the router send snapshots in chunks.
+struct Identity;
+
+impl SnapshotTransform for Identity {
+    fn encode(&self, _segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        Ok(data)
+    }
+
+    fn decode(&self, _segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        Ok(data)
+    }
+}
+
+/// The sender and the receiver of a snapshot report the progress of the transfer in metrics.
+///
+/// What does this test do?
+///
+/// - build a single node cluster that sends snapshots in small chunks at a low rate.
+/// - send enough requests to the node that a snapshot is built, and purge the logs.
+/// - add learner and assert that the leader reports the bytes sent, the size and the ETA, and the
+///   learner reports the bytes received, of the same snapshot.
+/// - assert that both reports are removed when the transfer completes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_transfer_metrics() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            snapshot_max_chunk_size: 10,
+            snapshot_max_bytes_per_sec: 200,
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::builder(config.clone()).snapshot_transform(Arc::new(Identity)).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- send just enough logs to trigger snapshot");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner, the transfer is reported by both ends");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        let m = router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let t = m.snapshot_sending.as_ref().and_then(|s| s.get(&1));
+                    t.map(|t| t.bytes > 0 && t.eta_millis.is_some()).unwrap_or(false)
+                },
+                "leader reports sending snapshot to learner-1",
+            )
+            .await?;
+        let sending = m.snapshot_sending.unwrap()[&1].clone();
+        assert_eq!(Some(log_id(1, 0, log_index - 1)), sending.last_log_id);
+        let total = sending.total_bytes.expect("the sender knows the size");
+        assert!(sending.bytes < total, "sent: {} of {}", sending.bytes, total);
+
+        let m = router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.snapshot_receiving.as_ref().map(|t| t.bytes > 0).unwrap_or(false),
+                "learner-1 reports receiving snapshot",
+            )
+            .await?;
+        let receiving = m.snapshot_receiving.unwrap();
+        assert_eq!(sending.snapshot_id, receiving.snapshot_id);
+        assert_eq!(sending.last_log_id, receiving.last_log_id);
+        assert_eq!(None, receiving.total_bytes, "the receiver does not know the size");
+
+        router
+            .wait(&1, Some(Duration::from_secs(10)))
+            .applied_index(Some(log_index), "sync all data to learner-1")
+            .await?;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.snapshot_sending.as_ref().map(|s| s.is_empty()).unwrap_or(false),
+                "leader finished sending",
+            )
+            .await?;
+        router
+            .wait(&1, timeout())
+            .metrics(|m| m.snapshot_receiving.is_none(), "learner-1 finished receiving")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
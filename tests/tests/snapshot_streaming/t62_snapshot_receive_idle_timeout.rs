@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::Capabilities;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::RaftEvent;
+use openraft::storage::SnapshotMeta;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A partially received snapshot is dropped if no chunk is received for
+/// `snapshot_receive_idle_timeout`, and it is reported in an event and in the metrics.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster.
+/// - send the first chunk of a snapshot and no more.
+/// - assert that a `SnapshotReceiveTimedOut` event is emitted and the metrics count it.
+/// - assert that the next chunk is rejected because the partial snapshot is dropped.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_receive_idle_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_tick: false,
+            snapshot_receive_idle_timeout: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut rx0 = n0.subscribe();
+
+    let make_req = |offset| InstallSnapshotRequest {
+        vote: Vote::new_committed(2, 1),
+        meta: SnapshotMeta {
+            snapshot_id: "ss1".into(),
+            last_log_id: Some(log_id(1, 0, 0)),
+            last_membership: Default::default(),
+        },
+        offset,
+        data: vec![1, 2, 3],
+        done: false,
+        checksum: None,
+        compressed: false,
+        protocol_version: Capabilities::PROTOCOL_VERSION,
+    };
+
+    tracing::info!(log_index, "--- send the first chunk, then stall");
+    {
+        n0.install_snapshot(make_req(0)).await?;
+    }
+
+    tracing::info!(log_index, "--- the partial snapshot is dropped after the idle timeout");
+    {
+        let fu = async {
+            loop {
+                let ev = rx0.recv().await.expect("subscriber is not closed");
+                tracing::info!("received event: {}", ev);
+                if let RaftEvent::SnapshotReceiveTimedOut { snapshot_id } = ev {
+                    return snapshot_id;
+                }
+            }
+        };
+        let snapshot_id = tokio::time::timeout(Duration::from_millis(1_000), fu).await?;
+        assert_eq!("ss1", snapshot_id);
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.snapshot_receive_timeouts == 1 && m.snapshot_receiving.is_none(),
+                "the timeout is counted",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- the next chunk has to restart from offset 0");
+    {
+        let res = n0.install_snapshot(make_req(3)).await;
+        assert_eq!(
+            "snapshot segment id mismatch, expect: ss1+0, got: ss1+3",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
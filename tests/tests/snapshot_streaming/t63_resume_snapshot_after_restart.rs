@@ -0,0 +1,124 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::network::SnapshotTransform;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::SnapshotSegmentId;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Send chunks as is; record the offset of every decoded chunk.
+#[derive(Default)]
+struct Record {
+    decoded: Mutex<Vec<u64>>,
+}
+
+impl SnapshotTransform for Record {
+    fn encode(&self, _segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        Ok(data)
+    }
+
+    fn decode(&self, segment: &SnapshotSegmentId, data: Vec<u8>) -> Result<Vec<u8>, io::Error> {
+        self.decoded.lock().unwrap().push(segment.offset);
+        Ok(data)
+    }
+}
+
+/// A snapshot partially received before the receiver restarts is resumed from where it stopped,
+/// instead of being received again from the beginning.
+///
+/// What does this test do?
+///
+/// - build a single node cluster whose nodes send and receive snapshots in small throttled chunks.
+/// - send enough requests to the node that a snapshot is built, and purge the logs.
+/// - add a learner, and restart it with the same store when it has received part of the snapshot.
+/// - assert that the learner installs the snapshot, and the first chunk is received only once.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn resume_snapshot_after_restart() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            snapshot_max_chunk_size: 10,
+            snapshot_max_bytes_per_sec: 200,
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let record = Arc::new(Record::default());
+    let mut router = RaftRouter::builder(config.clone()).snapshot_transform(record.clone()).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- send just enough logs to trigger snapshot");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner and restart it while receiving snapshot");
+    {
+        router.new_raft_node(1).await;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.snapshot_receiving.as_ref().map(|s| s.bytes).unwrap_or_default() >= 30,
+                "learner-1 received part of the snapshot",
+            )
+            .await?;
+
+        let (n1, sto1, sm1) = router.remove_node(1).unwrap();
+        n1.shutdown().await?;
+
+        tracing::info!(log_index, "--- restart learner-1");
+        router.new_raft_node_with_sto(1, sto1, sm1).await;
+    }
+
+    tracing::info!(log_index, "--- learner-1 resumes receiving the snapshot");
+    {
+        router
+            .wait(&1, Some(Duration::from_millis(5_000)))
+            .snapshot(log_id(1, 0, log_index - 1), "learner-1 snapshot")
+            .await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "sync all data to learner-1").await?;
+
+        let decoded = record.decoded.lock().unwrap().clone();
+        assert_eq!(
+            1,
+            decoded.iter().filter(|offset| **offset == 0).count(),
+            "the snapshot is not received again from the beginning, decoded offsets: {:?}",
+            decoded
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}